@@ -0,0 +1,42 @@
+//! Reads against a LocalStorage.db grown the way long-played installs grow: thousands of cached
+//! JSON rows of several kilobytes each. Listing keys and previewing a value back the key browser
+//! and run on the UI thread, so they must stay far below a frame even here.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use wuwa_ploom_core::db;
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::{fixture_db, TempDir, V1_2_SETTING};
+
+const ROWS: usize = 5000;
+const VALUE_BYTES: usize = 8 * 1024;
+
+fn cached_value(index: usize) -> String {
+    let items: Vec<String> = (0..VALUE_BYTES / 32).map(|item| format!(r#"{{"id":{},"slot":{}}}"#, index * 1000 + item, item)).collect();
+    format!("[{}]", items.join(","))
+}
+
+fn large_db(dir: &TempDir) -> String {
+    let keys: Vec<String> = (0..ROWS).map(|index| format!("CachedQuestData_{:05}", index)).collect();
+    let values: Vec<String> = (0..ROWS).map(cached_value).collect();
+    let mut rows: Vec<(&str, &str)> = keys.iter().map(String::as_str).zip(values.iter().map(String::as_str)).collect();
+    rows.push(("GameQualitySetting", V1_2_SETTING));
+    fixture_db(dir.path(), &rows, true)
+}
+
+fn reads(c: &mut Criterion) {
+    let dir = TempDir::new("bench-large");
+    let db_path = large_db(&dir);
+
+    c.bench_function("list_keys first 2000", |b| b.iter(|| db::list_keys(black_box(&db_path), "", 2000).unwrap()));
+    c.bench_function("list_keys filtered", |b| b.iter(|| db::list_keys(black_box(&db_path), "_0420", 2000).unwrap()));
+    c.bench_function("read_value_prefix 64 KiB", |b| {
+        b.iter(|| db::read_value_prefix(black_box(&db_path), "CachedQuestData_02500", 64 * 1024).unwrap())
+    });
+    c.bench_function("read_quality_setting_file", |b| b.iter(|| db::read_quality_setting_file(black_box(&db_path)).unwrap()));
+}
+
+criterion_group!(benches, reads);
+criterion_main!(benches);
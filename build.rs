@@ -1,5 +1,57 @@
 extern crate embed_resource;
 
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Reads the `license = "..."` line from a dependency's unpacked manifest, if cargo has it on disk.
+fn find_license(registry_src: &Option<PathBuf>, name: &str, version: &str) -> Option<String> {
+    let registry_src = registry_src.as_ref()?;
+    for index in fs::read_dir(registry_src).ok()?.flatten() {
+        let manifest = index.path().join(format!("{}-{}", name, version)).join("Cargo.toml");
+        let Ok(text) = fs::read_to_string(manifest) else {
+            continue;
+        };
+        return text
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("license = "))
+            .map(|license| license.trim_matches('"').to_string());
+    }
+    None
+}
+
+/// Lists every locked dependency with its version and license for the About tab.
+fn write_credits(out_dir: &Path) {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let lock_path = Path::new(&manifest_dir).join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock_path.display());
+
+    let registry_src = env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("USERPROFILE").or_else(|| env::var_os("HOME")).map(|home| PathBuf::from(home).join(".cargo")))
+        .map(|cargo_home| cargo_home.join("registry").join("src"));
+
+    let mut credits = String::new();
+    let lock = fs::read_to_string(&lock_path).unwrap_or_default();
+    let mut name = None;
+    for line in lock.lines() {
+        if let Some(value) = line.strip_prefix("name = ") {
+            name = Some(value.trim_matches('"').to_string());
+        } else if let Some(value) = line.strip_prefix("version = ") {
+            let (Some(name), version) = (name.take(), value.trim_matches('"')) else {
+                continue;
+            };
+            if name == env::var("CARGO_PKG_NAME").unwrap() {
+                continue;
+            }
+            let license = find_license(&registry_src, &name, version).unwrap_or_else(|| "see crates.io".into());
+            credits.push_str(&format!("{} {}\t{}\n", name, version, license));
+        }
+    }
+    fs::write(out_dir.join("credits.txt"), credits).unwrap();
+}
+
 fn main() {
     embed_resource::compile("app.rc", std::iter::empty::<&str>());
+    write_credits(Path::new(&env::var("OUT_DIR").unwrap()));
 }
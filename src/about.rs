@@ -0,0 +1,63 @@
+use eframe::egui;
+use egui::WidgetType;
+use wuwa_ploom_core::changelog;
+
+use crate::{describe, whats_new};
+
+const LICENSE_MIT: &str = include_str!("../LICENSE-MIT");
+const LICENSE_APACHE: &str = include_str!("../LICENSE-APACHE");
+/// `name version<TAB>license` per line, generated from Cargo.lock by build.rs.
+const CREDITS: &str = include_str!(concat!(env!("OUT_DIR"), "/credits.txt"));
+
+fn license_text(ui: &mut egui::Ui, title: &str, text: &str) {
+    egui::CollapsingHeader::new(title).show(ui, |ui| {
+        egui::ScrollArea::vertical().id_source(title).max_height(200.0).show(ui, |ui| {
+            ui.monospace(text);
+        });
+    });
+}
+
+pub fn show(ui: &mut egui::Ui) {
+    ui.heading(format!("WuWa Ploom {}", env!("CARGO_PKG_VERSION")));
+    ui.label(env!("CARGO_PKG_DESCRIPTION"));
+    ui.add_space(10.0);
+    ui.label("Made by abellio");
+    ui.horizontal(|ui| {
+        ui.label("Github:");
+        describe(ui.hyperlink("https://github.com/jakubs7"), WidgetType::Link, "Author's GitHub profile");
+    });
+    ui.label("Support my Gacha addiction:");
+    ui.horizontal(|ui| {
+        ui.label("ko-fi:");
+        describe(ui.hyperlink("https://ko-fi.com/abellio"), WidgetType::Link, "Support the author on ko-fi");
+    });
+
+    ui.add_space(10.0);
+    ui.separator();
+    egui::CollapsingHeader::new("Release notes").show(ui, |ui| {
+        let releases = changelog::releases();
+        whats_new::show_releases(ui, &releases.iter().collect::<Vec<_>>());
+    });
+    ui.separator();
+    ui.label(format!("Licensed under {}.", env!("CARGO_PKG_LICENSE")));
+    license_text(ui, "MIT License", LICENSE_MIT);
+    license_text(ui, "Apache License 2.0", LICENSE_APACHE);
+
+    ui.separator();
+    egui::CollapsingHeader::new("Third-party crates").show(ui, |ui| {
+        if CREDITS.is_empty() {
+            ui.label("Dependency list unavailable in this build.");
+            return;
+        }
+        egui::ScrollArea::vertical().id_source("credits").max_height(200.0).show(ui, |ui| {
+            egui::Grid::new("credits_grid").striped(true).show(ui, |ui| {
+                for line in CREDITS.lines() {
+                    let (name, license) = line.split_once('\t').unwrap_or((line, ""));
+                    ui.label(name);
+                    ui.label(license);
+                    ui.end_row();
+                }
+            });
+        });
+    });
+}
@@ -0,0 +1,145 @@
+//! Whether this process may write the database, and writing through an elevated copy of the
+//! program when only an administrator may. Installs under Program Files leave LocalStorage.db
+//! writable by administrators alone; checking when the file is loaded lets frontends say so up
+//! front instead of failing on the first write.
+//!
+//! The elevated copy is started with `ELEVATED_WRITE_ARG`, a request file and a result file. It
+//! selects the request's game and key names, applies its patch and writes the outcome back,
+//! then exits.
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, instrument, warn};
+
+use crate::db::{self, Patch};
+use crate::games;
+use crate::keymap::{self, KeyNames};
+use crate::platform::Platform;
+use crate::{MyError, Result};
+
+/// Passed to the elevated helper, followed by the request file and the result file.
+pub const ELEVATED_WRITE_ARG: &str = "--elevated-write";
+const PROBE_FILE: &str = ".wuwa-ploom-write-probe";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WriteAccess {
+    Writable,
+    /// Only an administrator may write; writes go through `write_elevated`.
+    NeedsElevation,
+    /// Nothing this process can do makes the file writable, e.g. a read-only file or drive.
+    ReadOnly(String),
+}
+
+impl WriteAccess {
+    /// Probes `db_path` without changing it: the file and its folder (SQLite creates its journal
+    /// there) must take writes, and the database must hand out its write lock. A lock held by
+    /// the game counts as writable; that is reported where the game is.
+    #[instrument(skip(platform))]
+    pub fn check(db_path: &Path, platform: &dyn Platform) -> Self {
+        let access = match probe(db_path) {
+            Ok(()) => WriteAccess::Writable,
+            Err(MyError::Locked(_)) => WriteAccess::Writable,
+            Err(MyError::Permission(_)) if platform.can_elevate() => WriteAccess::NeedsElevation,
+            Err(err) => WriteAccess::ReadOnly(err.summary()),
+        };
+        info!(?access, "write access");
+        access
+    }
+
+    /// One line for the UI.
+    pub fn describe(&self) -> String {
+        match self {
+            WriteAccess::Writable => "Writable".into(),
+            WriteAccess::NeedsElevation => "Needs administrator rights; you will be asked when writing".into(),
+            WriteAccess::ReadOnly(reason) => format!("Read-only: {}", reason),
+        }
+    }
+}
+
+fn probe(db_path: &Path) -> Result<()> {
+    let metadata = fs::metadata(db_path)?;
+    if metadata.permissions().readonly() {
+        return Err(MyError::Io(io::Error::other("the file is marked read-only")));
+    }
+    // Opening for append writes nothing.
+    OpenOptions::new().append(true).open(db_path)?;
+    let folder = db_path.parent().unwrap_or(Path::new("."));
+    let probe_path = folder.join(PROBE_FILE);
+    OpenOptions::new().write(true).create_new(true).open(&probe_path)?;
+    let _ = fs::remove_file(&probe_path);
+    db::probe_write_lock(&db_path.display().to_string())
+}
+
+#[derive(Serialize, Deserialize)]
+struct Request {
+    db_path: String,
+    patch: Patch,
+    /// `GameAdapter::id` of the caller's game; the helper starts with the default one.
+    game: String,
+    /// The key names the caller resolved for the loaded client version.
+    names: KeyNames,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Outcome {
+    inverse: Option<Patch>,
+    error: Option<String>,
+}
+
+/// `db::apply_patch` run by an elevated copy of this program; asks the user for consent first.
+#[instrument(skip(platform, patch), err(level = "warn"))]
+pub fn write_elevated(platform: &dyn Platform, db_path: &str, patch: &Patch) -> Result<Option<Patch>> {
+    let dir = env::temp_dir().join(format!("wuwa-ploom-elevated-{}", std::process::id()));
+    fs::create_dir_all(&dir)?;
+    let request_path = dir.join("request.json");
+    let result_path = dir.join("result.json");
+    let _ = fs::remove_file(&result_path);
+    let request = Request { db_path: db_path.to_string(), patch: patch.clone(), game: games::active().id().to_string(), names: keymap::active() };
+    fs::write(&request_path, serde_json::to_vec(&request)?)?;
+
+    let args = [ELEVATED_WRITE_ARG.to_string(), request_path.display().to_string(), result_path.display().to_string()];
+    let exit_code = platform.run_elevated(&env::current_exe()?, &args);
+    let outcome = fs::read(&result_path).map_err(MyError::from).and_then(|bytes| Ok(serde_json::from_slice::<Outcome>(&bytes)?));
+    let _ = fs::remove_dir_all(&dir);
+    match (exit_code, outcome) {
+        (None, _) => Err(MyError::Permission("administrator rights were not granted".into())),
+        (Some(_), Ok(Outcome { error: Some(error), .. })) => {
+            Err(MyError::Io(io::Error::other(format!("the elevated write failed: {}", error))))
+        }
+        (Some(_), Ok(Outcome { inverse, error: None })) => Ok(inverse),
+        (Some(code), Err(err)) => {
+            warn!(code, %err, "elevated helper left no result");
+            Err(MyError::Io(io::Error::other(format!("the elevated helper exited with code {}", code))))
+        }
+    }
+}
+
+/// The helper's side of `write_elevated`; returns the process exit code.
+pub fn serve_elevated(request_path: &Path, result_path: &Path) -> i32 {
+    let result = fs::read(request_path)
+        .map_err(MyError::from)
+        .and_then(|bytes| Ok(serde_json::from_slice::<Request>(&bytes)?))
+        .and_then(|request| {
+            if !games::set_active(&request.game) {
+                return Err(MyError::Io(io::Error::other(format!("unknown game {}", request.game))));
+            }
+            keymap::set_active(request.names);
+            db::apply_patch(&request.db_path, &request.patch)
+        });
+    let (outcome, code) = match result {
+        Ok(inverse) => (Outcome { inverse, error: None }, 0),
+        Err(err) => (Outcome { inverse: None, error: Some(err.summary()) }, 1),
+    };
+    info!(code, "elevated write done");
+    match serde_json::to_vec(&outcome).map_err(io::Error::from).and_then(|bytes| fs::write(result_path, bytes)) {
+        Ok(()) => code,
+        Err(err) => {
+            warn!(%err, "could not hand the result back");
+            2
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use eframe::egui;
+
+/// What the user types to turn advanced mode on.
+const ACKNOWLEDGMENT: &str = "I have a backup";
+
+/// Raw JSON edits, SQL writes and removing or replacing rows can leave the game unable to read
+/// its settings or log in, so they stay locked until the user turns advanced mode on for this
+/// session and types an acknowledgment. Nothing is remembered: every start begins locked. The FPS
+/// patch and the Graphics tab never need it.
+#[derive(Default)]
+pub struct AdvancedMode {
+    on: bool,
+    confirming: bool,
+    typed: String,
+}
+
+impl AdvancedMode {
+    /// Why advanced actions are disabled, phrased for a tooltip; `None` while the mode is on.
+    pub fn block_reason(&self) -> Option<&'static str> {
+        (!self.on).then_some("Turn on advanced mode at the top of this tab first.")
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// The switch at the top of a tab with advanced actions.
+    pub fn show_switch(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if self.on {
+                ui.colored_label(ui.visuals().warn_fg_color, "Advanced mode is on until WuWa Ploom closes.");
+                if ui.small_button("Turn off").clicked() {
+                    self.on = false;
+                }
+            } else {
+                ui.label("Editing here is locked.");
+                if ui.small_button("Turn on advanced mode...").clicked() {
+                    self.confirming = true;
+                    self.typed.clear();
+                }
+            }
+        });
+        ui.separator();
+    }
+
+    /// The acknowledgment window, while it is open.
+    pub fn show_confirm(&mut self, ctx: &egui::Context) {
+        if !self.confirming {
+            return;
+        }
+        let mut answer = None;
+        egui::Window::new("Turn on advanced mode?").collapsible(false).resizable(false).show(ctx, |ui| {
+            ui.label("Advanced mode unlocks raw JSON edits, SQL writes, and removing or replacing rows in the configuration file.");
+            ui.label("A wrong value can reset your settings or log you out of the game, and the app can't always undo it. Backups are taken first when they are turned on in Settings.");
+            ui.label(format!("Type \"{}\" to continue:", ACKNOWLEDGMENT));
+            ui.text_edit_singleline(&mut self.typed);
+            ui.horizontal(|ui| {
+                let typed = self.typed.trim().eq_ignore_ascii_case(ACKNOWLEDGMENT);
+                let unlock = ui.add_enabled(typed, egui::Button::new("Turn on"));
+                if unlock.on_disabled_hover_text(format!("Type \"{}\" first.", ACKNOWLEDGMENT)).clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    answer = Some(false);
+                }
+            });
+        });
+        if let Some(confirmed) = answer {
+            self.confirming = false;
+            self.on = confirmed;
+            tracing::info!(confirmed, "advanced mode confirmation answered");
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Start with Windows: a value under the per-user Run key that starts the app minimized, where it
+//! keeps watched keys re-applied in the background. The entry is read back from the registry
+//! every time instead of being remembered, so the switch shows the truth after the user removes
+//! it by hand or moves the app.
+
+use std::path::Path;
+
+use tracing::info;
+
+use crate::platform::Platform;
+use crate::registry::{Hive, Location, View};
+use crate::{MyError, Result};
+
+/// Starts the app minimized to the taskbar.
+pub const MINIMIZED_ARG: &str = "--minimized";
+const RUN_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+const VALUE_NAME: &str = "WuWa Ploom";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum State {
+    Off,
+    On,
+    /// An entry starts another copy of the app, e.g. from before it was moved.
+    Elsewhere(String),
+}
+
+fn location() -> Location {
+    Location::new(Hive::CurrentUser, View::Registry64, RUN_KEY)
+}
+
+/// The Run entry's command line for the app at `exe`.
+pub fn command(exe: &Path) -> String {
+    format!("\"{}\" {}", exe.display(), MINIMIZED_ARG)
+}
+
+/// Whether Windows starts the app at `exe` on sign-in.
+pub fn state(platform: &dyn Platform, exe: &Path) -> State {
+    match platform.registry_string(&location(), VALUE_NAME) {
+        None => State::Off,
+        Some(entry) if entry.eq_ignore_ascii_case(&command(exe)) => State::On,
+        Some(entry) => State::Elsewhere(entry),
+    }
+}
+
+/// Adds or removes the Run entry for the app at `exe`.
+pub fn set(platform: &dyn Platform, exe: &Path, enabled: bool) -> Result<()> {
+    let command = enabled.then(|| command(exe));
+    if !platform.set_registry_string(&location(), VALUE_NAME, command.as_deref()) {
+        return Err(MyError::Permission(format!("could not change {}\\{}", location(), VALUE_NAME)));
+    }
+    info!(enabled, "changed start with Windows");
+    Ok(())
+}
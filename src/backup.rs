@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::{info, instrument, warn};
+
+use crate::db::{wait, Db};
+use crate::{presets, Result};
+
+/// This PC's folder under `root`, named after the machine. Backups go there, so a backup folder
+/// inside a sync folder holds every PC's backups apart and pruning only ever sees this PC's.
+pub fn for_this_pc(root: &Path) -> PathBuf {
+    let name: String = presets::machine_name()
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    root.join(name)
+}
+
+/// Copies the DB (and its write-ahead log and shared-memory index, if present) into `backup_dir`
+/// under a timestamped name, queued behind any write of ours in flight.
+#[instrument(err(level = "warn"))]
+pub fn backup_db(db_path: &str, backup_dir: &Path) -> Result<PathBuf> {
+    wait(Db::new(db_path).backup(backup_dir.to_path_buf()))
+}
+
+/// The copy itself; runs on the database's worker.
+pub(crate) fn copy_db(db_path: &str, backup_dir: &Path) -> Result<PathBuf> {
+    let target = copy_new(Path::new(db_path), backup_dir, "LocalStorage", "db")?;
+    for suffix in ["-wal", "-shm"] {
+        let side = format!("{}{}", db_path, suffix);
+        if Path::new(&side).exists() {
+            copy_to(Path::new(&side), &PathBuf::from(format!("{}{}", target.display(), suffix)))?;
+        }
+    }
+    info!(target = %target.display(), "backed up database");
+    Ok(target)
+}
+
+/// Copies `source` into `backup_dir` as `{stem}-{secs}.{ext}`, or `{stem}-{secs}-{n}.{ext}` when
+/// a backup was already taken that second; an existing backup is never written over.
+pub(crate) fn copy_new(source: &Path, backup_dir: &Path, stem: &str, ext: &str) -> Result<PathBuf> {
+    let mut from = File::open(source)?;
+    fs::create_dir_all(backup_dir)?;
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|since| since.as_secs()).unwrap_or_default();
+    for n in 0.. {
+        let name = match n {
+            0 => format!("{}-{}.{}", stem, stamp, ext),
+            n => format!("{}-{}-{}.{}", stem, stamp, n, ext),
+        };
+        let target = backup_dir.join(name);
+        match OpenOptions::new().write(true).create_new(true).open(&target) {
+            Ok(mut to) => {
+                if let Err(err) = io::copy(&mut from, &mut to) {
+                    let _ = fs::remove_file(&target);
+                    return Err(err.into());
+                }
+                return Ok(target);
+            }
+            Err(err) if err.kind() == ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+    unreachable!("ran out of backup names")
+}
+
+/// Copies `source` to `target`, failing if `target` is already there.
+fn copy_to(source: &Path, target: &Path) -> Result<()> {
+    let mut to = OpenOptions::new().write(true).create_new(true).open(target)?;
+    io::copy(&mut File::open(source)?, &mut to)?;
+    Ok(())
+}
+
+/// A backup in the backup folder: a database copy with its write-ahead log, or an INI copy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    pub path: PathBuf,
+    /// What was backed up: the name before the time stamp, e.g. `LocalStorage` or `Engine`.
+    pub source: String,
+    /// Seconds since the Unix epoch, from the name the backup was given.
+    pub taken: u64,
+    /// Bytes on disk, the write-ahead log and shared-memory index included.
+    pub size: u64,
+}
+
+/// The source and time stamp in a backup name such as `LocalStorage-1714557600.db` or, for a
+/// second backup within that second, `LocalStorage-1714557600-1.db`.
+fn parse_name(path: &Path) -> Option<(String, u64)> {
+    let (rest, last) = path.file_stem()?.to_str()?.rsplit_once('-')?;
+    let last = last.parse().ok()?;
+    match rest.rsplit_once('-').map(|(source, stamp)| (source, stamp.parse())) {
+        Some((source, Ok(stamp))) => Some((source.to_string(), stamp)),
+        _ => Some((rest.to_string(), last)),
+    }
+}
+
+/// Backups in `backup_dir`, oldest first; other files there are left alone.
+pub fn snapshots(backup_dir: &Path) -> Vec<Snapshot> {
+    let mut snapshots: Vec<Snapshot> = fs::read_dir(backup_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "db" || ext == "ini"))
+        .filter_map(|path| {
+            let (source, taken) = parse_name(&path)?;
+            let sides: u64 = ["-wal", "-shm"].iter().map(|suffix| fs::metadata(format!("{}{}", path.display(), suffix)).map_or(0, |meta| meta.len())).sum();
+            let size = fs::metadata(&path).ok()?.len() + sides;
+            Some(Snapshot { path, source, taken, size })
+        })
+        .collect();
+    snapshots.sort_by_key(|snapshot| snapshot.taken);
+    snapshots
+}
+
+/// Deletes the oldest backups in `backup_dir` until they fit in `budget` bytes, always keeping
+/// the newest one of each source, so an INI backup never costs the last database backup.
+/// Returns the backups removed.
+#[instrument]
+pub fn prune(backup_dir: &Path, budget: u64) -> Vec<Snapshot> {
+    let snapshots = snapshots(backup_dir);
+    let newest: HashMap<&str, &Path> = snapshots.iter().map(|snapshot| (snapshot.source.as_str(), snapshot.path.as_path())).collect();
+    let mut total: u64 = snapshots.iter().map(|snapshot| snapshot.size).sum();
+    let mut removed = Vec::new();
+    for snapshot in snapshots.iter().filter(|snapshot| newest.get(snapshot.source.as_str()) != Some(&snapshot.path.as_path())) {
+        if total <= budget {
+            break;
+        }
+        if let Err(err) = fs::remove_file(&snapshot.path) {
+            warn!(%err, path = %snapshot.path.display(), "could not remove old backup");
+            continue;
+        }
+        for suffix in ["-wal", "-shm"] {
+            let _ = fs::remove_file(format!("{}{}", snapshot.path.display(), suffix));
+        }
+        total -= snapshot.size;
+        removed.push(snapshot.clone());
+    }
+    if !removed.is_empty() {
+        info!(removed = removed.len(), left = total, "pruned old backups");
+    }
+    removed
+}
+
+/// Puts a backup taken by `backup_db` back in place of the DB, together with its write-ahead log
+/// and shared-memory index.
+#[instrument(err(level = "warn"))]
+pub fn restore_db(backup: &Path, db_path: &str) -> Result<()> {
+    let backup = backup.to_path_buf();
+    wait(Db::new(db_path).submit_file(move |db_path| put_back(&backup, db_path)))
+}
+
+fn put_back(backup: &Path, db_path: &str) -> Result<()> {
+    fs::copy(backup, db_path)?;
+    for suffix in ["-wal", "-shm"] {
+        let side = format!("{}{}", backup.display(), suffix);
+        let db_side = format!("{}{}", db_path, suffix);
+        if Path::new(&side).exists() {
+            fs::copy(&side, &db_side)?;
+        } else if Path::new(&db_side).exists() {
+            // A newer log would be replayed over the restored file.
+            fs::remove_file(&db_side)?;
+        }
+    }
+    info!("restored database");
+    Ok(())
+}
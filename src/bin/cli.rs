@@ -0,0 +1,405 @@
+//! Command-line frontend for scripts and headless use; no GUI stack involved.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Instant, SystemTime};
+
+use serde_json::Value;
+use wuwa_ploom_core::access::WriteAccess;
+use wuwa_ploom_core::db::{self, Patch};
+use wuwa_ploom_core::metadata::{Language, Metadata, Problem};
+use wuwa_ploom_core::stats::{self, Event};
+use wuwa_ploom_core::status::StatusEvent;
+use wuwa_ploom_core::watchdog::{self, Budget, Tuning};
+use wuwa_ploom_core::{backup, crash_loop, detect, frame_rate, game_update, games, history, keymap, logging, paths, platform, presets, process, sample, Result};
+
+const USAGE: &str = "\
+Usage: wuwa-ploom-cli [options] <command>
+
+Commands:
+  locate                   Print the path of the game's LocalStorage.db
+  get [KEY]                Print GameQualitySetting, or one of its keys
+  set-fps FPS              Write a frame-rate limit
+  set KEY=VALUE...         Write top-level keys; VALUE is JSON, or a string if it isn't
+  apply-preset FILE        Write the settings of a preset file
+  share-code               Print the current settings as a share code
+  apply-code CODE          Show what the settings of a share code change; with --yes,
+                           write them. Invalid values and display keys are skipped
+  backup                   Copy the database into the backup folder
+  restore FILE             Put a backup back in place
+  keep KEY=VALUE...        Stay running and re-apply the values whenever they change
+  reset-sample             Replace the sample database with a fresh one
+
+Options:
+  --db PATH                Use this LocalStorage.db instead of locating it
+  --sample                 Try commands on a made-up LocalStorage.db in the app folder,
+                           created on first use; the game's file is not touched
+  --game ID                Patch another game: wuwa (default), or unreal for any other
+                           Unreal game's LocalStorage.db (with --db)
+  --backup-dir DIR         Where backups go (default: the app's backups folder)
+  --no-backup              Don't back up before writing
+  --yes                    Let apply-code write what it shows
+  --interval SECS          How often `keep` checks the file (default: 10)
+  --debounce SECS          How long the game must leave the file alone before `keep`
+                           re-applies (default: 5)
+  --max-per-hour N         Re-applies `keep` makes in an hour before it pauses, so it
+                           can't loop with the game; 0 for no limit (default: 10)
+  -v, --verbose            Log debug detail to the log file
+  -h, --help               Show this text";
+
+struct Options {
+    db: Option<String>,
+    sample: bool,
+    backup_dir: Option<PathBuf>,
+    backup: bool,
+    /// apply-code may write; without it, it only shows the changes.
+    yes: bool,
+    watchdog: Tuning,
+    verbose: bool,
+    command: Vec<String>,
+}
+
+fn parse_args(args: &[String]) -> std::result::Result<Options, String> {
+    let watchdog = Tuning { poll_secs: 10, ..Tuning::default() };
+    let mut options = Options { db: None, sample: false, backup_dir: None, backup: true, yes: false, watchdog, verbose: false, command: Vec::new() };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().cloned().ok_or_else(|| format!("{} needs a value", name));
+        match arg.as_str() {
+            "--db" => options.db = Some(value(arg)?),
+            "--game" => {
+                let id = value(arg)?;
+                if !games::set_active(&id) {
+                    let known: Vec<&str> = games::ALL.iter().map(|game| game.id()).collect();
+                    return Err(format!("unknown game {}; known: {}", id, known.join(", ")));
+                }
+            }
+            "--backup-dir" => options.backup_dir = Some(PathBuf::from(value(arg)?)),
+            "--sample" => options.sample = true,
+            "--no-backup" => options.backup = false,
+            "--yes" => options.yes = true,
+            "-v" | "--verbose" => options.verbose = true,
+            "--interval" => {
+                let secs: u64 = value(arg)?.parse().map_err(|_| "--interval takes whole seconds".to_string())?;
+                options.watchdog.poll_secs = secs;
+            }
+            "--debounce" => {
+                options.watchdog.debounce_secs = value(arg)?.parse().map_err(|_| "--debounce takes whole seconds".to_string())?;
+            }
+            "--max-per-hour" => {
+                options.watchdog.max_per_hour = value(arg)?.parse().map_err(|_| "--max-per-hour takes a whole number".to_string())?;
+            }
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ => options.command.push(arg.clone()),
+        }
+    }
+    Ok(options)
+}
+
+/// `KEY=VALUE` pairs as a patch; values that aren't valid JSON are taken as strings.
+fn parse_assignments(args: &[String]) -> std::result::Result<Patch, String> {
+    if args.is_empty() {
+        return Err("expected at least one KEY=VALUE".into());
+    }
+    let mut patch = Patch::new();
+    for arg in args {
+        let (key, value) = arg.split_once('=').ok_or_else(|| format!("{} is not KEY=VALUE", arg))?;
+        let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_string()));
+        patch.insert(key.to_string(), value);
+    }
+    Ok(patch)
+}
+
+impl Options {
+    fn db_path(&self) -> Result<String> {
+        match &self.db {
+            Some(path) => Ok(path.clone()),
+            None if self.sample => sample::existing_or_new(),
+            None => detect::get_game_install_path(),
+        }
+    }
+
+    fn backup_dir(&self) -> PathBuf {
+        let default = if self.sample { sample::backup_dir() } else { backup::for_this_pc(&paths::app_dir().join("backups")) };
+        self.backup_dir.clone().unwrap_or(default)
+    }
+
+    /// Backs up (unless disabled) and applies `patch`, refusing while the game holds the file.
+    fn write(&self, db_path: &str, label: &str, patch: &Patch) -> std::result::Result<(), String> {
+        let game_file = !sample::is_sample(db_path);
+        if game_file && process::is_game_running(platform::native()) {
+            return Err(report(StatusEvent::Refused { reason: "Close the game first; it overwrites the file when it exits.".into() }));
+        }
+        if let Some(sign) = game_file.then(|| game_update::in_progress(platform::native(), Path::new(db_path))).flatten() {
+            let reason = format!("The launcher is updating the game ({}); try again once it finishes.", sign.describe());
+            return Err(report(StatusEvent::Refused { reason }));
+        }
+        check_access(db_path)?;
+        if self.backup {
+            let path = backup::backup_db(db_path, &self.backup_dir())
+                .map_err(|err| report(StatusEvent::Failed { error: format!("backup failed: {}", err) }))?;
+            print(&StatusEvent::BackedUp { path });
+            stats::record(Event::Backup);
+        }
+        match db::apply_patch(db_path, patch).map_err(|err| report(StatusEvent::Failed { error: err.to_string() }))? {
+            Some(inverse) => {
+                stats::record(Event::Patch);
+                let event = StatusEvent::Written { label: label.to_string(), keys: inverse.len() };
+                history::record_write(&event, patch, &inverse);
+                print(&event);
+            }
+            None => print(&StatusEvent::AlreadySet { label: label.to_string() }),
+        }
+        Ok(())
+    }
+
+    /// Polls the file and re-applies `patch` whenever the game has changed any of its keys. A
+    /// round that fails on a locked file is retried; any other failure is logged as a crash and
+    /// retried, until crashes repeat too quickly; see `crash_loop`.
+    fn keep(&self, db_path: &str, patch: &Patch) -> std::result::Result<(), String> {
+        println!("Keeping {} key(s) in {}; press Ctrl+C to stop", patch.len(), db_path);
+        let mut state = KeepState::default();
+        loop {
+            let reason = match panic::catch_unwind(AssertUnwindSafe(|| keep_round(db_path, patch, &self.watchdog, &mut state))) {
+                Ok(Ok(())) => None,
+                Ok(Err(err)) if !crash_loop::is_crash(&err) => {
+                    tracing::info!(%err, "the file is busy; trying again next round");
+                    None
+                }
+                Ok(Err(err)) => Some(err.to_string()),
+                Err(_) => Some("panicked".to_string()),
+            };
+            if let Some(reason) = reason {
+                // Earlier crashes, including those of watchdogs started before this one.
+                let mut crashes = crash_loop::logged(crash_loop::CRASH_MARKER);
+                crashes.push(SystemTime::now());
+                if crash_loop::is_loop(&crashes, SystemTime::now()) {
+                    tracing::error!(%reason, "{}", crash_loop::STOP_MARKER);
+                    let log = crash_loop::current_log().unwrap_or_else(logging::log_dir);
+                    let error = format!(
+                        "the watchdog failed {} times within {} minutes and stopped ({}). Details are in {}",
+                        crash_loop::MAX_CRASHES,
+                        crash_loop::WINDOW.as_secs() / 60,
+                        reason,
+                        log.display()
+                    );
+                    return Err(report(StatusEvent::Failed { error }));
+                }
+                tracing::error!(%reason, "{}", crash_loop::CRASH_MARKER);
+                print(&StatusEvent::Failed { error: format!("watchdog round failed ({}); trying again", reason) });
+            }
+            thread::sleep(self.watchdog.poll_interval());
+        }
+    }
+}
+
+/// What `Options::keep` remembers between rounds.
+#[derive(Default)]
+struct KeepState {
+    /// The settings as the game last left them with kept keys changed, and when that was seen.
+    drifted: Option<(Value, Instant)>,
+    budget: Budget,
+    /// Set once the hourly limit was reported, until a re-apply goes through again.
+    paused: bool,
+}
+
+/// One look at the file for `Options::keep`. Kept keys are only written back once the game has
+/// left the file alone for the debounce, and no more often than the hourly limit.
+fn keep_round(db_path: &str, patch: &Patch, tuning: &Tuning, state: &mut KeepState) -> Result<()> {
+    // A failed read usually means the game is mid-write; try again next round.
+    let setting = db::read_quality_setting_file(db_path).ok();
+    let updating = game_update::in_progress(platform::native(), Path::new(db_path)).is_some();
+    if let Some(setting) = setting.filter(|_| !updating && !process::is_game_running(platform::native())) {
+        if db::inverse_patch(&setting, patch).is_empty() {
+            state.drifted = None;
+            return Ok(());
+        }
+        if state.drifted.as_ref().is_none_or(|(seen, _)| *seen != setting) {
+            state.drifted = Some((setting, Instant::now()));
+        }
+        let settled = state.drifted.as_ref().is_some_and(|(_, since)| since.elapsed() >= tuning.debounce());
+        if !settled {
+            return Ok(());
+        }
+        if !state.budget.take(Instant::now(), tuning.max_per_hour) {
+            if !state.paused {
+                state.paused = true;
+                print(&StatusEvent::Refused {
+                    reason: format!(
+                        "The game changed kept keys back {} times within an hour; pausing re-applies for {} min so the two don't loop.",
+                        tuning.max_per_hour,
+                        state.budget.frees_at().map_or(0, |at| watchdog::minutes_until(at, Instant::now()))
+                    ),
+                });
+            }
+            return Ok(());
+        }
+        state.paused = false;
+        state.drifted = None;
+        tracing::info!("the game changed kept keys; re-applying");
+        stats::record(Event::Revert);
+        let event = StatusEvent::Reapplied { keys: patch.len() };
+        if let Some(inverse) = db::apply_patch(db_path, patch)? {
+            history::record_write(&event, patch, &inverse);
+        }
+        stats::record(Event::Patch);
+        print(&event);
+    }
+    Ok(())
+}
+
+/// Results go to standard output; backups taken on the way, warnings and errors to standard
+/// error, so scripts can read the outcome alone.
+fn print(event: &StatusEvent) {
+    event.log();
+    match event {
+        StatusEvent::Written { .. } | StatusEvent::AlreadySet { .. } | StatusEvent::Reapplied { .. } => println!("{}", event.message()),
+        _ => eprintln!("{}", event.message()),
+    }
+}
+
+/// Logs an event that ends the command and returns its message, which `main` prints to
+/// standard error like `print` would.
+fn report(event: StatusEvent) -> String {
+    event.log();
+    event.message()
+}
+
+/// A share code's settings as the app's review stages them: keys that fail validation or
+/// describe the display of the PC the code came from are left out, each with a note.
+fn reviewed_code(settings: &Patch, game_version: Option<&str>) -> Patch {
+    let metadata = Metadata::load(Language::English);
+    let mut patch = Patch::new();
+    for (key, value) in settings {
+        if presets::is_machine_specific(key) {
+            eprintln!("Skipped {}: it describes the display of the PC the code came from.", key);
+            continue;
+        }
+        match metadata.get(key).and_then(|meta| meta.check(value, game_version)) {
+            Some(Problem::Invalid(text)) => {
+                eprintln!("Skipped: {}.", text);
+                continue;
+            }
+            Some(Problem::Unavailable(text)) => eprintln!("Warning: {}.", text),
+            None => {}
+        }
+        patch.insert(key.clone(), value.clone());
+    }
+    patch
+}
+
+/// One line per value `patch` changes in `setting`, as `KEY: before -> after`.
+fn print_changes(setting: &Value, patch: &Patch) {
+    let changed = db::inverse_patch(setting, patch);
+    for (key, value) in patch.iter().filter(|(key, _)| changed.contains_key(*key)) {
+        let before = setting.get(key).map_or_else(|| "(not set)".to_string(), Value::to_string);
+        println!("  {}: {} -> {}", key, before, value);
+    }
+}
+
+/// Fails before any backup or write when the file can't be written from this process.
+fn check_access(db_path: &str) -> std::result::Result<(), String> {
+    match WriteAccess::check(Path::new(db_path), platform::native()) {
+        WriteAccess::Writable => Ok(()),
+        WriteAccess::NeedsElevation => {
+            Err(report(StatusEvent::Refused { reason: format!("{} can only be written as administrator; run this from an elevated prompt.", db_path) }))
+        }
+        access => Err(report(StatusEvent::Refused { reason: access.describe() })),
+    }
+}
+
+fn run(options: &Options) -> std::result::Result<(), String> {
+    let Some((command, rest)) = options.command.split_first() else {
+        return Err(USAGE.into());
+    };
+    let db_path = || options.db_path().map_err(|err| format!("Could not locate LocalStorage.db: {}", err));
+    match (command.as_str(), rest) {
+        ("locate", []) => println!("{}", db_path()?),
+        ("reset-sample", []) => println!("{}", sample::reset().map_err(|err| err.to_string())?),
+        ("get", []) => {
+            let setting = db::read_quality_setting_file(&db_path()?).map_err(|err| err.to_string())?;
+            println!("{}", serde_json::to_string_pretty(&setting).unwrap_or_default());
+        }
+        ("get", [key]) => {
+            let setting = db::read_quality_setting_file(&db_path()?).map_err(|err| err.to_string())?;
+            let value = setting.get(key).ok_or_else(|| format!("{} is not set", key))?;
+            println!("{}", value);
+        }
+        ("set-fps", [fps]) => {
+            let fps: i64 = fps.parse().map_err(|_| format!("{} is not a number", fps))?;
+            let db_path = db_path()?;
+            let warning = db::read_quality_setting_file(&db_path).ok().and_then(|setting| frame_rate::menu_option(&setting).warning());
+            if let Some(warning) = warning.filter(|_| !frame_rate::MENU_LIMITS.contains(&fps)) {
+                eprintln!("Warning: {}", warning);
+            }
+            options.write(&db_path, &format!("FPS set to {}", fps), &db::fps_patch(fps))?;
+        }
+        ("set", assignments) => {
+            let patch = parse_assignments(assignments)?;
+            options.write(&db_path()?, &format!("Set {}", patch.keys().cloned().collect::<Vec<_>>().join(", ")), &patch)?;
+        }
+        ("apply-preset", [file]) => {
+            let preset = presets::read_preset(Path::new(file)).map_err(|err| format!("Error reading {}: {}", file, err))?;
+            options.write(&db_path()?, &format!("Applied preset {}", preset.name), &preset.settings)?;
+        }
+        ("share-code", []) => {
+            let setting = db::read_quality_setting_file(&db_path()?).map_err(|err| err.to_string())?;
+            let code = presets::from_setting("Shared settings", &setting).and_then(|preset| presets::to_share_code(&preset));
+            println!("{}", code.map_err(|err| err.to_string())?);
+        }
+        ("apply-code", [code]) => {
+            let preset = presets::from_share_code(code).map_err(|err| format!("Error reading the share code: {}", err))?;
+            let db_path = db_path()?;
+            let patch = reviewed_code(&preset.settings, detect::get_game_version().as_deref());
+            let setting = db::read_quality_setting_file(&db_path).map_err(|err| err.to_string())?;
+            if !db::inverse_patch(&setting, &patch).is_empty() {
+                println!("The share code changes:");
+                print_changes(&setting, &patch);
+                if !options.yes {
+                    return Err(report(StatusEvent::Refused { reason: "Nothing was written; run again with --yes to write these changes.".into() }));
+                }
+            }
+            options.write(&db_path, "Applied the share code", &patch)?;
+        }
+        ("backup", []) => {
+            let path = backup::backup_db(&db_path()?, &options.backup_dir()).map_err(|err| err.to_string())?;
+            stats::record(Event::Backup);
+            println!("{}", path.display());
+        }
+        ("restore", [file]) => {
+            backup::restore_db(Path::new(file), &db_path()?).map_err(|err| err.to_string())?;
+            println!("Restored {}", file);
+        }
+        ("keep", assignments) => {
+            let patch = parse_assignments(assignments)?;
+            let db_path = db_path()?;
+            check_access(&db_path)?;
+            options.keep(&db_path, &patch)?;
+        }
+        _ => return Err(format!("unknown command or wrong arguments: {}\n\n{}", options.command.join(" "), USAGE)),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let result = parse_args(&args).and_then(|options| {
+        let _log_guard = logging::init(options.verbose);
+        tracing::info!(command = ?options.command, "wuwa-ploom-cli");
+        keymap::select(detect::get_game_version().as_deref());
+        run(&options)
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
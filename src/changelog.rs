@@ -0,0 +1,62 @@
+//! Release notes, shipped inside the binary so the app can show what changed since the version
+//! the user last ran without going online. `CHANGELOG.md` has a `## version` section per
+//! release, newest first; a `### Game settings` heading inside one lists changes to how game
+//! settings are read or written.
+
+use crate::update::is_newer;
+
+pub const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+const GAME_SETTINGS: &str = "game settings";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Release {
+    pub version: String,
+    pub notes: Vec<String>,
+    /// Changes to game settings or their names that this release handles.
+    pub game_settings: Vec<String>,
+}
+
+/// The releases in `text`, in file order.
+pub fn parse(text: &str) -> Vec<Release> {
+    let mut releases: Vec<Release> = Vec::new();
+    let mut in_game_settings = false;
+    for line in text.lines().map(str::trim) {
+        if let Some(version) = line.strip_prefix("## ") {
+            // Headings may carry a date after the version.
+            let version = version.split_whitespace().next().unwrap_or_default().trim_start_matches('v');
+            releases.push(Release { version: version.to_string(), ..Default::default() });
+            in_game_settings = false;
+        } else if let Some(heading) = line.strip_prefix("### ") {
+            in_game_settings = heading.trim().eq_ignore_ascii_case(GAME_SETTINGS);
+        } else if let Some(note) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            let Some(release) = releases.last_mut() else {
+                continue;
+            };
+            let list = if in_game_settings { &mut release.game_settings } else { &mut release.notes };
+            list.push(note.trim().to_string());
+        } else if !line.is_empty() {
+            // A bullet carried onto the next line.
+            let Some(release) = releases.last_mut() else {
+                continue;
+            };
+            if let Some(note) = if in_game_settings { release.game_settings.last_mut() } else { release.notes.last_mut() } {
+                note.push(' ');
+                note.push_str(line);
+            }
+        }
+    }
+    releases
+}
+
+/// Releases after `last_seen` up to and including `current`.
+pub fn since<'a>(releases: &'a [Release], last_seen: &str, current: &str) -> Vec<&'a Release> {
+    releases
+        .iter()
+        .filter(|release| is_newer(&release.version, last_seen) && !is_newer(&release.version, current))
+        .collect()
+}
+
+/// The shipped notes.
+pub fn releases() -> Vec<Release> {
+    parse(CHANGELOG)
+}
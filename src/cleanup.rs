@@ -0,0 +1,102 @@
+//! LocalStorage rows the client only caches and rebuilds when they are missing. Stale copies
+//! left by an update are a common cause of odd settings behaviour, so the Keys tab can remove
+//! them after a backup. Which rows qualify is data, like the key map: an embedded table of name
+//! patterns, extended by a `transient_keys.json` in the app folder. The embedded table is empty
+//! until a row is known to be rebuilt by the client; a guessed pattern would delete data the
+//! game never gets back. The settings row and account or device identifiers are never touched,
+//! whatever the patterns say.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tracing::{info, instrument, warn};
+
+use crate::backup;
+use crate::db::{self, KeyEntry};
+use crate::keymap;
+use crate::paths::app_dir;
+use crate::privacy;
+use crate::Result;
+
+const TRANSIENT_KEYS: &str = include_str!("../misc/transient_keys.json");
+const OVERRIDE_FILE: &str = "transient_keys.json";
+
+/// Rows matching `pattern` are safe to remove; `*` stands for any run of characters.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Transient {
+    pub pattern: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Case-insensitive match of `key` against `pattern`.
+pub fn matches(pattern: &str, key: &str) -> bool {
+    let (pattern, key) = (pattern.to_ascii_lowercase(), key.to_ascii_lowercase());
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = key.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // No `*`: the whole key had to match.
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(index) => rest = &rest[index + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// The embedded table, with the app folder's override file added.
+pub fn registry() -> Vec<Transient> {
+    let mut entries: Vec<Transient> = serde_json::from_str(TRANSIENT_KEYS).expect("embedded transient key table is valid JSON");
+    let path = app_dir().join(OVERRIDE_FILE);
+    if let Ok(text) = fs::read_to_string(&path) {
+        match serde_json::from_str::<Vec<Transient>>(&text) {
+            Ok(extra) => entries.extend(extra),
+            Err(err) => warn!(path = %path.display(), %err, "ignored transient key override"),
+        }
+    }
+    entries
+}
+
+/// The first registry entry covering the row `key`, unless the row must be kept.
+pub fn transient<'a>(registry: &'a [Transient], key: &str) -> Option<&'a Transient> {
+    if key == keymap::active().setting_row || privacy::classify(key).is_some() {
+        return None;
+    }
+    registry.iter().find(|entry| matches(&entry.pattern, key))
+}
+
+/// Rows of the database at `db_path` that can be removed, with the entry covering each.
+pub fn find(db_path: &str) -> Result<Vec<(KeyEntry, Transient)>> {
+    find_with(db_path, &registry())
+}
+
+/// `find` against the entries of `registry`.
+pub fn find_with(db_path: &str, registry: &[Transient]) -> Result<Vec<(KeyEntry, Transient)>> {
+    let listing = db::list_keys(db_path, "", usize::MAX)?;
+    Ok(listing
+        .entries
+        .into_iter()
+        .filter_map(|entry| {
+            let rule = transient(registry, &entry.key)?.clone();
+            Some((entry, rule))
+        })
+        .collect())
+}
+
+/// Backs the database up into `backup_dir`, then removes `keys`. Returns the backup and the
+/// number of rows removed.
+#[instrument(skip(keys), err(level = "warn"))]
+pub fn clean(db_path: &str, backup_dir: &Path, keys: &[String]) -> Result<(PathBuf, usize)> {
+    let backup = backup::backup_db(db_path, backup_dir)?;
+    let removed = db::delete_rows(db_path, keys)?;
+    info!(removed, "removed cached rows");
+    Ok((backup, removed))
+}
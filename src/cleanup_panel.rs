@@ -0,0 +1,59 @@
+use std::path::Path;
+
+use eframe::egui;
+use wuwa_ploom_core::cleanup::{self, Transient};
+use wuwa_ploom_core::db::KeyEntry;
+
+use crate::toasts::ToastLevel;
+
+/// Finds cached rows that regenerate and removes them after a backup, for settings that act up
+/// after an update.
+#[derive(Default)]
+pub struct CleanupPanel {
+    found: Option<Result<Vec<(KeyEntry, Transient)>, String>>,
+}
+
+impl CleanupPanel {
+    /// Returns a message to show once rows were removed, or removing failed.
+    pub fn show(&mut self, ui: &mut egui::Ui, db_path: &str, blocked: Option<&str>, backup_dir: &Path) -> Option<(ToastLevel, String)> {
+        let mut clean = false;
+        egui::CollapsingHeader::new("Clean up cached data").show(ui, |ui| {
+            ui.label("If settings behave oddly after a game update, removing the rows the game only caches can help. The game rebuilds them on its next start. A backup is taken first.");
+            if ui.button("Find cached rows").clicked() {
+                self.found = Some(cleanup::find(db_path).map_err(|err| err.to_string()));
+            }
+            match &self.found {
+                Some(Ok(rows)) if rows.is_empty() => {
+                    ui.label("Nothing to clean up. Only rows named in transient_keys.json in the app folder are listed.");
+                }
+                Some(Ok(rows)) => {
+                    egui::Grid::new("cleanup_rows").striped(true).num_columns(3).show(ui, |ui| {
+                        for (entry, rule) in rows {
+                            ui.label(&entry.key);
+                            ui.label(format!("{} B", entry.size));
+                            ui.weak(&rule.description);
+                            ui.end_row();
+                        }
+                    });
+                    let remove = ui.add_enabled(blocked.is_none(), egui::Button::new(format!("Back up and remove {} row(s)", rows.len())));
+                    clean = remove.on_disabled_hover_text(blocked.unwrap_or_default()).clicked();
+                }
+                Some(Err(err)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, err);
+                }
+                None => {}
+            }
+        });
+        if !clean {
+            return None;
+        }
+        let Some(Ok(rows)) = self.found.take() else {
+            return None;
+        };
+        let keys: Vec<String> = rows.into_iter().map(|(entry, _)| entry.key).collect();
+        Some(match cleanup::clean(db_path, backup_dir, &keys) {
+            Ok((backup, removed)) => (ToastLevel::Success, format!("Removed {} cached row(s); backup at {}", removed, backup.display())),
+            Err(err) => (ToastLevel::Error, format!("Error cleaning up: {}", err)),
+        })
+    }
+}
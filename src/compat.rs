@@ -0,0 +1,40 @@
+use serde_json::Value;
+
+/// A set of field values that users have reported to crash or hang the client when stored together.
+struct Combination {
+    fields: &'static [(&'static str, i64)],
+    warning: &'static str,
+}
+
+const KNOWN_BAD: &[Combination] = &[
+    Combination {
+        fields: &[("KeyNvidiaSuperSamplingEnable", 1), ("KeyFsrEnable", 1)],
+        warning: "DLSS and FSR are both on; reported to crash the client on start.",
+    },
+    Combination {
+        fields: &[("KeyNvidiaSuperSamplingEnable", 1), ("KeyXessEnable", 1)],
+        warning: "DLSS and XeSS are both on; reported to crash the client on start.",
+    },
+    Combination {
+        fields: &[("KeyFsrEnable", 1), ("KeyXessEnable", 1)],
+        warning: "FSR and XeSS are both on; reported to hang the client while loading.",
+    },
+    Combination {
+        fields: &[("KeyNvidiaSuperSamplingMode", 0), ("KeyAntiAliasing", 0)],
+        warning: "DLAA needs anti-aliasing on; with it off the game is reported to show a black screen.",
+    },
+];
+
+/// Warnings for every known-bad combination present in `setting`.
+pub fn warnings(setting: &Value) -> Vec<&'static str> {
+    KNOWN_BAD
+        .iter()
+        .filter(|combination| {
+            combination
+                .fields
+                .iter()
+                .all(|(key, value)| setting.get(key).and_then(Value::as_i64) == Some(*value))
+        })
+        .map(|combination| combination.warning)
+        .collect()
+}
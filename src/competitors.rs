@@ -0,0 +1,104 @@
+//! Other FPS unlockers. Two tools writing the frame-rate limit keep overwriting each other,
+//! which users see as the value flip-flopping. We look for other unlockers' processes and
+//! scheduled tasks and for the frame-rate lines some of them put into Engine.ini, and notice a
+//! value that keeps switching back and forth while the game is closed.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+use crate::engine_ini::{self, SYSTEM_SETTINGS};
+use crate::platform::Platform;
+use crate::{ini, MyError, Result};
+
+/// Engine.ini lines that cap or lift the frame rate outside the settings database.
+const INI_LINES: &[(&str, &str)] = &[(SYSTEM_SETTINGS, "t.MaxFPS"), ("/Script/Engine.Engine", "FrameRateLimit")];
+/// Changes within this long that make the value flip-flop.
+pub const FLIP_WINDOW: Duration = Duration::from_secs(10 * 60);
+/// Returns to an earlier value within `FLIP_WINDOW` that count as a fight.
+const FLIPS: usize = 2;
+
+/// Evidence of another unlocker.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Sign {
+    Process(String),
+    ScheduledTask(String),
+    EngineIni { key: String, value: String },
+}
+
+impl Sign {
+    pub fn describe(&self) -> String {
+        match self {
+            Sign::Process(name) => format!("{} is running", name),
+            Sign::ScheduledTask(name) => format!("the scheduled task {} runs an unlocker", name),
+            Sign::EngineIni { key, value } => format!("Engine.ini sets {}={}", key, value),
+        }
+    }
+}
+
+/// Whether a program or task called `name` looks like an FPS unlocker other than ours.
+pub fn is_unlocker_name(name: &str) -> bool {
+    let name: String = name.chars().filter(char::is_ascii_alphanumeric).collect::<String>().to_ascii_lowercase();
+    name.contains("unlock") && ["fps", "framerate", "wuwa", "wuthering"].iter().any(|word| name.contains(word)) && !name.contains("ploom")
+}
+
+pub fn unlocker_processes(platform: &dyn Platform) -> Vec<Sign> {
+    let mut names: Vec<String> = platform.processes().into_iter().map(|process| process.name).filter(|name| is_unlocker_name(name)).collect();
+    names.sort();
+    names.dedup();
+    names.into_iter().map(Sign::Process).collect()
+}
+
+/// Task names in `schtasks /query /fo csv /nh` output that look like unlockers.
+pub fn parse_scheduled_tasks(csv: &str) -> Vec<String> {
+    let mut names: Vec<String> = csv
+        .lines()
+        .filter_map(|line| line.split("\",\"").next())
+        .map(|name| name.trim().trim_matches('"').to_string())
+        .filter(|name| is_unlocker_name(name.rsplit('\\').next().unwrap_or(name)))
+        .collect();
+    names.dedup();
+    names
+}
+
+fn schtasks() -> Result<String> {
+    if !cfg!(windows) {
+        return Err(MyError::Io(io::Error::from(io::ErrorKind::Unsupported)));
+    }
+    let mut command = Command::new("schtasks");
+    command.args(["/query", "/fo", "csv", "/nh"]);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    Ok(String::from_utf8_lossy(&command.output()?.stdout).into_owned())
+}
+
+/// Frame-rate lines in Engine.ini `text`.
+pub fn ini_signs(text: &str) -> Vec<Sign> {
+    INI_LINES
+        .iter()
+        .filter_map(|(section, key)| Some(Sign::EngineIni { key: key.to_string(), value: ini::get(text, section, key)? }))
+        .collect()
+}
+
+/// Every sign of another unlocker on this PC and in the game's config next to `db_path`.
+pub fn find(platform: &dyn Platform, db_path: &str) -> Vec<Sign> {
+    let mut signs = unlocker_processes(platform);
+    signs.extend(schtasks().map(|csv| parse_scheduled_tasks(&csv)).unwrap_or_default().into_iter().map(Sign::ScheduledTask));
+    let ini = engine_ini::path_for_db(Path::new(db_path)).and_then(|path| engine_ini::read(&path).ok());
+    signs.extend(ini.map(|text| ini_signs(&text)).unwrap_or_default());
+    signs
+}
+
+/// Whether `changes`, the frame-rate values seen with when they appeared while the game was
+/// closed, keep going back to earlier values within `FLIP_WINDOW` before `now`.
+pub fn is_flip_flopping(changes: &[(SystemTime, i64)], now: SystemTime) -> bool {
+    let recent: Vec<i64> =
+        changes.iter().filter(|(time, _)| now.duration_since(*time).is_ok_and(|age| age <= FLIP_WINDOW)).map(|(_, value)| *value).collect();
+    let returns = recent.iter().enumerate().filter(|(index, value)| index >= &2 && recent[..index - 1].contains(value)).count();
+    returns >= FLIPS
+}
@@ -0,0 +1,179 @@
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use wuwa_ploom_core::backup;
+use wuwa_ploom_core::games::{self, GameAdapter};
+pub use wuwa_ploom_core::metadata::Language;
+use wuwa_ploom_core::platform::Priority;
+use wuwa_ploom_core::schema::{self, Migration};
+use wuwa_ploom_core::store;
+use wuwa_ploom_core::watchdog::Tuning;
+
+use crate::paths::app_dir;
+use crate::pinned;
+use crate::watch::WatchedKey;
+use crate::Result;
+
+const CONFIG_FILE: &str = "config.json";
+/// Upgrades for the config file, oldest first; see `schema::migrate`.
+const MIGRATIONS: &[Migration] = &[schema::unversioned_to_v1, detect_on_launch];
+const CONFIG_VERSION: u64 = MIGRATIONS.len() as u64;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Theme {
+    Dark,
+    Light,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub version: u64,
+    /// `GameAdapter::id` of the game being patched.
+    pub game: String,
+    pub backup_enabled: bool,
+    /// Empty means the default folder under the app directory. Backups go in this PC's folder
+    /// inside it; see `backup::for_this_pc`.
+    pub backup_dir: String,
+    /// Megabytes the backups may take before the oldest are deleted; 0 for no limit.
+    pub backup_budget_mb: u64,
+    /// Folder inside OneDrive, Dropbox or the like holding profiles, and backups in per-PC
+    /// folders unless `backup_dir` is set, so every PC sees them; empty for the app folder.
+    pub sync_folder: String,
+    pub theme: Theme,
+    pub language: Language,
+    /// Replace the native window frame with a title bar drawn in the app theme.
+    pub custom_title_bar: bool,
+    /// Bigger text and controls for handheld and TV screens.
+    pub large_ui: bool,
+    pub start_minimized: bool,
+    pub auto_detect_on_launch: bool,
+    pub confirm_before_write: bool,
+    pub auto_refresh: bool,
+    /// Ask GitHub for a newer release at startup; off unless the user opts in.
+    pub check_updates: bool,
+    /// Announce background events through the system while the window is in the background.
+    pub system_notifications: bool,
+    /// Shows the SQL console tab; off unless the user opts in.
+    pub sql_console: bool,
+    /// After an FPS change, offer to set the same limit in the game's RivaTuner Statistics
+    /// Server profile.
+    pub rtss_profile: bool,
+    /// Keep the NVIDIA driver's Max Frame Rate for the game equal to the FPS limit we write.
+    pub nvidia_frame_limit: bool,
+    /// Suggest a frame-rate limit just below the display's refresh rate, for G-Sync and FreeSync.
+    pub vrr_mode: bool,
+    /// Put back the display's own refresh rate when the game exits after we switched it.
+    pub restore_refresh_rate: bool,
+    /// A few minutes after the game starts following an FPS change, read the file again and
+    /// say whether the limit survived.
+    pub read_back_after_launch: bool,
+    /// Switch to the fastest power plan when the game starts; the user's plan comes back when it exits.
+    pub power_plan_while_playing: bool,
+    /// Priority given to the game's processes when they start; `Normal` leaves them alone.
+    pub game_priority: Priority,
+    /// Logical CPUs the game may run on, e.g. `0-7`; empty for all of them.
+    pub game_affinity: String,
+    /// After each write, copy the frame-rate limit and resolution into GameUserSettings.ini where
+    /// it disagrees, so the game doesn't snap back to the INI's values.
+    pub sync_game_user_settings: bool,
+    /// Show in Discord that re-asserted watched keys are being kept, through the Discord
+    /// application `discord_app_id`.
+    pub discord_presence: bool,
+    pub discord_app_id: String,
+    pub watched: Vec<WatchedKey>,
+    /// How often the file is checked, and how eagerly re-asserted watched keys are put back.
+    pub watchdog: Tuning,
+    /// GameQualitySetting fields read out on the Patcher tab.
+    pub pinned_keys: Vec<String>,
+    /// Every configuration file loaded, to compare installs with each other.
+    pub known_dbs: Vec<String>,
+    /// The app version last started, for showing what changed after an update; empty before
+    /// the first start.
+    pub last_seen_version: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            game: games::WutheringWaves.id().into(),
+            backup_enabled: true,
+            backup_dir: String::new(),
+            backup_budget_mb: 200,
+            sync_folder: String::new(),
+            theme: Theme::Dark,
+            language: Language::English,
+            custom_title_bar: false,
+            large_ui: false,
+            start_minimized: false,
+            auto_detect_on_launch: true,
+            confirm_before_write: false,
+            auto_refresh: true,
+            check_updates: false,
+            system_notifications: true,
+            sql_console: false,
+            rtss_profile: false,
+            nvidia_frame_limit: false,
+            vrr_mode: false,
+            restore_refresh_rate: true,
+            read_back_after_launch: true,
+            power_plan_while_playing: false,
+            game_priority: Priority::Normal,
+            game_affinity: String::new(),
+            sync_game_user_settings: false,
+            discord_presence: false,
+            discord_app_id: String::new(),
+            watched: Vec::new(),
+            watchdog: Tuning::default(),
+            pinned_keys: pinned::default_keys(),
+            known_dbs: Vec::new(),
+            last_seen_version: String::new(),
+        }
+    }
+}
+
+/// For version 1 -> 2: locating the game on launch became the default. Version 1 files saved the
+/// old default of off along with everything else, so they can't tell a choice from a default.
+fn detect_on_launch(fields: &mut Map<String, Value>) {
+    fields.insert("auto_detect_on_launch".into(), Value::Bool(true));
+}
+
+impl Config {
+    /// Reads the config file, upgrading older formats and falling back to defaults if it is
+    /// missing or unreadable. A damaged file is kept aside; see `store::read_json`.
+    pub fn load() -> Self {
+        let path = app_dir().join(CONFIG_FILE);
+        let Some(mut value) = store::read_json(&path).ok().flatten() else {
+            return Self::default();
+        };
+        if let Err(err) = schema::migrate(&mut value, MIGRATIONS) {
+            // Saving from this build would drop whatever the newer one added, so keep a copy.
+            let kept = path.with_extension(format!("v{}.json", schema::file_version(&value)));
+            tracing::warn!(%err, kept = %kept.display(), "config is from a newer version");
+            let _ = fs::copy(&path, kept);
+        }
+        let mut config: Self = serde_json::from_value(value).unwrap_or_default();
+        config.version = CONFIG_VERSION;
+        config
+    }
+
+    pub fn save(&self) -> Result<()> {
+        store::write_json(&app_dir().join(CONFIG_FILE), self)
+    }
+
+    pub fn backup_dir(&self) -> PathBuf {
+        let root = if !self.backup_dir.is_empty() {
+            PathBuf::from(&self.backup_dir)
+        } else {
+            self.sync_folder().unwrap_or_else(app_dir).join("backups")
+        };
+        backup::for_this_pc(&root)
+    }
+
+    pub fn sync_folder(&self) -> Option<PathBuf> {
+        (!self.sync_folder.is_empty()).then(|| PathBuf::from(&self.sync_folder))
+    }
+}
@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use serde_json::Value;
+use wuwa_ploom_core::engine_ini::{self, SYSTEM_SETTINGS, TWEAKS};
+use wuwa_ploom_core::{game_user_settings, ini};
+
+use crate::toasts::ToastLevel;
+
+/// What to change in Engine.ini: (section, key, new value or `None` to remove the line).
+type Edit = (&'static str, String, Option<&'static str>);
+
+/// The Config Tweaks tab: known Engine.ini lines as toggles, the other `[SystemSettings]`
+/// lines with a way to remove them, and GameUserSettings.ini compared with the database.
+#[derive(Default)]
+pub struct ConfigTweaks {
+    /// (Engine.ini, its text) as last read.
+    loaded: Option<(PathBuf, String)>,
+    /// GameUserSettings.ini's text, if the client keeps one.
+    user_settings: Option<String>,
+    error: Option<String>,
+}
+
+impl ConfigTweaks {
+    fn reload(&mut self, path: PathBuf) {
+        // Both files live in the same config folder.
+        let user_settings = path.with_file_name("GameUserSettings.ini");
+        self.user_settings = user_settings.is_file().then(|| engine_ini::read(&user_settings).ok()).flatten();
+        match engine_ini::read(&path) {
+            Ok(text) => {
+                self.loaded = Some((path, text));
+                self.error = None;
+            }
+            Err(err) => {
+                self.loaded = Some((path, String::new()));
+                self.error = Some(err.to_string());
+            }
+        }
+    }
+
+    /// `blocked` says why editing is disabled; `setting` is GameQualitySetting as last read.
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        db_path: &str,
+        setting: Option<&Value>,
+        blocked: Option<&str>,
+        backup_dir: Option<PathBuf>,
+    ) -> Option<(ToastLevel, String)> {
+        let Some(path) = engine_ini::path_for_db(Path::new(db_path)) else {
+            ui.label("The configuration file is not inside a game's Saved folder, so Engine.ini can't be found.");
+            return None;
+        };
+        if self.loaded.as_ref().is_none_or(|(loaded, _)| *loaded != path) {
+            self.reload(path.clone());
+        }
+        ui.horizontal(|ui| {
+            ui.weak(path.display().to_string());
+            if ui.small_button("Reload").clicked() {
+                self.reload(path.clone());
+            }
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+        if let Some(reason) = blocked {
+            ui.weak(reason);
+        }
+        let (_, text) = self.loaded.as_ref()?;
+
+        let mut edit: Option<Edit> = None;
+        ui.strong("Tweaks");
+        egui::Grid::new("engine_tweaks").striped(true).num_columns(2).show(ui, |ui| {
+            for tweak in TWEAKS {
+                let current = engine_ini::current(text, tweak);
+                let mut on = current.as_deref().is_some_and(|value| value.eq_ignore_ascii_case(tweak.value));
+                let toggle = ui.add_enabled(blocked.is_none(), egui::Checkbox::new(&mut on, tweak.label));
+                if toggle.on_hover_text(format!("{}\n\n[{}] {}={}", tweak.description, tweak.section, tweak.key, tweak.value)).changed() {
+                    edit = Some((tweak.section, tweak.key.to_string(), on.then_some(tweak.value)));
+                }
+                match current {
+                    Some(value) if !value.eq_ignore_ascii_case(tweak.value) => ui.weak(format!("set to {} by hand", value)),
+                    _ => ui.label(""),
+                };
+                ui.end_row();
+            }
+        });
+
+        let others: Vec<(String, String)> = ini::entries(text, SYSTEM_SETTINGS)
+            .into_iter()
+            .filter(|(key, _)| !TWEAKS.iter().any(|tweak| tweak.key.eq_ignore_ascii_case(key)))
+            .collect();
+        if !others.is_empty() {
+            ui.add_space(6.0);
+            ui.strong(format!("Other lines in [{}]", SYSTEM_SETTINGS));
+            egui::Grid::new("engine_other_lines").striped(true).num_columns(2).show(ui, |ui| {
+                for (key, value) in others {
+                    ui.monospace(format!("{}={}", key, value));
+                    if ui.add_enabled(blocked.is_none(), egui::Button::new("Remove").small()).clicked() {
+                        edit = Some((SYSTEM_SETTINGS, key, None));
+                    }
+                    ui.end_row();
+                }
+            });
+        }
+        ui.add_space(6.0);
+        ui.weak("Changes take effect the next time the game starts.");
+
+        let mut sync = false;
+        if let (Some(text), Some(setting)) = (&self.user_settings, setting) {
+            ui.separator();
+            ui.strong("GameUserSettings.ini");
+            let mismatches = game_user_settings::mismatches(text, setting);
+            egui::Grid::new("game_user_settings").striped(true).num_columns(3).show(ui, |ui| {
+                ui.label("");
+                ui.strong("INI");
+                ui.strong("Database");
+                ui.end_row();
+                for pair in game_user_settings::pairs() {
+                    ui.label(pair.ini_key).on_hover_text(&pair.db_key);
+                    ui.label(ini::get(text, game_user_settings::SECTION, pair.ini_key).unwrap_or_else(|| "-".into()));
+                    let db = setting.get(&pair.db_key).map_or_else(|| "-".into(), Value::to_string);
+                    if mismatches.iter().any(|mismatch| mismatch.pair == pair) {
+                        ui.colored_label(ToastLevel::Warning.color(), db);
+                    } else {
+                        ui.label(db);
+                    }
+                    ui.end_row();
+                }
+            });
+            if !mismatches.is_empty() {
+                ui.weak("The game may snap back to the INI's values while they differ.");
+                let button = ui.add_enabled(blocked.is_none(), egui::Button::new("Copy the database's values into the INI"));
+                sync = button.clicked();
+            }
+        }
+        if sync {
+            let message = sync_user_settings(db_path, setting?, backup_dir.as_deref());
+            self.reload(path);
+            return Some(message);
+        }
+
+        let (section, key, value) = edit?;
+        let result = engine_ini::edit(&path, section, &key, value, backup_dir.as_deref());
+        self.reload(path);
+        Some(match result {
+            Ok(Some(backup)) => (ToastLevel::Success, format!("Updated {} in Engine.ini; backup saved to {}.", key, backup.display())),
+            Ok(None) => (ToastLevel::Success, format!("Updated {} in Engine.ini.", key)),
+            Err(err) => (ToastLevel::Error, format!("Error editing Engine.ini: {}", err)),
+        })
+    }
+}
+
+/// Copies the database's values into the GameUserSettings.ini of the client at `db_path`; the
+/// message to show.
+pub fn sync_user_settings(db_path: &str, setting: &Value, backup_dir: Option<&Path>) -> (ToastLevel, String) {
+    let Some(path) = game_user_settings::path_for_db(Path::new(db_path)) else {
+        return (ToastLevel::Info, "No GameUserSettings.ini next to this configuration file.".into());
+    };
+    match game_user_settings::sync(&path, setting, backup_dir) {
+        Ok((0, _)) => (ToastLevel::Info, "GameUserSettings.ini already matches the database.".into()),
+        Ok((changed, _)) => (ToastLevel::Success, format!("Updated {} value(s) in GameUserSettings.ini.", changed)),
+        Err(err) => (ToastLevel::Error, format!("Error updating GameUserSettings.ini: {}", err)),
+    }
+}
@@ -0,0 +1,45 @@
+//! Touches that only change how the app looks to the shell: the console title and the window
+//! icon. Each is tried once and a failure is logged, never shown: the app is built for the
+//! Windows subsystem, so there is no console to print to, and nothing else depends on them.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, warn};
+use wuwa_ploom_core::platform;
+
+use crate::WINDOW_TITLE;
+
+const ICON_FILE: &str = "misc/ploom.ico";
+
+/// Titles the console when one is attached, as when started from a terminal.
+#[cfg(windows)]
+pub fn set_console_title(title: &str) {
+    use winapi::um::wincon::SetConsoleTitleW;
+
+    let wide: Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
+    if unsafe { SetConsoleTitleW(wide.as_ptr()) } == 0 {
+        debug!(err = %std::io::Error::last_os_error(), "no console to title");
+    }
+}
+
+#[cfg(not(windows))]
+pub fn set_console_title(_title: &str) {}
+
+/// Gives the window the app icon. The window must exist, so call it from the first frame
+/// rather than around `run_native`, which only returns once the window has closed.
+pub fn apply_window_icon() {
+    let Some(icon) = icon_path() else {
+        debug!(file = ICON_FILE, "no window icon file");
+        return;
+    };
+    if !platform::native().set_window_icon(WINDOW_TITLE, &icon) {
+        warn!(icon = %icon.display(), "could not set the window icon");
+    }
+}
+
+/// The icon next to the executable, or relative to the working directory as in a dev checkout.
+fn icon_path() -> Option<PathBuf> {
+    let beside_exe = env::current_exe().ok().as_deref().and_then(Path::parent).map(|dir| dir.join(ICON_FILE));
+    beside_exe.into_iter().chain(Some(PathBuf::from(ICON_FILE))).find(|path| path.is_file())
+}
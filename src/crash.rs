@@ -0,0 +1,87 @@
+use std::backtrace::Backtrace;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use rfd::{MessageButtons, MessageDialog, MessageLevel};
+
+use crate::logging;
+use crate::paths::app_dir;
+use crate::report;
+
+const LOG_TAIL_LINES: usize = 50;
+
+/// What the app was doing, refreshed every frame so a crash report can include it.
+static STATE: Mutex<String> = Mutex::new(String::new());
+
+pub fn note_state(summary: String) {
+    if let Ok(mut state) = STATE.lock() {
+        *state = summary;
+    }
+}
+
+fn crash_dir() -> PathBuf {
+    app_dir().join("crashes")
+}
+
+/// The last lines of the newest log file.
+fn log_tail() -> Vec<String> {
+    let newest = fs::read_dir(logging::log_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .max_by_key(|entry| entry.metadata().and_then(|meta| meta.modified()).ok());
+    let text = newest.and_then(|entry| fs::read_to_string(entry.path()).ok()).unwrap_or_default();
+    let lines: Vec<String> = text.lines().map(str::to_string).collect();
+    lines[lines.len().saturating_sub(LOG_TAIL_LINES)..].to_vec()
+}
+
+fn build_report(info: &PanicHookInfo, backtrace: &Backtrace) -> String {
+    let state = STATE.lock().map(|state| state.clone()).unwrap_or_default();
+    let mut out = String::new();
+    out.push_str("WuWa Ploom crash report\n");
+    out.push_str(&format!("App version: {}\n", env!("CARGO_PKG_VERSION")));
+    out.push_str(&format!("OS: {}\n", report::os_version()));
+    out.push_str(&format!("\nPanic: {}\n", info));
+    out.push_str(&format!("\nApp state:\n{}\n", if state.is_empty() { "  not started" } else { &state }));
+    out.push_str(&format!("\nBacktrace:\n{}\n", backtrace));
+    out.push_str("\nLast log lines:\n");
+    for line in log_tail() {
+        out.push_str(&format!("  {}\n", line));
+    }
+    report::redact(&out)
+}
+
+fn write_report(text: &str) -> Option<PathBuf> {
+    let dir = crash_dir();
+    fs::create_dir_all(&dir).ok()?;
+    let stamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|since| since.as_secs()).unwrap_or_default();
+    let path = dir.join(format!("crash-{}.txt", stamp));
+    fs::write(&path, text).ok()?;
+    Some(path)
+}
+
+/// Replaces the default panic output, which nobody sees without a console, with a crash report
+/// on disk and a dialog that points to it.
+pub fn install_hook() {
+    panic::set_hook(Box::new(|info| {
+        let backtrace = Backtrace::force_capture();
+        tracing::error!(panic = %info, "crashed");
+        let text = build_report(info, &backtrace);
+        let description = match write_report(&text) {
+            Some(path) => format!(
+                "WuWa Ploom ran into a bug and has to close.\n\nA crash report was saved to:\n{}\n\nPlease attach it when reporting the problem.",
+                path.display()
+            ),
+            None => format!("WuWa Ploom ran into a bug and has to close.\n\n{}", info),
+        };
+        MessageDialog::new()
+            .set_level(MessageLevel::Error)
+            .set_title("WuWa Ploom crashed")
+            .set_description(&description)
+            .set_buttons(MessageButtons::Ok)
+            .show();
+    }));
+}
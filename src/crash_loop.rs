@@ -0,0 +1,84 @@
+//! Noticing a watchdog (the CLI's `keep`) that crashes over and over. Every crash is logged with
+//! a marker, and the log is the one record that survives restarts by launcher hooks or a
+//! scheduled task, so the crash history is read back from it. After `MAX_CRASHES` within
+//! `WINDOW` the watchdog stops restarting and says where the log is, rather than either burning
+//! CPU in a loop or quietly no longer protecting the settings.
+
+use std::cmp::Reverse;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::logging::log_dir;
+use crate::MyError;
+
+/// Logged with each crash.
+pub const CRASH_MARKER: &str = "watchdog crashed";
+/// Logged when the watchdog gives up.
+pub const STOP_MARKER: &str = "watchdog stopped after repeated crashes";
+/// Crashes within `WINDOW` that make a loop.
+pub const MAX_CRASHES: usize = 3;
+pub const WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// Days from 1970-01-01 to the given civil date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The time at the start of a log line, `2024-05-01T10:15:00.123456Z` in UTC.
+fn line_time(line: &str) -> Option<SystemTime> {
+    let stamp = line.split_whitespace().next()?;
+    let (date, time) = stamp.strip_suffix('Z')?.split_once('T')?;
+    let mut date = date.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.split(':');
+    let hours: u64 = time.next()?.parse().ok()?;
+    let minutes: u64 = time.next()?.parse().ok()?;
+    let seconds: f64 = time.next()?.parse().ok()?;
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    let whole = days * 86400 + hours * 3600 + minutes * 60;
+    Some(UNIX_EPOCH + Duration::from_secs(whole) + Duration::from_secs_f64(seconds))
+}
+
+/// When each line of `log` containing `marker` was written.
+pub fn marked_times(log: &str, marker: &str) -> Vec<SystemTime> {
+    log.lines().filter(|line| line.contains(marker)).filter_map(line_time).collect()
+}
+
+/// Whether a failed round counts as a crash. The game holding the file is how a round fails
+/// while it starts or saves, and clears up by itself; only panics and other errors count.
+pub fn is_crash(err: &MyError) -> bool {
+    !matches!(err, MyError::Locked(_))
+}
+
+/// Whether `crashes` hold `MAX_CRASHES` within `WINDOW` before `now`.
+pub fn is_loop(crashes: &[SystemTime], now: SystemTime) -> bool {
+    crashes.iter().filter(|time| now.duration_since(**time).is_ok_and(|age| age <= WINDOW)).count() >= MAX_CRASHES
+}
+
+/// Log files, newest first.
+fn logs() -> Vec<PathBuf> {
+    let mut logs: Vec<(SystemTime, PathBuf)> = fs::read_dir(log_dir())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| Some((entry.metadata().and_then(|meta| meta.modified()).ok()?, entry.path())))
+        .collect();
+    logs.sort_by_key(|(modified, _)| Reverse(*modified));
+    logs.into_iter().map(|(_, path)| path).collect()
+}
+
+/// The log being written to now.
+pub fn current_log() -> Option<PathBuf> {
+    logs().into_iter().next()
+}
+
+/// Times `marker` was logged in the last two log files, which span midnight.
+pub fn logged(marker: &str) -> Vec<SystemTime> {
+    logs().iter().take(2).filter_map(|path| fs::read_to_string(path).ok()).flat_map(|text| marked_times(&text, marker)).collect()
+}
@@ -0,0 +1,283 @@
+//! Reading and patching LocalStorage.db. Every function here runs its work on the database's
+//! worker (see `Db`), which owns the only connection this process opens to the file.
+
+use std::fs;
+
+use rusqlite::types::ValueRef;
+use rusqlite::{params, Connection};
+use serde_json::{Map, Value};
+use tracing::{debug, info, instrument};
+
+use crate::keymap;
+use crate::settings::GameQualitySetting;
+use crate::{MyError, Result};
+
+mod worker;
+
+pub use worker::{wait, Db};
+
+/// Field replacements for GameQualitySetting; a `Null` value removes the field.
+pub type Patch = Map<String, Value>;
+
+pub fn file_exists(path: &str) -> Result<()> {
+    if fs::metadata(path).is_err() {
+        Err(MyError::Detection(format!("{} does not exist", path)))
+    } else {
+        Ok(())
+    }
+}
+
+pub fn read_game_quality_setting(conn: &Connection) -> Result<Value> {
+    let mut stmt = conn.prepare("SELECT value FROM LocalStorage WHERE key = ?1;")?;
+    let mut rows = stmt.query(params![keymap::active().setting_row])?;
+
+    let game_quality_setting_json: String = rows.next()?.ok_or(rusqlite::Error::QueryReturnedNoRows)?.get(0)?;
+    let game_quality_setting: Value = serde_json::from_str(&game_quality_setting_json)?;
+    Ok(game_quality_setting)
+}
+
+#[instrument(level = "debug", err(level = "warn"))]
+pub fn read_quality_setting_file(db_path: &str) -> Result<Value> {
+    wait(Db::new(db_path).read())
+}
+
+/// `read_quality_setting_file` without waiting out a lock: while the game holds the file it
+/// fails with `Locked` at once. For reads on the UI thread, which try again at the next poll.
+pub fn read_quality_setting_now(db_path: &str) -> Result<Value> {
+    wait(Db::new(db_path).read_now())
+}
+
+/// GameQualitySetting as the typed model; fails if a known field has the wrong type.
+pub fn read_settings(db_path: &str) -> Result<GameQualitySetting> {
+    GameQualitySetting::from_value(&read_quality_setting_file(db_path)?)
+}
+
+/// The frame-rate limit, from the field the active key map names.
+pub fn fps_from_setting(game_quality_setting: &Value) -> Result<i64> {
+    let key = keymap::active().frame_rate;
+    game_quality_setting
+        .get(&key)
+        .and_then(Value::as_i64)
+        .ok_or_else(|| MyError::Schema(format!("{} not found or not an integer", key)))
+}
+
+pub fn update_game_quality_setting(conn: &Connection, game_quality_setting: &Value) -> Result<()> {
+    let updated_game_quality_setting_json = game_quality_setting.to_string();
+    conn.execute(
+        "UPDATE LocalStorage SET value = ?1 WHERE key = ?2;",
+        params![updated_game_quality_setting_json, keymap::active().setting_row],
+    )?;
+    Ok(())
+}
+
+/// The patch that would restore `setting` after `patch` is applied; empty when nothing would change.
+pub fn inverse_patch(setting: &Value, patch: &Patch) -> Patch {
+    patch
+        .iter()
+        .filter(|(key, value)| setting.get(key.as_str()).unwrap_or(&Value::Null) != *value)
+        .map(|(key, _)| (key.clone(), setting.get(key.as_str()).cloned().unwrap_or(Value::Null)))
+        .collect()
+}
+
+pub fn apply_to(setting: &mut Value, patch: &Patch) -> Result<()> {
+    let fields = setting
+        .as_object_mut()
+        .ok_or_else(|| MyError::Schema("GameQualitySetting is not a JSON object".into()))?;
+    for (key, value) in patch {
+        if value.is_null() {
+            fields.remove(key);
+        } else {
+            fields.insert(key.clone(), value.clone());
+        }
+    }
+    Ok(())
+}
+
+/// Applies `patch` on top of the stored blob in one transaction, so fields the game changed
+/// in the meantime survive. Returns the inverse patch, or `None` if nothing changed.
+#[instrument(skip(patch), fields(keys = ?patch.keys().collect::<Vec<_>>()), err(level = "warn"))]
+pub fn apply_patch(db_path: &str, patch: &Patch) -> Result<Option<Patch>> {
+    wait(Db::new(db_path).patch(patch.clone()))
+}
+
+fn patch_in(conn: &mut Connection, patch: &Patch) -> Result<Option<Patch>> {
+    let tx = conn.transaction()?;
+    let mut setting = read_game_quality_setting(&tx)?;
+
+    let inverse = inverse_patch(&setting, patch);
+    if inverse.is_empty() {
+        debug!("patch matches the stored values; nothing written");
+        return Ok(None);
+    }
+    apply_to(&mut setting, patch)?;
+    update_game_quality_setting(&tx, &setting)?;
+    tx.commit()?;
+    let previous = Value::Object(inverse.clone());
+    info!(%previous, "patched GameQualitySetting");
+    Ok(Some(inverse))
+}
+
+pub fn fps_patch(fps: i64) -> Patch {
+    Patch::from_iter([(keymap::active().frame_rate, Value::from(fps))])
+}
+
+/// One row of the LocalStorage table as the key browser lists it.
+pub struct KeyEntry {
+    pub key: String,
+    /// Size of the value in bytes.
+    pub size: usize,
+}
+
+/// Up to `limit` keys containing `filter`, and how many match in all.
+pub struct KeyListing {
+    pub entries: Vec<KeyEntry>,
+    pub total: usize,
+}
+
+/// `LIKE` pattern matching `filter` anywhere, with its wildcards taken literally.
+fn contains_pattern(filter: &str) -> String {
+    let escaped = filter.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+/// Keys whose name contains `filter` (case-insensitively), with value sizes, at most `limit`
+/// of them. Values stay in SQLite, so this is quick however large the cached blobs grow.
+#[instrument(level = "debug", err(level = "warn"))]
+pub fn list_keys(db_path: &str, filter: &str, limit: usize) -> Result<KeyListing> {
+    let pattern = contains_pattern(filter);
+    Db::new(db_path).run(move |conn| {
+        let total: i64 =
+            conn.query_row("SELECT count(*) FROM LocalStorage WHERE key LIKE ?1 ESCAPE '\\';", params![pattern], |row| row.get(0))?;
+        let mut stmt = conn.prepare(
+            "SELECT key, length(CAST(value AS BLOB)) FROM LocalStorage WHERE key LIKE ?1 ESCAPE '\\' ORDER BY key LIMIT ?2;",
+        )?;
+        let rows = stmt.query_map(params![pattern, limit as i64], |row| {
+            let size: Option<i64> = row.get(1)?;
+            Ok(KeyEntry { key: row.get(0)?, size: size.unwrap_or(0) as usize })
+        })?;
+        let entries = rows.collect::<rusqlite::Result<_>>()?;
+        Ok(KeyListing { entries, total: total as usize })
+    })
+}
+
+#[instrument(level = "debug", err(level = "warn"))]
+pub fn read_value(db_path: &str, key: &str) -> Result<String> {
+    let key = key.to_string();
+    Db::new(db_path).run(move |conn| {
+        let value = conn.query_row("SELECT value FROM LocalStorage WHERE key = ?1;", params![key], |row| {
+            Ok(value_text(row.get_ref(0)?))
+        })?;
+        Ok(value)
+    })
+}
+
+/// The first `max_bytes` of a row's value and the value's full size, for previews that must not
+/// pull a multi-megabyte blob into the UI. A cut may split a character, which shows as U+FFFD.
+#[instrument(level = "debug", err(level = "warn"))]
+pub fn read_value_prefix(db_path: &str, key: &str, max_bytes: usize) -> Result<(String, usize)> {
+    let key = key.to_string();
+    Db::new(db_path).run(move |conn| {
+        let sql = "SELECT substr(CAST(value AS BLOB), 1, ?2), length(CAST(value AS BLOB)) FROM LocalStorage WHERE key = ?1;";
+        let (prefix, size) = conn.query_row(sql, params![key, max_bytes as i64], |row| {
+            let size: Option<i64> = row.get(1)?;
+            Ok((value_text(row.get_ref(0)?), size.unwrap_or(0) as usize))
+        })?;
+        Ok((prefix, size))
+    })
+}
+
+/// Some rows hold blobs or numbers rather than text; show them all as text.
+fn value_text(value: ValueRef) -> String {
+    match value {
+        ValueRef::Null => String::new(),
+        ValueRef::Integer(number) => number.to_string(),
+        ValueRef::Real(number) => number.to_string(),
+        ValueRef::Text(bytes) | ValueRef::Blob(bytes) => String::from_utf8_lossy(bytes).into_owned(),
+    }
+}
+
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Runs `sql` with the connection in `query_only` mode, so anything but a query fails instead
+/// of writing.
+#[instrument(level = "debug", err(level = "warn"))]
+pub fn run_query(db_path: &str, sql: &str) -> Result<QueryResult> {
+    let sql = sql.to_string();
+    Db::new(db_path).run(move |conn| {
+        conn.execute_batch("PRAGMA query_only = ON;")?;
+        let result = query(conn, &sql);
+        conn.execute_batch("PRAGMA query_only = OFF;")?;
+        result
+    })
+}
+
+fn query(conn: &Connection, sql: &str) -> Result<QueryResult> {
+    let mut stmt = conn.prepare(sql)?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|name| name.to_string()).collect();
+    let width = columns.len();
+    let mut rows = Vec::new();
+    let mut cursor = stmt.query([])?;
+    while let Some(row) = cursor.next()? {
+        let cells = (0..width).map(|index| row.get_ref(index).map(value_text)).collect::<rusqlite::Result<_>>()?;
+        rows.push(cells);
+    }
+    Ok(QueryResult { columns, rows })
+}
+
+/// Executes a write statement in a transaction and returns the number of rows it changed.
+#[instrument(err(level = "warn"))]
+pub fn execute_sql(db_path: &str, sql: &str) -> Result<usize> {
+    let sql = sql.to_string();
+    Db::new(db_path).run(move |conn| {
+        let tx = conn.transaction()?;
+        let changed = tx.execute(&sql, [])?;
+        tx.commit()?;
+        info!(changed, "executed SQL");
+        Ok(changed)
+    })
+}
+
+/// Replaces the value of any LocalStorage row, for editors that work outside GameQualitySetting.
+#[instrument(skip(value), err(level = "warn"))]
+pub fn write_value(db_path: &str, key: &str, value: &Value) -> Result<()> {
+    let (key, value) = (key.to_string(), value.to_string());
+    Db::new(db_path).run(move |conn| {
+        let tx = conn.transaction()?;
+        let changed = tx.execute("UPDATE LocalStorage SET value = ?1 WHERE key = ?2;", params![value, key])?;
+        if changed == 0 {
+            return Err(rusqlite::Error::QueryReturnedNoRows.into());
+        }
+        tx.commit()?;
+        info!("replaced row value");
+        Ok(())
+    })
+}
+
+/// Removes the rows `keys` in one transaction and returns how many existed.
+#[instrument(skip(keys), err(level = "warn"))]
+pub fn delete_rows(db_path: &str, keys: &[String]) -> Result<usize> {
+    let keys = keys.to_vec();
+    Db::new(db_path).run(move |conn| {
+        let tx = conn.transaction()?;
+        let mut removed = 0;
+        for key in &keys {
+            removed += tx.execute("DELETE FROM LocalStorage WHERE key = ?1;", params![key])?;
+        }
+        tx.commit()?;
+        info!(removed, "deleted rows");
+        Ok(removed)
+    })
+}
+
+/// Takes and releases the write lock, proving a write could start without changing anything.
+pub fn probe_write_lock(db_path: &str) -> Result<()> {
+    Db::new(db_path).run(|conn| Ok(conn.execute_batch("BEGIN IMMEDIATE; ROLLBACK;")?))
+}
+
+/// When the database or its write-ahead log last changed; see `Db::watch`.
+pub fn modified_time(db_path: &str) -> Option<std::time::SystemTime> {
+    wait(Db::new(db_path).watch()).ok().flatten()
+}
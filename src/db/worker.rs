@@ -0,0 +1,218 @@
+//! One thread per database owns its connection and runs the commands queued for it in order,
+//! so reads, our writes, backups and change checks never race each other, and busy handling
+//! and retries live in one place. The thread closes the connection and exits after a few idle
+//! seconds, so neither the game nor a restore finds the file held open for long.
+
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
+
+use rusqlite::Connection;
+use serde_json::Value;
+use tracing::{debug, warn};
+
+use super::Patch;
+use crate::{backup, MyError, Result};
+
+/// How long a worker waits for more work before closing the connection and exiting.
+const IDLE: Duration = Duration::from_secs(5);
+/// How long SQLite waits on a lock before a command fails with `Locked`.
+const BUSY_TIMEOUT: Duration = Duration::from_millis(500);
+/// Runs per command while the database stays locked; the game is usually mid-write.
+const ATTEMPTS: u32 = 3;
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+struct State {
+    path: String,
+    conn: Option<Connection>,
+}
+
+impl State {
+    fn connection(&mut self) -> Result<&mut Connection> {
+        let conn = match self.conn.take() {
+            Some(conn) => conn,
+            None => {
+                super::file_exists(&self.path)?;
+                let conn = Connection::open(&self.path)?;
+                conn.busy_timeout(BUSY_TIMEOUT)?;
+                conn
+            }
+        };
+        Ok(self.conn.insert(conn))
+    }
+}
+
+/// A queued command. It gets whether this is the last attempt and returns whether it finished;
+/// unfinished ones run again after a pause.
+type Job = Box<dyn FnMut(&mut State, bool) -> bool + Send>;
+
+/// Queues of the running workers by database path. Jobs are sent while holding the lock, and
+/// an idle worker leaves the list under it, so no job reaches a worker that is exiting.
+static WORKERS: Mutex<Vec<(String, Sender<Job>)>> = Mutex::new(Vec::new());
+
+fn enqueue(path: &str, job: Job) {
+    let mut workers = WORKERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let job = match workers.iter().find(|(worker_path, _)| worker_path == path) {
+        Some((_, queue)) => match queue.send(job) {
+            Ok(()) => return,
+            // The worker panicked; start over with a fresh one.
+            Err(mpsc::SendError(job)) => job,
+        },
+        None => job,
+    };
+    workers.retain(|(worker_path, _)| worker_path != path);
+    let (queue, jobs) = mpsc::channel();
+    let state = State { path: path.to_string(), conn: None };
+    let spawned = thread::Builder::new().name("db-worker".into()).spawn(move || serve(state, jobs));
+    if let Err(err) = spawned {
+        warn!(%err, "could not start a database worker");
+        return;
+    }
+    let _ = queue.send(job);
+    workers.push((path.to_string(), queue));
+}
+
+fn serve(mut state: State, jobs: Receiver<Job>) {
+    debug!(path = %state.path, "database worker started");
+    loop {
+        let job = match jobs.recv_timeout(IDLE) {
+            Ok(job) => job,
+            Err(RecvTimeoutError::Timeout) => {
+                let mut workers = WORKERS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                match jobs.try_recv() {
+                    Ok(job) => job,
+                    Err(_) => {
+                        workers.retain(|(path, _)| *path != state.path);
+                        break;
+                    }
+                }
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        run(&mut state, job);
+    }
+    debug!(path = %state.path, "database worker idle; closed");
+}
+
+fn run(state: &mut State, mut job: Job) {
+    for attempt in 1..=ATTEMPTS {
+        if job(state, attempt == ATTEMPTS) {
+            return;
+        }
+        debug!(attempt, "database locked; retrying");
+        thread::sleep(RETRY_DELAY * attempt);
+    }
+}
+
+/// Waits for a command's result.
+pub fn wait<T>(result: Receiver<Result<T>>) -> Result<T> {
+    result.recv().unwrap_or_else(|_| Err(MyError::Io(io::Error::other("the database worker stopped"))))
+}
+
+/// Handle to the worker of one database file; cheap to create, as workers are shared by path.
+/// Commands return a receiver right away; `wait` blocks for the result.
+#[derive(Clone, Debug)]
+pub struct Db {
+    path: String,
+}
+
+impl Db {
+    pub fn new(db_path: &str) -> Self {
+        Self { path: db_path.to_string() }
+    }
+
+    /// Queues `op` to run on the connection. Commands failing with `Locked` run again a few
+    /// times; after any failure the connection is reopened for the next command.
+    pub fn submit<T: Send + 'static>(&self, mut op: impl FnMut(&mut Connection) -> Result<T> + Send + 'static) -> Receiver<Result<T>> {
+        let (reply, result) = mpsc::channel();
+        let job: Job = Box::new(move |state, last| {
+            let outcome = state.connection().and_then(&mut op);
+            if !last && matches!(outcome, Err(MyError::Locked(_))) {
+                return false;
+            }
+            if outcome.is_err() {
+                state.conn = None;
+            }
+            let _ = reply.send(outcome);
+            true
+        });
+        enqueue(&self.path, job);
+        result
+    }
+
+    /// Queues `op` on the file itself, with the connection closed first so the file can be
+    /// replaced.
+    pub fn submit_file<T: Send + 'static>(&self, mut op: impl FnMut(&str) -> Result<T> + Send + 'static) -> Receiver<Result<T>> {
+        let (reply, result) = mpsc::channel();
+        let job: Job = Box::new(move |state, _| {
+            state.conn = None;
+            let _ = reply.send(op(&state.path));
+            true
+        });
+        enqueue(&self.path, job);
+        result
+    }
+
+    /// `submit`, waiting for the result.
+    pub fn run<T: Send + 'static>(&self, op: impl FnMut(&mut Connection) -> Result<T> + Send + 'static) -> Result<T> {
+        wait(self.submit(op))
+    }
+
+    /// The GameQualitySetting blob.
+    pub fn read(&self) -> Receiver<Result<Value>> {
+        self.submit(|conn| super::read_game_quality_setting(conn))
+    }
+
+    /// The GameQualitySetting blob in one attempt that fails with `Locked` at once instead of
+    /// waiting on the lock, for callers that must not stall.
+    pub fn read_now(&self) -> Receiver<Result<Value>> {
+        let (reply, result) = mpsc::channel();
+        let job: Job = Box::new(move |state, _| {
+            let outcome = state.connection().and_then(|conn| {
+                conn.busy_timeout(Duration::ZERO)?;
+                let outcome = super::read_game_quality_setting(conn);
+                conn.busy_timeout(BUSY_TIMEOUT)?;
+                outcome
+            });
+            if outcome.is_err() {
+                state.conn = None;
+            }
+            let _ = reply.send(outcome);
+            true
+        });
+        enqueue(&self.path, job);
+        result
+    }
+
+    /// Applies `patch`; see `db::apply_patch`.
+    pub fn patch(&self, patch: Patch) -> Receiver<Result<Option<Patch>>> {
+        self.submit(move |conn| super::patch_in(conn, &patch))
+    }
+
+    /// Copies the file into `backup_dir`, between other commands so no write of ours is half done.
+    pub fn backup(&self, backup_dir: PathBuf) -> Receiver<Result<PathBuf>> {
+        self.submit_file(move |path| backup::copy_db(path, &backup_dir))
+    }
+
+    /// When the database or its write-ahead log last changed, to notice the game's writes.
+    pub fn watch(&self) -> Receiver<Result<Option<SystemTime>>> {
+        let (reply, result) = mpsc::channel();
+        let job: Job = Box::new(move |state, _| {
+            let _ = reply.send(Ok(modified_time(&state.path)));
+            true
+        });
+        enqueue(&self.path, job);
+        result
+    }
+}
+
+fn modified_time(db_path: &str) -> Option<SystemTime> {
+    let wal_path = format!("{}-wal", db_path);
+    [db_path, wal_path.as_str()]
+        .iter()
+        .filter_map(|path| fs::metadata(path).and_then(|metadata| metadata.modified()).ok())
+        .max()
+}
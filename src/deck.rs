@@ -0,0 +1,94 @@
+//! Steam Deck detection and the frame-rate and quality suggestions that suit its screens.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::presets::{PresetFile, PRESET_VERSION};
+use crate::settings::GameQualitySetting;
+
+const DMI_DIR: &str = "/sys/class/dmi/id";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeckModel {
+    /// The original 60 Hz LCD model ("Jupiter").
+    Lcd,
+    /// The 90 Hz OLED model ("Galileo").
+    Oled,
+}
+
+impl DeckModel {
+    pub fn name(self) -> &'static str {
+        match self {
+            DeckModel::Lcd => "Steam Deck LCD",
+            DeckModel::Oled => "Steam Deck OLED",
+        }
+    }
+
+    /// Highest refresh rate of the built-in screen.
+    pub fn refresh_hz(self) -> i64 {
+        match self {
+            DeckModel::Lcd => 60,
+            DeckModel::Oled => 90,
+        }
+    }
+
+    /// Frame-rate limits that divide evenly into a refresh rate the screen can run at, best first.
+    /// 40 and 45 need the screen set to 40/45 Hz or a matching Gamescope limit to pace evenly.
+    pub fn fps_presets(self) -> &'static [i64] {
+        match self {
+            DeckModel::Lcd => &[60, 40, 30],
+            DeckModel::Oled => &[90, 45, 60],
+        }
+    }
+
+    /// Settings that hold the first frame-rate preset in open areas: FSR on, the heavy options down.
+    pub fn quality_preset(self) -> PresetFile {
+        let settings = GameQualitySetting {
+            custom_frame_rate: Some(self.fps_presets()[1]),
+            pc_vsync: Some(0),
+            new_shadow_quality: Some(0),
+            niagara_quality: Some(1),
+            image_detail: Some(1),
+            scene_ao: Some(0),
+            volume_fog: Some(0),
+            motion_blur: Some(0),
+            fsr_enable: Some(1),
+            fsr_mode: Some(1),
+            ..Default::default()
+        };
+        PresetFile {
+            version: PRESET_VERSION,
+            name: format!("{} battery", self.name()),
+            author: "wuwa-ploom".into(),
+            description: format!(
+                "{} FPS with FSR Balanced and shadows, fog and ambient occlusion off, for a steady frame rate on battery.",
+                self.fps_presets()[1]
+            ),
+            settings: settings.to_patch(),
+        }
+    }
+}
+
+/// Reads the board vendor and product name from a DMI directory like `/sys/class/dmi/id`.
+pub fn detect_in(dmi_dir: &Path) -> Option<DeckModel> {
+    let read = |name: &str| fs::read_to_string(dmi_dir.join(name)).map(|text| text.trim().to_string()).ok();
+    if read("board_vendor")? != "Valve" {
+        return None;
+    }
+    match read("product_name")?.as_str() {
+        "Jupiter" => Some(DeckModel::Lcd),
+        "Galileo" => Some(DeckModel::Oled),
+        _ => None,
+    }
+}
+
+pub fn detect() -> Option<DeckModel> {
+    detect_in(Path::new(DMI_DIR))
+}
+
+/// Whether we run inside Gamescope, i.e. Steam's Game Mode or Big Picture session.
+pub fn in_gamescope() -> bool {
+    env::var_os("GAMESCOPE_WAYLAND_DISPLAY").is_some()
+        || env::var("XDG_CURRENT_DESKTOP").is_ok_and(|desktop| desktop.eq_ignore_ascii_case("gamescope"))
+}
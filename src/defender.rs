@@ -0,0 +1,137 @@
+//! Windows Defender's Controlled Folder Access. It blocks programs it doesn't know from changing
+//! files in protected folders, and to the program that only looks like "access denied", so a
+//! game kept under Documents or a protected library fails to patch with no hint why. We read
+//! its settings through PowerShell and can allow this program, which takes administrator rights.
+
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::{info, instrument};
+
+use crate::platform::Platform;
+use crate::{MyError, Result};
+
+/// Separates the values `query` prints.
+const SEPARATOR: &str = "--";
+/// `EnableControlledFolderAccess` when writes are blocked; 2 only audits them.
+const MODE_BLOCK: u32 = 1;
+
+/// What Controlled Folder Access is set to.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cfa {
+    pub mode: u32,
+    /// Folders the user protected in addition to Windows' defaults.
+    pub protected: Vec<PathBuf>,
+    pub allowed: Vec<PathBuf>,
+}
+
+fn starts_with_ignoring_case(path: &Path, base: &Path) -> bool {
+    let mut path = path.components();
+    base.components().all(|part| path.next().is_some_and(|other| other.as_os_str().eq_ignore_ascii_case(part.as_os_str())))
+}
+
+impl Cfa {
+    pub fn blocks_writes(&self) -> bool {
+        self.mode == MODE_BLOCK
+    }
+
+    /// Whether `path` is in a protected folder, `defaults` being Windows' own list.
+    pub fn protects(&self, path: &Path, defaults: &[PathBuf]) -> bool {
+        self.protected.iter().chain(defaults).any(|folder| starts_with_ignoring_case(path, folder))
+    }
+
+    pub fn allows(&self, exe: &Path) -> bool {
+        self.allowed.iter().any(|allowed| allowed.as_os_str().eq_ignore_ascii_case(exe.as_os_str()))
+    }
+
+    /// Whether `exe` writing to `path` is blocked.
+    pub fn blocks(&self, path: &Path, exe: &Path, defaults: &[PathBuf]) -> bool {
+        self.blocks_writes() && self.protects(path, defaults) && !self.allows(exe)
+    }
+}
+
+/// Reads what `query` printed: the mode, then protected folders, then allowed programs, one per
+/// line, the lists introduced by `--`.
+pub fn parse(text: &str) -> Option<Cfa> {
+    let mut lines = text.lines().map(str::trim);
+    let mode = lines.next()?.parse().ok()?;
+    let mut lists: [Vec<PathBuf>; 2] = Default::default();
+    let mut current = None;
+    for line in lines {
+        if line == SEPARATOR {
+            current = Some(current.map_or(0, |index: usize| index + 1));
+        } else if let Some(list) = current.and_then(|index| lists.get_mut(index)).filter(|_| !line.is_empty()) {
+            list.push(PathBuf::from(line));
+        }
+    }
+    let [protected, allowed] = lists;
+    Some(Cfa { mode, protected, allowed })
+}
+
+/// The folders Windows protects without being asked: the user's and the public libraries and
+/// the desktop.
+pub fn default_protected() -> Vec<PathBuf> {
+    let libraries = ["Documents", "Pictures", "Videos", "Music", "Desktop", "Favorites"];
+    ["USERPROFILE", "PUBLIC"]
+        .iter()
+        .filter_map(env::var_os)
+        .flat_map(|home| libraries.iter().map(move |library| Path::new(&home).join(library)))
+        .collect()
+}
+
+fn powershell(command: &str) -> Result<String> {
+    if !cfg!(windows) {
+        return Err(MyError::Io(io::Error::from(io::ErrorKind::Unsupported)));
+    }
+    let mut process = Command::new("powershell");
+    process.args(["-NoProfile", "-NonInteractive", "-Command", command]);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        process.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = process.output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// The current settings; `None` off Windows or when Defender isn't the antivirus.
+#[instrument]
+pub fn query() -> Option<Cfa> {
+    let command = format!(
+        "$p = Get-MpPreference; $p.EnableControlledFolderAccess; '{0}'; $p.ControlledFolderAccessProtectedFolders; '{0}'; $p.ControlledFolderAccessAllowedApplications",
+        SEPARATOR
+    );
+    let cfa = parse(&powershell(&command).ok()?);
+    info!(?cfa, "controlled folder access");
+    cfa
+}
+
+/// Whether Controlled Folder Access stops this program writing `path`.
+pub fn blocks_us(path: &Path) -> bool {
+    let Ok(exe) = env::current_exe() else {
+        return false;
+    };
+    query().is_some_and(|cfa| cfa.blocks(path, &exe, &default_protected()))
+}
+
+/// The PowerShell command allowing `exe`, for users who would rather run it themselves.
+pub fn allow_command(exe: &Path) -> String {
+    format!("Add-MpPreference -ControlledFolderAccessAllowedApplications '{}'", exe.display().to_string().replace('\'', "''"))
+}
+
+/// Allows `exe` through Controlled Folder Access from an elevated PowerShell, asking the user.
+#[instrument(skip(platform), err(level = "warn"))]
+pub fn allow(platform: &dyn Platform, exe: &Path) -> Result<()> {
+    let args = ["-NoProfile".to_string(), "-NonInteractive".to_string(), "-Command".to_string(), allow_command(exe)];
+    match platform.run_elevated(Path::new("powershell.exe"), &args) {
+        None => Err(MyError::Permission("administrator rights were not granted".into())),
+        Some(0) => {
+            info!(exe = %exe.display(), "allowed through controlled folder access");
+            Ok(())
+        }
+        Some(code) => Err(MyError::Io(io::Error::other(format!("PowerShell exited with code {}", code)))),
+    }
+}
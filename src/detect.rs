@@ -0,0 +1,209 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[cfg(windows)]
+use crate::platform;
+use crate::games;
+use crate::platform::Platform;
+use crate::registry::{self, Candidate};
+use crate::wine;
+use tracing::{debug, info, instrument};
+use crate::{MyError, Result};
+
+/// Deep enough for `SteamLibrary\steamapps\common\Wuthering Waves\Wuthering Waves Game`.
+const MAX_DEPTH: usize = 5;
+const SKIPPED_DIRS: &[&str] = &["Windows", "$Recycle.Bin", "System Volume Information", "ProgramData"];
+
+/// Every install folder the launcher registered, with where each was found.
+pub fn install_candidates(platform: &dyn Platform) -> Vec<Candidate> {
+    let read = |location: &registry::Location, name: &str| platform.registry_string(location, name);
+    registry::candidates(read, &registry::game_locations(), registry::INSTALL_PATH_NAMES)
+}
+
+/// The first of `db_paths` that exists, else the first at all.
+fn preferred_db(db_paths: Vec<PathBuf>) -> Option<PathBuf> {
+    let existing = db_paths.iter().position(|db| db.is_file());
+    db_paths.into_iter().nth(existing.unwrap_or(0))
+}
+
+/// LocalStorage.db of the install the launcher registered; with several registrations, the
+/// first whose database exists.
+#[instrument(level = "debug", skip(platform), ret, err(level = "info"))]
+pub fn install_db_path(platform: &dyn Platform) -> Result<String> {
+    let candidates = install_candidates(platform);
+    debug!(candidates = ?candidates.iter().map(ToString::to_string).collect::<Vec<_>>(), "registered installs");
+    let db_paths = candidates.iter().map(|candidate| db_path_in(Path::new(&candidate.data))).collect();
+    preferred_db(db_paths)
+        .map(|db| db.display().to_string())
+        .ok_or_else(|| MyError::Detection("the launcher has not registered an install".into()))
+}
+
+/// Version string the launcher recorded for the installed client, e.g. "1.1.0".
+pub fn game_version(platform: &dyn Platform) -> Option<String> {
+    let read = |location: &registry::Location, name: &str| platform.registry_string(location, name);
+    let found = registry::candidates(read, &registry::game_locations(), registry::VERSION_NAMES);
+    found.into_iter().next().map(|candidate| candidate.data)
+}
+
+/// LocalStorage.db of the install the launcher registered inside a Wine or Proton prefix.
+#[instrument(level = "debug", ret, err(level = "debug"))]
+pub fn prefix_install_db_path(prefix: &Path) -> Result<String> {
+    let read = |location: &registry::Location, name: &str| wine::registry_string(prefix, location, name);
+    let candidates = registry::candidates(read, &registry::game_locations(), registry::INSTALL_PATH_NAMES);
+    if candidates.is_empty() {
+        return Err(MyError::Detection(format!("no install registered in {}", prefix.display())));
+    }
+    let db_paths = candidates
+        .iter()
+        .filter_map(|candidate| wine::host_path(prefix, &candidate.data))
+        .map(|install_dir| db_path_in(&install_dir))
+        .collect();
+    preferred_db(db_paths)
+        .map(|db| db.display().to_string())
+        .ok_or_else(|| MyError::Detection(format!("{} is not mapped to a host folder", candidates[0].data)))
+}
+
+/// `$WINEPREFIX` first, then every prefix `wine::prefixes` knows about.
+#[cfg(not(windows))]
+fn known_prefixes() -> Vec<PathBuf> {
+    let mut prefixes: Vec<PathBuf> = std::env::var_os("WINEPREFIX").map(PathBuf::from).into_iter().collect();
+    prefixes.extend(wine::home().map(|home| wine::prefixes(&home)).unwrap_or_default());
+    prefixes
+}
+
+/// The prefix whose registered install actually has a LocalStorage.db, else the first with any
+/// registered install.
+#[cfg(not(windows))]
+fn installed_prefix() -> Option<(PathBuf, String)> {
+    let registered: Vec<(PathBuf, String)> = known_prefixes()
+        .into_iter()
+        .filter_map(|prefix| prefix_install_db_path(&prefix).ok().map(|db| (prefix, db)))
+        .collect();
+    let existing = registered.iter().position(|(_, db)| Path::new(db).is_file());
+    info!(registered = registered.len(), existing, "looked for installs in Wine prefixes");
+    registered.into_iter().nth(existing.unwrap_or(0))
+}
+
+#[cfg(windows)]
+pub fn get_game_install_path() -> Result<String> {
+    install_db_path(platform::native())
+}
+
+#[cfg(not(windows))]
+pub fn get_game_install_path() -> Result<String> {
+    installed_prefix()
+        .map(|(_, db)| db)
+        .ok_or_else(|| MyError::Detection("no Wine or Proton prefix has the game registered".into()))
+}
+
+#[cfg(windows)]
+pub fn get_game_version() -> Option<String> {
+    game_version(platform::native())
+}
+
+#[cfg(not(windows))]
+pub fn get_game_version() -> Option<String> {
+    let (prefix, _) = installed_prefix()?;
+    let read = |location: &registry::Location, name: &str| wine::registry_string(&prefix, location, name);
+    let found = registry::candidates(read, &registry::game_locations(), registry::VERSION_NAMES);
+    found.into_iter().next().map(|candidate| candidate.data)
+}
+
+/// Where the active game keeps LocalStorage.db under a launcher install folder.
+pub fn db_path_in(install_dir: &Path) -> PathBuf {
+    games::active().db_path_in(install_dir)
+}
+
+/// LocalStorage.db of the install a running client executable belongs to, if it exists.
+pub fn db_path_for_exe(exe: &Path) -> Option<PathBuf> {
+    let game_dir = games::active().game_dir();
+    exe.ancestors()
+        .find(|dir| dir.file_name().is_some_and(|name| name == game_dir))
+        .and_then(Path::parent)
+        .map(db_path_in)
+        .filter(|db| db.is_file())
+}
+
+#[cfg(windows)]
+fn fixed_drives() -> Vec<PathBuf> {
+    (b'A'..=b'Z')
+        .map(|letter| PathBuf::from(format!("{}:\\", letter as char)))
+        .filter(|root| root.exists())
+        .collect()
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_type().map(|kind| kind.is_dir()).unwrap_or(false))
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && !SKIPPED_DIRS.iter().any(|skipped| name.eq_ignore_ascii_case(skipped))
+        })
+        .map(|entry| entry.path())
+        .collect()
+}
+
+fn search(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    if dir.file_name().is_some_and(|name| name == games::active().game_dir()) {
+        let db = db_path_in(dir.parent().unwrap_or(dir));
+        if db.is_file() {
+            debug!(db = %db.display(), "found install");
+            found.push(db);
+        }
+        return;
+    }
+    if depth == 0 {
+        return;
+    }
+    for child in subdirs(dir) {
+        search(&child, depth - 1, found);
+    }
+}
+
+/// Top-level folders to search, each one unit of progress: every folder of every drive.
+#[cfg(windows)]
+fn scan_targets() -> Vec<PathBuf> {
+    fixed_drives().iter().flat_map(|drive| subdirs(drive)).collect()
+}
+
+/// Top-level folders to search, each one unit of progress: every folder of each prefix's
+/// `drive_c`, then `steamapps/common` of each Steam library for the Steam release.
+#[cfg(not(windows))]
+fn scan_targets() -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = known_prefixes().iter().flat_map(|prefix| subdirs(&prefix.join("drive_c"))).collect();
+    if let Some(home) = wine::home() {
+        roots.extend(wine::steam_libraries(&home).into_iter().map(|library| library.join("steamapps").join("common")));
+    }
+    roots
+}
+
+/// Searches every drive (or, off Windows, every Wine prefix and Steam library) for game installs.
+/// `progress` is called before each top-level folder with (done, total, folder) and can return
+/// false to stop early.
+pub fn scan_drives(progress: impl FnMut(usize, usize, &Path) -> bool) -> Vec<PathBuf> {
+    if games::active().game_dir().is_empty() {
+        info!("the active game has no install folder to look for");
+        return Vec::new();
+    }
+    scan_roots(&scan_targets(), progress)
+}
+
+/// Searches below each of `roots` for game installs; see `scan_drives`.
+pub fn scan_roots(roots: &[PathBuf], mut progress: impl FnMut(usize, usize, &Path) -> bool) -> Vec<PathBuf> {
+    let total = roots.len();
+    info!(total, "scanning for installs");
+    let mut found = Vec::new();
+    for (done, root) in roots.iter().enumerate() {
+        if !progress(done, total, root) {
+            break;
+        }
+        search(root, MAX_DEPTH - 1, &mut found);
+    }
+    info!(found = found.len(), "scan finished");
+    found
+}
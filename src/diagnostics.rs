@@ -0,0 +1,223 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use eframe::egui;
+use serde_json::Value;
+
+use crate::access::WriteAccess;
+use crate::competitors::{self, Sign};
+use crate::db::{fps_from_setting, read_quality_setting_file};
+use crate::detect::get_game_install_path;
+use crate::gpu::{self, Gpu, Tier};
+use crate::frame_rate::{self, MenuOption};
+use crate::{defender, engine_ini, game_user_settings, keymap, platform, process};
+
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+impl CheckStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+        }
+    }
+
+    pub fn color(self) -> egui::Color32 {
+        match self {
+            CheckStatus::Pass => egui::Color32::from_rgb(80, 180, 80),
+            CheckStatus::Warn => egui::Color32::from_rgb(220, 170, 50),
+            CheckStatus::Fail => egui::Color32::from_rgb(220, 80, 80),
+        }
+    }
+}
+
+pub struct CheckResult {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self { name, status, detail: detail.into() }
+    }
+}
+
+/// Every launcher registration found, so per-user or stale entries show up in reports.
+#[cfg(windows)]
+fn registration_check() -> CheckResult {
+    let candidates = crate::detect::install_candidates(platform::native());
+    if candidates.is_empty() {
+        return CheckResult::new("Launcher registration", CheckStatus::Warn, "No install registered under HKLM or HKCU in either registry view.");
+    }
+    let found: Vec<String> = candidates.iter().map(ToString::to_string).collect();
+    CheckResult::new("Launcher registration", CheckStatus::Pass, found.join("; "))
+}
+
+/// Runs the manual setup steps as checks against `db_path`, or the detected DB if none is loaded.
+pub fn run_checks(db_path: &str) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+
+    let detected = get_game_install_path();
+    results.push(match &detected {
+        Ok(path) => CheckResult::new("Game detected", CheckStatus::Pass, path.clone()),
+        Err(err) => CheckResult::new("Game detected", CheckStatus::Warn, err.to_string()),
+    });
+    #[cfg(windows)]
+    results.push(registration_check());
+
+    let db_path = if db_path.is_empty() {
+        detected.unwrap_or_default()
+    } else {
+        db_path.to_string()
+    };
+
+    results.push(if process::is_game_running(platform::native()) {
+        CheckResult::new("Game not running", CheckStatus::Fail, "Close the game before patching; it rewrites the file on exit.")
+    } else {
+        CheckResult::new("Game not running", CheckStatus::Pass, "No game process found.")
+    });
+    results.push(check_competitors(&db_path));
+
+    let gpus = gpu::adapters();
+    if !gpus.is_empty() {
+        let listed: Vec<String> = gpus.iter().map(Gpu::describe).collect();
+        results.push(CheckResult::new("Graphics", CheckStatus::Pass, listed.join("; ")));
+    }
+
+    if db_path.is_empty() || fs::metadata(&db_path).is_err() {
+        results.push(CheckResult::new("Database readable", CheckStatus::Fail, "No configuration file found or selected."));
+        return results;
+    }
+
+    let setting = match read_quality_setting_file(&db_path) {
+        Ok(setting) => {
+            results.push(CheckResult::new("Database readable", CheckStatus::Pass, db_path.clone()));
+            setting
+        }
+        Err(err) => {
+            results.push(CheckResult::new("Database readable", CheckStatus::Fail, err.to_string()));
+            return results;
+        }
+    };
+
+    results.push(check_writable(&db_path));
+    results.extend(check_controlled_folder_access(&db_path));
+    results.push(check_vsync(&setting));
+    results.extend(check_frame_rate(&setting));
+    results.extend(gpu::primary(&gpus).and_then(|gpu| check_gpu_headroom(gpu, &setting)));
+    results.extend(check_game_user_settings(&db_path, &setting));
+    results
+}
+
+fn check_competitors(db_path: &str) -> CheckResult {
+    const NAME: &str = "No other FPS unlocker";
+    let signs: Vec<String> = competitors::find(platform::native(), db_path).iter().map(Sign::describe).collect();
+    if signs.is_empty() {
+        return CheckResult::new(NAME, CheckStatus::Pass, "No other unlocker found.");
+    }
+    CheckResult::new(
+        NAME,
+        CheckStatus::Warn,
+        format!("{}. Two tools writing the FPS limit overwrite each other; keep only one.", signs.join("; ")),
+    )
+}
+
+fn check_writable(db_path: &str) -> CheckResult {
+    const NAME: &str = "Database writable";
+    let access = WriteAccess::check(Path::new(db_path), platform::native());
+    let status = match access {
+        WriteAccess::Writable => CheckStatus::Pass,
+        WriteAccess::NeedsElevation => CheckStatus::Warn,
+        WriteAccess::ReadOnly(_) => CheckStatus::Fail,
+    };
+    CheckResult::new(NAME, status, access.describe())
+}
+
+/// Nothing to report unless Controlled Folder Access is on.
+fn check_controlled_folder_access(db_path: &str) -> Option<CheckResult> {
+    const NAME: &str = "Controlled Folder Access";
+    let cfa = defender::query().filter(|cfa| cfa.blocks_writes())?;
+    let exe = env::current_exe().ok()?;
+    Some(if cfa.blocks(Path::new(db_path), &exe, &defender::default_protected()) {
+        CheckResult::new(NAME, CheckStatus::Fail, "The game folder is protected and WuWa Ploom isn't allowed; allow it from the Patcher tab.")
+    } else {
+        CheckResult::new(NAME, CheckStatus::Pass, "On, but it doesn't block writes to the game folder.")
+    })
+}
+
+fn check_vsync(setting: &Value) -> CheckResult {
+    const NAME: &str = "VSync off";
+    let key = keymap::active().vsync;
+    match setting[&key].as_i64() {
+        Some(0) => CheckResult::new(NAME, CheckStatus::Pass, format!("{} = 0", key)),
+        Some(value) => CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{} = {}; VSync caps the frame rate to your refresh rate.", key, value),
+        ),
+        None => CheckResult::new(NAME, CheckStatus::Warn, format!("{} not present in this client version.", key)),
+    }
+}
+
+/// Nothing to report when the client keeps no GameUserSettings.ini.
+fn check_game_user_settings(db_path: &str, setting: &Value) -> Option<CheckResult> {
+    const NAME: &str = "GameUserSettings.ini agrees";
+    let path = game_user_settings::path_for_db(Path::new(db_path)).filter(|path| path.is_file())?;
+    let text = match engine_ini::read(&path) {
+        Ok(text) => text,
+        Err(err) => return Some(CheckResult::new(NAME, CheckStatus::Warn, err.to_string())),
+    };
+    let mismatches = game_user_settings::mismatches(&text, setting);
+    if mismatches.is_empty() {
+        return Some(CheckResult::new(NAME, CheckStatus::Pass, "Same values as the database."));
+    }
+    let detail: Vec<String> =
+        mismatches.iter().map(|mismatch| format!("{} is {} there but {} in the database", mismatch.pair.ini_key, mismatch.ini, mismatch.db)).collect();
+    Some(CheckResult::new(
+        NAME,
+        CheckStatus::Warn,
+        format!("{}; the game may snap back. Sync it on the Config Tweaks tab.", detail.join("; ")),
+    ))
+}
+
+/// A warning when an entry-level or integrated GPU is asked for more than 60 FPS; nothing to
+/// say about faster or unknown models.
+fn check_gpu_headroom(gpu: &Gpu, setting: &Value) -> Option<CheckResult> {
+    let fps = fps_from_setting(setting).ok()?;
+    if fps <= 60 || gpu.tier()? > Tier::Entry {
+        return None;
+    }
+    Some(CheckResult::new(
+        "GPU can hold the limit",
+        CheckStatus::Warn,
+        format!(
+            "{} is entry-level or integrated graphics and may not reach {} FPS. Lower the resolution or graphics settings to get closer.",
+            gpu.name, fps
+        ),
+    ))
+}
+
+fn check_frame_rate(setting: &Value) -> Vec<CheckResult> {
+    let fps = match fps_from_setting(setting) {
+        Ok(fps) => fps,
+        Err(err) => return vec![CheckResult::new("Current FPS", CheckStatus::Fail, err.to_string())],
+    };
+    let current = CheckResult::new("Current FPS", CheckStatus::Pass, format!("{} = {}", keymap::active().frame_rate, fps));
+    const NAME: &str = "In-game limit set to 60";
+    let option = frame_rate::menu_option(setting);
+    let in_game = match (option, option.warning()) {
+        (MenuOption::Patched(fps), _) => CheckResult::new(NAME, CheckStatus::Pass, format!("Already patched to {}.", fps)),
+        (_, Some(warning)) => CheckResult::new(NAME, CheckStatus::Fail, warning),
+        (_, None) => CheckResult::new(NAME, CheckStatus::Pass, "Ready to patch."),
+    };
+    vec![current, in_game]
+}
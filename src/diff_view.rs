@@ -0,0 +1,89 @@
+use eframe::egui::{self, Color32, RichText};
+use serde_json::Value;
+
+use crate::json_diff::{self, Diff};
+use crate::toasts::ToastLevel;
+
+const STRING_COLOR: Color32 = Color32::from_rgb(206, 145, 120);
+const NUMBER_COLOR: Color32 = Color32::from_rgb(181, 206, 168);
+const KEYWORD_COLOR: Color32 = Color32::from_rgb(86, 156, 214);
+
+/// Lists `members` by name: added ones in green, removed ones struck through in red and changed
+/// ones in yellow as before -> after. Nested objects and arrays collapse, and start open. Unchanged
+/// members only show with `show_same`. `id` keeps two diffs on one screen apart.
+pub fn show(ui: &mut egui::Ui, id: &str, members: &[(String, Diff)], show_same: bool) {
+    let counts = json_diff::count(members);
+    ui.weak(counts.summary());
+    for (name, diff) in members {
+        show_member(ui, &format!("{}.{}", id, name), Some(name), diff, show_same);
+    }
+}
+
+/// One diff without a name, e.g. in a column of a grid that names the key already.
+pub fn show_one(ui: &mut egui::Ui, id: &str, diff: &Diff) {
+    show_member(ui, id, None, diff, false);
+}
+
+/// `path` keeps egui ids unique for nested members that share a name.
+fn show_member(ui: &mut egui::Ui, path: &str, name: Option<&str>, diff: &Diff, show_same: bool) {
+    let name_text = |color: Color32| RichText::new(name.unwrap_or_default()).color(color);
+    match diff {
+        Diff::Same(value) => {
+            if show_same || name.is_none() {
+                ui.horizontal(|ui| {
+                    ui.weak(name.unwrap_or_default());
+                    ui.label(value_text(value));
+                    ui.weak("(unchanged)");
+                });
+            }
+        }
+        Diff::Added(value) => {
+            let color = ToastLevel::Success.color();
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("+").color(color).monospace());
+                ui.label(name_text(color));
+                ui.label(value_text(value));
+            });
+        }
+        Diff::Removed(value) => {
+            let color = ToastLevel::Error.color();
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("-").color(color).monospace());
+                ui.label(name_text(color).strikethrough());
+                ui.label(value_text(value).strikethrough());
+            });
+        }
+        Diff::Changed { before, after } => {
+            let color = ToastLevel::Warning.color();
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("~").color(color).monospace());
+                ui.label(name_text(color));
+                ui.label(value_text(before).strikethrough());
+                ui.label("->");
+                ui.label(value_text(after));
+            });
+        }
+        Diff::Nested(members) => {
+            let title = format!("{} ({})", name.unwrap_or("nested"), json_diff::count(members).summary());
+            egui::CollapsingHeader::new(RichText::new(title).color(ToastLevel::Warning.color()))
+                .id_source(path)
+                .default_open(true)
+                .show(ui, |ui| {
+                    for (member, diff) in members {
+                        show_member(ui, &format!("{}.{}", path, member), Some(member), diff, show_same);
+                    }
+                });
+        }
+    }
+}
+
+/// `value` as compact JSON, coloured by type.
+fn value_text(value: &Value) -> RichText {
+    let color = match value {
+        Value::String(_) => STRING_COLOR,
+        Value::Number(_) => NUMBER_COLOR,
+        Value::Bool(_) | Value::Null => KEYWORD_COLOR,
+        Value::Array(_) | Value::Object(_) => return RichText::new(value.to_string()).monospace(),
+    };
+    RichText::new(value.to_string()).color(color).monospace()
+}
@@ -0,0 +1,186 @@
+//! Discord Rich Presence through the local IPC socket the Discord client listens on, so users
+//! can see in Discord that the watchdog is running without opening the app. One background
+//! thread connects when there is something to show and retries while Discord is closed; closing
+//! the connection clears the status.
+
+use std::io::{self, Read, Write};
+use std::process;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::{MyError, Result};
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+/// Discord numbers its sockets from 0 when several clients run.
+const SOCKETS: u32 = 10;
+/// Replies are small; anything larger is not Discord.
+const MAX_FRAME: u32 = 64 * 1024;
+/// How often an undelivered status is tried again, e.g. until Discord starts.
+const RETRY: Duration = Duration::from_secs(30);
+
+/// One IPC message: opcode and length, little-endian, then the JSON payload.
+pub fn encode(op: u32, payload: &Value) -> Vec<u8> {
+    let body = payload.to_string();
+    let mut frame = Vec::with_capacity(8 + body.len());
+    frame.extend_from_slice(&op.to_le_bytes());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(body.as_bytes());
+    frame
+}
+
+/// Reads one message written as `encode` does.
+pub fn decode(reader: &mut (impl Read + ?Sized)) -> Result<(u32, Value)> {
+    let mut header = [0; 8];
+    reader.read_exact(&mut header)?;
+    let op = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    if len > MAX_FRAME {
+        return Err(MyError::Io(io::Error::other(format!("a {} byte Discord message", len))));
+    }
+    let mut body = vec![0; len as usize];
+    reader.read_exact(&mut body)?;
+    Ok((op, serde_json::from_slice(&body)?))
+}
+
+/// The command setting our status to `text`, or clearing it. `started` (Unix seconds) is shown
+/// as elapsed time.
+pub fn activity(pid: u32, text: Option<&str>, started: u64, nonce: u64) -> Value {
+    let activity = text.map(|text| json!({ "details": text, "timestamps": { "start": started } }));
+    json!({ "cmd": "SET_ACTIVITY", "args": { "pid": pid, "activity": activity }, "nonce": nonce.to_string() })
+}
+
+trait Stream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Stream for T {}
+
+#[cfg(windows)]
+fn open(index: u32) -> io::Result<Box<dyn Stream>> {
+    let pipe = std::fs::OpenOptions::new().read(true).write(true).open(format!(r"\\.\pipe\discord-ipc-{}", index))?;
+    Ok(Box::new(pipe))
+}
+
+/// Discord's socket is in the runtime or temp folder, or inside the Flatpak and Snap sandboxes.
+#[cfg(unix)]
+fn open(index: u32) -> io::Result<Box<dyn Stream>> {
+    use std::os::unix::net::UnixStream;
+    use std::path::PathBuf;
+
+    let bases: Vec<PathBuf> = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .map(PathBuf::from)
+        .chain([PathBuf::from("/tmp")])
+        .collect();
+    let name = format!("discord-ipc-{}", index);
+    let mut last = io::Error::from(io::ErrorKind::NotFound);
+    for base in &bases {
+        for sandbox in ["", "app/com.discordapp.Discord", "snap.discord"] {
+            match UnixStream::connect(base.join(sandbox).join(&name)) {
+                Ok(stream) => return Ok(Box::new(stream)),
+                Err(err) => last = err,
+            }
+        }
+    }
+    Err(last)
+}
+
+#[cfg(not(any(windows, unix)))]
+fn open(_index: u32) -> io::Result<Box<dyn Stream>> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// Writes `payload` and reads the reply, which Discord sends for every message.
+fn exchange(stream: &mut dyn Stream, op: u32, payload: &Value) -> Result<Value> {
+    stream.write_all(&encode(op, payload))?;
+    let (_, reply) = decode(stream)?;
+    if reply["evt"] == "ERROR" {
+        let message = reply["data"]["message"].as_str().unwrap_or("unknown error");
+        return Err(MyError::Io(io::Error::other(format!("Discord refused: {}", message))));
+    }
+    Ok(reply)
+}
+
+fn connect(app_id: &str) -> Result<Box<dyn Stream>> {
+    let mut last = None;
+    for index in 0..SOCKETS {
+        match open(index) {
+            Ok(mut stream) => {
+                exchange(stream.as_mut(), OP_HANDSHAKE, &json!({ "v": 1, "client_id": app_id }))?;
+                return Ok(stream);
+            }
+            Err(err) => last = Some(err),
+        }
+    }
+    Err(MyError::Io(last.unwrap_or_else(|| io::Error::from(io::ErrorKind::NotFound))))
+}
+
+/// Handle to the presence thread. Dropping it closes the connection, which clears the status.
+pub struct Presence {
+    app_id: String,
+    queue: Sender<Option<String>>,
+}
+
+impl Presence {
+    /// Starts the thread for the Discord application `app_id`; nothing is shown until `set`.
+    pub fn start(app_id: &str) -> Self {
+        let (queue, updates) = mpsc::channel();
+        let id = app_id.to_string();
+        if let Err(err) = thread::Builder::new().name("discord".into()).spawn(move || serve(&id, updates)) {
+            debug!(%err, "could not start the Discord presence thread");
+        }
+        Self { app_id: app_id.to_string(), queue }
+    }
+
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    /// Shows `text` as our status, or clears it.
+    pub fn set(&self, text: Option<String>) {
+        let _ = self.queue.send(text);
+    }
+}
+
+fn serve(app_id: &str, updates: Receiver<Option<String>>) {
+    let started = SystemTime::now().duration_since(UNIX_EPOCH).map(|since| since.as_secs()).unwrap_or_default();
+    let mut stream: Option<Box<dyn Stream>> = None;
+    let mut wanted: Option<String> = None;
+    let mut delivered = true;
+    let mut nonce = 0;
+    loop {
+        match updates.recv_timeout(RETRY) {
+            Ok(text) => {
+                wanted = text;
+                // Without a connection there is no status to clear.
+                delivered = wanted.is_none() && stream.is_none();
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if delivered {
+            continue;
+        }
+        nonce += 1;
+        let command = activity(process::id(), wanted.as_deref(), started, nonce);
+        let sent = match &mut stream {
+            Some(stream) => exchange(stream.as_mut(), OP_FRAME, &command),
+            None => connect(app_id).and_then(|mut new| {
+                exchange(new.as_mut(), OP_FRAME, &command)?;
+                stream = Some(new);
+                Ok(Value::Null)
+            }),
+        };
+        match sent {
+            Ok(_) => delivered = true,
+            Err(err) => {
+                debug!(%err, "Discord presence not delivered; retrying later");
+                stream = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! The main display's refresh rate. A frame-rate limit above the refresh rate only tears or
+//! waits, so when the desktop runs at 60 Hz on a faster monitor the app offers to switch before
+//! the game starts. Changes are not saved to the registry: restoring, or a reboot, brings back
+//! the user's own mode.
+
+#[cfg(windows)]
+use std::mem;
+#[cfg(windows)]
+use std::ptr;
+
+#[cfg(windows)]
+use winapi::um::wingdi::{DEVMODEW, DM_DISPLAYFREQUENCY};
+#[cfg(windows)]
+use winapi::um::winuser::{ChangeDisplaySettingsExW, EnumDisplaySettingsW, DISP_CHANGE_SUCCESSFUL, ENUM_CURRENT_SETTINGS};
+
+/// How far below the refresh rate a VRR (G-Sync/FreeSync) display should be capped. At the
+/// refresh rate itself frames fall out of the VRR range into VSync lag or tearing.
+pub const VRR_MARGIN: u32 = 3;
+
+/// The frame-rate limit for a VRR display at `hz`, kept within `min` and `max`, the limits the
+/// game accepts. `None` for the 0 and 1 Hz that Windows reports for "hardware default".
+pub fn vrr_cap(hz: u32, min: Option<i64>, max: Option<i64>) -> Option<i64> {
+    if hz <= VRR_MARGIN {
+        return None;
+    }
+    let cap = i64::from(hz - VRR_MARGIN);
+    let cap = max.map_or(cap, |max| cap.min(max));
+    Some(min.map_or(cap, |min| cap.max(min)))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Display {
+    /// Current refresh rate in Hz.
+    pub hz: u32,
+    /// Rates the display offers at its current resolution, ascending.
+    pub rates: Vec<u32>,
+}
+
+impl Display {
+    /// The lowest offered rate that fits `fps` frames, or the highest one if none does; `None`
+    /// when the current rate is already that.
+    pub fn rate_for(&self, fps: i64) -> Option<u32> {
+        let fitting = self.rates.iter().copied().find(|&rate| i64::from(rate) >= fps);
+        let best = fitting.or(self.rates.last().copied())?;
+        (i64::from(self.hz) < fps && best > self.hz).then_some(best)
+    }
+
+    /// The VRR limit for each offered rate, ascending, as "165 Hz: 162 FPS" lines.
+    pub fn vrr_ladder(&self, min: Option<i64>, max: Option<i64>) -> String {
+        let lines: Vec<String> = self.rates.iter().filter_map(|&hz| Some(format!("{} Hz: {} FPS", hz, vrr_cap(hz, min, max)?))).collect();
+        lines.join("\n")
+    }
+}
+
+#[cfg(windows)]
+fn mode(index: u32) -> Option<DEVMODEW> {
+    // SAFETY: DEVMODEW is plain data; dmSize tells Windows which version we pass.
+    unsafe {
+        let mut mode: DEVMODEW = mem::zeroed();
+        mode.dmSize = mem::size_of::<DEVMODEW>() as u16;
+        (EnumDisplaySettingsW(ptr::null(), index, &mut mode) != 0).then_some(mode)
+    }
+}
+
+/// The primary display's mode; `None` when Windows doesn't say, or elsewhere.
+#[cfg(windows)]
+pub fn current() -> Option<Display> {
+    let current = mode(ENUM_CURRENT_SETTINGS)?;
+    let same_size = |other: &DEVMODEW| {
+        other.dmPelsWidth == current.dmPelsWidth && other.dmPelsHeight == current.dmPelsHeight && other.dmBitsPerPel == current.dmBitsPerPel
+    };
+    let mut rates: Vec<u32> = (0..).map_while(mode).filter(same_size).map(|mode| mode.dmDisplayFrequency).filter(|&hz| hz > 1).collect();
+    rates.sort_unstable();
+    rates.dedup();
+    Some(Display { hz: current.dmDisplayFrequency, rates })
+}
+
+/// Switches the primary display to `hz` for this session only.
+#[cfg(windows)]
+pub fn set_refresh_rate(hz: u32) -> Result<(), String> {
+    let mut mode = mode(ENUM_CURRENT_SETTINGS).ok_or("the current display mode is unknown")?;
+    mode.dmDisplayFrequency = hz;
+    mode.dmFields = DM_DISPLAYFREQUENCY;
+    // SAFETY: `mode` is a valid DEVMODEW; flags 0 change the mode without saving it.
+    let result = unsafe { ChangeDisplaySettingsExW(ptr::null(), &mut mode, ptr::null_mut(), 0, ptr::null_mut()) };
+    if result == DISP_CHANGE_SUCCESSFUL {
+        tracing::info!(hz, "switched refresh rate");
+        Ok(())
+    } else {
+        Err(format!("Windows refused {} Hz (code {})", hz, result))
+    }
+}
+
+/// Back to the mode saved in the registry, i.e. the user's own.
+#[cfg(windows)]
+pub fn restore() {
+    // SAFETY: a null mode asks Windows to reload the registry mode.
+    let result = unsafe { ChangeDisplaySettingsExW(ptr::null(), ptr::null_mut(), ptr::null_mut(), 0, ptr::null_mut()) };
+    tracing::info!(result, "restored display mode");
+}
+
+#[cfg(not(windows))]
+pub fn current() -> Option<Display> {
+    None
+}
+
+#[cfg(not(windows))]
+pub fn set_refresh_rate(_hz: u32) -> Result<(), String> {
+    Err("switching the refresh rate is only supported on Windows".into())
+}
+
+#[cfg(not(windows))]
+pub fn restore() {}
@@ -0,0 +1,112 @@
+//! Tweaks in the game's `Engine.ini`, the Unreal config that sits beside LocalStorage.db under
+//! `Saved`. Each tweak is one line the user turns on or off; the file is backed up before every
+//! change and only those lines are touched.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use tracing::{info, instrument};
+
+use crate::{backup, ini, store, Result};
+
+pub const SYSTEM_SETTINGS: &str = "SystemSettings";
+const ENGINE_SECTION: &str = "/Script/Engine.Engine";
+/// Config folders of Unreal 4 and Unreal 5 clients, checked in this order.
+const PLATFORM_DIRS: &[&str] = &["WindowsNoEditor", "Windows"];
+
+/// A line we know to be useful, with what it does.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Tweak {
+    pub section: &'static str,
+    pub key: &'static str,
+    /// Written when the tweak is turned on.
+    pub value: &'static str,
+    pub label: &'static str,
+    pub description: &'static str,
+}
+
+pub const TWEAKS: &[Tweak] = &[
+    Tweak {
+        section: SYSTEM_SETTINGS,
+        key: "r.Streaming.PoolSize",
+        value: "3072",
+        label: "Larger texture pool (3 GB)",
+        description: "Fewer texture reloads and the stutters they cause; for cards with 6 GB of video memory or more.",
+    },
+    Tweak {
+        section: SYSTEM_SETTINGS,
+        key: "r.Streaming.FullyLoadUsedTextures",
+        value: "1",
+        label: "Fully load used textures",
+        description: "Loads textures in full once used instead of streaming them in, trading memory for less pop-in.",
+    },
+    Tweak {
+        section: SYSTEM_SETTINGS,
+        key: "r.GTSyncType",
+        value: "1",
+        label: "Sync the game thread with the render thread",
+        description: "Lower input latency at high frame rates, at a small cost in throughput.",
+    },
+    Tweak {
+        section: ENGINE_SECTION,
+        key: "bSmoothFrameRate",
+        value: "False",
+        label: "Frame smoothing off",
+        description: "Stops the engine from holding frames back to even out pacing, which can fight an FPS limit.",
+    },
+];
+
+/// The folder of the client's INI files for the LocalStorage.db at `db_path`, whether or not
+/// it exists yet.
+pub fn config_dir_for_db(db_path: &Path) -> Option<PathBuf> {
+    let saved = db_path.parent()?.parent()?;
+    let config = saved.join("Config");
+    Some(PLATFORM_DIRS.iter().map(|dir| config.join(dir)).find(|dir| dir.is_dir()).unwrap_or_else(|| config.join(PLATFORM_DIRS[0])))
+}
+
+/// Where `Engine.ini` is for the LocalStorage.db at `db_path`.
+pub fn path_for_db(db_path: &Path) -> Option<PathBuf> {
+    Some(config_dir_for_db(db_path)?.join("Engine.ini"))
+}
+
+/// The file's text; empty if it doesn't exist yet.
+pub fn read(path: &Path) -> Result<String> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(text),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(String::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// The value `tweak`'s key has in `text`, whether or not it is the tweak's own.
+pub fn current(text: &str, tweak: &Tweak) -> Option<String> {
+    ini::get(text, tweak.section, tweak.key)
+}
+
+/// Sets `key` in `section` to `value`, or removes it with `None`, after backing up the file
+/// into `backup_dir` if given. Returns the backup.
+#[instrument(skip(backup_dir), err(level = "warn"))]
+pub fn edit(path: &Path, section: &str, key: &str, value: Option<&str>, backup_dir: Option<&Path>) -> Result<Option<PathBuf>> {
+    let text = read(path)?;
+    let updated = match value {
+        Some(value) => ini::set(&text, section, key, value),
+        None => ini::remove(&text, section, key),
+    };
+    replace_text(path, &text, &updated, backup_dir)
+}
+
+/// Writes `updated` over the INI file at `path`, whose content was `text`, backing it up into
+/// `backup_dir` first if given; nothing happens when they are the same. Returns the backup.
+pub fn replace_text(path: &Path, text: &str, updated: &str, backup_dir: Option<&Path>) -> Result<Option<PathBuf>> {
+    if updated == text {
+        return Ok(None);
+    }
+    let backup = match backup_dir.filter(|_| !text.is_empty()) {
+        Some(dir) => Some(backup::copy_new(path, dir, &path.file_stem().unwrap_or_default().to_string_lossy(), "ini")?),
+        None => None,
+    };
+    store::write_atomic(path, updated.as_bytes())?;
+    info!(path = %path.display(), "edited INI file");
+    Ok(backup)
+}
@@ -0,0 +1,52 @@
+//! Where the game's own FPS option was left. The instructions ask to set it to 60 in the game
+//! before patching: the menu only offers a few limits and the game keeps a value it wrote itself, so a
+//! limit patched over 30 or 45 may be put back on its next start. The frame-rate key tells which
+//! one the menu is on, so the step can be checked instead of trusted.
+
+use serde_json::Value;
+
+use crate::keymap;
+
+/// Frame-rate limits the in-game menu offers.
+pub const MENU_LIMITS: [i64; 3] = [30, 45, 60];
+/// The menu entry the instructions ask for.
+pub const EXPECTED: i64 = 60;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuOption {
+    /// On 60, ready to patch.
+    Expected,
+    /// On another menu entry.
+    Other(i64),
+    /// A limit the menu doesn't offer, so patched already.
+    Patched(i64),
+    /// The game never saved a choice: the key is missing or not a number.
+    Unset,
+}
+
+impl MenuOption {
+    /// What to do about it, or `None` when nothing needs doing.
+    pub fn warning(self) -> Option<String> {
+        match self {
+            MenuOption::Expected | MenuOption::Patched(_) => None,
+            MenuOption::Other(fps) => Some(format!(
+                "The in-game FPS option is on {}, not {}. Set it to {} in the game and close it before patching, or the game may put its own limit back.",
+                fps, EXPECTED, EXPECTED
+            )),
+            MenuOption::Unset => Some(format!(
+                "The game hasn't saved an FPS choice yet. Pick {} in its graphics settings and close it before patching.",
+                EXPECTED
+            )),
+        }
+    }
+}
+
+/// Where the in-game FPS option of `setting` stands.
+pub fn menu_option(setting: &Value) -> MenuOption {
+    match setting.get(&keymap::active().frame_rate).and_then(Value::as_i64) {
+        None => MenuOption::Unset,
+        Some(EXPECTED) => MenuOption::Expected,
+        Some(fps) if MENU_LIMITS.contains(&fps) => MenuOption::Other(fps),
+        Some(fps) => MenuOption::Patched(fps),
+    }
+}
@@ -0,0 +1,77 @@
+//! Noticing a game update in progress. The launcher replaces the client's files while it
+//! updates, and writing the settings then has left some users with a broken Saved folder, so
+//! writes wait until it is done. Two signs give an update away: an updater process running from
+//! the launcher's install folder, and half-downloaded files next to the game's paks.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::games;
+use crate::platform::Platform;
+
+/// Words in the names of the launcher's update and download helpers.
+const UPDATER_WORDS: &[&str] = &["update", "patch", "download", "install"];
+/// Endings of files the launcher is still writing.
+const PARTIAL_ENDINGS: &[&str] = &[".tmp", ".temp", ".part", ".download", ".lock"];
+/// Folders below the game folder where the launcher downloads; not searched recursively, as
+/// the install holds tens of thousands of files.
+const DOWNLOAD_DIRS: &[&[&str]] = &[&[], &["Client"], &["Client", "Content", "Paks"]];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UpdateSign {
+    Updater(String),
+    PartialFile(PathBuf),
+}
+
+impl UpdateSign {
+    pub fn describe(&self) -> String {
+        match self {
+            UpdateSign::Updater(name) => format!("{} is running", name),
+            UpdateSign::PartialFile(path) => format!("{} is still being downloaded", path.display()),
+        }
+    }
+}
+
+/// Whether `name` is a file the launcher hasn't finished writing.
+pub fn is_partial_file(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    PARTIAL_ENDINGS.iter().any(|ending| name.ends_with(ending))
+}
+
+/// Update helpers running from `install_dir`, the launcher's folder. Processes whose path we
+/// can't see are left out, so an unrelated installer elsewhere never blocks writes.
+pub fn updater_processes(platform: &dyn Platform, install_dir: &Path) -> Vec<String> {
+    platform
+        .processes()
+        .into_iter()
+        .filter(|process| process.exe.as_deref().is_some_and(|exe| exe.starts_with(install_dir)))
+        .filter(|process| {
+            let name = process.name.to_ascii_lowercase();
+            UPDATER_WORDS.iter().any(|word| name.contains(word))
+        })
+        .map(|process| process.name)
+        .collect()
+}
+
+/// Half-written files in the download folders below `game_folder`.
+pub fn partial_files(game_folder: &Path) -> Vec<PathBuf> {
+    DOWNLOAD_DIRS
+        .iter()
+        .map(|parts| parts.iter().fold(game_folder.to_path_buf(), |path, part| path.join(part)))
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_file()) && is_partial_file(&entry.file_name().to_string_lossy()))
+        .map(|entry| entry.path())
+        .collect()
+}
+
+/// The first sign of an update to the install holding `db_path`, if one is under way.
+pub fn in_progress(platform: &dyn Platform, db_path: &Path) -> Option<UpdateSign> {
+    let game_folder = games::game_folder_of(db_path)?;
+    let install_dir = game_folder.parent().unwrap_or(game_folder);
+    if let Some(name) = updater_processes(platform, install_dir).into_iter().next() {
+        return Some(UpdateSign::Updater(name));
+    }
+    partial_files(game_folder).into_iter().next().map(UpdateSign::PartialFile)
+}
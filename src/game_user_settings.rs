@@ -0,0 +1,72 @@
+//! `GameUserSettings.ini`, Unreal's own store for the frame-rate limit and resolution. Some
+//! client versions read these from it as well as from LocalStorage.db, and when the two disagree
+//! the game snaps back to the INI's value. The database is what this tool edits, so syncing
+//! copies its values into the INI.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+use crate::engine_ini::{self, config_dir_for_db};
+use crate::{ini, keymap, Result};
+
+pub const SECTION: &str = "/Script/Engine.GameUserSettings";
+
+/// A value stored in both places; the INI key and the GameQualitySetting key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pair {
+    pub ini_key: &'static str,
+    pub db_key: String,
+}
+
+/// The values kept in sync.
+pub fn pairs() -> Vec<Pair> {
+    vec![
+        Pair { ini_key: "FrameRateLimit", db_key: keymap::active().frame_rate },
+        Pair { ini_key: "ResolutionSizeX", db_key: "KeyPcResolutionWidth".into() },
+        Pair { ini_key: "ResolutionSizeY", db_key: "KeyPcResolutionHeight".into() },
+    ]
+}
+
+/// A value that differs between the two stores.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    pub pair: Pair,
+    pub ini: String,
+    pub db: f64,
+}
+
+/// Where `GameUserSettings.ini` is for the LocalStorage.db at `db_path`.
+pub fn path_for_db(db_path: &Path) -> Option<PathBuf> {
+    Some(config_dir_for_db(db_path)?.join("GameUserSettings.ini"))
+}
+
+/// Values set in both `text` and `setting` that differ. Unreal writes the limit as a float
+/// (`120.000000`), so values compare as numbers.
+pub fn mismatches(text: &str, setting: &Value) -> Vec<Mismatch> {
+    pairs()
+        .into_iter()
+        .filter_map(|pair| {
+            let ini = ini::get(text, SECTION, pair.ini_key)?;
+            let db = setting.get(&pair.db_key)?.as_f64()?;
+            (ini.parse::<f64>().ok() != Some(db)).then_some(Mismatch { pair, ini, db })
+        })
+        .collect()
+}
+
+/// `text` with every mismatched value replaced by the database's, in the INI's own format.
+pub fn synced(text: &str, setting: &Value) -> String {
+    mismatches(text, setting).iter().fold(text.to_string(), |text, mismatch| {
+        let value = if mismatch.ini.contains('.') { format!("{:.6}", mismatch.db) } else { format!("{}", mismatch.db as i64) };
+        ini::set(&text, SECTION, mismatch.pair.ini_key, &value)
+    })
+}
+
+/// Copies the database's values into the INI at `path` where they differ, backing it up into
+/// `backup_dir` first if given. Returns how many values changed and the backup.
+pub fn sync(path: &Path, setting: &Value, backup_dir: Option<&Path>) -> Result<(usize, Option<PathBuf>)> {
+    let text = engine_ini::read(path)?;
+    let changed = mismatches(&text, setting).len();
+    let backup = engine_ini::replace_text(path, &text, &synced(&text, setting), backup_dir)?;
+    Ok((changed, backup))
+}
@@ -0,0 +1,112 @@
+//! The games this tool can patch. Kuro's launcher installs each game the same way and their
+//! clients keep settings in a LocalStorage.db, so an adapter is mostly a set of names: the
+//! launcher registration, the install folder, the processes and the settings keys. Detection,
+//! scanning, process watching and the key map work on the active adapter, which is Wuthering
+//! Waves unless the user picks another.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tracing::info;
+
+use crate::db::{self, Patch};
+use crate::keymap::{KeyMap, KeyNames};
+use crate::registry::{self, Location};
+
+mod unknown;
+mod wuthering_waves;
+
+pub use unknown::UnknownGame;
+pub use wuthering_waves::WutheringWaves;
+
+/// Where a client keeps LocalStorage.db below its game folder.
+const DB_SUBPATH: &[&str] = &["Client", "Saved", "LocalStorage", "LocalStorage.db"];
+/// Where an Unreal client keeps its executable below its game folder.
+const EXE_SUBPATH: &[&str] = &["Client", "Binaries", "Win64"];
+
+pub trait GameAdapter: Sync {
+    /// Short stable name for the config file and the command line.
+    fn id(&self) -> &'static str;
+    fn name(&self) -> &'static str;
+    /// Uninstall entries the launcher writes, below HKLM or HKCU.
+    fn uninstall_keys(&self) -> &'static [&'static str];
+    /// Folder of a launcher install holding the client; scans look for it by name.
+    fn game_dir(&self) -> &'static str;
+    /// Executables the client runs as; any of them holds the database open.
+    fn process_names(&self) -> &'static [&'static str];
+    /// Embedded key map in the format `keymap` reads.
+    fn key_map(&self) -> &'static str;
+    /// Name of files next to the executable or in the app folder overriding `key_map`.
+    fn key_map_file(&self) -> &'static str;
+
+    /// The executable that starts the game, at the top of its folder; the last process name.
+    fn launch_exe(&self) -> Option<&'static str> {
+        self.process_names().last().copied()
+    }
+
+    /// Whether the client keeps the GameQualitySetting row that the FPS patcher and the graphics
+    /// editor work on. Without it only raw row editing is offered.
+    fn has_quality_setting(&self) -> bool {
+        true
+    }
+
+    /// Every place the launcher may have registered the game; see `registry::locations`.
+    fn registry_locations(&self) -> Vec<Location> {
+        registry::locations(self.uninstall_keys())
+    }
+
+    /// The client executable, the first process name, of the install whose LocalStorage.db is
+    /// `db_path`. Unreal clients share generic names like "Client-Win64-Shipping.exe", so this
+    /// full path is what tells this game's install apart from other games.
+    fn client_exe_for(&self, db_path: &Path) -> Option<PathBuf> {
+        let exe = self.process_names().first()?;
+        let folder = game_folder_of(db_path)?;
+        Some(EXE_SUBPATH.iter().fold(folder.to_path_buf(), |path, part| path.join(part)).join(exe))
+    }
+
+    /// LocalStorage.db below a launcher install folder.
+    fn db_path_in(&self, install_dir: &Path) -> PathBuf {
+        DB_SUBPATH.iter().fold(install_dir.join(self.game_dir()), |path, part| path.join(part))
+    }
+
+    /// Settings keys of `game_version`, overrides included.
+    fn key_names(&self, game_version: Option<&str>) -> KeyNames {
+        KeyMap::load_from(self.key_map(), self.key_map_file()).names_for(game_version)
+    }
+
+    /// The patch setting the frame-rate limit under the active keys.
+    fn fps_patch(&self, fps: i64) -> Patch {
+        db::fps_patch(fps)
+    }
+}
+
+/// The game folder holding the LocalStorage.db at `db_path`, if it sits where clients keep it.
+pub fn game_folder_of(db_path: &Path) -> Option<&Path> {
+    let parts: Vec<_> = db_path.components().rev().take(DB_SUBPATH.len()).collect();
+    let in_place = parts.iter().rev().zip(DB_SUBPATH).all(|(part, expected)| part.as_os_str().eq_ignore_ascii_case(expected));
+    db_path.ancestors().nth(DB_SUBPATH.len()).filter(|_| in_place && parts.len() == DB_SUBPATH.len())
+}
+
+/// Every adapter, the default first.
+pub static ALL: &[&dyn GameAdapter] = &[&WutheringWaves, &UnknownGame];
+
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+pub fn by_id(id: &str) -> Option<&'static dyn GameAdapter> {
+    ALL.iter().copied().find(|game| game.id().eq_ignore_ascii_case(id))
+}
+
+pub fn active() -> &'static dyn GameAdapter {
+    ALL[ACTIVE.load(Ordering::Relaxed)]
+}
+
+/// Makes the adapter with `id` active; false, changing nothing, if there is none.
+pub fn set_active(id: &str) -> bool {
+    let Some(index) = ALL.iter().position(|game| game.id().eq_ignore_ascii_case(id)) else {
+        return false;
+    };
+    if ACTIVE.swap(index, Ordering::Relaxed) != index {
+        info!(game = ALL[index].name(), "switched game");
+    }
+    true
+}
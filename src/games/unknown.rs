@@ -0,0 +1,41 @@
+use super::GameAdapter;
+
+/// Any Unreal game keeping a LocalStorage.db of its own. Nothing is known about it: there is no
+/// registration to detect or process to watch, so the user browses for the file and edits its
+/// rows as JSON on the Keys tab.
+pub struct UnknownGame;
+
+impl GameAdapter for UnknownGame {
+    fn id(&self) -> &'static str {
+        "unreal"
+    }
+
+    fn name(&self) -> &'static str {
+        "Other Unreal game"
+    }
+
+    fn uninstall_keys(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    /// Empty, which no folder is named, so scans find nothing.
+    fn game_dir(&self) -> &'static str {
+        ""
+    }
+
+    fn process_names(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    fn key_map(&self) -> &'static str {
+        "[]"
+    }
+
+    fn key_map_file(&self) -> &'static str {
+        "key_map_unreal.json"
+    }
+
+    fn has_quality_setting(&self) -> bool {
+        false
+    }
+}
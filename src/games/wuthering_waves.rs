@@ -0,0 +1,41 @@
+use super::GameAdapter;
+
+const KEY_MAP: &str = include_str!("../../misc/key_map.json");
+
+/// Uninstall entries the launcher writes, by release region.
+const UNINSTALL_KEYS: &[&str] = &[
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\KRInstall Wuthering Waves Overseas",
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\KRInstall Wuthering Waves",
+];
+
+pub struct WutheringWaves;
+
+impl GameAdapter for WutheringWaves {
+    fn id(&self) -> &'static str {
+        "wuwa"
+    }
+
+    fn name(&self) -> &'static str {
+        "Wuthering Waves"
+    }
+
+    fn uninstall_keys(&self) -> &'static [&'static str] {
+        UNINSTALL_KEYS
+    }
+
+    fn game_dir(&self) -> &'static str {
+        "Wuthering Waves Game"
+    }
+
+    fn process_names(&self) -> &'static [&'static str] {
+        &["Client-Win64-Shipping.exe", "Wuthering Waves.exe"]
+    }
+
+    fn key_map(&self) -> &'static str {
+        KEY_MAP
+    }
+
+    fn key_map_file(&self) -> &'static str {
+        "key_map.json"
+    }
+}
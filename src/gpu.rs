@@ -0,0 +1,194 @@
+//! The graphics adapters, their memory and driver version, and a coarse tier per model family.
+//! Many "the unlock doesn't work" reports come from hardware that never reached the new limit,
+//! so diagnostics point out entry-level and integrated graphics before anyone blames the patch.
+//! No frame rates are promised: the tiers only order the models.
+
+#[cfg(windows)]
+use std::mem;
+#[cfg(windows)]
+use std::ptr;
+
+#[cfg(windows)]
+use winapi::shared::dxgi::{CreateDXGIFactory1, IDXGIAdapter1, IDXGIDevice, IDXGIFactory1, DXGI_ADAPTER_DESC1, DXGI_ADAPTER_FLAG_SOFTWARE};
+#[cfg(windows)]
+use winapi::shared::winerror::SUCCEEDED;
+#[cfg(windows)]
+use winapi::um::winnt::LARGE_INTEGER;
+#[cfg(windows)]
+use winapi::Interface;
+
+pub const VENDOR_NVIDIA: u32 = 0x10de;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Gpu {
+    pub name: String,
+    pub vendor_id: u32,
+    /// Dedicated video memory in bytes; small on integrated graphics, which share system memory.
+    pub vram: u64,
+    /// The driver's file version, `a.b.c.d`.
+    pub driver: Option<[u16; 4]>,
+}
+
+impl Gpu {
+    /// The driver version as the vendor advertises it: NVIDIA's `551.86` rather than
+    /// `31.0.15.5186`; the file version for everyone else.
+    pub fn driver_version(&self) -> Option<String> {
+        let [a, b, c, d] = self.driver?;
+        Some(if self.vendor_id == VENDOR_NVIDIA {
+            let release = u32::from(c % 10) * 10000 + u32::from(d);
+            format!("{}.{:02}", release / 100, release % 100)
+        } else {
+            format!("{}.{}.{}.{}", a, b, c, d)
+        })
+    }
+
+    /// One line for diagnostics and reports.
+    pub fn describe(&self) -> String {
+        let mut text = self.name.clone();
+        if self.vram >= 1 << 30 {
+            text.push_str(&format!(" ({} GB)", (self.vram + (1 << 29)) >> 30));
+        }
+        if let Some(driver) = self.driver_version() {
+            text.push_str(&format!(", driver {}", driver));
+        }
+        text
+    }
+
+    pub fn tier(&self) -> Option<Tier> {
+        tier_of(&self.name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Tier {
+    Integrated,
+    Entry,
+    Mid,
+    High,
+    Enthusiast,
+}
+
+impl Tier {
+    fn lower(self) -> Self {
+        match self {
+            Tier::Integrated | Tier::Entry => Tier::Integrated,
+            Tier::Mid => Tier::Entry,
+            Tier::High => Tier::Mid,
+            Tier::Enthusiast => Tier::High,
+        }
+    }
+}
+
+/// Model families by tier, checked in order; the first match wins.
+const MODELS: &[(&str, Tier)] = &[
+    ("rtx 5090", Tier::Enthusiast),
+    ("rtx 5080", Tier::Enthusiast),
+    ("rtx 4090", Tier::Enthusiast),
+    ("rtx 4080", Tier::Enthusiast),
+    ("rtx 3090", Tier::Enthusiast),
+    ("rx 7900", Tier::Enthusiast),
+    ("rx 6950", Tier::Enthusiast),
+    ("rx 6900", Tier::Enthusiast),
+    ("rtx 5070", Tier::High),
+    ("rtx 4070", Tier::High),
+    ("rtx 3080", Tier::High),
+    ("rtx 3070", Tier::High),
+    ("rtx 2080", Tier::High),
+    ("rx 9070", Tier::High),
+    ("rx 7800", Tier::High),
+    ("rx 7700", Tier::High),
+    ("rx 6800", Tier::High),
+    ("rx 6700", Tier::High),
+    ("rtx 5060", Tier::Mid),
+    ("rtx 4060", Tier::Mid),
+    ("rtx 3060", Tier::Mid),
+    ("rtx 2070", Tier::Mid),
+    ("rtx 2060", Tier::Mid),
+    ("gtx 1080", Tier::Mid),
+    ("gtx 1070", Tier::Mid),
+    ("gtx 1660", Tier::Mid),
+    ("rx 7600", Tier::Mid),
+    ("rx 6600", Tier::Mid),
+    ("rx 5700", Tier::Mid),
+    ("rx 5600", Tier::Mid),
+    ("arc a770", Tier::Mid),
+    ("arc a750", Tier::Mid),
+    ("arc b580", Tier::Mid),
+    ("rtx 3050", Tier::Entry),
+    ("rtx 2050", Tier::Entry),
+    ("gtx 1650", Tier::Entry),
+    ("gtx 1060", Tier::Entry),
+    ("gtx 1050", Tier::Entry),
+    ("rx 6500", Tier::Entry),
+    ("rx 6400", Tier::Entry),
+    ("rx 5500", Tier::Entry),
+    ("rx 580", Tier::Entry),
+    ("rx 570", Tier::Entry),
+    ("arc a380", Tier::Entry),
+    ("radeon 890m", Tier::Entry),
+    ("radeon 880m", Tier::Entry),
+    ("radeon 780m", Tier::Entry),
+    ("uhd graphics", Tier::Integrated),
+    ("iris", Tier::Integrated),
+    ("radeon graphics", Tier::Integrated),
+];
+
+/// The tier of the adapter called `name`; laptop parts rank one lower than their desktop namesakes.
+pub fn tier_of(name: &str) -> Option<Tier> {
+    let name = name.to_ascii_lowercase().replace("(tm)", "").replace("(r)", "");
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    // APUs are called "Radeon Vega 8 Graphics"; the discrete RX Vega 56/64 and Frontier are not integrated.
+    let apu_vega = name.contains("radeon vega") && name.ends_with(" graphics");
+    let tier = if apu_vega { Tier::Integrated } else { MODELS.iter().find(|(model, _)| name.contains(model)).map(|(_, tier)| *tier)? };
+    Some(if name.contains("laptop") || name.contains("mobile") || name.contains("max-q") { tier.lower() } else { tier })
+}
+
+/// The adapter the game most likely renders on: the one with the most dedicated memory, which
+/// on hybrid laptops is the discrete GPU.
+pub fn primary(gpus: &[Gpu]) -> Option<&Gpu> {
+    gpus.iter().max_by_key(|gpu| gpu.vram)
+}
+
+/// The hardware adapters DXGI lists; empty where it can't be asked.
+#[cfg(windows)]
+pub fn adapters() -> Vec<Gpu> {
+    let mut gpus = Vec::new();
+    // SAFETY: DXGI objects are released exactly once; the descriptors are plain data.
+    unsafe {
+        let mut factory: *mut IDXGIFactory1 = ptr::null_mut();
+        if !SUCCEEDED(CreateDXGIFactory1(&IDXGIFactory1::uuidof(), &mut factory as *mut _ as *mut _)) {
+            return gpus;
+        }
+        let mut index = 0;
+        loop {
+            let mut adapter: *mut IDXGIAdapter1 = ptr::null_mut();
+            if !SUCCEEDED((*factory).EnumAdapters1(index, &mut adapter)) {
+                break;
+            }
+            let mut desc: DXGI_ADAPTER_DESC1 = mem::zeroed();
+            if SUCCEEDED((*adapter).GetDesc1(&mut desc)) && desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE == 0 {
+                let len = desc.Description.iter().position(|&c| c == 0).unwrap_or(desc.Description.len());
+                let mut version: LARGE_INTEGER = mem::zeroed();
+                let driver = SUCCEEDED((*adapter).CheckInterfaceSupport(&IDXGIDevice::uuidof(), &mut version)).then(|| {
+                    let raw = *version.QuadPart() as u64;
+                    [(raw >> 48) as u16, (raw >> 32) as u16, (raw >> 16) as u16, raw as u16]
+                });
+                gpus.push(Gpu {
+                    name: String::from_utf16_lossy(&desc.Description[..len]).trim().to_string(),
+                    vendor_id: desc.VendorId,
+                    vram: desc.DedicatedVideoMemory as u64,
+                    driver,
+                });
+            }
+            (*adapter).Release();
+            index += 1;
+        }
+        (*factory).Release();
+    }
+    gpus
+}
+
+#[cfg(not(windows))]
+pub fn adapters() -> Vec<Gpu> {
+    Vec::new()
+}
@@ -0,0 +1,345 @@
+use eframe::egui;
+use serde_json::Value;
+
+use crate::compat;
+use crate::db::{self, Patch};
+use crate::metadata::{Category, KeyMeta, Metadata};
+
+/// Keys that the bulk Max and Min actions leave alone because they aren't image quality.
+/// Fractional keys like brightness are skipped too.
+const BULK_EXCLUDED: &[&str] = &["KeyCustomFrameRate", "KeyPcVsync", "KeyNvidiaReflex"];
+
+#[derive(Clone, Copy)]
+enum Extreme {
+    Highest,
+    Lowest,
+}
+
+pub enum EditorOutcome {
+    Idle,
+    Apply,
+    Discard,
+}
+
+/// Shows a typed editor for every GameQualitySetting field, grouped by category. Edits collect in `draft`
+/// so they can be written together in one transaction.
+pub fn show(ui: &mut egui::Ui, metadata: &Metadata, setting: &Value, draft: &mut Patch, blocked: Option<&str>) -> EditorOutcome {
+    let Some(fields) = setting.as_object() else {
+        ui.label("GameQualitySetting is not a JSON object; nothing to edit.");
+        return EditorOutcome::Idle;
+    };
+
+    ui.horizontal(|ui| {
+        let max = ui.button("Max everything").on_hover_text("Stage the highest supported value of every quality key");
+        if max.clicked() {
+            stage_extreme(metadata, setting, draft, Extreme::Highest);
+        }
+        let min = ui.button("Min everything").on_hover_text("Stage the lowest supported value of every quality key");
+        if min.clicked() {
+            stage_extreme(metadata, setting, draft, Extreme::Lowest);
+        }
+    });
+    ui.add_space(4.0);
+
+    show_category(ui, metadata, fields, draft, Category::Graphics);
+    for category in Category::ALL {
+        let present = metadata.keys().iter().any(|meta| meta.category == category && fields.contains_key(&meta.key));
+        if category != Category::Graphics && present {
+            egui::CollapsingHeader::new(category.name()).default_open(true).show(ui, |ui| {
+                show_category(ui, metadata, fields, draft, category);
+            });
+        }
+    }
+
+    // Fields the metadata doesn't describe yet still get a plain number or toggle editor.
+    let unknown: Vec<(&String, &Value)> = fields
+        .iter()
+        .filter(|(key, value)| metadata.get(key).is_none() && (value.is_number() || value.is_boolean()))
+        .collect();
+    if !unknown.is_empty() {
+        egui::CollapsingHeader::new(format!("Other keys ({})", unknown.len())).show(ui, |ui| {
+            egui::Grid::new("graphics_editor_other").striped(true).num_columns(2).show(ui, |ui| {
+                for (key, stored) in unknown {
+                    key_label(ui, key, draft.contains_key(key.as_str()));
+                    edit_raw(ui, key, stored, draft);
+                    ui.end_row();
+                }
+            });
+        });
+    }
+
+    show_apply_bar(ui, metadata, setting, draft, blocked)
+}
+
+/// Editors for the known keys of one category that the blob actually stores.
+fn show_category(ui: &mut egui::Ui, metadata: &Metadata, fields: &serde_json::Map<String, Value>, draft: &mut Patch, category: Category) {
+    egui::Grid::new(("settings_editor", category.name())).striped(true).num_columns(2).show(ui, |ui| {
+        for meta in metadata.keys().iter().filter(|meta| meta.category == category) {
+            let Some(stored) = fields.get(&meta.key) else {
+                continue;
+            };
+            key_label(ui, &meta.label, draft.contains_key(&meta.key)).on_hover_text(meta.tooltip());
+            edit_known(ui, meta, stored, draft);
+            ui.end_row();
+        }
+    });
+}
+
+/// Preview, compatibility warnings and the Apply/Discard buttons for a draft; shared by every editor view.
+pub fn show_apply_bar(ui: &mut egui::Ui, metadata: &Metadata, setting: &Value, draft: &Patch, blocked: Option<&str>) -> EditorOutcome {
+    let mut outcome = EditorOutcome::Idle;
+    if !draft.is_empty() {
+        ui.separator();
+        show_preview(ui, metadata, setting, draft);
+    }
+
+    // Check what the blob would look like after applying, so warnings track the draft.
+    let mut preview = setting.clone();
+    let warnings = match db::apply_to(&mut preview, draft) {
+        Ok(()) => compat::warnings(&preview),
+        Err(_) => Vec::new(),
+    };
+    for warning in &warnings {
+        ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", warning));
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        let apply_blocked = blocked.or(draft.is_empty().then_some("No changes to apply."));
+        let apply = ui
+            .add_enabled(apply_blocked.is_none(), egui::Button::new(format!("Apply {} change(s)", draft.len())))
+            .on_disabled_hover_text(apply_blocked.unwrap_or_default());
+        if apply.clicked() {
+            outcome = EditorOutcome::Apply;
+        }
+        if ui.add_enabled(!draft.is_empty(), egui::Button::new("Discard")).clicked() {
+            outcome = EditorOutcome::Discard;
+        }
+    });
+    outcome
+}
+
+/// Keys with a one-click toggle on the Patcher tab: motion blur, plus any camera or screen shake key the blob has.
+fn comfort_keys(fields: &serde_json::Map<String, Value>) -> Vec<&String> {
+    fields
+        .iter()
+        .filter(|(key, value)| {
+            (key.as_str() == "KeyMotionBlur" || key.to_lowercase().contains("shake")) && toggle_state(value).is_some()
+        })
+        .map(|(key, _)| key)
+        .collect()
+}
+
+fn toggle_state(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(on) => Some(*on),
+        Value::Number(number) => number.as_i64().filter(|number| *number == 0 || *number == 1).map(|number| number == 1),
+        _ => None,
+    }
+}
+
+/// Same type as `previous`, so booleans stay booleans and 0/1 flags stay numbers.
+fn toggle_value(previous: &Value, on: bool) -> Value {
+    match previous {
+        Value::Bool(_) => Value::from(on),
+        _ => Value::from(on as i64),
+    }
+}
+
+/// Dedicated toggles for effects people commonly want off. Returns a one-field patch when one is flipped.
+pub fn show_comfort(ui: &mut egui::Ui, metadata: &Metadata, setting: &Value, blocked: Option<&str>) -> Option<(String, Patch)> {
+    let fields = setting.as_object()?;
+    let keys = comfort_keys(fields);
+    if keys.is_empty() {
+        return None;
+    }
+    let mut flipped = None;
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Comfort:");
+        for key in keys {
+            let value = &fields[key.as_str()];
+            let Some(mut on) = toggle_state(value) else {
+                continue;
+            };
+            let label = metadata.get(key).map_or(key.as_str(), |meta| meta.label.as_str());
+            let toggle = ui
+                .add_enabled(blocked.is_none(), egui::Checkbox::new(&mut on, label))
+                .on_hover_text(format!("{} (writes immediately)", key))
+                .on_disabled_hover_text(blocked.unwrap_or_default());
+            if toggle.changed() {
+                let mut patch = Patch::new();
+                patch.insert(key.clone(), toggle_value(value, on));
+                let state = if on { "on" } else { "off" };
+                flipped = Some((format!("{} turned {}", label, state), patch));
+            }
+        }
+    });
+    flipped
+}
+
+/// Fills the draft with the highest or lowest value each recognized key accepts.
+fn stage_extreme(metadata: &Metadata, setting: &Value, draft: &mut Patch, extreme: Extreme) {
+    for meta in metadata.keys() {
+        if meta.float || meta.category != Category::Graphics || BULK_EXCLUDED.contains(&meta.key.as_str()) {
+            continue;
+        }
+        let Some(stored) = setting.get(&meta.key) else {
+            continue;
+        };
+        let values = meta.options.iter().map(|option| option.value);
+        let target = match extreme {
+            Extreme::Highest => values.max().or(meta.max.map(|max| max as i64)),
+            Extreme::Lowest => values.min().or(meta.min.map(|min| min as i64)),
+        };
+        if let Some(target) = target {
+            set(&meta.key, Value::from(target), stored, draft);
+        }
+    }
+}
+
+/// Lists every staged change as "old -> new" so bulk edits can be reviewed before applying.
+fn show_preview(ui: &mut egui::Ui, metadata: &Metadata, setting: &Value, draft: &Patch) {
+    egui::CollapsingHeader::new(format!("Preview ({} change(s))", draft.len())).default_open(true).show(ui, |ui| {
+        egui::Grid::new("graphics_preview").striped(true).num_columns(2).show(ui, |ui| {
+            for (key, value) in draft {
+                let meta = metadata.get(key);
+                ui.label(meta.map_or(key.as_str(), |meta| meta.label.as_str()));
+                let old = setting.get(key).unwrap_or(&Value::Null);
+                ui.label(format!("{} -> {}", display_value(meta, old), display_value(meta, value)));
+                ui.end_row();
+            }
+        });
+    });
+}
+
+fn display_value(meta: Option<&KeyMeta>, value: &Value) -> String {
+    let option = value
+        .as_i64()
+        .and_then(|number| meta?.options.iter().find(|option| option.value == number));
+    match option {
+        Some(option) => option.label.clone(),
+        None if value.is_null() => "(absent)".into(),
+        None => value.to_string(),
+    }
+}
+
+/// Edited keys are drawn in the warning colour until they are applied or discarded.
+fn key_label(ui: &mut egui::Ui, text: &str, edited: bool) -> egui::Response {
+    if edited {
+        let color = ui.visuals().warn_fg_color;
+        ui.label(egui::RichText::new(format!("{} *", text)).color(color))
+    } else {
+        ui.label(text)
+    }
+}
+
+/// The value the editor should show: the pending edit if there is one, else what's on disk.
+fn current<'a>(key: &str, stored: &'a Value, draft: &'a Patch) -> &'a Value {
+    draft.get(key).unwrap_or(stored)
+}
+
+/// Records `value` in the draft, dropping the entry again when it matches the stored value.
+fn set(key: &str, value: Value, stored: &Value, draft: &mut Patch) {
+    if value == *stored {
+        draft.remove(key);
+    } else {
+        draft.insert(key.to_string(), value);
+    }
+}
+
+fn is_toggle(meta: &KeyMeta) -> bool {
+    let mut values: Vec<i64> = meta.options.iter().map(|option| option.value).collect();
+    values.sort_unstable();
+    values == [0, 1]
+}
+
+fn edit_known(ui: &mut egui::Ui, meta: &KeyMeta, stored: &Value, draft: &mut Patch) {
+    let value = current(&meta.key, stored, draft);
+    let Some(number) = value.as_f64() else {
+        // Not a number on disk, so the metadata doesn't apply; fall back to the raw editor.
+        edit_raw(ui, &meta.key, stored, draft);
+        return;
+    };
+
+    ui.horizontal(|ui| {
+        edit_number(ui, meta, number, stored, draft);
+        let hint = meta.option(current(&meta.key, stored, draft)).map(|option| option.hint.as_str()).unwrap_or_default();
+        if !hint.is_empty() {
+            ui.weak(hint);
+        }
+    });
+}
+
+fn edit_number(ui: &mut egui::Ui, meta: &KeyMeta, number: f64, stored: &Value, draft: &mut Patch) {
+    if is_toggle(meta) {
+        let mut on = number != 0.0;
+        if ui.checkbox(&mut on, "").on_hover_text(meta.tooltip()).changed() {
+            set(&meta.key, Value::from(on as i64), stored, draft);
+        }
+    } else if !meta.options.is_empty() {
+        let shown_value = number as i64;
+        let mut selected = shown_value;
+        let shown = meta
+            .options
+            .iter()
+            .find(|option| option.value == selected)
+            .map(|option| option.label.clone())
+            .unwrap_or_else(|| selected.to_string());
+        egui::ComboBox::from_id_source(&meta.key).selected_text(shown).show_ui(ui, |ui| {
+            for option in &meta.options {
+                let item = ui.selectable_value(&mut selected, option.value, &option.label);
+                if !option.hint.is_empty() {
+                    item.on_hover_text(&option.hint);
+                }
+            }
+        });
+        if selected != shown_value {
+            set(&meta.key, Value::from(selected), stored, draft);
+        }
+    } else if let (Some(step), Some(min), Some(max)) = (meta.step, meta.min, meta.max) {
+        let mut edited = number;
+        let field = egui::DragValue::new(&mut edited).speed(step).clamp_range(min..=max).max_decimals(3);
+        if ui.add(field).on_hover_text(meta.tooltip()).changed() {
+            let edited = if meta.float { Value::from(edited) } else { Value::from(edited as i64) };
+            set(&meta.key, edited, stored, draft);
+        }
+    } else if let (true, Some(min), Some(max)) = (meta.float, meta.min, meta.max) {
+        let mut edited = number;
+        let slider = egui::Slider::new(&mut edited, min..=max).fixed_decimals(2);
+        if ui.add(slider).on_hover_text(meta.tooltip()).changed() {
+            set(&meta.key, Value::from(edited), stored, draft);
+        }
+    } else if let (Some(min), Some(max)) = (meta.min, meta.max) {
+        let mut edited = number as i64;
+        if ui.add(egui::Slider::new(&mut edited, min as i64..=max as i64)).on_hover_text(meta.tooltip()).changed() {
+            set(&meta.key, Value::from(edited), stored, draft);
+        }
+    } else {
+        edit_raw(ui, &meta.key, stored, draft);
+    }
+}
+
+fn edit_raw(ui: &mut egui::Ui, key: &str, stored: &Value, draft: &mut Patch) {
+    match current(key, stored, draft).clone() {
+        Value::Bool(mut on) => {
+            if ui.checkbox(&mut on, "").changed() {
+                set(key, Value::from(on), stored, draft);
+            }
+        }
+        Value::Number(number) if number.is_i64() => {
+            let mut edited = number.as_i64().unwrap_or_default();
+            if ui.add(egui::DragValue::new(&mut edited)).changed() {
+                set(key, Value::from(edited), stored, draft);
+            }
+        }
+        Value::Number(number) => {
+            let mut edited = number.as_f64().unwrap_or_default();
+            if ui.add(egui::DragValue::new(&mut edited).speed(0.01)).changed() {
+                set(key, Value::from(edited), stored, draft);
+            }
+        }
+        other => {
+            ui.weak(other.to_string());
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Carrying what the user was in the middle of across a restart of the app, such as the one a
+//! self-update does. The old instance saves its intent just before it exits and the new one
+//! takes it on start, so the user lands back on the same file with their edits and queued writes
+//! instead of in a blank window.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::db::Patch;
+use crate::paths::app_dir;
+use crate::{store, Result};
+
+const HANDOFF_FILE: &str = "handoff.json";
+/// Older intents are dropped: they were left by an instance that never got restarted.
+pub const MAX_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// A write the user asked for that had not happened yet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueuedWrite {
+    pub label: String,
+    pub patch: Patch,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Intent {
+    /// The configuration file loaded; empty for none.
+    pub db_path: String,
+    /// Edits staged on the Graphics tab but not applied.
+    pub draft: Patch,
+    pub queued: Option<QueuedWrite>,
+    /// A write that was waiting for the game to exit; see `staged`.
+    #[serde(default)]
+    pub staged: Option<QueuedWrite>,
+}
+
+impl Intent {
+    /// Whether there is anything worth resuming.
+    pub fn is_empty(&self) -> bool {
+        self.db_path.is_empty() && self.draft.is_empty() && self.queued.is_none() && self.staged.is_none()
+    }
+}
+
+pub fn save_to(path: &Path, intent: &Intent) -> Result<()> {
+    store::write_json(path, intent)
+}
+
+/// The intent in `path` if it was saved within `MAX_AGE` before `now`. The file is removed
+/// either way, so an intent is resumed at most once.
+pub fn take_from(path: &Path, now: SystemTime) -> Option<Intent> {
+    let saved = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    let value = store::read_json(path).ok().flatten();
+    let _ = fs::remove_file(path);
+    if now.duration_since(saved).map_or(true, |age| age > MAX_AGE) {
+        info!("dropped a stale handoff");
+        return None;
+    }
+    value.and_then(|value| serde_json::from_value(value).ok())
+}
+
+fn handoff_path() -> PathBuf {
+    app_dir().join(HANDOFF_FILE)
+}
+
+/// Saves `intent` for the next instance in the app folder; a failure is only logged, as the
+/// restart goes ahead regardless.
+pub fn save(intent: &Intent) {
+    if intent.is_empty() {
+        return;
+    }
+    match save_to(&handoff_path(), intent) {
+        Ok(()) => info!("saved intent for the next instance"),
+        Err(err) => warn!(%err, "could not save intent for the next instance"),
+    }
+}
+
+/// The intent a previous instance left in the app folder; see `take_from`.
+pub fn take() -> Option<Intent> {
+    take_from(&handoff_path(), SystemTime::now())
+}
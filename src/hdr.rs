@@ -0,0 +1,75 @@
+#[cfg(windows)]
+use std::mem;
+#[cfg(windows)]
+use std::ptr;
+
+use serde_json::Value;
+#[cfg(windows)]
+use winapi::shared::dxgi::{CreateDXGIFactory1, IDXGIAdapter1, IDXGIFactory1, IDXGIOutput};
+#[cfg(windows)]
+use winapi::shared::dxgi1_6::{IDXGIOutput6, DXGI_OUTPUT_DESC1};
+#[cfg(windows)]
+use winapi::shared::dxgitype::DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020;
+#[cfg(windows)]
+use winapi::shared::winerror::SUCCEEDED;
+#[cfg(windows)]
+use winapi::Interface;
+
+use crate::db::{self, Patch};
+use crate::settings::GameQualitySetting;
+
+/// Whether Windows drives any attached display in HDR (the PQ / BT.2020 colour space).
+/// `None` when DXGI 1.6 isn't available to ask, e.g. before Windows 10 1803.
+#[cfg(windows)]
+pub fn os_hdr_enabled() -> Option<bool> {
+    unsafe {
+        let mut factory: *mut IDXGIFactory1 = ptr::null_mut();
+        if !SUCCEEDED(CreateDXGIFactory1(&IDXGIFactory1::uuidof(), &mut factory as *mut _ as *mut _)) {
+            return None;
+        }
+        let mut asked = false;
+        let mut enabled = false;
+        let mut adapter_index = 0;
+        loop {
+            let mut adapter: *mut IDXGIAdapter1 = ptr::null_mut();
+            if !SUCCEEDED((*factory).EnumAdapters1(adapter_index, &mut adapter)) {
+                break;
+            }
+            let mut output_index = 0;
+            loop {
+                let mut output: *mut IDXGIOutput = ptr::null_mut();
+                if !SUCCEEDED((*adapter).EnumOutputs(output_index, &mut output)) {
+                    break;
+                }
+                let mut output6: *mut IDXGIOutput6 = ptr::null_mut();
+                if SUCCEEDED((*output).QueryInterface(&IDXGIOutput6::uuidof(), &mut output6 as *mut _ as *mut _)) {
+                    let mut desc: DXGI_OUTPUT_DESC1 = mem::zeroed();
+                    if SUCCEEDED((*output6).GetDesc1(&mut desc)) {
+                        asked = true;
+                        enabled |= desc.ColorSpace == DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020;
+                    }
+                    (*output6).Release();
+                }
+                (*output).Release();
+                output_index += 1;
+            }
+            (*adapter).Release();
+            adapter_index += 1;
+        }
+        (*factory).Release();
+        asked.then_some(enabled)
+    }
+}
+
+#[cfg(not(windows))]
+pub fn os_hdr_enabled() -> Option<bool> {
+    None
+}
+
+/// Warns when the game would be set to HDR while Windows has it off, because the game then ignores the setting.
+pub fn warning(setting: &Value, draft: &Patch, os_hdr: Option<bool>) -> Option<&'static str> {
+    let mut merged = setting.clone();
+    db::apply_to(&mut merged, draft).ok()?;
+    let wanted = GameQualitySetting::from_value(&merged).ok()?.hdr_enable? == 1;
+    (wanted && os_hdr == Some(false)).then_some("HDR is on in the game settings but off in Windows display settings; the game will fall back to SDR.")
+}
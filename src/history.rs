@@ -0,0 +1,181 @@
+//! Who changed which setting, kept in the app folder. Our own writes go into an audit log as
+//! they happen; any other change to the file is matched against it by modification time, and
+//! against when the game last ran, to tell the game's resets from a third program. This answers
+//! "who put my FPS back to 60?".
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use crate::db::Patch;
+use crate::paths::app_dir;
+use crate::status::StatusEvent;
+use crate::{store, Result};
+
+const HISTORY_FILE: &str = "history.json";
+/// Entries kept of each kind, newest last.
+const KEEP: usize = 500;
+/// How far a file's modification time may lie from our write, or from the last moment the game
+/// was seen running, and still be theirs. Covers the poll intervals and file system rounding.
+pub const SLACK_SECS: u64 = 10;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Author {
+    /// The app or the CLI.
+    Tool,
+    Game,
+    External,
+}
+
+impl Author {
+    pub fn label(self) -> &'static str {
+        match self {
+            Author::Tool => "this tool",
+            Author::Game => "the game",
+            Author::External => "unknown external",
+        }
+    }
+}
+
+/// One write of ours in the audit log: when, how it was reported, and the values written.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Write {
+    pub time: u64,
+    /// `StatusEvent::action` of the report, e.g. "written"; empty in logs from before it was kept.
+    #[serde(default)]
+    pub action: String,
+    /// `StatusEvent::message` of the report.
+    #[serde(default)]
+    pub message: String,
+    pub keys: BTreeMap<String, Value>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Change {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub key: String,
+    pub from: Value,
+    pub to: Value,
+    pub author: Author,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct History {
+    writes: Vec<Write>,
+    changes: Vec<Change>,
+}
+
+impl History {
+    /// The history in `path`; empty when there is none yet or the file is damaged.
+    pub fn load_from(path: &Path) -> Self {
+        store::read_json(path).ok().flatten().and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        store::write_json(path, self)
+    }
+
+    /// Our writes, oldest first.
+    pub fn writes(&self) -> &[Write] {
+        &self.writes
+    }
+
+    /// Changes, oldest first.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Logs a write of ours at `time`, reported as `event`, that set `written`, where `before`
+    /// holds the values it replaced (the inverse patch).
+    pub fn record_write(&mut self, time: u64, event: &StatusEvent, written: &Patch, before: &Patch) {
+        self.writes.push(Write {
+            time,
+            action: event.action().into(),
+            message: event.message(),
+            keys: written.iter().map(|(key, value)| (key.clone(), value.clone())).collect(),
+        });
+        for (key, from) in before {
+            let to = written.get(key).cloned().unwrap_or(Value::Null);
+            self.changes.push(Change { time, key: key.clone(), from: from.clone(), to, author: Author::Tool });
+        }
+        self.trim();
+    }
+
+    /// Who set `key` to `to` in a file last modified at `modified`. `game_seen` is the last
+    /// time the game was seen running, which is when it writes its settings.
+    pub fn attribute(&self, key: &str, to: &Value, modified: u64, game_seen: Option<u64>) -> Author {
+        let ours = self.writes.iter().any(|write| write.time.abs_diff(modified) <= SLACK_SECS && write.keys.get(key) == Some(to));
+        if ours {
+            Author::Tool
+        } else if game_seen.is_some_and(|seen| seen.abs_diff(modified) <= SLACK_SECS) {
+            Author::Game
+        } else {
+            Author::External
+        }
+    }
+
+    /// Logs how the setting went from `before` to `after` in a file modified at `modified` and
+    /// returns the new entries. Our own writes are left out, as the writer logged them already.
+    pub fn record_changes(&mut self, before: &Value, after: &Value, modified: u64, game_seen: Option<u64>) -> Vec<Change> {
+        let (Some(before), Some(after)) = (before.as_object(), after.as_object()) else {
+            return Vec::new();
+        };
+        let keys: Vec<&String> = before.keys().chain(after.keys().filter(|key| !before.contains_key(*key))).collect();
+        let mut added = Vec::new();
+        for key in keys {
+            let (from, to) = (before.get(key).unwrap_or(&Value::Null), after.get(key).unwrap_or(&Value::Null));
+            if from == to {
+                continue;
+            }
+            let author = self.attribute(key, to, modified, game_seen);
+            if author != Author::Tool {
+                added.push(Change { time: modified, key: key.clone(), from: from.clone(), to: to.clone(), author });
+            }
+        }
+        self.changes.extend(added.iter().cloned());
+        self.trim();
+        added
+    }
+
+    fn trim(&mut self) {
+        self.writes.drain(..self.writes.len().saturating_sub(KEEP));
+        self.changes.drain(..self.changes.len().saturating_sub(KEEP));
+    }
+}
+
+fn history_path() -> PathBuf {
+    app_dir().join(HISTORY_FILE)
+}
+
+/// Seconds since the Unix epoch.
+pub fn unix_time(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|since| since.as_secs()).unwrap_or_default()
+}
+
+/// The history in the app folder.
+pub fn load() -> History {
+    History::load_from(&history_path())
+}
+
+/// Runs `update` on the history in the app folder and saves it. Read and written each time, so
+/// the app and the CLI can both log; a failure is only logged, as history never blocks a write.
+pub fn update<T>(update: impl FnOnce(&mut History) -> T) -> T {
+    let path = history_path();
+    let mut history = History::load_from(&path);
+    let result = update(&mut history);
+    if let Err(err) = history.save_to(&path) {
+        warn!(%err, "could not save the change history");
+    }
+    result
+}
+
+/// Adds a write of ours to the history in the app folder; see `History::record_write`.
+pub fn record_write(event: &StatusEvent, written: &Patch, before: &Patch) {
+    update(|history| history.record_write(unix_time(SystemTime::now()), event, written, before));
+}
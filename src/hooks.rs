@@ -0,0 +1,80 @@
+//! Pre-launch hooks for game launchers: scripts that run the CLI to re-apply the frame-rate limit
+//! every time the game starts, so a reset by the game never lasts past a restart. Playnite runs
+//! a PowerShell snippet before the game; Steam starts a script added as a non-Steam game, which
+//! then starts the game; Heroic runs a script before its own launch.
+
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+use crate::{store, Result};
+
+/// Everything a hook needs to re-apply the limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hook {
+    /// The command-line tool.
+    pub cli: PathBuf,
+    pub game_id: String,
+    pub db_path: String,
+    pub fps: i64,
+}
+
+impl Hook {
+    /// CLI arguments after the program. No backup: the hook runs on every start and writes
+    /// nothing when the value is already set.
+    pub fn args(&self) -> Vec<String> {
+        vec!["--game".into(), self.game_id.clone(), "--db".into(), self.db_path.clone(), "--no-backup".into(), "set-fps".into(), self.fps.to_string()]
+    }
+
+    /// For Playnite's "Execute before starting a game" script.
+    pub fn powershell(&self) -> String {
+        let quote = |text: &str| format!("'{}'", text.replace('\'', "''"));
+        let args: Vec<String> = self.args().iter().map(|arg| quote(arg)).collect();
+        format!("& {} {}", quote(&self.cli.display().to_string()), args.join(" "))
+    }
+
+    /// A batch file applying the limit, then starting `launch` if given.
+    pub fn cmd_script(&self, launch: Option<&Path>) -> String {
+        let quote = |text: &str| format!("\"{}\"", text);
+        let args: Vec<String> = self.args().iter().map(|arg| quote(arg)).collect();
+        let mut script = format!(
+            "@echo off\r\nrem Written by WuWa Ploom: re-applies the frame-rate limit before the game starts.\r\n{} {}\r\n",
+            quote(&self.cli.display().to_string()),
+            args.join(" ")
+        );
+        if let Some(launch) = launch {
+            let folder = launch.parent().unwrap_or(Path::new("."));
+            script.push_str(&format!("start \"\" /d {} {}\r\n", quote(&folder.display().to_string()), quote(&launch.display().to_string())));
+        }
+        script
+    }
+
+    /// A shell script applying the limit, for Heroic on Linux.
+    pub fn shell_script(&self) -> String {
+        let quote = |text: &str| format!("'{}'", text.replace('\'', "'\\''"));
+        let args: Vec<String> = self.args().iter().map(|arg| quote(arg)).collect();
+        format!(
+            "#!/bin/sh\n# Written by WuWa Ploom: re-applies the frame-rate limit before the game starts.\n{} {}\n",
+            quote(&self.cli.display().to_string()),
+            args.join(" ")
+        )
+    }
+}
+
+/// Writes a hook script to `path`, executable where that matters.
+pub fn write_script(path: &Path, text: &str) -> Result<()> {
+    store::write_atomic(path, text.as_bytes())?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+    }
+    info!(path = %path.display(), "wrote launcher hook");
+    Ok(())
+}
+
+/// The command-line tool shipped next to `exe`, if it is there.
+pub fn cli_next_to(exe: &Path) -> Option<PathBuf> {
+    let name = if cfg!(windows) { "wuwa-ploom-cli.exe" } else { "wuwa-ploom-cli" };
+    Some(exe.parent()?.join(name)).filter(|cli| cli.is_file())
+}
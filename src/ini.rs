@@ -0,0 +1,113 @@
+//! Minimal editing of INI files such as Unreal's `Engine.ini` and RTSS profiles: single keys are
+//! read, set or removed and every other line, comment and line ending is left as it was.
+//! Section and key names compare case-insensitively, as both readers do.
+
+fn is_header(line: &str, section: &str) -> bool {
+    line.trim().strip_prefix('[').and_then(|rest| rest.strip_suffix(']')).is_some_and(|name| name.eq_ignore_ascii_case(section))
+}
+
+fn key_of(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.starts_with(';') || line.starts_with('#') {
+        return None;
+    }
+    line.split_once('=').map(|(key, _)| key.trim())
+}
+
+/// Index range of the lines below `section`'s header, up to the next header.
+fn section_body(lines: &[&str], section: &str) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|line| is_header(line, section))? + 1;
+    let end = lines[start..].iter().position(|line| line.trim().starts_with('[')).map_or(lines.len(), |offset| start + offset);
+    Some((start, end))
+}
+
+fn line_ending(text: &str) -> &'static str {
+    if text.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    }
+}
+
+fn join(lines: &[&str], ending: &str) -> String {
+    let mut text = lines.join(ending);
+    if !text.is_empty() {
+        text.push_str(ending);
+    }
+    text
+}
+
+/// The value of `key` in `section`; the first one if the key repeats.
+pub fn get(text: &str, section: &str, key: &str) -> Option<String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let (start, end) = section_body(&lines, section)?;
+    lines[start..end]
+        .iter()
+        .find(|line| key_of(line).is_some_and(|name| name.eq_ignore_ascii_case(key)))
+        .and_then(|line| line.split_once('='))
+        .map(|(_, value)| value.trim().to_string())
+}
+
+/// `(key, value)` of every entry in `section`, in file order.
+pub fn entries(text: &str, section: &str) -> Vec<(String, String)> {
+    let lines: Vec<&str> = text.lines().collect();
+    let Some((start, end)) = section_body(&lines, section) else {
+        return Vec::new();
+    };
+    lines[start..end]
+        .iter()
+        .filter(|line| key_of(line).is_some())
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// `text` with `key` in `section` set to `value`: the first such line is replaced and repeats
+/// dropped, or the line is added at the top of the section, which is added at the end if missing.
+pub fn set(text: &str, section: &str, key: &str, value: &str) -> String {
+    let ending = line_ending(text);
+    let new_line = format!("{}={}", key, value);
+    let header = format!("[{}]", section);
+    let mut lines: Vec<&str> = text.lines().collect();
+    match section_body(&lines, section) {
+        Some((start, end)) => {
+            let matching: Vec<usize> =
+                (start..end).filter(|&index| key_of(lines[index]).is_some_and(|name| name.eq_ignore_ascii_case(key))).collect();
+            match matching.split_first() {
+                Some((&first, repeats)) => {
+                    lines[first] = &new_line;
+                    for &index in repeats.iter().rev() {
+                        lines.remove(index);
+                    }
+                }
+                None => lines.insert(start, &new_line),
+            }
+        }
+        None => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push("");
+            }
+            lines.push(&header);
+            lines.push(&new_line);
+        }
+    }
+    join(&lines, ending)
+}
+
+/// `text` without `key` in `section`; the section goes too if nothing is left in it.
+pub fn remove(text: &str, section: &str, key: &str) -> String {
+    let ending = line_ending(text);
+    let mut lines: Vec<&str> = text.lines().collect();
+    let Some((start, end)) = section_body(&lines, section) else {
+        return text.to_string();
+    };
+    let kept: Vec<&str> = lines[start..end].iter().copied().filter(|line| !key_of(line).is_some_and(|name| name.eq_ignore_ascii_case(key))).collect();
+    if kept.iter().all(|line| line.trim().is_empty()) {
+        // The header and, if it was there, the blank line that set it apart.
+        let from = if start >= 2 && lines[start - 2].trim().is_empty() { start - 2 } else { start - 1 };
+        lines.drain(from..end);
+    } else {
+        lines.splice(start..end, kept);
+    }
+    join(&lines, ending)
+}
@@ -0,0 +1,144 @@
+use std::env;
+
+use serde_json::Value;
+
+use crate::gpu::{self, Gpu};
+use crate::metadata::{Category, Metadata, Problem};
+use crate::report::{os_version, redact};
+
+/// One known key for the report.
+struct Row {
+    section: &'static str,
+    label: String,
+    key: String,
+    /// `None` when the file doesn't store the key.
+    value: Option<String>,
+    meaning: String,
+    flag: String,
+}
+
+/// A read-only snapshot of the graphics settings to attach to forum posts when asking for help
+/// with performance: every known key with its value and what it means, values outside the range
+/// the game accepts flagged, then whatever else the row holds.
+pub struct Inspection {
+    about: Vec<(&'static str, String)>,
+    known: Vec<Row>,
+    other: Vec<(String, String)>,
+}
+
+impl Inspection {
+    pub fn new(metadata: &Metadata, setting: &Value, game_version: Option<&str>) -> Self {
+        let gpus: Vec<String> = gpu::adapters().iter().map(Gpu::describe).collect();
+        let about = vec![
+            ("App version", env!("CARGO_PKG_VERSION").to_string()),
+            ("Game version", game_version.unwrap_or("unknown").to_string()),
+            ("OS", os_version()),
+            ("Graphics", if gpus.is_empty() { "unknown".into() } else { gpus.join("; ") }),
+        ];
+        let mut known = Vec::new();
+        for category in Category::ALL {
+            for meta in metadata.keys().iter().filter(|meta| meta.category == category) {
+                let value = setting.get(&meta.key).filter(|value| !value.is_null());
+                let flag = match value.map(|value| meta.check(value, game_version)) {
+                    None => "not set".into(),
+                    Some(Some(Problem::Invalid(_))) => format!("⚠ out of range ({})", meta.range_text()),
+                    Some(Some(Problem::Unavailable(_))) => "needs a newer client".into(),
+                    Some(None) => String::new(),
+                };
+                known.push(Row {
+                    section: category.name(),
+                    label: meta.label.clone(),
+                    key: meta.key.clone(),
+                    value: value.map(Value::to_string),
+                    meaning: value.and_then(|value| meta.option(value)).map(|option| option.label.clone()).unwrap_or_default(),
+                    flag,
+                });
+            }
+        }
+        let other = setting
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter(|(key, _)| metadata.get(key).is_none())
+            .map(|(key, value)| (key.clone(), value.to_string()))
+            .collect();
+        Self { about, known, other }
+    }
+
+    /// Values outside what the game accepts.
+    pub fn flagged(&self) -> usize {
+        self.known.iter().filter(|row| row.flag.starts_with('⚠')).count()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let cell = |text: &str| text.replace('|', "\\|");
+        let mut out = String::from("# WuWa Ploom inspection report\n\n");
+        for (name, value) in &self.about {
+            out.push_str(&format!("- **{}:** {}\n", name, cell(value)));
+        }
+        out.push_str(&format!("\n{} value(s) out of range.\n", self.flagged()));
+        let mut section = "";
+        for row in &self.known {
+            if row.section != section {
+                section = row.section;
+                out.push_str(&format!("\n## {}\n\n| Setting | Key | Value | Meaning | Note |\n|---|---|---|---|---|\n", section));
+            }
+            let value = row.value.as_deref().unwrap_or("-");
+            let cells = [&row.label, &row.key, value, &row.meaning, &row.flag].map(&cell);
+            out.push_str(&format!("| {} |\n", cells.join(" | ")));
+        }
+        if !self.other.is_empty() {
+            out.push_str("\n## Other keys\n\n| Key | Value |\n|---|---|\n");
+            for (key, value) in &self.other {
+                out.push_str(&format!("| {} | {} |\n", cell(key), cell(value)));
+            }
+        }
+        redact(&out)
+    }
+
+    pub fn to_html(&self) -> String {
+        let escape = |text: &str| text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;");
+        let mut out = String::from(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>WuWa Ploom inspection report</title>\n\
+             <style>body{font-family:sans-serif}table{border-collapse:collapse}td,th{border:1px solid #999;padding:2px 6px}.flag{color:#b00}</style>\n\
+             </head><body>\n<h1>WuWa Ploom inspection report</h1>\n<ul>\n",
+        );
+        for (name, value) in &self.about {
+            out.push_str(&format!("<li><b>{}:</b> {}</li>\n", name, escape(value)));
+        }
+        out.push_str(&format!("</ul>\n<p>{} value(s) out of range.</p>\n", self.flagged()));
+        let mut section = "";
+        for row in &self.known {
+            if row.section != section {
+                if !section.is_empty() {
+                    out.push_str("</table>\n");
+                }
+                section = row.section;
+                out.push_str(&format!("<h2>{}</h2>\n<table>\n<tr><th>Setting</th><th>Key</th><th>Value</th><th>Meaning</th><th>Note</th></tr>\n", section));
+            }
+            let value = row.value.as_deref().unwrap_or("-");
+            let class = if row.flag.starts_with('⚠') { " class=\"flag\"" } else { "" };
+            out.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td{}>{}</td></tr>\n",
+                escape(&row.label),
+                escape(&row.key),
+                escape(value),
+                escape(&row.meaning),
+                class,
+                escape(&row.flag)
+            ));
+        }
+        if !section.is_empty() {
+            out.push_str("</table>\n");
+        }
+        if !self.other.is_empty() {
+            out.push_str("<h2>Other keys</h2>\n<table>\n<tr><th>Key</th><th>Value</th></tr>\n");
+            for (key, value) in &self.other {
+                out.push_str(&format!("<tr><td>{}</td><td>{}</td></tr>\n", escape(key), escape(value)));
+            }
+            out.push_str("</table>\n");
+        }
+        out.push_str("</body></html>\n");
+        redact(&out)
+    }
+}
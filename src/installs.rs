@@ -0,0 +1,109 @@
+//! Several copies of the game's settings on one PC: more than one install, or installs used from
+//! different Windows accounts. Each keeps its own LocalStorage.db, so an FPS limit set in one is
+//! missing in the others. The app remembers every database it loaded, compares their settings
+//! and can copy one install's values to the rest, except those tied to the PC's display.
+
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+use tracing::{info, instrument};
+
+use crate::db::{self, Patch};
+use crate::detect::{db_path_in, install_candidates};
+use crate::platform::Platform;
+use crate::{access, backup, games, presets, MyError, Result};
+
+/// Every known LocalStorage.db of the active game that exists: the ones the launcher
+/// registered, then `remembered`, each listed once.
+pub fn known(platform: &dyn Platform, remembered: &[String]) -> Vec<PathBuf> {
+    let registered = install_candidates(platform).into_iter().map(|candidate| db_path_in(Path::new(&candidate.data)));
+    let mut known: Vec<PathBuf> = Vec::new();
+    for path in registered.chain(remembered.iter().map(PathBuf::from)) {
+        if path.is_file() && of_active_game(&path) && !known.iter().any(|seen| same_file(seen, &path)) {
+            known.push(path);
+        }
+    }
+    known
+}
+
+/// Whether `db_path` sits in a folder of the active game, so files remembered while another game
+/// was picked are never compared with or copied to.
+fn of_active_game(db_path: &Path) -> bool {
+    games::game_folder_of(db_path).and_then(Path::file_name).is_some_and(|name| name.eq_ignore_ascii_case(games::active().game_dir()))
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Keys whose values are not the same in all of `settings`, in order of first appearance.
+pub fn diverging(settings: &[Value]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for key in settings.iter().filter_map(Value::as_object).flat_map(|fields| fields.keys()) {
+        if !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+    keys.retain(|key| settings.windows(2).any(|pair| pair[0].get(key) != pair[1].get(key)));
+    keys
+}
+
+/// The values of `from` that `to` doesn't have yet, leaving out resolution, display and HDR
+/// keys; see `presets::is_machine_specific`.
+pub fn sync_patch(from: &Value, to: &Value) -> Patch {
+    let all = from.as_object().cloned().unwrap_or_default();
+    let changed = db::inverse_patch(to, &all);
+    all.into_iter().filter(|(key, _)| changed.contains_key(key) && !presets::is_machine_specific(key)).collect()
+}
+
+/// What copying settings did to one install.
+#[derive(Debug)]
+pub struct Synced {
+    pub target: PathBuf,
+    pub patch: Patch,
+    /// The values the patch replaced, `None` if it changed nothing.
+    pub result: Result<Option<Patch>>,
+}
+
+/// Copies the settings of the database at `from` to each of `to`; see `write_to`. One install
+/// failing doesn't stop the rest, so each gets its own result. Installs already matching are
+/// left out.
+#[instrument(skip(to, platform), err(level = "warn"))]
+pub fn sync(from: &Path, to: &[PathBuf], backup_dir: Option<&Path>, platform: &dyn Platform) -> Result<Vec<Synced>> {
+    let source = db::read_quality_setting_file(&from.display().to_string())?;
+    let mut synced = Vec::new();
+    for target in to.iter().filter(|target| !same_file(target, from)) {
+        let patch = match db::read_quality_setting_file(&target.display().to_string()) {
+            Ok(setting) => sync_patch(&source, &setting),
+            Err(err) => {
+                synced.push(Synced { target: target.clone(), patch: Patch::new(), result: Err(err) });
+                continue;
+            }
+        };
+        if !patch.is_empty() {
+            let result = write_to(target, &patch, backup_dir, platform);
+            synced.push(Synced { target: target.clone(), patch, result });
+        }
+    }
+    Ok(synced)
+}
+
+/// Writes `patch` into the install at `target`, backing it up into `backup_dir` first when given
+/// and going through the elevated helper where only an administrator may write. Returns the
+/// values it replaced.
+#[instrument(skip(patch, platform), err(level = "warn"))]
+pub fn write_to(target: &Path, patch: &Patch, backup_dir: Option<&Path>, platform: &dyn Platform) -> Result<Option<Patch>> {
+    let target = target.display().to_string();
+    if let Some(dir) = backup_dir {
+        backup::backup_db(&target, dir)?;
+    }
+    let inverse = match db::apply_patch(&target, patch) {
+        Err(MyError::Permission(_)) if platform.can_elevate() => access::write_elevated(platform, &target, patch)?,
+        result => result?,
+    };
+    info!(target, keys = patch.len(), "copied settings to another install");
+    Ok(inverse)
+}
@@ -0,0 +1,77 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use serde_json::Value;
+
+use crate::db::Patch;
+use crate::{diff_view, json_diff};
+
+/// One known install as last compared.
+pub struct Install {
+    pub db_path: PathBuf,
+    pub fps: Option<i64>,
+    pub setting: Value,
+}
+
+/// Settings about to be copied from one install to the others that lack them.
+pub struct SyncPlan {
+    pub from: PathBuf,
+    /// Each install to change, with its settings as compared and the patch it gets.
+    pub targets: Vec<(PathBuf, Value, Patch)>,
+}
+
+/// Asks before copying, showing what changes in each install. Some(true) to go ahead.
+pub fn show_confirm(ctx: &egui::Context, plan: &SyncPlan) -> Option<bool> {
+    let mut answer = None;
+    egui::Window::new("Copy settings to other installs").collapsible(false).resizable(false).show(ctx, |ui| {
+        ui.label(format!("From {}", plan.from.display()));
+        egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+            for (index, (target, setting, patch)) in plan.targets.iter().enumerate() {
+                ui.strong(target.display().to_string());
+                diff_view::show(ui, &format!("sync_{}", index), &json_diff::patch_diff(setting, patch), false);
+            }
+        });
+        ui.label("Each install is backed up first. Resolution, display and HDR settings are not copied.");
+        ui.horizontal(|ui| {
+            if ui.button("Copy").clicked() {
+                answer = Some(true);
+            }
+            if ui.button("Cancel").clicked() {
+                answer = Some(false);
+            }
+        });
+    });
+    answer
+}
+
+/// Lists the installs with their FPS limit and, when their settings differ, which keys. Returns
+/// the index of the install whose settings should be copied to the others.
+pub fn show(ui: &mut egui::Ui, installs: &[Install], diverging: &[String], loaded: &Path, blocked: Option<&str>) -> Option<usize> {
+    let mut copy = None;
+    egui::CollapsingHeader::new(format!("Installs ({})", installs.len())).show(ui, |ui| {
+        if diverging.is_empty() {
+            ui.label("All installs have the same settings.");
+        } else {
+            ui.colored_label(ui.visuals().warn_fg_color, format!("Settings differ: {}", diverging.join(", ")));
+        }
+        egui::Grid::new("installs_grid").striped(true).num_columns(3).show(ui, |ui| {
+            for (index, install) in installs.iter().enumerate() {
+                let mut name = install.db_path.display().to_string();
+                if install.db_path == loaded {
+                    name.push_str(" (loaded)");
+                }
+                ui.label(name);
+                ui.label(install.fps.map_or("-".into(), |fps| format!("{} FPS", fps)));
+                let button = ui
+                    .add_enabled(blocked.is_none() && !diverging.is_empty(), egui::Button::new("Copy to the others"))
+                    .on_hover_text("Backs up the other installs, then gives them these settings")
+                    .on_disabled_hover_text(blocked.unwrap_or("The settings are already the same."));
+                if button.clicked() {
+                    copy = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+    });
+    copy
+}
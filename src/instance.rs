@@ -0,0 +1,144 @@
+//! Keeping to one window. The first instance holds a named mutex and listens on a loopback
+//! port; a later one, e.g. started by opening a file, forwards its arguments there and exits.
+//! The port is published in the app folder with a random token that each connection has to
+//! send first, so other local programs can't make the app load a file of their choosing.
+
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::PathBuf;
+#[cfg(windows)]
+use std::ptr;
+use std::sync::mpsc::{channel, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use eframe::egui;
+#[cfg(windows)]
+use winapi::shared::winerror::ERROR_ALREADY_EXISTS;
+#[cfg(windows)]
+use winapi::um::errhandlingapi::GetLastError;
+#[cfg(windows)]
+use winapi::um::handleapi::CloseHandle;
+#[cfg(windows)]
+use winapi::um::synchapi::CreateMutexW;
+#[cfg(windows)]
+use winapi::um::winnt::HANDLE;
+
+const MUTEX_NAME: &str = "Local\\WuWaPloomSingleInstance";
+const PORT_FILE: &str = "wuwa-ploom.port";
+/// How long a forwarding instance may take to send its arguments; a stalled one is dropped.
+const READ_TIMEOUT: Duration = Duration::from_secs(2);
+/// More than any argument list; a client sending more is cut off.
+const MAX_PAYLOAD: u64 = 64 * 1024;
+
+pub enum Instance {
+    Primary(PrimaryInstance),
+    Secondary,
+}
+
+/// Holds the named mutex for as long as the first instance is alive.
+pub struct PrimaryInstance {
+    #[cfg(windows)]
+    _mutex: HANDLE,
+}
+
+fn port_file() -> PathBuf {
+    crate::paths::app_dir().join(PORT_FILE)
+}
+
+/// 128 random bits as hex. `RandomState` seeds its keys from the OS's random source.
+fn new_token() -> String {
+    (0..2).map(|_| format!("{:016x}", RandomState::new().build_hasher().finish())).collect()
+}
+
+/// Writes the port file readable by this user only, where the platform allows it.
+fn publish(port: u16, token: &str) -> io::Result<()> {
+    let path = port_file();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(format!("{}\n{}", port, token).as_bytes())
+}
+
+/// The arguments in a forwarded `payload`, if it starts with `token`.
+fn forwarded_args(payload: &str, token: &str) -> Option<Vec<String>> {
+    let mut lines = payload.lines();
+    (lines.next()? == token).then(|| lines.map(str::to_string).collect())
+}
+
+#[cfg(windows)]
+pub fn acquire() -> Instance {
+    let name: Vec<u16> = MUTEX_NAME.encode_utf16().chain(Some(0)).collect();
+    unsafe {
+        let mutex = CreateMutexW(ptr::null_mut(), 0, name.as_ptr());
+        if mutex.is_null() {
+            // Without a mutex we can't tell, so behave like the only instance.
+            return Instance::Primary(PrimaryInstance { _mutex: mutex });
+        }
+        if GetLastError() == ERROR_ALREADY_EXISTS {
+            CloseHandle(mutex);
+            return Instance::Secondary;
+        }
+        Instance::Primary(PrimaryInstance { _mutex: mutex })
+    }
+}
+
+/// There is no named mutex to check here, so every instance runs on its own.
+#[cfg(not(windows))]
+pub fn acquire() -> Instance {
+    Instance::Primary(PrimaryInstance {})
+}
+
+impl PrimaryInstance {
+    /// Accepts argument lists forwarded by later instances and wakes the UI for each one.
+    pub fn listen(&self, ctx: egui::Context) -> Option<Receiver<Vec<String>>> {
+        let listener = TcpListener::bind("127.0.0.1:0").ok()?;
+        let port = listener.local_addr().ok()?.port();
+        let token = new_token();
+        publish(port, &token).ok()?;
+
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if stream.set_read_timeout(Some(READ_TIMEOUT)).is_err() {
+                    continue;
+                }
+                let mut payload = String::new();
+                if stream.take(MAX_PAYLOAD).read_to_string(&mut payload).is_err() {
+                    continue;
+                }
+                let Some(args) = forwarded_args(&payload, &token) else {
+                    tracing::warn!("ignored a connection without the instance token");
+                    continue;
+                };
+                if tx.send(args).is_err() {
+                    break;
+                }
+                ctx.request_repaint();
+            }
+        });
+        Some(rx)
+    }
+}
+
+/// Hands the arguments to the running instance over the loopback socket it advertised.
+pub fn forward_to_primary(args: &[String]) -> io::Result<()> {
+    let published = fs::read_to_string(port_file())?;
+    let bad = || io::Error::new(io::ErrorKind::InvalidData, "bad port file");
+    let (port, token) = published.trim().split_once('\n').ok_or_else(bad)?;
+    let port: u16 = port.trim().parse().map_err(|_| bad())?;
+    let mut stream = TcpStream::connect(("127.0.0.1", port))?;
+    let payload: Vec<&str> = std::iter::once(token.trim()).chain(args.iter().map(String::as_str)).collect();
+    stream.write_all(payload.join("\n").as_bytes())?;
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Differences between two JSON values, for showing what a write or an import would change.
+//! Objects are compared by key and arrays by position down to the leaves, so a change deep in
+//! a nested field shows as that one field instead of the whole value.
+
+use serde_json::Value;
+
+use crate::db::Patch;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Diff {
+    Same(Value),
+    Added(Value),
+    Removed(Value),
+    Changed { before: Value, after: Value },
+    /// Two objects or two arrays that differ somewhere, member by member, by key or index.
+    Nested(Vec<(String, Diff)>),
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Counts {
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+}
+
+impl Counts {
+    pub fn is_empty(&self) -> bool {
+        *self == Counts::default()
+    }
+
+    /// E.g. "2 changed, 1 added"; "no changes" when empty.
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [(self.changed, "changed"), (self.added, "added"), (self.removed, "removed")]
+            .into_iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, what)| format!("{} {}", count, what))
+            .collect();
+        if parts.is_empty() {
+            "no changes".into()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    fn add(&mut self, other: Counts) {
+        self.added += other.added;
+        self.removed += other.removed;
+        self.changed += other.changed;
+    }
+}
+
+impl Diff {
+    /// Leaves added, removed and changed, however deep.
+    pub fn counts(&self) -> Counts {
+        match self {
+            Diff::Same(_) => Counts::default(),
+            Diff::Added(_) => Counts { added: 1, ..Counts::default() },
+            Diff::Removed(_) => Counts { removed: 1, ..Counts::default() },
+            Diff::Changed { .. } => Counts { changed: 1, ..Counts::default() },
+            Diff::Nested(members) => count(members),
+        }
+    }
+}
+
+/// `Diff::counts` summed over `members`.
+pub fn count(members: &[(String, Diff)]) -> Counts {
+    let mut counts = Counts::default();
+    for (_, diff) in members {
+        counts.add(diff.counts());
+    }
+    counts
+}
+
+pub fn diff(before: &Value, after: &Value) -> Diff {
+    if before == after {
+        return Diff::Same(after.clone());
+    }
+    match (before, after) {
+        (Value::Object(old), Value::Object(new)) => {
+            let mut members: Vec<(String, Diff)> = old.iter().map(|(key, value)| (key.clone(), member(Some(value), new.get(key)))).collect();
+            members.extend(new.iter().filter(|(key, _)| !old.contains_key(*key)).map(|(key, value)| (key.clone(), Diff::Added(value.clone()))));
+            Diff::Nested(members)
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            let members = (0..old.len().max(new.len())).map(|index| (index.to_string(), member(old.get(index), new.get(index)))).collect();
+            Diff::Nested(members)
+        }
+        _ => Diff::Changed { before: before.clone(), after: after.clone() },
+    }
+}
+
+/// A member present on one side or both.
+pub fn member(before: Option<&Value>, after: Option<&Value>) -> Diff {
+    match (before, after) {
+        (Some(before), Some(after)) => diff(before, after),
+        (Some(before), None) => Diff::Removed(before.clone()),
+        (None, after) => Diff::Added(after.cloned().unwrap_or_default()),
+    }
+}
+
+/// What writing `patch` would do to the fields of `setting`, in the patch's order.
+pub fn patch_diff(setting: &Value, patch: &Patch) -> Vec<(String, Diff)> {
+    patch.iter().map(|(key, value)| (key.clone(), member(setting.get(key), Some(value)))).collect()
+}
@@ -0,0 +1,99 @@
+use eframe::egui;
+use serde_json::Value;
+
+use crate::db::{self, Patch};
+use crate::metadata::{Metadata, Problem};
+
+/// Renders the whole blob as an expandable tree with inline editors for every leaf.
+/// Changes land in `draft` as replacements of the top-level field they belong to; without
+/// `editable` the editors are disabled.
+pub fn show(ui: &mut egui::Ui, metadata: &Metadata, setting: &Value, draft: &mut Patch, game_version: Option<&str>, editable: bool) {
+    let mut working = setting.clone();
+    if db::apply_to(&mut working, draft).is_err() {
+        ui.label("GameQualitySetting is not a JSON object; nothing to edit.");
+        return;
+    }
+    let Some(fields) = working.as_object_mut() else {
+        return;
+    };
+
+    for (key, value) in fields.iter_mut() {
+        let before = value.clone();
+        let meta = metadata.get(key);
+        show_value(ui, key, key, value, editable);
+        match meta.and_then(|meta| meta.check(value, game_version)) {
+            Some(Problem::Invalid(problem)) => {
+                ui.colored_label(ui.visuals().error_fg_color, problem);
+            }
+            Some(Problem::Unavailable(problem)) => {
+                ui.colored_label(ui.visuals().warn_fg_color, problem);
+            }
+            None => {}
+        }
+        if *value != before {
+            let stored = setting.get(key.as_str()).unwrap_or(&Value::Null);
+            if value == stored {
+                draft.remove(key);
+            } else {
+                draft.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+/// `path` keeps egui ids unique for nested fields that share a name.
+fn show_value(ui: &mut egui::Ui, path: &str, name: &str, value: &mut Value, editable: bool) {
+    match value {
+        Value::Object(fields) => {
+            egui::CollapsingHeader::new(format!("{} {{{}}}", name, fields.len()))
+                .id_source(path)
+                .show(ui, |ui| {
+                    for (key, child) in fields.iter_mut() {
+                        show_value(ui, &format!("{}.{}", path, key), key, child, editable);
+                    }
+                });
+        }
+        Value::Array(items) => {
+            egui::CollapsingHeader::new(format!("{} [{}]", name, items.len()))
+                .id_source(path)
+                .show(ui, |ui| {
+                    for (index, child) in items.iter_mut().enumerate() {
+                        show_value(ui, &format!("{}[{}]", path, index), &index.to_string(), child, editable);
+                    }
+                });
+        }
+        leaf => {
+            ui.horizontal(|ui| {
+                ui.label(name);
+                ui.add_enabled_ui(editable, |ui| edit_leaf(ui, leaf));
+            });
+        }
+    }
+}
+
+/// Editors keep the value's JSON type, so a number can't turn into a string by accident.
+fn edit_leaf(ui: &mut egui::Ui, value: &mut Value) {
+    match value {
+        Value::Bool(on) => {
+            ui.checkbox(on, "");
+        }
+        Value::Number(number) if number.is_i64() => {
+            let mut edited = number.as_i64().unwrap_or_default();
+            if ui.add(egui::DragValue::new(&mut edited)).changed() {
+                *value = Value::from(edited);
+            }
+        }
+        Value::Number(number) => {
+            let mut edited = number.as_f64().unwrap_or_default();
+            if ui.add(egui::DragValue::new(&mut edited).speed(0.01)).changed() {
+                *value = Value::from(edited);
+            }
+        }
+        Value::String(text) => {
+            ui.text_edit_singleline(text);
+        }
+        _ => {
+            ui.weak("null");
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use serde_json::Value;
+
+use crate::backup;
+use crate::db::{self, KeyListing};
+use crate::privacy::{self, Identifier};
+use crate::toasts::ToastLevel;
+
+/// Rows listed at once; the search narrows down the rest.
+const LIST_LIMIT: usize = 2000;
+/// Bytes of a value previewed before the user asks for all of it.
+const PREVIEW_BYTES: usize = 64 * 1024;
+
+struct Preview {
+    key: String,
+    /// Pretty-printed when the whole value was loaded and parses as JSON.
+    text: String,
+    size: usize,
+    complete: bool,
+    /// Known once the whole value was loaded.
+    valid_json: Option<bool>,
+    /// The text being edited, once the user started editing a complete JSON value.
+    edit: Option<String>,
+    /// Set for account and device identifiers, which are masked until revealed and never edited.
+    identifier: Option<Identifier>,
+    revealed: bool,
+}
+
+/// Inspector for every row of the LocalStorage table. Only keys and sizes are listed; values
+/// are read when selected, and large ones only in part until asked. Where the game has no
+/// dedicated editor, complete JSON values can be edited and written back.
+#[derive(Default)]
+pub struct KeyBrowser {
+    listing: Option<KeyListing>,
+    filter: String,
+    /// Lists only rows holding account or device identifiers.
+    identifiers_only: bool,
+    selected: Option<Preview>,
+    error: Option<String>,
+}
+
+impl KeyBrowser {
+    fn reload(&mut self, db_path: &str) {
+        match db::list_keys(db_path, &self.filter, LIST_LIMIT) {
+            Ok(listing) => {
+                self.listing = Some(listing);
+                self.error = None;
+            }
+            Err(err) => {
+                self.listing = Some(KeyListing { entries: Vec::new(), total: 0 });
+                self.error = Some(err.to_string());
+            }
+        }
+    }
+
+    fn select(&mut self, db_path: &str, key: &str, max_bytes: usize) {
+        let identifier = privacy::classify(key);
+        let revealed = self.selected.as_ref().is_some_and(|preview| preview.key == key && preview.revealed);
+        let mut preview = match db::read_value_prefix(db_path, key, max_bytes) {
+            Ok((raw, size)) if raw.len() >= size => {
+                let parsed = serde_json::from_str::<Value>(&raw).ok();
+                let text = parsed.as_ref().and_then(|value| serde_json::to_string_pretty(value).ok()).unwrap_or(raw);
+                Preview {
+                    key: key.to_string(),
+                    text,
+                    size,
+                    complete: true,
+                    valid_json: Some(parsed.is_some()),
+                    edit: None,
+                    identifier: None,
+                    revealed: false,
+                }
+            }
+            Ok((raw, size)) => Preview {
+                key: key.to_string(),
+                text: raw,
+                size,
+                complete: false,
+                valid_json: None,
+                edit: None,
+                identifier: None,
+                revealed: false,
+            },
+            Err(err) => Preview {
+                key: key.to_string(),
+                text: format!("Error reading value: {}", err),
+                size: 0,
+                complete: true,
+                valid_json: None,
+                edit: None,
+                identifier: None,
+                revealed: false,
+            },
+        };
+        preview.identifier = identifier;
+        preview.revealed = revealed;
+        self.selected = Some(preview);
+    }
+
+    /// Backs up (when `backup_dir` is set) and writes the edited value of the selected row.
+    fn save(&mut self, db_path: &str, backup_dir: Option<&Path>) -> crate::Result<String> {
+        let Some(Preview { key, edit: Some(text), .. }) = &self.selected else {
+            return Ok(String::new());
+        };
+        let key = key.clone();
+        let value: Value = serde_json::from_str(text)?;
+        if let Some(dir) = backup_dir {
+            backup::backup_db(db_path, dir)?;
+        }
+        db::write_value(db_path, &key, &value)?;
+        self.reload(db_path);
+        self.select(db_path, &key, usize::MAX);
+        Ok(key)
+    }
+
+    /// `editable` offers editing of complete JSON values; `blocked` says why saving is disabled.
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        db_path: &str,
+        editable: bool,
+        blocked: Option<&str>,
+        backup_dir: Option<PathBuf>,
+    ) -> Option<(ToastLevel, String)> {
+        if self.listing.is_none() {
+            self.reload(db_path);
+        }
+        ui.horizontal(|ui| {
+            ui.label("Search:");
+            let search = ui.text_edit_singleline(&mut self.filter);
+            if ui.small_button("Reload").clicked() || search.changed() {
+                self.reload(db_path);
+            }
+            ui.checkbox(&mut self.identifiers_only, "Account and device IDs only")
+                .on_hover_text("Rows the game uses to recognise your account and this PC; shown, never edited");
+        });
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        let mut clicked = None;
+        let Some(listing) = &self.listing else {
+            return None;
+        };
+        if listing.entries.len() < listing.total {
+            ui.weak(format!("First {} of {} keys; search to narrow them down", listing.entries.len(), listing.total));
+        } else {
+            ui.weak(format!("{} keys", listing.total));
+        }
+        let entries: Vec<_> = listing
+            .entries
+            .iter()
+            .map(|entry| (entry, privacy::classify(&entry.key)))
+            .filter(|(_, identifier)| !self.identifiers_only || identifier.is_some())
+            .collect();
+        let row_height = ui.text_style_height(&egui::TextStyle::Body) + ui.spacing().item_spacing.y;
+        // Only the visible rows are laid out, so long listings scroll without stalling.
+        egui::ScrollArea::vertical().id_source("key_browser_table").max_height(240.0).show_rows(
+            ui,
+            row_height,
+            entries.len(),
+            |ui, range| {
+                egui::Grid::new("key_browser").striped(true).num_columns(3).show(ui, |ui| {
+                    for (entry, identifier) in &entries[range] {
+                        let selected = self.selected.as_ref().is_some_and(|preview| preview.key == entry.key);
+                        if ui.selectable_label(selected, &entry.key).clicked() {
+                            clicked = Some(entry.key.clone());
+                        }
+                        ui.label(format!("{} B", entry.size));
+                        match identifier {
+                            Some(identifier) => ui.colored_label(ui.visuals().warn_fg_color, format!("🔒 {}", identifier.name())),
+                            None => ui.label(""),
+                        };
+                        ui.end_row();
+                    }
+                });
+            },
+        );
+        if let Some(key) = clicked {
+            self.select(db_path, &key, PREVIEW_BYTES);
+        }
+
+        let mut load_all = None;
+        let mut save = false;
+        if let Some(preview) = &mut self.selected {
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.strong(&preview.key);
+                match preview.valid_json {
+                    Some(true) => ui.weak("JSON"),
+                    Some(false) => ui.weak("not JSON"),
+                    None => ui.weak(""),
+                };
+            });
+            if let Some(identifier) = preview.identifier {
+                ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", identifier.warning()));
+                let label = if preview.revealed { "Hide" } else { "Reveal" };
+                if ui.small_button(label).on_hover_text("Values are hidden so they don't end up in screenshots").clicked() {
+                    preview.revealed = !preview.revealed;
+                }
+            }
+            if !preview.complete {
+                ui.horizontal(|ui| {
+                    ui.weak(format!("Showing the first {} KiB of {} KiB.", PREVIEW_BYTES / 1024, preview.size / 1024));
+                    if ui.small_button("Load all").clicked() {
+                        load_all = Some((preview.key.clone(), preview.size));
+                    }
+                });
+            }
+            if let Some(text) = &mut preview.edit {
+                let parsed = serde_json::from_str::<Value>(text);
+                ui.horizontal(|ui| {
+                    let reason = blocked.or(parsed.is_err().then_some("The value is not valid JSON."));
+                    let button = ui.add_enabled(reason.is_none(), egui::Button::new("Save"));
+                    save = button.on_disabled_hover_text(reason.unwrap_or_default()).clicked();
+                    if ui.button("Cancel").clicked() {
+                        preview.edit = None;
+                    }
+                });
+                if let Err(err) = &parsed {
+                    ui.colored_label(ui.visuals().error_fg_color, err.to_string());
+                }
+            } else if editable && preview.identifier.is_none() && preview.valid_json == Some(true) && ui.small_button("Edit").clicked() {
+                preview.edit = Some(preview.text.clone());
+            }
+            egui::ScrollArea::vertical().id_source("key_browser_preview").show(ui, |ui| match &mut preview.edit {
+                Some(text) => {
+                    ui.add(egui::TextEdit::multiline(text).code_editor().desired_width(f32::INFINITY));
+                }
+                None if preview.identifier.is_some() && !preview.revealed => {
+                    ui.add(egui::Label::new(egui::RichText::new(privacy::mask(&preview.text)).monospace()).wrap(true));
+                }
+                None => {
+                    ui.add(egui::Label::new(egui::RichText::new(&preview.text).monospace()).wrap(true));
+                }
+            });
+        }
+        if let Some((key, size)) = load_all {
+            self.select(db_path, &key, size);
+        }
+        if !save {
+            return None;
+        }
+        Some(match self.save(db_path, backup_dir.as_deref()) {
+            Ok(key) => (ToastLevel::Success, format!("Saved {}.", key)),
+            Err(err) => (ToastLevel::Error, format!("Error saving the value: {}", err)),
+        })
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use rfd::FileDialog;
+use serde_json::{Map, Value};
+
+use crate::backup;
+use crate::db;
+use crate::toasts::ToastLevel;
+
+/// Name fragments of LocalStorage rows that may hold input bindings.
+const BINDING_HINTS: &[&str] = &["input", "keymap", "binding", "keyboard"];
+
+/// Editable view of one LocalStorage row that looks like a binding table.
+/// Bindings are the string leaves of the stored object, addressed by dotted path.
+#[derive(Default)]
+pub struct KeybindingEditor {
+    candidates: Option<Vec<String>>,
+    selected: Option<String>,
+    original: Value,
+    bindings: BTreeMap<String, String>,
+}
+
+impl KeybindingEditor {
+    fn detect(&mut self, db_path: &str) {
+        let mut candidates: Vec<String> = BINDING_HINTS
+            .iter()
+            .filter_map(|hint| db::list_keys(db_path, hint, usize::MAX).ok())
+            .flat_map(|listing| listing.entries.into_iter().map(|entry| entry.key))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+        self.candidates = Some(candidates);
+    }
+
+    fn load(&mut self, db_path: &str, key: &str) -> Result<(), String> {
+        let raw = db::read_value(db_path, key).map_err(|err| err.to_string())?;
+        let value: Value = serde_json::from_str(&raw).map_err(|err| err.to_string())?;
+        self.bindings.clear();
+        flatten("", &value, &mut self.bindings);
+        self.original = value;
+        self.selected = Some(key.to_string());
+        Ok(())
+    }
+
+    /// Bound keys used by more than one action.
+    fn conflicts(&self) -> BTreeMap<&str, Vec<&str>> {
+        let mut by_key: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (action, key) in &self.bindings {
+            if !key.is_empty() {
+                by_key.entry(key.as_str()).or_default().push(action.as_str());
+            }
+        }
+        by_key.retain(|_, actions| actions.len() > 1);
+        by_key
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, db_path: &str, blocked: Option<&str>, backup_dir: Option<PathBuf>) -> Option<(ToastLevel, String)> {
+        if self.candidates.is_none() {
+            self.detect(db_path);
+        }
+        let candidates = self.candidates.clone().unwrap_or_default();
+        if candidates.is_empty() {
+            ui.label("No input bindings were found in this LocalStorage database.");
+            return None;
+        }
+
+        let mut message = None;
+        let mut pick = None;
+        egui::ComboBox::from_label("Binding table")
+            .selected_text(self.selected.clone().unwrap_or_else(|| "Choose...".into()))
+            .show_ui(ui, |ui| {
+                for key in &candidates {
+                    if ui.selectable_label(self.selected.as_ref() == Some(key), key).clicked() {
+                        pick = Some(key.clone());
+                    }
+                }
+            });
+        if let Some(key) = pick {
+            if let Err(err) = self.load(db_path, &key) {
+                message = Some((ToastLevel::Error, format!("Error reading {}: {}", key, err)));
+            }
+        }
+        let Some(selected) = self.selected.clone() else {
+            return message;
+        };
+
+        // Owned copies, because the grid below borrows the bindings mutably.
+        let conflicts = self.conflicts();
+        let warnings: Vec<String> = conflicts.iter().map(|(key, actions)| format!("⚠ {} is bound to {}", key, actions.join(", "))).collect();
+        let conflicting: Vec<String> = conflicts.values().flatten().map(|action| action.to_string()).collect();
+        for warning in warnings {
+            ui.colored_label(ui.visuals().warn_fg_color, warning);
+        }
+
+        egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+            egui::Grid::new("keybindings").striped(true).num_columns(2).show(ui, |ui| {
+                for (action, key) in self.bindings.iter_mut() {
+                    if conflicting.contains(action) {
+                        ui.colored_label(ui.visuals().warn_fg_color, action.as_str());
+                    } else {
+                        ui.label(action.as_str());
+                    }
+                    ui.text_edit_singleline(key);
+                    ui.end_row();
+                }
+            });
+        });
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            let save = ui.add_enabled(blocked.is_none(), egui::Button::new("Save")).on_disabled_hover_text(blocked.unwrap_or_default());
+            if save.clicked() {
+                message = Some(match self.save(db_path, &selected, backup_dir.as_deref()) {
+                    Ok(()) => (ToastLevel::Success, format!("Saved bindings to {}.", selected)),
+                    Err(err) => (ToastLevel::Error, format!("Error saving bindings: {}", err)),
+                });
+            }
+            if ui.button("Export...").clicked() {
+                message = self.export().map(|result| match result {
+                    Ok(path) => (ToastLevel::Success, format!("Exported bindings to {}", path.display())),
+                    Err(err) => (ToastLevel::Error, format!("Error exporting bindings: {}", err)),
+                });
+            }
+            if ui.button("Import...").clicked() {
+                message = self.import().map(|result| match result {
+                    Ok(count) => (ToastLevel::Info, format!("Imported {} binding(s); review and save.", count)),
+                    Err(err) => (ToastLevel::Error, format!("Error importing bindings: {}", err)),
+                });
+            }
+            if ui.button("Revert").clicked() {
+                self.bindings.clear();
+                flatten("", &self.original, &mut self.bindings);
+            }
+        });
+        message
+    }
+
+    fn save(&mut self, db_path: &str, key: &str, backup_dir: Option<&Path>) -> crate::Result<()> {
+        let mut value = self.original.clone();
+        for (path, binding) in &self.bindings {
+            set_path(&mut value, path, binding);
+        }
+        if let Some(dir) = backup_dir {
+            backup::backup_db(db_path, dir)?;
+        }
+        db::write_value(db_path, key, &value)?;
+        self.original = value;
+        Ok(())
+    }
+
+    fn export(&self) -> Option<Result<PathBuf, String>> {
+        let path = FileDialog::new().add_filter("JSON", &["json"]).set_file_name("keybindings.json").save_file()?;
+        let map: Map<String, Value> = self.bindings.iter().map(|(action, key)| (action.clone(), Value::from(key.as_str()))).collect();
+        let text = serde_json::to_string_pretty(&map).map_err(|err| err.to_string());
+        Some(text.and_then(|text| fs::write(&path, text).map_err(|err| err.to_string())).map(|()| path))
+    }
+
+    /// Merges a previously exported set; actions this table doesn't have are ignored.
+    fn import(&mut self) -> Option<Result<usize, String>> {
+        let path = FileDialog::new().add_filter("JSON", &["json"]).pick_file()?;
+        let result = fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| serde_json::from_str::<BTreeMap<String, String>>(&text).map_err(|err| err.to_string()))
+            .map(|imported| {
+                let mut count = 0;
+                for (action, key) in imported {
+                    if let Some(binding) = self.bindings.get_mut(&action) {
+                        *binding = key;
+                        count += 1;
+                    }
+                }
+                count
+            });
+        Some(result)
+    }
+}
+
+fn flatten(prefix: &str, value: &Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(fields) => {
+            for (key, child) in fields {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(&path, child, out);
+            }
+        }
+        Value::String(binding) => {
+            out.insert(prefix.to_string(), binding.clone());
+        }
+        _ => {}
+    }
+}
+
+fn set_path(value: &mut Value, path: &str, binding: &str) {
+    let mut target = value;
+    for part in path.split('.') {
+        match target.get_mut(part) {
+            Some(next) => target = next,
+            None => return,
+        }
+    }
+    *target = Value::from(binding);
+}
@@ -0,0 +1,158 @@
+//! Names the client stores its settings under, per client version. They are data rather than
+//! code so that when an update renames them, a `key_map.json` from the community or the user
+//! fixes the tool without waiting for a release. Each game adapter embeds its own table and
+//! names its override file.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::games;
+use crate::paths::app_dir;
+use crate::update::parse_version;
+use crate::Result;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyNames {
+    /// Row of the LocalStorage table holding the quality settings.
+    pub setting_row: String,
+    /// Field of that row holding the frame-rate limit.
+    pub frame_rate: String,
+    #[serde(default = "default_vsync")]
+    pub vsync: String,
+    /// Field holding the graphics preset.
+    #[serde(default = "default_quality_level")]
+    pub quality_level: String,
+}
+
+impl Default for KeyNames {
+    fn default() -> Self {
+        Self {
+            setting_row: "GameQualitySetting".into(),
+            frame_rate: "KeyCustomFrameRate".into(),
+            vsync: default_vsync(),
+            quality_level: default_quality_level(),
+        }
+    }
+}
+
+fn default_vsync() -> String {
+    "KeyPcVsync".into()
+}
+
+fn default_quality_level() -> String {
+    "KeyQualityLevel".into()
+}
+
+/// The names used from client version `since` until the next entry's.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyMapEntry {
+    pub since: String,
+    #[serde(flatten)]
+    pub names: KeyNames,
+}
+
+#[derive(Clone, Debug)]
+pub struct KeyMap {
+    /// Oldest client version first.
+    entries: Vec<KeyMapEntry>,
+}
+
+impl KeyMap {
+    pub fn from_json(text: &str) -> Result<Self> {
+        let mut map = Self { entries: Vec::new() };
+        map.merge(serde_json::from_str(text)?);
+        Ok(map)
+    }
+
+    /// The active game's embedded table.
+    pub fn builtin() -> Self {
+        Self::embedded(games::active().key_map())
+    }
+
+    fn embedded(json: &str) -> Self {
+        // The tables are embedded at compile time, so a parse failure is a packaging bug.
+        Self::from_json(json).expect("embedded key map is valid JSON")
+    }
+
+    /// The active game's table with its override files merged in; see `load_from`.
+    pub fn load() -> Self {
+        let game = games::active();
+        Self::load_from(game.key_map(), game.key_map_file())
+    }
+
+    /// The embedded table `json` with files named `override_file` merged in: next to the
+    /// executable, then the app folder, later ones winning. Broken files are logged and skipped.
+    pub fn load_from(json: &str, override_file: &str) -> Self {
+        let mut map = Self::embedded(json);
+        for path in override_paths(override_file) {
+            let Ok(text) = fs::read_to_string(&path) else {
+                continue;
+            };
+            match serde_json::from_str(&text) {
+                Ok(entries) => {
+                    info!(path = %path.display(), "loaded key map override");
+                    map.merge(entries);
+                }
+                Err(err) => warn!(path = %path.display(), %err, "ignored key map override"),
+            }
+        }
+        map
+    }
+
+    /// Entries replace ones with the same `since`; new versions are added in order.
+    pub fn merge(&mut self, entries: Vec<KeyMapEntry>) {
+        for entry in entries {
+            match self.entries.iter_mut().find(|existing| existing.since == entry.since) {
+                Some(existing) => *existing = entry,
+                None => self.entries.push(entry),
+            }
+        }
+        self.entries.sort_by_key(|entry| parse_version(&entry.since));
+    }
+
+    /// Names for `game_version`; the newest entry when the version is unknown or newer than all.
+    pub fn names_for(&self, game_version: Option<&str>) -> KeyNames {
+        let applicable = match game_version.map(parse_version) {
+            Some(version) => self.entries.iter().rev().find(|entry| parse_version(&entry.since) <= version),
+            None => self.entries.last(),
+        };
+        applicable.or(self.entries.first()).map(|entry| entry.names.clone()).unwrap_or_default()
+    }
+}
+
+fn override_paths(file: &str) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(dir) = env::current_exe().ok().as_deref().and_then(Path::parent) {
+        paths.push(dir.join(file));
+    }
+    paths.push(app_dir().join(file));
+    paths
+}
+
+static ACTIVE: RwLock<Option<KeyNames>> = RwLock::new(None);
+
+/// The names database code uses; the built-in ones until `select` runs.
+pub fn active() -> KeyNames {
+    ACTIVE.read().ok().and_then(|names| names.clone()).unwrap_or_default()
+}
+
+pub fn set_active(names: KeyNames) {
+    if let Ok(mut active) = ACTIVE.write() {
+        *active = Some(names);
+    }
+}
+
+/// Loads the active game's key map and activates the names for `game_version`.
+pub fn select(game_version: Option<&str>) -> KeyNames {
+    let names = games::active().key_names(game_version);
+    if names != KeyNames::default() {
+        info!(?names, game_version, "using renamed settings keys");
+    }
+    set_active(names.clone());
+    names
+}
@@ -0,0 +1,86 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+use wuwa_ploom_core::games::{self, GameAdapter};
+use wuwa_ploom_core::hooks::{self, Hook};
+use wuwa_ploom_core::paths::app_dir;
+
+use crate::toasts::ToastLevel;
+
+/// The Launchers tab: hooks for Playnite, Steam and Heroic that re-apply the frame-rate limit
+/// each time the game starts.
+#[derive(Default)]
+pub struct Launchers {
+    /// Frame-rate limit the hooks apply; the current one until changed.
+    fps: Option<i64>,
+}
+
+fn script_path(game: &dyn GameAdapter, suffix: &str) -> PathBuf {
+    app_dir().join("launchers").join(format!("{}-{}", game.id(), suffix))
+}
+
+fn write(path: &Path, text: &str) -> (ToastLevel, String) {
+    match hooks::write_script(path, text) {
+        Ok(()) => (ToastLevel::Success, format!("Wrote {}.", path.display())),
+        Err(err) => (ToastLevel::Error, format!("Error writing {}: {}", path.display(), err)),
+    }
+}
+
+impl Launchers {
+    pub fn show(&mut self, ui: &mut egui::Ui, db_path: &str, current_fps: Option<i64>) -> Option<(ToastLevel, String)> {
+        let Some(cli) = env::current_exe().ok().as_deref().and_then(hooks::cli_next_to) else {
+            ui.label("The hooks run wuwa-ploom-cli, which is not next to this program. Put it in the same folder (it comes with every release) and reopen this tab.");
+            return None;
+        };
+        let game = games::active();
+        let fps = self.fps.get_or_insert(current_fps.unwrap_or(120));
+        ui.horizontal(|ui| {
+            ui.label("Frame-rate limit to apply on every start:");
+            ui.add(egui::DragValue::new(fps).clamp_range(30..=360));
+        });
+        let hook = Hook { cli, game_id: game.id().into(), db_path: db_path.into(), fps: *fps };
+        let mut message = None;
+
+        ui.separator();
+        ui.strong("Playnite");
+        ui.label("Edit the game, open Scripts and paste this under \"Execute before starting a game\":");
+        let snippet = hook.powershell();
+        ui.add(egui::Label::new(egui::RichText::new(&snippet).monospace()).wrap(true));
+        if ui.small_button("Copy").clicked() {
+            ui.output().copied_text = snippet;
+            message = Some((ToastLevel::Success, "Copied the Playnite script.".into()));
+        }
+
+        ui.separator();
+        ui.strong("Steam");
+        let launch = games::game_folder_of(Path::new(db_path)).zip(game.launch_exe()).map(|(folder, exe)| folder.join(exe));
+        match &launch {
+            Some(launch) if cfg!(windows) => {
+                ui.label("Writes a script that applies the limit and then starts the game. In Steam, use Add a Game > Add a Non-Steam Game and browse to it.");
+                let path = script_path(game, "launch.cmd");
+                ui.weak(path.display().to_string());
+                if ui.small_button("Write script").clicked() {
+                    message = Some(write(&path, &hook.cmd_script(Some(launch))));
+                }
+            }
+            Some(_) => {
+                ui.label("On Linux, add the game through Proton as usual and use the Heroic script below as its launch wrapper: set the launch options to the script's path followed by %command%.");
+            }
+            None => {
+                ui.label("The game's executable is unknown for this configuration file, so no Steam script can start it.");
+            }
+        }
+
+        ui.separator();
+        ui.strong("Heroic");
+        ui.label("Writes a script for Heroic to run before it launches the game; pick it in the game's settings under the script to run before launch.");
+        let (suffix, text) = if cfg!(windows) { ("before-launch.cmd", hook.cmd_script(None)) } else { ("before-launch.sh", hook.shell_script()) };
+        let path = script_path(game, suffix);
+        ui.weak(path.display().to_string());
+        if ui.small_button("Write script").clicked() {
+            message = Some(write(&path, &text));
+        }
+        message
+    }
+}
@@ -0,0 +1,167 @@
+//! Core of WuWa Ploom: finding the game's LocalStorage.db, reading and patching
+//! GameQualitySetting, backups, and saved profiles. Frontends (the GUI, the CLI)
+//! build on this and hold no DB or file-format logic of their own.
+
+use std::fmt;
+
+use thiserror::Error;
+
+pub mod access;
+pub mod autostart;
+pub mod backup;
+pub mod changelog;
+pub mod cleanup;
+pub mod competitors;
+pub mod crash_loop;
+pub mod db;
+pub mod deck;
+pub mod defender;
+pub mod detect;
+pub mod discord;
+pub mod engine_ini;
+pub mod frame_rate;
+pub mod game_update;
+pub mod game_user_settings;
+pub mod games;
+pub mod gpu;
+pub mod handoff;
+pub mod history;
+pub mod hooks;
+pub mod ini;
+pub mod installs;
+pub mod json_diff;
+pub mod keymap;
+pub mod logging;
+pub mod metadata;
+pub mod paths;
+pub mod platform;
+pub mod power;
+pub mod presentmon;
+pub mod presets;
+pub mod privacy;
+pub mod process;
+pub mod profiles;
+pub mod registry;
+pub mod relocate;
+pub mod rtss;
+pub mod sample;
+pub mod schema;
+pub mod settings;
+pub mod staged;
+pub mod stats;
+pub mod status;
+pub mod store;
+pub mod update;
+pub mod watchdog;
+pub mod wine;
+
+/// What went wrong, grouped by what the user can do about it; see `hint`.
+///
+/// `Display` ends with the hint, so every frontend and log line that prints an error tells the
+/// user how to fix it.
+#[derive(Error, Debug)]
+pub enum MyError {
+    /// The game install or its LocalStorage.db could not be found.
+    Detection(String),
+    /// Another process, usually the game, holds the database.
+    Locked(String),
+    /// The OS refused access to a file or folder.
+    Permission(String),
+    /// The data is readable but not shaped the way this version expects.
+    Schema(String),
+    /// The file is damaged: not a database, or not valid JSON.
+    Corruption(String),
+    /// A request to GitHub failed or timed out.
+    Network(String),
+    /// A release could not be downloaded, verified or installed.
+    Update(String),
+    /// Any other IO failure.
+    Io(#[source] std::io::Error),
+}
+
+impl MyError {
+    /// What the user can do about the error, as one sentence.
+    pub fn hint(&self) -> &'static str {
+        match self {
+            MyError::Detection(_) => {
+                "Start the game once so it creates its settings, or pick LocalStorage.db by hand with Locate."
+            }
+            MyError::Locked(_) => "Close Wuthering Waves and its launcher, then try again.",
+            MyError::Permission(_) => {
+                "Make sure you can write to the file; on Windows, accept the administrator prompt, or run WuWa Ploom as administrator if the game is under Program Files."
+            }
+            MyError::Schema(_) => {
+                "The game may have changed its settings format; update WuWa Ploom, and report the game version if this persists."
+            }
+            MyError::Corruption(_) => {
+                "Restore a backup, or delete LocalStorage.db and let the game recreate it (this resets in-game settings)."
+            }
+            MyError::Network(_) => "Check your internet connection and try again later.",
+            MyError::Update(_) => "Download the new version from the releases page instead.",
+            MyError::Io(_) => "Check that the file exists and isn't open in another program.",
+        }
+    }
+
+    pub(crate) fn summary(&self) -> String {
+        match self {
+            MyError::Detection(detail) => format!("Game not found: {}", detail),
+            MyError::Locked(detail) => format!("The database is in use: {}", detail),
+            MyError::Permission(detail) => format!("Permission denied: {}", detail),
+            MyError::Schema(detail) => format!("Unexpected settings data: {}", detail),
+            MyError::Corruption(detail) => format!("The file is damaged: {}", detail),
+            MyError::Network(detail) => format!("Network error: {}", detail),
+            MyError::Update(detail) => format!("Update failed: {}", detail),
+            MyError::Io(err) => format!("IO error: {}", err),
+        }
+    }
+}
+
+impl fmt::Display for MyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}. Hint: {}", self.summary(), self.hint())
+    }
+}
+
+impl From<rusqlite::Error> for MyError {
+    fn from(err: rusqlite::Error) -> Self {
+        use rusqlite::ErrorCode;
+        let detail = err.to_string();
+        match &err {
+            rusqlite::Error::SqliteFailure(failure, _) => match failure.code {
+                ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked => MyError::Locked(detail),
+                ErrorCode::PermissionDenied | ErrorCode::ReadOnly | ErrorCode::CannotOpen => MyError::Permission(detail),
+                // SQLITE_ERROR: usually a missing table or column.
+                ErrorCode::Unknown => MyError::Schema(detail),
+                ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase => MyError::Corruption(detail),
+                // A full disk, an I/O failure or no memory says nothing about the file itself.
+                _ => MyError::Io(std::io::Error::other(detail)),
+            },
+            rusqlite::Error::QueryReturnedNoRows => MyError::Schema("an expected row is not in the database".into()),
+            rusqlite::Error::InvalidColumnType(..) | rusqlite::Error::FromSqlConversionFailure(..) => {
+                MyError::Schema(detail)
+            }
+            _ => MyError::Io(std::io::Error::other(detail)),
+        }
+    }
+}
+
+impl From<serde_json::Error> for MyError {
+    fn from(err: serde_json::Error) -> Self {
+        match err.classify() {
+            serde_json::error::Category::Data => MyError::Schema(err.to_string()),
+            serde_json::error::Category::Io => MyError::Io(err.into()),
+            serde_json::error::Category::Syntax | serde_json::error::Category::Eof => MyError::Corruption(err.to_string()),
+        }
+    }
+}
+
+impl From<std::io::Error> for MyError {
+    fn from(err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::PermissionDenied => MyError::Permission(err.to_string()),
+            _ => MyError::Io(err),
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, MyError>;
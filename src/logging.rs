@@ -0,0 +1,42 @@
+//! Log file setup shared by the frontends. Logs rotate daily under `app_dir()/logs`.
+
+use std::fs;
+use std::path::PathBuf;
+
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{Builder, Rotation};
+use tracing_subscriber::EnvFilter;
+
+use crate::paths::app_dir;
+
+/// Overrides the verbosity with a filter such as `debug` or `wuwa_ploom_core::db=trace`.
+pub const FILTER_ENV: &str = "WUWA_PLOOM_LOG";
+const LOG_PREFIX: &str = "wuwa-ploom";
+const KEPT_LOG_FILES: usize = 7;
+
+pub fn log_dir() -> PathBuf {
+    app_dir().join("logs")
+}
+
+/// Starts writing logs to the log folder; keep the guard alive until exit so buffered lines are flushed.
+/// Logs at info level, or debug with `verbose`, unless `WUWA_PLOOM_LOG` says otherwise.
+pub fn init(verbose: bool) -> Option<WorkerGuard> {
+    fs::create_dir_all(log_dir()).ok()?;
+    let appender = Builder::new()
+        .rotation(Rotation::DAILY)
+        .filename_prefix(LOG_PREFIX)
+        .filename_suffix("log")
+        .max_log_files(KEPT_LOG_FILES)
+        .build(log_dir())
+        .ok()?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    let default = if verbose { "debug" } else { "info" };
+    let filter = EnvFilter::try_from_env(FILTER_ENV).unwrap_or_else(|_| EnvFilter::new(default));
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_env_filter(filter)
+        .try_init()
+        .ok()?;
+    Some(guard)
+}
@@ -1,189 +1,2796 @@
 #![windows_subsystem = "windows"]
 
-use rusqlite::{params, Connection};
-use serde_json::{Value, json};
+mod about;
+mod advanced_mode;
+mod cleanup_panel;
+mod compat;
+mod config;
+mod config_tweaks;
+mod cosmetic;
+mod crash;
+mod diagnostics;
+mod diff_view;
+mod display;
+mod graphics_editor;
+mod hdr;
+mod inspection;
+mod installs_panel;
+mod instance;
+mod json_tree;
+mod key_browser;
+mod keybindings;
+mod launchers;
+mod notifications;
+mod nvidia;
+mod pinned;
+mod preset_review;
+mod profiles_panel;
+mod report;
+mod scan;
+mod settings_window;
+mod sql_console;
+mod stats_panel;
+mod status_panel;
+mod tasks;
+mod toasts;
+mod troubleshoot;
+mod transfer_wizard;
+mod ultrawide;
+mod update_banner;
+mod watch;
+mod whats_new;
+
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::env;
 use std::fs;
-use thiserror::Error;
+use wuwa_ploom_core::{access, autostart, backup, changelog, competitors, crash_loop, db, deck, defender, detect, discord, engine_ini, frame_rate, game_update, game_user_settings, games, gpu, handoff, history, installs, json_diff, keymap, logging, metadata, paths, platform, power, presentmon, presets, privacy, process, profiles, registry, relocate, rtss, sample, settings, staged, stats, status, update, watchdog, MyError, Result};
 use eframe::{egui, App, CreationContext, Frame, NativeOptions};
-use egui::CentralPanel;
+use egui::{CentralPanel, Key, WidgetInfo, WidgetType};
 use rfd::FileDialog;
-use winapi::um::wincon::SetConsoleTitleW;
-use winapi::shared::windef::{HWND, HICON};
-use winapi::um::winuser::{
-    FindWindowW, GetWindowLongW, SetWindowLongW, SetClassLongPtrW, LoadImageW,
-    GWL_STYLE, WS_SYSMENU, WS_MINIMIZEBOX, GCLP_HICON, GCLP_HICONSM, LR_DEFAULTSIZE, LR_LOADFROMFILE, IMAGE_ICON,
-};
-use std::ffi::OsStr;
-use std::os::windows::ffi::OsStrExt;
-use std::ptr;
-use winreg::enums::*;
-use winreg::RegKey;
-use serde::de::Error as SerdeError;
-
-#[derive(Error, Debug)]
-enum MyError {
-    #[error("Database error: {0}")]
-    RusqliteError(#[from] rusqlite::Error),
-    #[error("JSON error: {0}")]
-    SerdeJsonError(#[from] serde_json::Error),
-    #[error("IO error: {0}")]
-    IoError(#[from] std::io::Error),
-    #[error("Registry error: Could not access the registry key or value.")]
-    RegistryError,
-    #[error("File not found or inaccessible: {0}")]
-    FileNotFoundError(String),
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::time::{Duration, Instant, SystemTime};
+use toasts::{ToastLevel, Toasts};
+use config::{Config, Theme};
+use metadata::{Category, Metadata, Problem};
+use deck::DeckModel;
+use notifications::Source;
+use process::{GameEvent, GameWatcher};
+use game_update::UpdateSign;
+use staged::{Finish, Staged};
+use status::StatusEvent;
+use watchdog::Budget;
+use watch::WatchedKey;
+use platform::Priority;
+use update::Release;
+use update_banner::{BannerAction, DownloadUpdate};
+use db::{fps_from_setting, read_quality_setting_file, Patch};
+use graphics_editor::EditorOutcome;
+use key_browser::KeyBrowser;
+use cleanup_panel::CleanupPanel;
+use config_tweaks::ConfigTweaks;
+use launchers::Launchers;
+use keybindings::KeybindingEditor;
+use preset_review::{PresetReview, ReviewOutcome};
+use transfer_wizard::{TransferWizard, WizardOutcome};
+use profiles_panel::ProfileAction;
+use sql_console::SqlConsole;
+use advanced_mode::AdvancedMode;
+use stats::{Event, Stats};
+use history::{Change, History};
+use handoff::{Intent, QueuedWrite};
+use scan::ScanUpdate;
+use tasks::Task;
+use settings_window::SettingsOutcome;
+use std::path::{Path, PathBuf};
+use instance::Instance;
+use installs_panel::{Install, SyncPlan};
+use inspection::Inspection;
+use access::WriteAccess;
+use diagnostics::{CheckResult, CheckStatus};
+use troubleshoot::{Finding, Fix, Symptom};
+
+/// Frame-rate values offered as one-click presets, in shortcut order (Ctrl+1, Ctrl+2, ...).
+/// A Steam Deck gets `DeckModel::fps_presets` instead.
+const FPS_PRESETS: [i64; 2] = [120, 60];
+const PRESET_KEYS: [Key; 3] = [Key::Num1, Key::Num2, Key::Num3];
+/// Multiplies the system scale when the enlarged UI is on.
+const LARGE_UI_SCALE: f32 = 1.5;
+
+#[derive(Clone, Copy)]
+enum Action {
+    Locate,
+    Browse,
+    ScanDrives,
+    CancelScan,
+    TrySample,
+    DiscardStaged,
+    ApplyFps(i64),
+    ApplyGraphics,
+    ImportPreset,
+    CopyShareCode,
+    PasteShareCode,
+    MoveSettings,
+    Undo,
+    Refresh,
+    SetCompact(bool),
+    OpenSettings,
+    Diagnose,
+    CopyReport,
+    SaveReport,
+    CopyInspection,
+    SaveInspection,
+    ReviewDeckProfile,
+    ClearDriverLimit,
+    SwitchRefreshRate(u32),
+    RestoreDisplay,
+    VerifyUnlock,
+    SwitchPowerPlan,
+    RestorePowerPlan,
+    AllowInDefender,
+    CopyDefenderCommand,
+    Troubleshoot(Symptom),
+    /// Copy the settings of this entry of `installs` to the others.
+    SyncInstalls(usize),
+    /// Switch to the file found in place of the one that is gone.
+    Relocate,
+    ApplyFix(Fix),
+}
+
+const SHORTCUT_HINTS: &[(&str, &str)] = &[
+    ("Ctrl+L", "Locate configuration file"),
+    ("Ctrl+O", "Browse for configuration file"),
+    ("Ctrl+Z", "Undo last change"),
+    ("F5", "Refresh current FPS"),
+];
+
+#[derive(Debug, Default, PartialEq, Eq)]
+enum Tab {
+    #[default]
+    Patcher,
+    Graphics,
+    RawJson,
+    Keys,
+    Keybindings,
+    ConfigTweaks,
+    Launchers,
+    Sql,
+    Stats,
+    About,
+}
+
+/// A frame-rate limit waiting for the user's go-ahead before it goes into an RTSS profile.
+struct RtssOffer {
+    install: PathBuf,
+    exe: &'static str,
+    fps: i64,
+    current: Option<i64>,
+}
+
+/// A GameQualitySetting change on its way to disk; `label` names it in messages and the undo list.
+struct PendingWrite {
+    label: String,
+    patch: Patch,
+    undo: bool,
 }
 
-type Result<T> = std::result::Result<T, MyError>;
+#[derive(Default)]
+struct FPSUnlockerApp {
+    tab: Tab,
+    db_path: String,
+    toasts: Toasts,
+    show_history: bool,
+    current_fps: Option<i64>,
+    /// A `keep` watchdog gave up after repeated crashes in the last day.
+    watchdog_stopped: bool,
+    /// Frame-rate values other programs wrote while the game was closed, to notice a tool fighting over it.
+    fps_changes: Vec<(SystemTime, i64)>,
+    /// The full GameQualitySetting blob as last read.
+    quality_setting: Option<Value>,
+    metadata: Metadata,
+    /// Inverse patches of our own writes, most recent last.
+    undo_stack: Vec<(String, Patch)>,
+    /// Unapplied edits from the graphics editor.
+    graphics_draft: Patch,
+    game_version: Option<String>,
+    /// Whether the loaded file can be written, checked when it is loaded.
+    write_access: Option<WriteAccess>,
+    /// Set when Defender's Controlled Folder Access stops us writing the file.
+    cfa_blocked: bool,
+    /// Defender is to be asked about `cfa_blocked`; see `poll_cfa`.
+    cfa_due: bool,
+    cfa_check: Option<Task<bool>>,
+    /// Whether Windows has HDR on for any display, checked when a DB is loaded.
+    os_hdr: Option<bool>,
+    /// The NVIDIA driver's frame-rate limit for the game, when syncing it is on and NVAPI answered.
+    driver_fps: Option<Option<u32>>,
+    /// The primary display's mode, read when a DB is loaded and after switching it.
+    display: Option<display::Display>,
+    /// Set while the display runs at a refresh rate we switched to.
+    display_switched: bool,
+    /// Power plans, read when a DB is loaded and after switching.
+    power_schemes: Vec<power::Scheme>,
+    /// The user's plan, while the game runs on one we switched to.
+    power_restore: Option<String>,
+    /// Power plan changes asked for, run one at a time by `poll_power`.
+    power_requests: VecDeque<PowerRequest>,
+    power_task: Option<Task<PowerDone>>,
+    /// The display, power plans and driver limit are to be read again; see `poll_system`.
+    system_due: bool,
+    system_read: Option<Task<SystemReadout>>,
+    /// Running while Discord presence is on; with the status last sent to it.
+    presence: Option<(discord::Presence, Option<String>)>,
+    /// Running PresentMon capture, with the limit it checks.
+    verify: Option<(i64, Task<Result<presentmon::FrameSample>>)>,
+    /// What the last capture found.
+    verified: Option<String>,
+    /// A write waiting for the game to exit, with its label.
+    staged: Option<(String, Staged)>,
+    /// The game exited with a write queued; it goes in once no other write is running.
+    finish_due: bool,
+    /// Writes carried over a restart, requested once the game has been checked for, so one made
+    /// while it runs is queued again.
+    resumed_write: Option<PendingWrite>,
+    /// Closing was asked for while a write is queued; see `on_close_event`.
+    confirm_close: bool,
+    /// The queued write may go: the user agreed, or it is in the handoff for a restart.
+    close_confirmed: bool,
+    /// An FPS limit we wrote that is still to be read back, and when: `None` until the game
+    /// starts.
+    read_back: Option<(i64, Option<Instant>)>,
+    key_browser: KeyBrowser,
+    cleanup: CleanupPanel,
+    sql_console: SqlConsole,
+    /// Unlocks raw edits on the Raw JSON, Keys and SQL tabs for this session.
+    advanced: AdvancedMode,
+    keybindings: KeybindingEditor,
+    config_tweaks: ConfigTweaks,
+    launchers: Launchers,
+    preset_review: Option<PresetReview>,
+    transfer: Option<TransferWizard>,
+    /// Text typed or pasted into the share code window while it is open.
+    share_code: Option<String>,
+    /// Releases since the version last run, shown once after an update.
+    whats_new: Vec<changelog::Release>,
+    /// Read from disk when the Stats tab shows, and again after anything is counted.
+    stats: Option<Stats>,
+    /// Read from disk when the Stats tab shows, and again after a change is logged.
+    history: Option<History>,
+    profile_name: String,
+    /// Saved profile names, listed on first use and after every change.
+    profile_names: Option<Vec<String>>,
+    /// Profiles a sync tool left several copies of, read along with the names.
+    profile_conflicts: Option<Vec<profiles::Conflict>>,
+    /// When the game last changed a re-asserted watched key; cleared once the write is started.
+    reassert_due: Option<Instant>,
+    /// Re-applies of watched keys in the last hour, limited by `Tuning::max_per_hour`.
+    reapplies: Budget,
+    /// Re-applying stopped at the hourly limit; set until one goes through again.
+    reapplies_paused: bool,
+    diagnostics: Vec<CheckResult>,
+    /// Setup checks running in the background.
+    diagnosing: Option<Task<Vec<CheckResult>>>,
+    /// The symptom last looked into on the Patcher tab and what its checks found.
+    troubleshooting: Option<(Symptom, Vec<Finding>)>,
+    compact: bool,
+    /// Window size to restore when leaving compact mode.
+    full_size: Option<egui::Vec2>,
+    forwarded_args: Option<Receiver<Vec<String>>>,
+    pending_write: Option<Task<WriteOutcome>>,
+    game_running: bool,
+    /// The last time the game was seen running; it writes its settings as it exits.
+    game_seen: Option<SystemTime>,
+    /// Known installs as last compared, when there are several.
+    installs: Vec<Install>,
+    /// Keys whose values differ between `installs`.
+    installs_diverging: Vec<String>,
+    installs_checked: Option<Instant>,
+    /// Reading every known install's settings for `installs`.
+    installs_read: Option<Task<Vec<(PathBuf, Value)>>>,
+    /// Copying settings between installs, waiting for the user's go-ahead.
+    confirm_sync: Option<SyncPlan>,
+    /// Copying settings between installs, from the install named.
+    pending_sync: Option<(PathBuf, Task<Vec<installs::Synced>>)>,
+    /// The loaded configuration file no longer exists; see `relocate`.
+    db_gone: bool,
+    /// Where the file that is gone seems to be now.
+    db_found: Option<PathBuf>,
+    db_path_checked: Option<Instant>,
+    /// The launcher is updating the game; writes wait until it finishes.
+    game_update: Option<UpdateSign>,
+    /// The look for a launcher update under way; see `poll_game_update`.
+    game_update_check: Option<Task<Option<UpdateSign>>>,
+    game_checked: Option<Instant>,
+    game_watcher: GameWatcher,
+    /// Game processes given the configured priority and affinity since the game started.
+    tuned_pids: Vec<u32>,
+    /// Set while the "close the game" confirmation is open.
+    confirm_close_game: bool,
+    /// Whether the window had keyboard focus this frame; background news goes to the system when not.
+    focused: bool,
+    config: Config,
+    /// Unsaved copy of `config` while the settings window is open.
+    settings_draft: Option<Config>,
+    /// Started with `autostart::MINIMIZED_ARG`, as Windows does on sign-in.
+    launched_minimized: bool,
+    /// The Start with Windows entry, read when Settings opens and after every change.
+    autostart: Option<autostart::State>,
+    /// A write waiting for the user to confirm it.
+    confirm_write: Option<PendingWrite>,
+    confirm_rtss: Option<RtssOffer>,
+    launched: bool,
+    scan: Option<Task<ScanUpdate>>,
+    /// (done, total, folder being searched) of the running drive scan.
+    scan_progress: Option<(usize, usize, String)>,
+    scan_results: Vec<PathBuf>,
+    /// Whether this session runs without native decorations; fixed at startup.
+    frameless: bool,
+    db_modified: Option<SystemTime>,
+    db_checked: Option<Instant>,
+    deck: Option<DeckModel>,
+    update_check: Option<Task<Result<Option<Release>>>>,
+    available_update: Option<Release>,
+    update_download: Option<Task<DownloadUpdate>>,
+    /// (bytes done, bytes total) of the running update download.
+    update_progress: Option<(u64, u64)>,
+    /// Scale the system asked for; the enlarged UI multiplies it.
+    native_scale: Option<f32>,
+}
+
+struct WriteOutcome {
+    label: String,
+    patch: Patch,
+    undo: bool,
+    /// The inverse patch if anything changed, and where the pre-write backup went if one was taken.
+    result: Result<(Option<Patch>, Option<PathBuf>)>,
+    /// The write waiting for the game to exit, when it was running.
+    queued: Option<Staged>,
+    /// The queued write this one was to replace, handed back when it failed.
+    previous: Option<(String, Staged)>,
+    /// This put a queued write in; when it failed, the write is offered again.
+    finishing: bool,
+}
+
+enum PowerRequest {
+    Switch,
+    Restore,
+}
+
+/// A power plan change as it went to `powercfg`.
+enum PowerJob {
+    /// To the fastest plan, `from` being the user's to put back.
+    Switch { to: power::Scheme, from: String },
+    Restore(String),
+}
+
+struct PowerDone {
+    job: PowerJob,
+    result: Result<()>,
+    /// The plans after the change.
+    schemes: Vec<power::Scheme>,
+}
+
+/// What `poll_system` reads off the UI thread.
+struct SystemReadout {
+    display: Option<display::Display>,
+    power_schemes: Vec<power::Scheme>,
+    driver_fps: Option<Option<u32>>,
+}
+
+const GAME_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+/// How long after the game starts a new FPS limit is read back.
+const READ_BACK_DELAY: Duration = Duration::from_secs(3 * 60);
+const INSTALLS_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+const DB_PATH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+/// Configuration files remembered for comparing installs, newest last.
+const MAX_KNOWN_DBS: usize = 10;
+
+impl FPSUnlockerApp {
+    fn notify(&mut self, level: ToastLevel, text: impl Into<String>) {
+        let text = text.into();
+        match level {
+            ToastLevel::Success | ToastLevel::Info => tracing::info!(toast = %text),
+            ToastLevel::Warning => tracing::warn!(toast = %text),
+            ToastLevel::Error => tracing::error!(toast = %text),
+        }
+        self.toasts.push(level, text);
+    }
+
+    /// For news from background work: a toast as usual, plus a system notification when the
+    /// window is in the background.
+    fn notify_from(&mut self, source: Source, level: ToastLevel, text: impl Into<String>) {
+        let text = text.into();
+        notifications::send_system(self.config.system_notifications, self.focused, source, &text);
+        self.notify(level, text);
+    }
+
+    /// Logs `event` and shows it; see `status`.
+    fn report(&mut self, event: StatusEvent) {
+        event.log();
+        self.notify(event.severity().into(), event.message());
+    }
+
+    fn report_from(&mut self, source: Source, event: StatusEvent) {
+        event.log();
+        self.notify_from(source, event.severity().into(), event.message());
+    }
+
+    fn count(&mut self, event: Event) {
+        stats::record(event);
+        self.stats = None;
+    }
+
+    /// What a crash report should know about the session, one indented line per item.
+    fn state_summary(&self) -> String {
+        format!(
+            "  tab: {:?}\n  database: {}\n  current FPS: {:?}\n  game running: {}\n  write in progress: {}\n  unapplied edits: {}\n  compact: {}",
+            self.tab,
+            if self.db_path.is_empty() { "none" } else { &self.db_path },
+            self.current_fps,
+            self.game_running,
+            self.pending_write.is_some(),
+            self.graphics_draft.len(),
+            self.compact,
+        )
+    }
+
+    fn history_lines(&self) -> Vec<String> {
+        self.toasts.history().iter().map(|toast| self.toasts.format_entry(toast)).collect()
+    }
+
+    fn copy_report(&mut self, ctx: &egui::Context) {
+        ctx.output().copied_text = report::build_report(&self.db_path, &self.diagnostics, &self.history_lines());
+        self.notify(ToastLevel::Success, "Diagnostic report copied to the clipboard.");
+    }
+
+    fn save_report(&mut self) {
+        let Some(path) = FileDialog::new().set_file_name("wuwa-ploom-report.txt").save_file() else {
+            return;
+        };
+        let report = report::build_report(&self.db_path, &self.diagnostics, &self.history_lines());
+        match fs::write(&path, report) {
+            Ok(()) => self.notify(ToastLevel::Success, format!("Diagnostic report saved to {}", path.display())),
+            Err(err) => self.notify(ToastLevel::Error, format!("Error saving report: {}", err)),
+        }
+    }
+
+    fn inspection(&self) -> Option<Inspection> {
+        let setting = self.quality_setting.as_ref()?;
+        Some(Inspection::new(&self.metadata, setting, self.game_version.as_deref()))
+    }
+
+    fn copy_inspection(&mut self, ctx: &egui::Context) {
+        let Some(inspection) = self.inspection() else {
+            return;
+        };
+        ctx.output().copied_text = inspection.to_markdown();
+        self.notify(ToastLevel::Success, "Inspection report copied to the clipboard; paste it into your forum post.");
+    }
+
+    fn save_inspection(&mut self) {
+        let Some(inspection) = self.inspection() else {
+            return;
+        };
+        let dialog = FileDialog::new()
+            .set_file_name("wuwa-ploom-inspection.md")
+            .add_filter("Markdown", &["md"])
+            .add_filter("HTML", &["html", "htm"]);
+        let Some(path) = dialog.save_file() else {
+            return;
+        };
+        let html = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm"));
+        let report = if html { inspection.to_html() } else { inspection.to_markdown() };
+        match fs::write(&path, report) {
+            Ok(()) => self.notify(ToastLevel::Success, format!("Inspection report saved to {}", path.display())),
+            Err(err) => self.notify(ToastLevel::Error, format!("Error saving report: {}", err)),
+        }
+    }
+
+    /// Deletes the oldest backups once they take more than the configured space, and says which.
+    fn prune_backups(&mut self) {
+        if self.config.backup_budget_mb == 0 {
+            return;
+        }
+        let removed = backup::prune(&self.backup_dir(), self.config.backup_budget_mb * 1024 * 1024);
+        if removed.is_empty() {
+            return;
+        }
+        let names: Vec<String> = removed.iter().filter_map(|snapshot| Some(snapshot.path.file_name()?.to_string_lossy().into_owned())).collect();
+        let text = format!(
+            "Backups went over {} MB, so the {} oldest were deleted: {}",
+            self.config.backup_budget_mb,
+            removed.len(),
+            names.join(", ")
+        );
+        self.notify_from(Source::Backup, ToastLevel::Info, text);
+    }
+
+    fn load_db(&mut self, path: String) {
+        if path != self.db_path {
+            self.undo_stack.clear();
+            self.graphics_draft.clear();
+            self.key_browser = KeyBrowser::default();
+            self.cleanup = CleanupPanel::default();
+            self.keybindings = KeybindingEditor::default();
+            self.config_tweaks = ConfigTweaks::default();
+            self.read_back = None;
+        }
+        self.db_path = path;
+        self.game_update_check = None;
+        self.db_gone = false;
+        self.db_found = None;
+        if !sample::is_sample(&self.db_path) && !self.config.known_dbs.contains(&self.db_path) {
+            self.config.known_dbs.push(self.db_path.clone());
+            let excess = self.config.known_dbs.len().saturating_sub(MAX_KNOWN_DBS);
+            self.config.known_dbs.drain(..excess);
+            self.save_config();
+            self.installs_checked = None;
+        }
+        self.db_modified = db::modified_time(&self.db_path);
+        self.game_version = detect::get_game_version();
+        self.write_access = Some(WriteAccess::check(Path::new(&self.db_path), platform::native()));
+        // Only worth asking Defender when writing looks refused.
+        self.cfa_blocked = false;
+        self.cfa_check = None;
+        self.cfa_due = self.write_access != Some(WriteAccess::Writable);
+        keymap::select(self.game_version.as_deref());
+        self.os_hdr = hdr::os_hdr_enabled();
+        self.system_due = true;
+        self.refresh();
+    }
+
+    fn switch_refresh_rate(&mut self, hz: u32) {
+        match display::set_refresh_rate(hz) {
+            Ok(()) => {
+                self.display_switched = true;
+                self.notify(ToastLevel::Success, format!("Display switched to {} Hz.", hz));
+            }
+            Err(err) => self.notify(ToastLevel::Error, format!("Could not switch the refresh rate: {}", err)),
+        }
+        self.system_due = true;
+    }
+
+    /// Applies the configured priority and affinity to game processes that started since the last poll.
+    fn tune_game(&mut self) {
+        let affinity = process::core_mask(&self.config.game_affinity);
+        if self.config.game_priority == Priority::Normal && affinity.is_none() {
+            return;
+        }
+        let pids = self.game_watcher.pids().to_vec();
+        let refused = process::tune_game(platform::native(), &pids, &mut self.tuned_pids, self.config.game_priority, affinity);
+        if !refused.is_empty() {
+            self.notify(ToastLevel::Warning, "Could not change the game's priority or CPU cores; it may need administrator rights.");
+        }
+    }
+
+    /// Adds this program to Controlled Folder Access's allowed apps, then checks again.
+    fn allow_in_defender(&mut self) {
+        let result = env::current_exe().map_err(MyError::from).and_then(|exe| defender::allow(platform::native(), &exe));
+        match result {
+            Ok(()) => {
+                self.notify(ToastLevel::Success, "WuWa Ploom may now change files in protected folders.");
+                let path = self.db_path.clone();
+                self.load_db(path);
+            }
+            Err(err) => self.notify(ToastLevel::Error, format!("Could not allow WuWa Ploom: {}", err)),
+        }
+    }
+
+    /// Switches to the fastest power plan, remembering the user's to put back.
+    fn switch_power_plan(&mut self) {
+        self.power_requests.push_back(PowerRequest::Switch);
+    }
+
+    fn restore_power_plan(&mut self) {
+        self.power_requests.push_back(PowerRequest::Restore);
+    }
+
+    /// Runs the power plan changes asked for in order, each in the background, as `powercfg`
+    /// can take a while. Each is worked out only when its turn comes, from the plans as the
+    /// one before left them.
+    fn poll_power(&mut self, ctx: &egui::Context) {
+        if let Some(task) = &self.power_task {
+            match task.try_recv() {
+                Ok(done) => {
+                    self.power_task = None;
+                    self.power_done(done);
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => self.power_task = None,
+            }
+        }
+        // Requests are worked out from the plans, so they wait for a read under way.
+        if self.system_due || self.system_read.is_some() {
+            return;
+        }
+        while let Some(request) = self.power_requests.pop_front() {
+            let Some(job) = self.power_job(request) else {
+                continue;
+            };
+            self.power_task = Some(tasks::spawn_once(ctx, "power plan", move || {
+                let result = match &job {
+                    PowerJob::Switch { to, .. } => power::set_active(&to.guid),
+                    PowerJob::Restore(guid) => power::set_active(guid),
+                };
+                PowerDone { job, result, schemes: power::schemes() }
+            }));
+            break;
+        }
+    }
+
+    fn power_job(&mut self, request: PowerRequest) -> Option<PowerJob> {
+        match request {
+            PowerRequest::Switch => {
+                let active = self.power_schemes.iter().find(|scheme| scheme.active)?;
+                let fast = power::fastest(&self.power_schemes)?;
+                (active.guid != fast.guid).then(|| PowerJob::Switch { to: fast.clone(), from: active.guid.clone() })
+            }
+            PowerRequest::Restore => self.power_restore.take().map(PowerJob::Restore),
+        }
+    }
+
+    fn power_done(&mut self, done: PowerDone) {
+        self.power_schemes = done.schemes;
+        match (done.job, done.result) {
+            (PowerJob::Switch { to, from }, Ok(())) => {
+                self.power_restore.get_or_insert(from);
+                self.notify(ToastLevel::Success, format!("Power plan switched to {} while the game runs.", to.name));
+            }
+            (PowerJob::Switch { .. }, Err(err)) => self.notify(ToastLevel::Error, format!("Could not switch the power plan: {}", err)),
+            (PowerJob::Restore(_), Ok(())) => {}
+            (PowerJob::Restore(_), Err(err)) => self.notify(ToastLevel::Error, format!("Could not restore the power plan: {}", err)),
+        }
+    }
+
+    /// Reads the display mode, power plans and NVIDIA driver limit in the background once
+    /// something asked for it; all three call into Windows or the driver and can be slow.
+    fn poll_system(&mut self, ctx: &egui::Context) {
+        if let Some(task) = &self.system_read {
+            match task.try_recv() {
+                Ok(readout) => {
+                    self.system_read = None;
+                    self.display = readout.display;
+                    self.power_schemes = readout.power_schemes;
+                    self.driver_fps = readout.driver_fps;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.system_read = None,
+            }
+            return;
+        }
+        if !std::mem::take(&mut self.system_due) {
+            return;
+        }
+        let driver_exe = games::active().client_exe_for(Path::new(&self.db_path)).filter(|_| self.config.nvidia_frame_limit);
+        self.system_read = Some(tasks::spawn_once(ctx, "read system", move || SystemReadout {
+            display: display::current(),
+            power_schemes: power::schemes(),
+            driver_fps: driver_exe.and_then(|exe| nvidia::max_frame_rate(&exe).ok()),
+        }));
+    }
+
+    fn restore_display(&mut self) {
+        display::restore();
+        self.display_switched = false;
+        self.system_due = true;
+        self.notify(ToastLevel::Info, "Display refresh rate restored.");
+    }
+
+    /// Copies the database's values into GameUserSettings.ini where they differ, if the user
+    /// wants that and the client keeps one.
+    fn sync_game_user_settings(&mut self) {
+        let Some(setting) = self.quality_setting.as_ref().filter(|_| self.config.sync_game_user_settings) else {
+            return;
+        };
+        let exists = game_user_settings::path_for_db(Path::new(&self.db_path)).is_some_and(|path| path.is_file());
+        if !exists {
+            return;
+        }
+        let backup_dir = self.config.backup_enabled.then(|| self.backup_dir());
+        let (level, text) = config_tweaks::sync_user_settings(&self.db_path, setting, backup_dir.as_deref());
+        // Nothing to say when they already agree.
+        if level != ToastLevel::Info {
+            self.notify(level, text);
+        }
+        self.config_tweaks = ConfigTweaks::default();
+    }
+
+    fn read_driver_limit(&mut self) {
+        self.driver_fps = None;
+        self.system_due = true;
+    }
+
+    /// Sets the NVIDIA driver's limit for the game to `fps`, or clears it with `None`.
+    fn sync_driver_limit(&mut self, fps: Option<u32>) {
+        let Some(exe) = games::active().client_exe_for(Path::new(&self.db_path)) else {
+            return;
+        };
+        match nvidia::set_max_frame_rate(&exe, fps) {
+            Ok(()) => {
+                let text = match fps {
+                    Some(fps) => format!("NVIDIA Max Frame Rate for {} set to {} FPS.", exe.display(), fps),
+                    None => format!("NVIDIA Max Frame Rate for {} cleared.", exe.display()),
+                };
+                self.notify(ToastLevel::Success, text);
+            }
+            Err(err) => self.notify(ToastLevel::Error, format!("Error changing the NVIDIA driver limit: {}", err)),
+        }
+        self.read_driver_limit();
+    }
+
+    fn refresh(&mut self) {
+        if !games::active().has_quality_setting() {
+            return;
+        }
+        match read_quality_setting_file(&self.db_path) {
+            Ok(setting) => {
+                match fps_from_setting(&setting) {
+                    Ok(fps) => self.current_fps = Some(fps),
+                    Err(err) => self.notify(ToastLevel::Error, format!("Error reading FPS setting: {}", err)),
+                }
+                self.quality_setting = Some(setting);
+            }
+            Err(err) => self.notify(ToastLevel::Error, format!("Error reading FPS setting: {}", err)),
+        }
+    }
+
+    /// Where backups of the loaded file go; the sample's are kept apart from the game's.
+    fn backup_dir(&self) -> PathBuf {
+        if sample::is_sample(&self.db_path) {
+            sample::backup_dir()
+        } else {
+            self.config.backup_dir()
+        }
+    }
+
+    fn fps_presets(&self) -> &'static [i64] {
+        self.deck.map_or(&FPS_PRESETS, DeckModel::fps_presets)
+    }
+
+    /// Why writes to GameQualitySetting are currently disabled, phrased for a tooltip; `None`
+    /// when a write may start.
+    /// While the game runs these writes are queued for its exit instead; see `staged`.
+    fn write_block_reason(&self) -> Option<&'static str> {
+        self.block_reason(true).or_else(|| {
+            (!games::active().has_quality_setting()).then_some("This game has no known settings row; edit its rows on the Keys tab.")
+        })
+    }
+
+    /// Why writes to any row are currently disabled; see `write_block_reason`.
+    fn row_write_block_reason(&self) -> Option<&'static str> {
+        self.block_reason(false)
+    }
+
+    fn block_reason(&self, queue_while_running: bool) -> Option<&'static str> {
+        if self.db_path.is_empty() {
+            Some("Locate or browse for the configuration file first.")
+        } else if self.db_gone {
+            Some("The configuration file is gone; use the one found again or locate it at the top of the Patcher tab.")
+        } else if self.pending_write.is_some() || self.confirm_write.is_some() || self.pending_sync.is_some() || self.confirm_sync.is_some() {
+            Some("A write is already in progress.")
+        } else if self.game_running && !queue_while_running && !sample::is_sample(&self.db_path) {
+            Some("Close the game first; it overwrites the file when it exits.")
+        } else if self.game_update.is_some() && !sample::is_sample(&self.db_path) {
+            Some("The launcher is updating the game; wait until it finishes.")
+        } else if matches!(self.write_access, Some(WriteAccess::ReadOnly(_))) {
+            Some("The configuration file is read-only; see the note under its path.")
+        } else {
+            None
+        }
+    }
+
+    fn poll_game_running(&mut self, ctx: &egui::Context) {
+        if self.game_checked.is_some_and(|checked| checked.elapsed() < GAME_CHECK_INTERVAL) {
+            return;
+        }
+        self.game_checked = Some(Instant::now());
+        let event = self.game_watcher.poll(platform::native());
+        let stopped = matches!(event, Some(GameEvent::Stopped));
+        let started = matches!(event, Some(GameEvent::Started(_)));
+        match event {
+            // The running client tells us where its install is even when the launcher didn't.
+            Some(GameEvent::Started(game)) if self.db_path.is_empty() => {
+                if let Some(db) = game.exe.as_deref().and_then(detect::db_path_for_exe) {
+                    self.load_db(db.display().to_string());
+                }
+            }
+            // The game writes its settings on exit.
+            Some(GameEvent::Stopped) if !self.db_path.is_empty() => {
+                self.finish_due = self.staged.is_some();
+                self.refresh();
+            }
+            _ => {}
+        }
+        if stopped && self.display_switched && self.config.restore_refresh_rate {
+            self.restore_display();
+        }
+        if started {
+            self.tuned_pids.clear();
+            if let Some((_, due @ None)) = &mut self.read_back {
+                *due = Some(Instant::now() + READ_BACK_DELAY);
+            }
+        }
+        self.tune_game();
+        if started && self.config.power_plan_while_playing {
+            self.switch_power_plan();
+        }
+        if stopped {
+            self.restore_power_plan();
+        }
+        self.game_running = self.game_watcher.running().is_some();
+        if self.game_running {
+            self.game_seen = Some(SystemTime::now());
+        }
+        self.poll_game_update(ctx);
+        self.poll_read_back();
+    }
+
+    /// Reads a new FPS limit back once the game has run for a while, or when it exits sooner,
+    /// and says whether it survived.
+    fn poll_read_back(&mut self) {
+        let Some((fps, Some(due))) = self.read_back else {
+            return;
+        };
+        if self.game_running && Instant::now() < due {
+            return;
+        }
+        self.read_back = None;
+        let read = db::read_quality_setting_now(&self.db_path).and_then(|setting| fps_from_setting(&setting));
+        match read {
+            // The game is saving; look again at the next check.
+            Err(MyError::Locked(_)) => self.read_back = Some((fps, Some(Instant::now() + GAME_CHECK_INTERVAL))),
+            Ok(found) if found == fps => {
+                let text = format!("The {} FPS limit is still set after the game started.", fps);
+                self.notify_from(Source::ReadBack, ToastLevel::Success, text);
+            }
+            Ok(found) => {
+                let text = format!("The game put the FPS limit back to {} after it started; {} was set. Don't open its FPS or VSync options, then set {} again after closing it.", found, fps, fps);
+                self.notify_from(Source::ReadBack, ToastLevel::Warning, text);
+            }
+            Err(err) => tracing::warn!(%err, "could not read the FPS limit back"),
+        }
+    }
+
+    /// Looks for a launcher update in the background, as it lists processes and the game
+    /// folder; called with each game check, and applies the last look's result.
+    fn poll_game_update(&mut self, ctx: &egui::Context) {
+        if self.db_path.is_empty() {
+            self.game_update_check = None;
+            self.set_game_update(None);
+            return;
+        }
+        if let Some(task) = &self.game_update_check {
+            match task.try_recv() {
+                Ok(update) => {
+                    self.game_update_check = None;
+                    self.set_game_update(update);
+                }
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => self.game_update_check = None,
+            }
+        }
+        let db_path = PathBuf::from(&self.db_path);
+        let task = tasks::spawn_once(ctx, "check game update", move || game_update::in_progress(platform::native(), &db_path));
+        self.game_update_check = Some(task);
+    }
+
+    fn set_game_update(&mut self, update: Option<UpdateSign>) {
+        match (&self.game_update, &update) {
+            (None, Some(sign)) => {
+                tracing::info!(sign = %sign.describe(), "game update in progress; holding writes");
+                let text = format!("The game is updating ({}). Changes wait until it finishes.", sign.describe());
+                self.notify(ToastLevel::Warning, text);
+            }
+            (Some(_), None) => self.notify(ToastLevel::Info, "The game update finished; changes can be made again."),
+            _ => {}
+        }
+        self.game_update = update;
+    }
+
+    fn show_confirm_close_game(&mut self, ctx: &egui::Context) {
+        if !self.confirm_close_game {
+            return;
+        }
+        let mut answer = None;
+        egui::Window::new("Close the game?").collapsible(false).resizable(false).show(ctx, |ui| {
+            ui.label("The game is closed without saving. Progress since the last autosave and settings changed in-game this session are lost.");
+            ui.horizontal(|ui| {
+                if ui.button("Close game").clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    answer = Some(false);
+                }
+            });
+        });
+        let Some(confirmed) = answer else {
+            return;
+        };
+        self.confirm_close_game = false;
+        if !confirmed {
+            return;
+        }
+        match process::terminate_game(platform::native(), Path::new(&self.db_path)) {
+            0 => self.notify(ToastLevel::Error, "Could not close the game; it may need administrator rights."),
+            _ => {
+                self.notify(ToastLevel::Info, "Closed the game.");
+                // Pick up the exit right away instead of at the next interval.
+                self.game_checked = None;
+            }
+        }
+    }
+
+    fn poll_db_changes(&mut self) {
+        if !self.config.auto_refresh || self.db_path.is_empty() || self.pending_write.is_some() {
+            return;
+        }
+        if self.db_checked.is_some_and(|checked| checked.elapsed() < self.config.watchdog.poll_interval()) {
+            return;
+        }
+        self.db_checked = Some(Instant::now());
+
+        let modified = db::modified_time(&self.db_path);
+        if modified == self.db_modified {
+            return;
+        }
+        // The game may be mid-write; a failed read is tried again at the next poll.
+        let Ok(setting) = db::read_quality_setting_now(&self.db_path) else {
+            return;
+        };
+        self.db_modified = modified;
+        let logged = self.log_changes(&setting);
+        if let Ok(fps) = fps_from_setting(&setting) {
+            if self.current_fps.is_some_and(|current| current != fps) {
+                let frame_rate = keymap::active().frame_rate;
+                let text = match logged.iter().find(|change| change.key == frame_rate) {
+                    Some(change) => format!("{} changed to {} by {}.", frame_rate, fps, change.author.label()),
+                    None => format!("{} changed to {}.", frame_rate, fps),
+                };
+                self.notify(ToastLevel::Info, text);
+                if !self.game_running {
+                    self.note_fps_change(fps);
+                }
+            }
+            self.current_fps = Some(fps);
+        }
+        if let Some(before) = &self.quality_setting {
+            let changes: Vec<(String, bool)> = watch::changed_by_game(&self.config.watched, before, &setting)
+                .into_iter()
+                .map(|(watched, now)| (format!("The game changed {} from {} to {}.", watched.key, watched.value, now), watched.reassert))
+                .collect();
+            for (text, reassert) in changes {
+                self.count(Event::Revert);
+                self.notify(ToastLevel::Warning, text);
+                if reassert {
+                    self.reassert_due = Some(Instant::now());
+                }
+            }
+        }
+        self.quality_setting = Some(setting);
+    }
+
+    /// Adds what changed since the last read of the setting to the change history, attributed to
+    /// us, the game or someone else, and returns the new entries.
+    fn log_changes(&mut self, setting: &Value) -> Vec<Change> {
+        let Some(before) = &self.quality_setting else {
+            return Vec::new();
+        };
+        let modified = history::unix_time(self.db_modified.unwrap_or_else(SystemTime::now));
+        let game_seen = self.game_seen.map(history::unix_time);
+        let logged = history::update(|history| history.record_changes(before, setting, modified, game_seen));
+        if !logged.is_empty() {
+            self.history = None;
+        }
+        logged
+    }
+
+    /// Compares the settings of every known install now and then, warning when they start to
+    /// differ.
+    /// Reads every known install's settings in the background now and then, and compares them.
+    fn poll_installs(&mut self, ctx: &egui::Context) {
+        if let Some(task) = &self.installs_read {
+            match task.try_recv() {
+                Ok(read) => {
+                    self.installs_read = None;
+                    self.compare_installs(read);
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.installs_read = None,
+            }
+            return;
+        }
+        if self.installs_checked.is_some_and(|checked| checked.elapsed() < INSTALLS_CHECK_INTERVAL) {
+            return;
+        }
+        self.installs_checked = Some(Instant::now());
+        let remembered = self.config.known_dbs.clone();
+        self.installs_read = Some(tasks::spawn_once(ctx, "read installs", move || {
+            installs::known(platform::native(), &remembered)
+                .into_iter()
+                .filter_map(|path| Some((path.clone(), read_quality_setting_file(&path.display().to_string()).ok()?)))
+                .collect()
+        }));
+    }
+
+    fn compare_installs(&mut self, read: Vec<(PathBuf, Value)>) {
+        if read.len() < 2 {
+            self.installs.clear();
+            self.installs_diverging.clear();
+            return;
+        }
+        let settings: Vec<Value> = read.iter().map(|(_, setting)| setting.clone()).collect();
+        let diverging = installs::diverging(&settings);
+        if !diverging.is_empty() && self.installs_diverging.is_empty() {
+            let text = format!("Your {} installs have different settings ({}). Compare them under Installs on the Patcher tab.", read.len(), diverging.join(", "));
+            self.notify(ToastLevel::Warning, text);
+        }
+        self.installs = read.into_iter().map(|(db_path, setting)| Install { fps: fps_from_setting(&setting).ok(), db_path, setting }).collect();
+        self.installs_diverging = diverging;
+    }
+
+    /// Notices when the loaded file disappears, e.g. after the game was moved or reinstalled, and
+    /// looks for it again until it is back or found elsewhere.
+    fn poll_db_path(&mut self) {
+        if self.db_path.is_empty() || sample::is_sample(&self.db_path) || self.game_update.is_some() {
+            return;
+        }
+        if self.db_path_checked.is_some_and(|checked| checked.elapsed() < DB_PATH_CHECK_INTERVAL) {
+            return;
+        }
+        self.db_path_checked = Some(Instant::now());
+        if Path::new(&self.db_path).is_file() {
+            if self.db_gone {
+                self.db_gone = false;
+                self.db_found = None;
+                self.refresh();
+            }
+            return;
+        }
+        if self.db_found.is_some() {
+            return;
+        }
+        let detected = detect::get_game_install_path().ok();
+        let known = installs::known(platform::native(), &self.config.known_dbs);
+        self.db_found = relocate::replacement(&self.db_path, detected.as_deref(), &known);
+        if self.db_gone && self.db_found.is_none() {
+            return;
+        }
+        let text = match &self.db_found {
+            Some(found) => format!("{} is gone; the game was probably moved or reinstalled. Found {}; switch to it on the Patcher tab.", self.db_path, found.display()),
+            None => format!("{} is gone; the game was probably moved or reinstalled. Locate the file again on the Patcher tab.", self.db_path),
+        };
+        self.db_gone = true;
+        tracing::warn!(db_path = %self.db_path, found = ?self.db_found, "the configuration file is gone");
+        self.notify(ToastLevel::Warning, text);
+    }
+
+    /// Switches to the file found in place of the one that is gone, carrying over what the app
+    /// remembers about the old path.
+    fn relocate(&mut self) {
+        let Some(found) = self.db_found.take() else {
+            return;
+        };
+        let found = found.display().to_string();
+        relocate::migrate(&mut self.config.known_dbs, &self.db_path, &found);
+        self.save_config();
+        // The queued write's clone went with the old file.
+        if let Some((label, queued)) = self.staged.take() {
+            let _ = staged::discard(queued);
+            self.notify(ToastLevel::Warning, format!("Dropped the queued change ({}); make it again if still needed.", label));
+        }
+        self.load_db(found.clone());
+        self.notify(ToastLevel::Success, format!("Now using {}. Backups and history carry over.", found));
+    }
+
+    /// Copies one install's settings to the others that lack them, asking first when writes are
+    /// confirmed; see `request_write`.
+    fn sync_installs(&mut self, ctx: &egui::Context, index: usize) {
+        if let Some(reason) = self.row_write_block_reason() {
+            self.notify(ToastLevel::Warning, reason);
+            return;
+        }
+        let Some(from) = self.installs.get(index) else {
+            return;
+        };
+        let targets: Vec<(PathBuf, Value, Patch)> = self
+            .installs
+            .iter()
+            .filter(|install| install.db_path != from.db_path)
+            .map(|install| (install.db_path.clone(), install.setting.clone(), installs::sync_patch(&from.setting, &install.setting)))
+            .filter(|(_, _, patch)| !patch.is_empty())
+            .collect();
+        if targets.is_empty() {
+            self.notify(ToastLevel::Info, "The other installs already have these settings.");
+            return;
+        }
+        let plan = SyncPlan { from: from.db_path.clone(), targets };
+        if self.config.confirm_before_write {
+            self.confirm_sync = Some(plan);
+            return;
+        }
+        self.start_sync(ctx, plan);
+    }
+
+    fn show_confirm_sync(&mut self, ctx: &egui::Context) {
+        let Some(confirmed) = self.confirm_sync.as_ref().and_then(|plan| installs_panel::show_confirm(ctx, plan)) else {
+            return;
+        };
+        if let Some(plan) = self.confirm_sync.take().filter(|_| confirmed) {
+            self.start_sync(ctx, plan);
+        }
+    }
+
+    fn start_sync(&mut self, ctx: &egui::Context, plan: SyncPlan) {
+        let backup_dir = self.config.backup_enabled.then(|| self.config.backup_dir());
+        let task = tasks::spawn_once(ctx, "sync installs", move || {
+            plan.targets
+                .into_iter()
+                .map(|(target, _, patch)| {
+                    let result = installs::write_to(&target, &patch, backup_dir.as_deref(), platform::native());
+                    if let Ok(Some(inverse)) = &result {
+                        let event = StatusEvent::Written { label: format!("Copied settings to {}", target.display()), keys: inverse.len() };
+                        history::record_write(&event, &patch, inverse);
+                    }
+                    installs::Synced { target, patch, result }
+                })
+                .collect()
+        });
+        self.pending_sync = Some((plan.from, task));
+    }
+
+    /// Says how copying settings went for each install. A change to the loaded file can be
+    /// undone like any other write.
+    fn poll_sync(&mut self) {
+        let Some((from, task)) = &self.pending_sync else {
+            return;
+        };
+        let synced = match task.try_recv() {
+            Ok(synced) => synced,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.pending_sync = None;
+                self.notify(ToastLevel::Error, "Copying the settings was interrupted.");
+                return;
+            }
+        };
+        let label = format!("Settings copied from {}", from.display());
+        self.pending_sync = None;
+        let mut copied = Vec::new();
+        let mut failed = Vec::new();
+        for install in synced {
+            match install.result {
+                Ok(inverse) => {
+                    if let Some(inverse) = inverse.filter(|_| install.target == Path::new(&self.db_path)) {
+                        self.undo_stack.push((label.clone(), inverse));
+                    }
+                    copied.push(install.target.display().to_string());
+                }
+                Err(err) => failed.push(format!("{}: {}", install.target.display(), err)),
+            }
+        }
+        if self.config.backup_enabled {
+            self.prune_backups();
+        }
+        if !copied.is_empty() {
+            self.count(Event::Patch);
+            self.notify(ToastLevel::Success, format!("{} to {}.", label, copied.join(", ")));
+        }
+        if !failed.is_empty() {
+            self.notify(ToastLevel::Error, format!("Could not copy the settings to {}", failed.join("; ")));
+        }
+        self.refresh();
+        self.installs_checked = None;
+    }
+
+    /// Warns once the frame-rate limit keeps flipping between values while the game is closed,
+    /// naming whatever other unlocker can be found.
+    fn note_fps_change(&mut self, fps: i64) {
+        let now = SystemTime::now();
+        self.fps_changes.retain(|(time, _)| now.duration_since(*time).is_ok_and(|age| age <= competitors::FLIP_WINDOW));
+        self.fps_changes.push((now, fps));
+        if !competitors::is_flip_flopping(&self.fps_changes, now) {
+            return;
+        }
+        self.fps_changes.clear();
+        let signs: Vec<String> = competitors::find(platform::native(), &self.db_path).iter().map(competitors::Sign::describe).collect();
+        let culprit = if signs.is_empty() { "Another program".to_string() } else { format!("Another FPS tool ({})", signs.join("; ")) };
+        let text = format!(
+            "{} keeps changing {} back and forth. Only let one tool manage the FPS limit.",
+            culprit,
+            keymap::active().frame_rate
+        );
+        self.notify(ToastLevel::Warning, text);
+    }
+
+    /// Writes re-asserted watched keys back once writes are allowed again, e.g. after the game
+    /// exits, and the game has left them alone for the debounce. Past the hourly limit the keys
+    /// are left as the game set them, so the two don't take turns forever.
+    fn poll_watched(&mut self, ctx: &egui::Context) {
+        let tuning = self.config.watchdog;
+        let settled = self.reassert_due.is_some_and(|changed| changed.elapsed() >= tuning.debounce());
+        if !settled || self.game_running || self.write_block_reason().is_some() {
+            return;
+        }
+        if !self.reapplies.take(Instant::now(), tuning.max_per_hour) {
+            if !self.reapplies_paused {
+                self.reapplies_paused = true;
+                let minutes = self.reapplies.frees_at().map_or(0, |at| watchdog::minutes_until(at, Instant::now()));
+                let text = format!(
+                    "The game changed watched keys back {} times within an hour; re-applying is paused for {} min. Raise the limit in Settings > Watchdog.",
+                    tuning.max_per_hour, minutes
+                );
+                self.notify_from(Source::Watchdog, ToastLevel::Warning, text);
+            }
+            return;
+        }
+        self.reapplies_paused = false;
+        self.reassert_due = None;
+        let Some(setting) = &self.quality_setting else {
+            return;
+        };
+        let patch = watch::reassert_patch(&self.config.watched, setting);
+        if !patch.is_empty() {
+            tracing::info!(keys = ?patch.keys().collect::<Vec<_>>(), "re-applying watched keys the game changed");
+            let text = format!("The game changed {} watched key(s); putting them back.", patch.len());
+            self.notify_from(Source::Watchdog, ToastLevel::Info, text);
+            let write = PendingWrite { label: format!("Re-applied {} watched key(s)", patch.len()), patch, undo: false };
+            self.request_write(ctx, write);
+        }
+    }
+
+    /// Keeps the Discord status in line with the watchdog: shown while a database is loaded and
+    /// re-asserted watched keys are kept, cleared otherwise.
+    fn poll_presence(&mut self) {
+        let app_id = self.config.discord_app_id.trim();
+        if !self.config.discord_presence || app_id.is_empty() {
+            self.presence = None;
+            return;
+        }
+        if self.presence.as_ref().is_none_or(|(presence, _)| presence.app_id() != app_id) {
+            self.presence = Some((discord::Presence::start(app_id), None));
+        }
+        let watching = !self.db_path.is_empty() && self.config.watched.iter().any(|watched| watched.reassert);
+        let text = watching.then(|| match self.current_fps {
+            Some(fps) => format!("FPS unlock active: {}", fps),
+            None => "Keeping watched settings".into(),
+        });
+        if let Some((presence, shown)) = &mut self.presence {
+            if *shown != text {
+                presence.set(text.clone());
+                *shown = text;
+            }
+        }
+    }
+
+    /// Measures the game's frame rate with PresentMon to see whether the limit took effect.
+    fn verify_unlock(&mut self, ctx: &egui::Context) {
+        let Some(limit) = self.current_fps else {
+            return;
+        };
+        let Some(presentmon) = presentmon::find() else {
+            let text = format!("Verifying needs PresentMon. Get it from {} and put it next to WuWa Ploom.", presentmon::DOWNLOAD_URL);
+            self.notify(ToastLevel::Warning, text);
+            return;
+        };
+        self.verified = None;
+        let task = tasks::spawn_once(ctx, "presentmon", move || {
+            presentmon::capture(&presentmon, games::active().process_names(), presentmon::CAPTURE_SECONDS)
+        });
+        self.verify = Some((limit, task));
+    }
+
+    fn poll_verify(&mut self) {
+        let Some((limit, task)) = &self.verify else {
+            return;
+        };
+        let limit = *limit;
+        match task.try_recv() {
+            Ok(Ok(sample)) => {
+                self.verify = None;
+                let text = sample.describe(limit);
+                self.notify(ToastLevel::Info, text.clone());
+                self.verified = Some(text);
+            }
+            Ok(Err(err)) => {
+                self.verify = None;
+                self.notify(ToastLevel::Error, format!("Could not measure the frame rate: {}", err));
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.verify = None,
+        }
+    }
+
+    /// Starts the write, or asks for confirmation first if the user wants that;
+    /// returns false if writes are blocked.
+    fn request_write(&mut self, ctx: &egui::Context, write: PendingWrite) -> bool {
+        if let Some(reason) = self.write_block_reason() {
+            self.report(StatusEvent::Refused { reason: reason.into() });
+            if write.undo {
+                self.undo_stack.push((write.label, write.patch));
+            }
+            return false;
+        }
+        // Undo restores values that were on disk before, so only new edits are validated.
+        if !write.undo {
+            let mut invalid = Vec::new();
+            for problem in self.metadata.check_patch(&write.patch, self.game_version.as_deref()) {
+                match problem {
+                    Problem::Invalid(text) => invalid.push(text),
+                    Problem::Unavailable(text) => self.notify(ToastLevel::Warning, text),
+                }
+            }
+            if !invalid.is_empty() {
+                self.notify(ToastLevel::Error, format!("Write refused: {}.", invalid.join("; ")));
+                return false;
+            }
+        }
+        if self.config.confirm_before_write {
+            self.confirm_write = Some(write);
+            return true;
+        }
+        self.start_write(ctx, write);
+        true
+    }
+
+    fn request_fps(&mut self, ctx: &egui::Context, fps: i64) {
+        // Picking a menu limit here does what setting 60 in the game asks for.
+        let setting = self.quality_setting.as_ref().filter(|_| !frame_rate::MENU_LIMITS.contains(&fps));
+        if let Some(warning) = setting.and_then(|setting| frame_rate::menu_option(setting).warning()) {
+            self.notify(ToastLevel::Warning, warning);
+        }
+        let write = PendingWrite { label: format!("FPS set to {}", fps), patch: db::fps_patch(fps), undo: false };
+        self.request_write(ctx, write);
+    }
+
+    fn start_write(&mut self, ctx: &egui::Context, mut write: PendingWrite) {
+        let db_path = self.db_path.clone();
+        let backup_dir = self.config.backup_enabled.then(|| self.backup_dir());
+        let elevate = self.write_access == Some(WriteAccess::NeedsElevation);
+        let queue = self.game_running && !sample::is_sample(&self.db_path);
+        // A second write while one is queued joins it in a new clone; the queued one stays
+        // until that is in place.
+        let mut previous = self.staged.take();
+        if let Some((label, _)) = &previous {
+            write.label = format!("{}; {}", label, write.label);
+        }
+        let task = tasks::spawn_once(ctx, "write", move || {
+            let PendingWrite { label, patch, undo } = write;
+            let mut target = patch.clone();
+            let mut queued = None;
+            let result = (|| {
+                if let Some((_, previous)) = &previous {
+                    target = previous.patch.clone().into_iter().chain(target.clone()).collect();
+                }
+                let current = read_quality_setting_file(&db_path)?;
+                if db::inverse_patch(&current, &target).is_empty() {
+                    return Ok((None, None));
+                }
+                let backup = match &backup_dir {
+                    Some(dir) => Some(backup::backup_db(&db_path, dir)?),
+                    None => None,
+                };
+                if queue {
+                    queued = staged::stage(&db_path, &target, elevate, previous.as_ref().map(|(_, previous)| previous))?;
+                    return Ok((None, backup));
+                }
+                // The backup goes to our own folder, so only the write itself needs the rights.
+                let inverse = if elevate {
+                    access::write_elevated(platform::native(), &db_path, &target)?
+                } else {
+                    match db::apply_patch(&db_path, &target) {
+                        Err(MyError::Permission(_)) if platform::native().can_elevate() => {
+                            access::write_elevated(platform::native(), &db_path, &target)?
+                        }
+                        result => result?,
+                    }
+                };
+                if let Some(inverse) = &inverse {
+                    history::record_write(&StatusEvent::Written { label: label.clone(), keys: inverse.len() }, &target, inverse);
+                }
+                Ok((inverse, backup))
+            })();
+            if result.is_ok() {
+                if let Some((_, previous)) = previous.take() {
+                    if let Err(err) = staged::discard(previous) {
+                        tracing::warn!(%err, "could not remove the staged clone");
+                    }
+                }
+            }
+            WriteOutcome { label, patch, undo, result, queued, previous, finishing: false }
+        });
+        self.pending_write = Some(task);
+    }
+
+    fn poll_write(&mut self) {
+        let Some(rx) = &self.pending_write else {
+            return;
+        };
+        let outcome = match rx.try_recv() {
+            Ok(outcome) => outcome,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.pending_write = None;
+                self.notify(ToastLevel::Error, "The write was interrupted.");
+                return;
+            }
+        };
+        self.pending_write = None;
+
+        let label = outcome.label;
+        match outcome.result {
+            Ok((inverse, backup)) => {
+                if let Some(backup) = backup {
+                    self.count(Event::Backup);
+                    self.report_from(Source::Backup, StatusEvent::BackedUp { path: backup });
+                    self.prune_backups();
+                }
+                if let Some(queued) = outcome.queued {
+                    self.report(StatusEvent::Queued { label: label.clone() });
+                    self.staged = Some((label, queued));
+                    return;
+                }
+                match inverse {
+                    Some(inverse) => self.written(label, &outcome.patch, inverse, outcome.undo),
+                    None => {
+                        self.drop_from_draft(&outcome.patch);
+                        self.report(StatusEvent::AlreadySet { label });
+                    }
+                }
+                self.refresh();
+                self.sync_game_user_settings();
+            }
+            Err(err) => {
+                if matches!(err, MyError::Permission(_)) && !self.cfa_blocked {
+                    self.cfa_due = true;
+                }
+                self.report(StatusEvent::Failed { error: err.to_string() });
+                if let Some(previous) = outcome.previous {
+                    self.staged = Some(previous);
+                }
+                if outcome.undo {
+                    self.undo_stack.push((label, outcome.patch));
+                } else if outcome.finishing && self.confirm_write.is_none() {
+                    // The clone is gone, but the change is not lost: offer to write it again.
+                    self.confirm_write = Some(PendingWrite { label, patch: outcome.patch, undo: false });
+                }
+            }
+        }
+    }
+
+    /// Follows up on a write of ours that changed the file; `inverse` holds the values it replaced.
+    fn written(&mut self, label: String, patch: &Patch, inverse: Patch, undo: bool) {
+        if watch::follow_write(&mut self.config.watched, patch) {
+            self.save_config();
+        }
+        self.count(Event::Patch);
+        self.drop_from_draft(patch);
+        self.report(StatusEvent::Written { label: label.clone(), keys: inverse.len() });
+        if let Some(fps) = patch.get(&keymap::active().frame_rate).and_then(Value::as_i64) {
+            let unlocked = !frame_rate::MENU_LIMITS.contains(&fps);
+            self.read_back = (self.config.read_back_after_launch && unlocked && !sample::is_sample(&self.db_path)).then_some((fps, None));
+            let driver_fps = u32::try_from(fps).ok().filter(|&fps| fps > 0);
+            if self.config.nvidia_frame_limit && driver_fps.is_some() && self.driver_fps != Some(driver_fps) {
+                self.sync_driver_limit(driver_fps);
+            }
+            self.offer_rtss(fps);
+        }
+        // Undoing is not itself undoable.
+        if !undo {
+            self.undo_stack.push((label, inverse));
+        }
+    }
+
+    /// Forgets graphics edits now in the file; ones changed since the write was asked for stay.
+    fn drop_from_draft(&mut self, patch: &Patch) {
+        self.graphics_draft.retain(|key, value| patch.get(key) != Some(value));
+    }
+
+    /// Writes the queued change now that the game has exited, in the background and after any
+    /// write already running; the outcome comes back through `poll_write`.
+    fn finish_staged(&mut self, ctx: &egui::Context) {
+        if !self.finish_due || self.pending_write.is_some() {
+            return;
+        }
+        self.finish_due = false;
+        let Some((label, queued)) = self.staged.take() else {
+            return;
+        };
+        let task = tasks::spawn_once(ctx, "finish staged write", move || {
+            let event_label = label.clone();
+            let patch = queued.patch.clone();
+            let cloned_inverse = queued.inverse.clone();
+            let result = staged::finish(queued, platform::native()).map(|finish| {
+                let inverse = match finish {
+                    Finish::Swapped => Some(cloned_inverse),
+                    Finish::Reapplied(inverse) => inverse,
+                };
+                if let Some(inverse) = &inverse {
+                    history::record_write(&StatusEvent::Written { label: event_label, keys: inverse.len() }, &patch, inverse);
+                }
+                (inverse, None)
+            });
+            WriteOutcome { label, patch, undo: false, result, queued: None, previous: None, finishing: true }
+        });
+        self.pending_write = Some(task);
+    }
+
+    fn discard_staged(&mut self) {
+        if let Some((label, queued)) = self.staged.take() {
+            if let Err(err) = staged::discard(queued) {
+                tracing::warn!(%err, "could not remove the staged clone");
+            }
+            self.notify(ToastLevel::Info, format!("Dropped the queued change: {}.", label));
+        }
+    }
+
+    fn poll_scan(&mut self) {
+        let Some(rx) = &self.scan else {
+            return;
+        };
+        let mut finished = None;
+        for update in rx.try_iter() {
+            match update {
+                ScanUpdate::Progress { done, total, current } => self.scan_progress = Some((done, total, current)),
+                ScanUpdate::Finished(found) => finished = Some(found),
+            }
+        }
+        let Some(found) = finished else {
+            return;
+        };
+        self.scan = None;
+        self.scan_progress = None;
+        if found.is_empty() {
+            self.notify(ToastLevel::Warning, "Drive scan finished without finding LocalStorage.db.");
+        } else {
+            self.notify(ToastLevel::Success, format!("Drive scan found {} install(s).", found.len()));
+        }
+        self.scan_results = found;
+    }
+
+    /// Hands over to the downloaded program and closes once the update is downloaded and verified.
+    fn poll_update_download(&mut self, frame: &mut Frame) {
+        let Some(rx) = &self.update_download else {
+            return;
+        };
+        let mut finished = None;
+        for update in rx.try_iter() {
+            match update {
+                DownloadUpdate::Progress(done, total) => self.update_progress = Some((done, total)),
+                DownloadUpdate::Finished(result) => finished = Some(result),
+            }
+        }
+        let Some(result) = finished else {
+            return;
+        };
+        self.update_download = None;
+        self.update_progress = None;
+        match result.and_then(|path| update::start_replace(&path)) {
+            Ok(()) => {
+                handoff::save(&self.intent());
+                self.close_confirmed = true;
+                frame.close();
+            }
+            Err(err) => self.notify(ToastLevel::Error, format!("Error installing the update: {}", err)),
+        }
+    }
+
+    /// What to resume after a restart; see `handoff`.
+    fn intent(&self) -> Intent {
+        let queued = self.confirm_write.as_ref().filter(|write| !write.undo);
+        Intent {
+            db_path: self.db_path.clone(),
+            draft: self.graphics_draft.clone(),
+            queued: queued.map(|write| QueuedWrite { label: write.label.clone(), patch: write.patch.clone() }),
+            staged: self.staged.as_ref().map(|(label, staged)| QueuedWrite { label: label.clone(), patch: staged.patch.clone() }),
+        }
+    }
+
+    /// Picks up where the instance before a restart left off: the same file, the unapplied edits
+    /// and the writes that were waiting, which go through the usual checks again.
+    fn resume_intent(&mut self) {
+        let Some(intent) = handoff::take() else {
+            return;
+        };
+        if !intent.db_path.is_empty() && Path::new(&intent.db_path).is_file() {
+            self.load_db(intent.db_path);
+        }
+        if self.db_path.is_empty() {
+            return;
+        }
+        self.graphics_draft = intent.draft;
+        self.notify(ToastLevel::Info, "Picked up where you left off before the restart.");
+        // The queued write was the older one, so the confirmed-later one goes on top of it.
+        let writes: Vec<QueuedWrite> = intent.staged.into_iter().chain(intent.queued).collect();
+        let label = writes.iter().map(|write| write.label.as_str()).collect::<Vec<_>>().join("; ");
+        let patch: Patch = writes.into_iter().flat_map(|write| write.patch).collect();
+        if !patch.is_empty() {
+            self.resumed_write = Some(PendingWrite { label, patch, undo: false });
+        }
+    }
+
+    /// Requests the write carried over a restart once the game has been checked for.
+    fn request_resumed_write(&mut self, ctx: &egui::Context) {
+        if self.game_checked.is_none() {
+            return;
+        }
+        if let Some(write) = self.resumed_write.take() {
+            self.request_write(ctx, write);
+        }
+    }
+
+    /// Asks before closing drops a write queued for when the game exits.
+    fn show_confirm_close(&mut self, ctx: &egui::Context, frame: &mut Frame) {
+        if !self.confirm_close {
+            return;
+        }
+        let Some((label, _)) = &self.staged else {
+            self.confirm_close = false;
+            return;
+        };
+        let mut answer = None;
+        egui::Window::new("Drop the queued change?").collapsible(false).resizable(false).show(ctx, |ui| {
+            ui.label(format!("\"{}\" is queued for when the game exits. Closing WuWa Ploom now drops it.", label));
+            ui.horizontal(|ui| {
+                if ui.button("Close anyway").clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Keep open").clicked() {
+                    answer = Some(false);
+                }
+            });
+        });
+        let Some(close) = answer else {
+            return;
+        };
+        self.confirm_close = false;
+        if close {
+            self.close_confirmed = true;
+            frame.close();
+        }
+    }
+
+    fn undo(&mut self, ctx: &egui::Context) {
+        let Some((label, patch)) = self.undo_stack.pop() else {
+            self.notify(ToastLevel::Info, "Nothing to undo.");
+            return;
+        };
+        // Failed or blocked undos put the entry back; see request_write and poll_write.
+        self.request_write(ctx, PendingWrite { label, patch, undo: true });
+    }
+
+    fn handle_profile(&mut self, action: ProfileAction) {
+        match action {
+            ProfileAction::Save => {
+                let Some(setting) = &self.quality_setting else {
+                    return;
+                };
+                match profiles::save(&self.profile_name, setting) {
+                    Ok(path) => {
+                        self.notify(ToastLevel::Success, format!("Saved profile to {}", path.display()));
+                        self.profile_name.clear();
+                    }
+                    Err(err) => self.notify(ToastLevel::Error, format!("Error saving profile: {}", err)),
+                }
+            }
+            ProfileAction::Restore(name) => match profiles::load(&name) {
+                Ok(preset) => self.preset_review = Some(PresetReview::new(preset, &self.metadata, self.game_version.as_deref())),
+                Err(err) => self.notify(ToastLevel::Error, format!("Error reading profile {}: {}", name, err)),
+            },
+            ProfileAction::Delete(name) => {
+                if let Err(err) = profiles::delete(&name) {
+                    self.notify(ToastLevel::Error, format!("Error deleting profile {}: {}", name, err));
+                }
+            }
+            ProfileAction::Resolve(conflict, keep) => match profiles::resolve(&conflict, &keep) {
+                Ok(()) => self.notify(ToastLevel::Success, format!("Kept one version of profile {}.", conflict.name)),
+                Err(err) => self.notify(ToastLevel::Error, format!("Error resolving profile {}: {}", conflict.name, err)),
+            },
+        }
+        self.profile_names = None;
+        self.profile_conflicts = None;
+    }
+
+    fn show_preset_review(&mut self, ctx: &egui::Context) {
+        let (Some(review), Some(setting)) = (&mut self.preset_review, &self.quality_setting) else {
+            return;
+        };
+        match review.show(ctx, &self.metadata, setting, self.game_version.as_deref()) {
+            ReviewOutcome::Open => {}
+            ReviewOutcome::Stage(patch) => {
+                let count = patch.len();
+                self.graphics_draft.extend(patch);
+                self.preset_review = None;
+                self.tab = Tab::Graphics;
+                self.notify(ToastLevel::Info, format!("Staged {} key(s) from the preset; review and apply.", count));
+            }
+            ReviewOutcome::Close => self.preset_review = None,
+        }
+    }
+
+    fn copy_share_code(&mut self, ctx: &egui::Context) {
+        let Some(setting) = self.quality_setting.as_ref().and_then(Value::as_object) else {
+            return;
+        };
+        let frame_rate = keymap::active().frame_rate;
+        let shared: serde_json::Map<String, Value> = setting
+            .iter()
+            .filter(|(key, _)| {
+                **key == frame_rate
+                    || self.metadata.get(key).is_some_and(|meta| matches!(meta.category, Category::Graphics | Category::Display))
+            })
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        match presets::from_setting("Shared settings", &Value::Object(shared)).and_then(|preset| presets::to_share_code(&preset)) {
+            Ok(code) => {
+                ctx.output().copied_text = code;
+                self.notify(ToastLevel::Success, "Copied the share code. Paste it anywhere; others import it with Paste share code.");
+            }
+            Err(err) => self.notify(ToastLevel::Error, format!("Error creating the share code: {}", err)),
+        }
+    }
+
+    fn show_paste_share_code(&mut self, ctx: &egui::Context) {
+        let Some(text) = &mut self.share_code else {
+            return;
+        };
+        let mut answer = None;
+        egui::Window::new("Paste share code").collapsible(false).show(ctx, |ui| {
+            ui.label("Paste a share code (it starts with WWP1.) or a preset's JSON. You review every setting before anything is staged.");
+            ui.add(egui::TextEdit::multiline(text).desired_rows(4).desired_width(f32::INFINITY));
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!text.trim().is_empty(), egui::Button::new("Review")).clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    answer = Some(false);
+                }
+            });
+        });
+        let Some(review) = answer else {
+            return;
+        };
+        let Some(text) = self.share_code.take() else {
+            return;
+        };
+        if !review {
+            return;
+        }
+        match presets::from_share_code(&text) {
+            Ok(preset) => self.preset_review = Some(PresetReview::transfer(preset, &self.metadata, self.game_version.as_deref())),
+            Err(err) => {
+                self.notify(ToastLevel::Error, format!("Error reading the share code: {}", err));
+                self.share_code = Some(text);
+            }
+        }
+    }
+
+    fn show_transfer(&mut self, ctx: &egui::Context) {
+        let Some(wizard) = &self.transfer else {
+            return;
+        };
+        let sync_folder = self.config.sync_folder();
+        match wizard.show(ctx, sync_folder.as_deref()) {
+            WizardOutcome::Open => {}
+            WizardOutcome::Export => self.export_transfer(sync_folder),
+            WizardOutcome::Import => self.import_transfer(),
+            WizardOutcome::Close => self.transfer = None,
+        }
+    }
+
+    fn export_transfer(&mut self, sync_folder: Option<PathBuf>) {
+        let Some(setting) = &self.quality_setting else {
+            self.notify(ToastLevel::Warning, "Load a configuration file on the Patcher tab first.");
+            return;
+        };
+        let mut dialog = FileDialog::new().add_filter("Settings", &["json"]).set_file_name(&format!("wuwa-settings-{}.json", presets::machine_name()));
+        if let Some(folder) = sync_folder {
+            dialog = dialog.set_directory(folder);
+        }
+        let Some(path) = dialog.save_file() else {
+            return;
+        };
+        match presets::export_transfer(&path, setting) {
+            Ok(()) => self.transfer = Some(TransferWizard::Exported(path)),
+            Err(err) => self.notify(ToastLevel::Error, format!("Error exporting to {}: {}", path.display(), err)),
+        }
+    }
+
+    fn import_transfer(&mut self) {
+        if self.quality_setting.is_none() {
+            self.notify(ToastLevel::Warning, "Load a configuration file on the Patcher tab first.");
+            return;
+        }
+        let mut dialog = FileDialog::new().add_filter("Settings", &["json"]);
+        if let Some(folder) = self.config.sync_folder() {
+            dialog = dialog.set_directory(folder);
+        }
+        let Some(path) = dialog.pick_file() else {
+            return;
+        };
+        match presets::read_preset(&path) {
+            Ok(preset) => {
+                self.preset_review = Some(PresetReview::transfer(preset, &self.metadata, self.game_version.as_deref()));
+                self.transfer = None;
+            }
+            Err(err) => self.notify(ToastLevel::Error, format!("Error importing {}: {}", path.display(), err)),
+        }
+    }
+
+    fn show_confirm_write(&mut self, ctx: &egui::Context) {
+        let Some(write) = &self.confirm_write else {
+            return;
+        };
+        let mut answer = None;
+        egui::Window::new("Confirm write").collapsible(false).resizable(false).show(ctx, |ui| {
+            let title = if write.undo { format!("Undo: {}", write.label) } else { write.label.clone() };
+            ui.strong(title);
+            let setting = self.quality_setting.as_ref().unwrap_or(&Value::Null);
+            egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                diff_view::show(ui, "confirm_write", &json_diff::patch_diff(setting, &write.patch), false);
+            });
+            ui.label("in:");
+            ui.label(&self.db_path);
+            ui.horizontal(|ui| {
+                if ui.button("Write").clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    answer = Some(false);
+                }
+            });
+        });
+        let Some(confirmed) = answer else {
+            return;
+        };
+        let Some(write) = self.confirm_write.take() else {
+            return;
+        };
+        if confirmed {
+            self.start_write(ctx, write);
+        } else if write.undo {
+            self.undo_stack.push((write.label, write.patch));
+        }
+    }
+
+    /// Asks whether RTSS should limit the game to `fps` too, if the user opted in and RTSS is
+    /// installed with a different limit.
+    fn offer_rtss(&mut self, fps: i64) {
+        let Some(&exe) = games::active().process_names().first() else {
+            return;
+        };
+        if !self.config.rtss_profile || fps <= 0 {
+            return;
+        }
+        let Some(install) = rtss::find_install(platform::native()) else {
+            self.notify(ToastLevel::Info, "RivaTuner Statistics Server was not found, so no RTSS limit was set.");
+            return;
+        };
+        let current = rtss::current_limit(&install, exe);
+        if current != Some(fps) {
+            self.confirm_rtss = Some(RtssOffer { install, exe, fps, current });
+        }
+    }
+
+    fn show_confirm_rtss(&mut self, ctx: &egui::Context) {
+        let Some(offer) = &self.confirm_rtss else {
+            return;
+        };
+        let mut answer = None;
+        egui::Window::new("Set the RTSS limit too?").collapsible(false).resizable(false).show(ctx, |ui| {
+            ui.label(format!("Limit {} to {} FPS in RivaTuner Statistics Server?", offer.exe, offer.fps));
+            match offer.current {
+                Some(current) => ui.label(format!("Its RTSS profile limits it to {} FPS now.", current)),
+                None => ui.label("RTSS does not limit it now."),
+            };
+            ui.weak(rtss::profile_path(&offer.install, offer.exe).display().to_string());
+            ui.horizontal(|ui| {
+                if ui.button("Write profile").clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Not now").clicked() {
+                    answer = Some(false);
+                }
+            });
+        });
+        let Some(confirmed) = answer else {
+            return;
+        };
+        let Some(offer) = self.confirm_rtss.take() else {
+            return;
+        };
+        if !confirmed {
+            return;
+        }
+        match rtss::write_limit(&offer.install, offer.exe, offer.fps) {
+            Ok(_) => self.notify(ToastLevel::Success, format!("RTSS limit for {} set to {} FPS.", offer.exe, offer.fps)),
+            Err(err) => self.notify(ToastLevel::Error, format!("Error writing the RTSS profile: {}", err)),
+        }
+    }
+
+    fn set_autostart(&mut self, enabled: bool) {
+        let result = env::current_exe().map_err(MyError::from).and_then(|exe| autostart::set(platform::native(), &exe, enabled));
+        match result {
+            Ok(()) if enabled => self.notify(ToastLevel::Success, "WuWa Ploom now starts minimized with Windows."),
+            Ok(()) => self.notify(ToastLevel::Info, "WuWa Ploom no longer starts with Windows."),
+            Err(err) => self.notify(ToastLevel::Error, format!("Could not change Start with Windows: {}", err)),
+        }
+        self.autostart = read_autostart();
+    }
+
+    fn show_settings(&mut self, ctx: &egui::Context) {
+        let Some(draft) = &mut self.settings_draft else {
+            return;
+        };
+        match settings_window::show(ctx, draft, self.autostart.as_ref()) {
+            SettingsOutcome::Open => {}
+            SettingsOutcome::StartWithWindows(enabled) => self.set_autostart(enabled),
+            SettingsOutcome::Cancel => self.settings_draft = None,
+            SettingsOutcome::Save => {
+                if let Some(draft) = self.settings_draft.take() {
+                    let switched = draft.game != self.config.game;
+                    let driver_sync_toggled = draft.nvidia_frame_limit != self.config.nvidia_frame_limit;
+                    if draft.sync_folder != self.config.sync_folder {
+                        let previous = profiles::profiles_dir();
+                        profiles::set_sync_folder(draft.sync_folder());
+                        match profiles::copy_missing(&previous, &profiles::profiles_dir()) {
+                            Ok(0) => {}
+                            Ok(copied) => self.notify(ToastLevel::Success, format!("Copied {} profile(s) to the new folder.", copied)),
+                            Err(err) => self.notify(ToastLevel::Error, format!("Error copying profiles: {}", err)),
+                        }
+                        self.profile_names = None;
+                        self.profile_conflicts = None;
+                    }
+                    self.config = draft;
+                    if switched {
+                        self.switch_game();
+                    }
+                    if driver_sync_toggled {
+                        self.read_driver_limit();
+                    }
+                    apply_theme(ctx, self.config.theme);
+                    apply_scale(ctx, self.config.large_ui, self.native_scale);
+                    self.save_config();
+                }
+            }
+        }
+    }
+
+    /// Makes the configured game active and forgets the file loaded for the previous one.
+    fn switch_game(&mut self) {
+        games::set_active(&self.config.game);
+        self.db_path.clear();
+        self.current_fps = None;
+        self.quality_setting = None;
+        self.write_access = None;
+        self.undo_stack.clear();
+        self.graphics_draft.clear();
+        self.game_watcher = GameWatcher::new();
+        if !games::active().has_quality_setting() {
+            self.tab = Tab::Keys;
+        }
+        self.notify(ToastLevel::Info, format!("Now patching {}; locate or browse for its configuration file.", games::active().name()));
+    }
+
+    fn save_config(&mut self) {
+        if let Err(err) = self.config.save() {
+            self.notify(ToastLevel::Error, format!("Error saving settings: {}", err));
+        }
+    }
+
+    /// Collects the release notes since the version last run and records this one.
+    fn note_version(&mut self) {
+        let current = env!("CARGO_PKG_VERSION");
+        if self.config.last_seen_version == current {
+            return;
+        }
+        // A first start has nothing to compare with.
+        if !self.config.last_seen_version.is_empty() {
+            let releases = changelog::releases();
+            self.whats_new = changelog::since(&releases, &self.config.last_seen_version, current).into_iter().cloned().collect();
+        }
+        self.config.last_seen_version = current.to_string();
+        self.save_config();
+    }
+
+    /// Warns when a `keep` watchdog gave up after repeated crashes in the last day, since the
+    /// keys it kept are no longer protected.
+    fn note_stopped_watchdog(&mut self) {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let now = SystemTime::now();
+        self.watchdog_stopped = crash_loop::logged(crash_loop::STOP_MARKER).into_iter().any(|time| now.duration_since(time).is_ok_and(|age| age <= day));
+        if self.watchdog_stopped {
+            let log = crash_loop::current_log().unwrap_or_else(logging::log_dir);
+            let text = format!("The keep watchdog crashed repeatedly and stopped, so kept keys are no longer re-applied. See {}", log.display());
+            self.notify_from(Source::Watchdog, ToastLevel::Warning, text);
+        }
+    }
+
+    /// One-off work for the first frame, once the native window exists.
+    fn on_launch(&mut self, ctx: &egui::Context) {
+        self.launched = true;
+        cosmetic::apply_window_icon();
+        if !games::active().has_quality_setting() {
+            self.tab = Tab::Keys;
+        }
+        self.note_version();
+        self.note_stopped_watchdog();
+        self.prune_backups();
+        update::remove_downloads(&update::downloads_dir());
+        if let Some(text) = update::take_failure() {
+            self.notify(ToastLevel::Error, text);
+        }
+        let notes: Vec<(bool, String)> = self.metadata.notes().iter().map(|note| (note.loaded, note.text.clone())).collect();
+        for (loaded, text) in notes {
+            self.notify(if loaded { ToastLevel::Info } else { ToastLevel::Warning }, text);
+        }
+        if self.config.start_minimized || self.launched_minimized {
+            platform::native().minimize_window(WINDOW_TITLE);
+        }
+        self.game_version = detect::get_game_version();
+        self.resume_intent();
+        if self.config.auto_detect_on_launch && self.db_path.is_empty() {
+            if let Ok(path) = detect::get_game_install_path() {
+                self.load_db(path);
+            }
+        }
+        if self.config.check_updates {
+            self.update_check = Some(tasks::spawn_once(ctx, "update-check", update::check_latest));
+        }
+    }
 
-fn set_console_title(title: &str) {
-    let wide: Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
-    unsafe {
-        SetConsoleTitleW(wide.as_ptr());
+    fn poll_update_check(&mut self) {
+        let Some(rx) = &self.update_check else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.update_check = None;
+                // A failed check is logged but not worth interrupting the user for.
+                self.available_update = result.ok().flatten();
+                if let Some(release) = &self.available_update {
+                    let text = format!("Version {} of WuWa Ploom is available.", release.version());
+                    self.notify_from(Source::Updater, ToastLevel::Info, text);
+                }
+            }
+            Err(TryRecvError::Empty) => {}
+            Err(TryRecvError::Disconnected) => self.update_check = None,
+        }
     }
-}
 
-fn get_hwnd(title: &str) -> HWND {
-    let wide_title: Vec<u16> = OsStr::new(title).encode_wide().chain(Some(0)).collect();
-    unsafe {
-        FindWindowW(ptr::null(), wide_title.as_ptr())
+    fn set_compact(&mut self, frame: &mut Frame, compact: bool) {
+        if compact == self.compact {
+            return;
+        }
+        if compact {
+            self.full_size = Some(frame.info().window_info.size);
+            let title_bar = if self.frameless { TITLE_BAR_HEIGHT } else { 0.0 };
+            frame.set_window_size(COMPACT_WINDOW_SIZE + egui::vec2(0.0, title_bar));
+        } else if let Some(size) = self.full_size.take() {
+            frame.set_window_size(size);
+        }
+        frame.set_always_on_top(compact);
+        self.compact = compact;
+    }
+
+    /// Asks Defender whether Controlled Folder Access stops us writing the file, in the
+    /// background: the query starts PowerShell and can take seconds.
+    fn poll_cfa(&mut self, ctx: &egui::Context) {
+        if let Some(task) = &self.cfa_check {
+            match task.try_recv() {
+                Ok(blocked) => {
+                    self.cfa_check = None;
+                    self.cfa_blocked = blocked;
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Disconnected) => self.cfa_check = None,
+            }
+            return;
+        }
+        if !std::mem::take(&mut self.cfa_due) || self.db_path.is_empty() {
+            return;
+        }
+        let db_path = PathBuf::from(&self.db_path);
+        self.cfa_check = Some(tasks::spawn_once(ctx, "check defender", move || defender::blocks_us(&db_path)));
     }
-}
 
-fn remove_window_icon(hwnd: HWND) {
-    let style = unsafe { GetWindowLongW(hwnd, GWL_STYLE) };
-    let new_style = style & !(WS_SYSMENU as i32 | WS_MINIMIZEBOX as i32);
-    unsafe {
-        SetWindowLongW(hwnd, GWL_STYLE, new_style);
+    /// Runs the setup checks in the background; they query processes, GPUs and Defender.
+    fn diagnose(&mut self, ctx: &egui::Context) {
+        if self.diagnosing.is_some() {
+            return;
+        }
+        let db_path = self.db_path.clone();
+        self.diagnosing = Some(tasks::spawn_once(ctx, "diagnose", move || diagnostics::run_checks(&db_path)));
+    }
+
+    fn poll_diagnostics(&mut self) {
+        let Some(task) = &self.diagnosing else {
+            return;
+        };
+        self.diagnostics = match task.try_recv() {
+            Ok(results) => results,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.diagnosing = None;
+                return;
+            }
+        };
+        self.diagnosing = None;
+        let failed = self.diagnostics.iter().filter(|result| result.status == CheckStatus::Fail).count();
+        if failed == 0 {
+            self.notify(ToastLevel::Success, "All setup checks passed.");
+        } else {
+            self.notify(ToastLevel::Warning, format!("Setup check found {} problem(s).", failed));
+        }
     }
-}
 
-fn load_icon(path: &str) -> HICON {
-    let wide_path: Vec<u16> = OsStr::new(path).encode_wide().chain(Some(0)).collect();
-    unsafe {
-        LoadImageW(
-            ptr::null_mut(),
-            wide_path.as_ptr(),
-            IMAGE_ICON,
-            0,
-            0,
-            LR_DEFAULTSIZE | LR_LOADFROMFILE,
-        ) as HICON
+    fn troubleshoot(&mut self, symptom: Symptom) {
+        let detected = detect::get_game_install_path().ok();
+        let observed = troubleshoot::Observed {
+            db_path: &self.db_path,
+            detected: detected.as_deref(),
+            setting: self.quality_setting.as_ref(),
+            game_running: self.game_running,
+            write_access: self.write_access.as_ref(),
+            cfa_blocked: self.cfa_blocked,
+            watched: &self.config.watched,
+        };
+        self.troubleshooting = Some((symptom, troubleshoot::run(symptom, &observed)));
+    }
+
+    fn apply_fix(&mut self, fix: Fix, ctx: &egui::Context, frame: &mut Frame) {
+        match fix {
+            Fix::TurnOffVsync => {
+                let patch = Patch::from_iter([(keymap::active().vsync, Value::from(0))]);
+                self.request_write(ctx, PendingWrite { label: "Turned VSync off".into(), patch, undo: false });
+            }
+            Fix::KeepFrameRate => {
+                let Some(fps) = self.current_fps else {
+                    return;
+                };
+                let key = keymap::active().frame_rate;
+                self.config.watched.retain(|watched| watched.key != key);
+                self.config.watched.push(WatchedKey { key, value: Value::from(fps), reassert: true });
+                self.save_config();
+                self.notify(ToastLevel::Success, format!("The FPS limit of {} is now written back whenever the game changes it.", fps));
+            }
+            Fix::SyncGameUserSettings => self.tab = Tab::ConfigTweaks,
+            Fix::LauncherHooks => self.tab = Tab::Launchers,
+            Fix::CloseGame => self.confirm_close_game = true,
+            Fix::AllowInDefender => self.allow_in_defender(),
+            Fix::Locate => self.perform(Action::Locate, ctx, frame),
+        }
+    }
+
+    fn perform(&mut self, action: Action, ctx: &egui::Context, frame: &mut Frame) {
+        match action {
+            Action::Locate => match detect::get_game_install_path() {
+                Ok(path) => self.load_db(path),
+                Err(err) => self.notify(ToastLevel::Error, format!("Error locating game: {}", err)),
+            },
+            Action::ScanDrives => {
+                if self.scan.is_none() {
+                    self.scan_results.clear();
+                    self.scan = Some(scan::spawn_scan(ctx));
+                }
+            }
+            Action::CancelScan => {
+                // Dropping the task stops the worker at its next folder.
+                self.scan = None;
+                self.scan_progress = None;
+                self.notify(ToastLevel::Info, "Drive scan cancelled.");
+            }
+            Action::Browse => {
+                if let Some(path) = FileDialog::new().pick_file() {
+                    self.load_db(path.display().to_string());
+                }
+            }
+            Action::DiscardStaged => self.discard_staged(),
+            Action::TrySample => match sample::reset() {
+                Ok(path) => {
+                    self.load_db(path);
+                    self.notify(ToastLevel::Info, "Loaded a sample configuration file. Try anything; the game's own file is not touched.");
+                }
+                Err(err) => self.notify(ToastLevel::Error, format!("Could not create the sample: {}", err)),
+            },
+            Action::ApplyFps(fps) => self.request_fps(ctx, fps),
+            Action::ApplyGraphics => {
+                let patch = self.graphics_draft.clone();
+                // The draft stays until the write succeeds; see `written`.
+                let write = PendingWrite { label: format!("Updated {} graphics setting(s)", patch.len()), patch, undo: false };
+                self.request_write(ctx, write);
+            }
+            Action::ImportPreset => {
+                let Some(path) = FileDialog::new().add_filter("Preset", &["json"]).pick_file() else {
+                    return;
+                };
+                match presets::read_preset(&path) {
+                    Ok(preset) => {
+                        self.preset_review = Some(PresetReview::new(preset, &self.metadata, self.game_version.as_deref()));
+                    }
+                    Err(err) => self.notify(ToastLevel::Error, format!("Error importing {}: {}", path.display(), err)),
+                }
+            }
+            Action::CopyShareCode => self.copy_share_code(ctx),
+            Action::PasteShareCode => self.share_code = Some(String::new()),
+            Action::MoveSettings => self.transfer = Some(TransferWizard::default()),
+            Action::Undo => self.undo(ctx),
+            Action::Refresh => self.refresh(),
+            Action::ClearDriverLimit => self.sync_driver_limit(None),
+            Action::SwitchRefreshRate(hz) => self.switch_refresh_rate(hz),
+            Action::VerifyUnlock => self.verify_unlock(ctx),
+            Action::SwitchPowerPlan => self.switch_power_plan(),
+            Action::AllowInDefender => self.allow_in_defender(),
+            Action::CopyDefenderCommand => {
+                if let Ok(exe) = env::current_exe() {
+                    ctx.output().copied_text = defender::allow_command(&exe);
+                    self.notify(ToastLevel::Success, "Copied. Run it in PowerShell as administrator.");
+                }
+            }
+            Action::RestorePowerPlan => self.restore_power_plan(),
+            Action::RestoreDisplay => self.restore_display(),
+            Action::SetCompact(compact) => self.set_compact(frame, compact),
+            Action::OpenSettings => {
+                self.settings_draft = Some(self.config.clone());
+                self.autostart = read_autostart();
+            }
+            Action::Diagnose => self.diagnose(ctx),
+            Action::Troubleshoot(symptom) => self.troubleshoot(symptom),
+            Action::SyncInstalls(index) => self.sync_installs(ctx, index),
+            Action::Relocate => self.relocate(),
+            Action::ApplyFix(fix) => {
+                self.apply_fix(fix, ctx, frame);
+                if let Some((symptom, _)) = self.troubleshooting {
+                    self.troubleshoot(symptom);
+                }
+            }
+            Action::CopyReport => self.copy_report(ctx),
+            Action::SaveReport => self.save_report(),
+            Action::CopyInspection => self.copy_inspection(ctx),
+            Action::SaveInspection => self.save_inspection(),
+            Action::ReviewDeckProfile => {
+                if let Some(model) = self.deck {
+                    self.preset_review = Some(PresetReview::new(model.quality_preset(), &self.metadata, self.game_version.as_deref()));
+                }
+            }
+        }
+    }
+
+    fn handle_args(&mut self, args: &[String]) {
+        if let Some(path) = args.iter().find(|arg| !arg.starts_with('-')) {
+            self.load_db(path.clone());
+        }
     }
-}
 
-fn set_window_icon(hwnd: HWND, icon: HICON) {
-    unsafe {
-        SetClassLongPtrW(hwnd, GCLP_HICON as i32, icon as isize);
-        SetClassLongPtrW(hwnd, GCLP_HICONSM as i32, icon as isize);
+    fn poll_forwarded_args(&mut self) {
+        let forwarded: Vec<Vec<String>> = match &self.forwarded_args {
+            Some(rx) => rx.try_iter().collect(),
+            None => return,
+        };
+        for args in forwarded {
+            self.handle_args(&args);
+        }
     }
 }
 
-fn get_game_install_path() -> Result<String> {
-    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-    let game_key_path = "SOFTWARE\\WOW6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\KRInstall Wuthering Waves Overseas";
-    let game_key = hklm.open_subkey(game_key_path).map_err(|_| MyError::RegistryError)?;
-    let install_path: String = game_key.get_value("InstallPath").map_err(|_| MyError::RegistryError)?;
-    let full_path = format!("{}\\Wuthering Waves Game\\Client\\Saved\\LocalStorage\\LocalStorage.db", install_path);
-    Ok(full_path)
+/// Where the Run key exists, what it says about this copy of the app.
+fn read_autostart() -> Option<autostart::State> {
+    let exe = env::current_exe().ok().filter(|_| cfg!(windows))?;
+    Some(autostart::state(platform::native(), &exe))
 }
 
-fn file_exists(path: &str) -> Result<()> {
-    if fs::metadata(path).is_err() {
-        Err(MyError::FileNotFoundError(path.to_string()))
+fn shortcut_action(ctx: &egui::Context, presets: &[i64]) -> Option<Action> {
+    let input = ctx.input();
+    if input.key_pressed(Key::F5) {
+        return Some(Action::Refresh);
+    }
+    if !input.modifiers.ctrl {
+        return None;
+    }
+    if input.key_pressed(Key::L) {
+        Some(Action::Locate)
+    } else if input.key_pressed(Key::O) {
+        Some(Action::Browse)
+    } else if let Some(fps) = PRESET_KEYS.iter().zip(presets).find_map(|(key, fps)| input.key_pressed(*key).then_some(*fps)) {
+        Some(Action::ApplyFps(fps))
+    } else if input.key_pressed(Key::Z) {
+        Some(Action::Undo)
     } else {
-        Ok(())
+        None
     }
 }
 
-fn read_game_quality_setting(conn: &Connection) -> Result<Value> {
-    let mut stmt = conn.prepare("SELECT value FROM LocalStorage WHERE key = 'GameQualitySetting';")?;
-    let mut rows = stmt.query([])?;
+/// Lists the shortcuts in a corner overlay while Ctrl is held.
+fn show_shortcut_hints(ctx: &egui::Context, presets: &[i64]) {
+    if !ctx.input().modifiers.ctrl {
+        return;
+    }
+    egui::Area::new("shortcut_hints")
+        .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                egui::Grid::new("shortcut_hints_grid").show(ui, |ui| {
+                    for (keys, description) in SHORTCUT_HINTS {
+                        ui.strong(*keys);
+                        ui.label(*description);
+                        ui.end_row();
+                    }
+                    for (index, fps) in presets.iter().take(PRESET_KEYS.len()).enumerate() {
+                        ui.strong(format!("Ctrl+{}", index + 1));
+                        ui.label(format!("Set FPS to {}", fps));
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+}
 
-    let game_quality_setting_json: String = rows.next()?.ok_or(rusqlite::Error::QueryReturnedNoRows)?.get(0)?;
-    let game_quality_setting: Value = serde_json::from_str(&game_quality_setting_json)?;
-    Ok(game_quality_setting)
+fn dropped_file(ctx: &egui::Context) -> Option<String> {
+    ctx.input()
+        .raw
+        .dropped_files
+        .iter()
+        .find_map(|file| file.path.as_ref())
+        .map(|path| path.display().to_string())
 }
 
-fn read_current_fps_setting(db_path: &str) -> Result<i64> {
-    file_exists(db_path)?;
-    let conn = Connection::open(db_path)?;
-    let game_quality_setting = read_game_quality_setting(&conn)?;
-    let fps_setting = game_quality_setting["KeyCustomFrameRate"]
-        .as_i64()
-        .ok_or_else(|| MyError::SerdeJsonError(SerdeError::custom("KeyCustomFrameRate not found or not an integer")))?;
-    Ok(fps_setting)
+/// Dims the window while files are dragged over it so it is obvious the drop will be picked up.
+fn show_drop_target(ctx: &egui::Context) {
+    if ctx.input().raw.hovered_files.is_empty() {
+        return;
+    }
+    let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("file_drop_target")));
+    let screen_rect = ctx.input().screen_rect();
+    painter.rect_filled(screen_rect, 0.0, egui::Color32::from_black_alpha(192));
+    painter.text(
+        screen_rect.center(),
+        egui::Align2::CENTER_CENTER,
+        "Drop LocalStorage.db or a backup to load it",
+        egui::TextStyle::Heading.resolve(&ctx.style()),
+        egui::Color32::WHITE,
+    );
 }
 
-fn update_game_quality_setting(conn: &Connection, game_quality_setting: Value) -> Result<()> {
-    let updated_game_quality_setting_json = game_quality_setting.to_string();
-    conn.execute(
-        "UPDATE LocalStorage SET value = ?1 WHERE key = 'GameQualitySetting';",
-        params![updated_game_quality_setting_json],
-    )?;
-    Ok(())
+/// Gives a widget a screen-reader name that says what it does, not just what it shows.
+fn describe(response: egui::Response, typ: WidgetType, description: impl ToString) -> egui::Response {
+    let description = description.to_string();
+    response.widget_info(|| WidgetInfo::labeled(typ, &description));
+    response
 }
 
-fn unlock_fps(db_path: &str) -> Result<String> {
-    file_exists(db_path)?;
-    let conn = Connection::open(db_path)?;
-    let mut game_quality_setting = read_game_quality_setting(&conn)?;
+fn show_diagnostics(ui: &mut egui::Ui, results: &[CheckResult]) {
+    egui::Grid::new("diagnostics_grid").striped(true).show(ui, |ui| {
+        for result in results {
+            let text = result.status.label();
+            let status = ui.colored_label(result.status.color(), text);
+            describe(status, WidgetType::Label, format!("{}: {}, {}", result.name, text, result.detail));
+            ui.label(result.name);
+            ui.label(&result.detail);
+            ui.end_row();
+        }
+    });
+}
 
-    if game_quality_setting["KeyCustomFrameRate"] == json!(120) {
-        return Ok("FPS is already set to 120. No need to patch.".into());
+/// Persistent list of every notification shown this session, newest first.
+fn show_history(ui: &mut egui::Ui, toasts: &Toasts, open: &mut bool) {
+    let latest = toasts.history().last();
+    ui.horizontal(|ui| {
+        if let Some(toast) = latest {
+            let label = ui.colored_label(toast.level.color(), &toast.text);
+            describe(label, WidgetType::Label, format!("Latest message: {}", toast.text));
+        }
+        let toggle = if *open { "Hide history" } else { "Show history" };
+        if describe(ui.small_button(toggle), WidgetType::Button, "Toggle the message history").clicked() {
+            *open = !*open;
+        }
+    });
+    if !*open {
+        return;
     }
+    egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+        for toast in toasts.history().iter().rev() {
+            ui.colored_label(toast.level.color(), toasts.format_entry(toast));
+        }
+    });
+}
+
+/// Known keys present in the blob, each with its meaning and valid range on hover.
+fn show_settings_overview(ui: &mut egui::Ui, metadata: &Metadata, setting: &Value) {
+    egui::CollapsingHeader::new("Current settings").show(ui, |ui| {
+        egui::Grid::new("settings_overview").striped(true).show(ui, |ui| {
+            for meta in metadata.keys() {
+                let value = &setting[meta.key.as_str()];
+                if value.is_null() {
+                    continue;
+                }
+                let shown = value
+                    .as_i64()
+                    .and_then(|number| meta.options.iter().find(|option| option.value == number))
+                    .map(|option| format!("{} ({})", option.label, value))
+                    .unwrap_or_else(|| value.to_string());
+                let tooltip = meta.tooltip();
+                ui.label(&meta.label).on_hover_text(&tooltip);
+                let readout = ui.label(&shown).on_hover_text(&tooltip);
+                describe(readout, WidgetType::Label, format!("{}: {}. {}", meta.label, shown, tooltip));
+                ui.weak(&meta.key);
+                ui.end_row();
+            }
+        });
+    });
+}
 
-    game_quality_setting["KeyCustomFrameRate"] = json!(120);
-    update_game_quality_setting(&conn, game_quality_setting)?;
+const TITLE_BAR_HEIGHT: f32 = 28.0;
 
-    Ok("FPS successfully unlocked!".into())
+/// Title bar drawn by egui for the frameless window: drag anywhere on it, minimize or close on the right.
+fn show_title_bar(ctx: &egui::Context, frame: &mut Frame) {
+    egui::TopBottomPanel::top("title_bar").exact_height(TITLE_BAR_HEIGHT).show(ctx, |ui| {
+        let drag_area = ui.interact(ui.max_rect(), egui::Id::new("title_bar_drag"), egui::Sense::click_and_drag());
+        if drag_area.is_pointer_button_down_on() {
+            frame.drag_window();
+        }
+        ui.horizontal_centered(|ui| {
+            ui.strong(WINDOW_TITLE);
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let close = ui.button("🗙").on_hover_text("Close");
+                if describe(close, WidgetType::Button, "Close the window").clicked() {
+                    frame.close();
+                }
+                let minimize = ui.button("🗕").on_hover_text("Minimize");
+                if describe(minimize, WidgetType::Button, "Minimize the window").clicked() {
+                    platform::native().minimize_window(WINDOW_TITLE);
+                }
+            });
+        });
+    });
 }
 
-struct FPSUnlockerApp {
-    db_path: String,
-    status: String,
-    current_fps: Option<i64>,
+const COMPACT_WINDOW_SIZE: egui::Vec2 = egui::vec2(320.0, 64.0);
+
+/// The always-on-top strip: current value, the main preset, and a way back.
+fn show_compact(ctx: &egui::Context, current_fps: Option<i64>, preset: i64, blocked: Option<&str>, action: &mut Option<Action>) {
+    CentralPanel::default().show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            let readout = match current_fps {
+                Some(fps) => format!("FPS: {}", fps),
+                None => "FPS: -".to_string(),
+            };
+            describe(ui.strong(&readout), WidgetType::Label, format!("Current frame-rate limit, {}", readout));
+
+            let apply = ui
+                .add_enabled(blocked.is_none(), egui::Button::new(format!("Apply {}", preset)))
+                .on_hover_text("Ctrl+1")
+                .on_disabled_hover_text(blocked.unwrap_or_default());
+            let description = format!("Write a frame-rate limit of {} FPS to the configuration file", preset);
+            if describe(apply, WidgetType::Button, description).clicked() {
+                *action = Some(Action::ApplyFps(preset));
+            }
+
+            let expand = ui.button("Expand");
+            if describe(expand, WidgetType::Button, "Leave compact mode and show the full window").clicked() {
+                *action = Some(Action::SetCompact(false));
+            }
+        });
+    });
 }
 
-impl Default for FPSUnlockerApp {
-    fn default() -> Self {
-        Self {
-            db_path: String::new(),
-            status: String::new(),
-            current_fps: None,
-        }
-    }
+fn apply_theme(ctx: &egui::Context, theme: Theme) {
+    ctx.set_visuals(match theme {
+        Theme::Dark => egui::Visuals::dark(),
+        Theme::Light => egui::Visuals::light(),
+    });
+}
+
+/// Bigger text and controls for handheld and TV screens; always on inside Gamescope.
+fn apply_scale(ctx: &egui::Context, large_ui: bool, native_scale: Option<f32>) {
+    let scale = native_scale.unwrap_or(1.0);
+    let large = large_ui || deck::in_gamescope();
+    ctx.set_pixels_per_point(if large { scale * LARGE_UI_SCALE } else { scale });
 }
 
+const WINDOW_TITLE: &str = "WuWa Ploom Tools";
 const APP_TITLE: &str = "WuWa Ploom 120 FPS Unlock";
 const INSTRUCTIONS: &str = "
 1) Check and set your FPS limit to 60, then close your game.
 2) Do not touch FPS or VSync options in-game.
-3) You can either automatically find it or browse and choose the file.
+3) You can either automatically find it, browse and choose the file, or drop it on this window.
+Hold Ctrl to see keyboard shortcuts.
 ";
 
 impl App for FPSUnlockerApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut Frame) {
+    fn on_close_event(&mut self) -> bool {
+        if self.staged.is_none() || self.close_confirmed {
+            return true;
+        }
+        self.confirm_close = true;
+        false
+    }
+
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        notifications::clear();
+        if self.display_switched {
+            display::restore();
+        }
+        // The app is going away, so a switch under way is waited for and the plan put back here.
+        if let Some(done) = self.power_task.take().and_then(|task| task.recv().ok()) {
+            if let (PowerJob::Switch { from, .. }, Ok(())) = (done.job, done.result) {
+                self.power_restore.get_or_insert(from);
+            }
+        }
+        if let Some(guid) = self.power_restore.take() {
+            let _ = power::set_active(&guid);
+        }
+        // Closing with a change queued was confirmed, or the change went into the handoff; either
+        // way only its clone is left to clean up.
+        if let Some((_, queued)) = self.staged.take() {
+            let _ = staged::discard(queued);
+        }
+    }
+
+    fn update(&mut self, ctx: &egui::Context, frame: &mut Frame) {
+        crash::note_state(self.state_summary());
+        self.focused = ctx.input().raw.has_focus;
+        if !self.launched {
+            self.on_launch(ctx);
+        }
+        self.poll_forwarded_args();
+        self.poll_update_check();
+        self.poll_update_download(frame);
+        self.poll_write();
+        self.poll_scan();
+        self.poll_game_running(ctx);
+        self.finish_staged(ctx);
+        self.request_resumed_write(ctx);
+        self.poll_installs(ctx);
+        self.poll_cfa(ctx);
+        self.poll_power(ctx);
+        self.poll_system(ctx);
+        self.poll_diagnostics();
+        self.poll_sync();
+        self.poll_db_path();
+        self.poll_db_changes();
+        self.poll_watched(ctx);
+        self.poll_presence();
+        self.poll_verify();
+        // Keep polling the game process and the DB even when the window is otherwise idle.
+        ctx.request_repaint_after(GAME_CHECK_INTERVAL.min(self.config.watchdog.poll_interval()));
+        if let Some(path) = dropped_file(ctx) {
+            self.load_db(path);
+        }
+
+        let mut action = shortcut_action(ctx, self.fps_presets());
+        let mut save_config = false;
+        let mut comfort_write = None;
+        let mut profile_action = None;
+
+        if self.frameless {
+            show_title_bar(ctx, frame);
+        }
+
+        if self.compact {
+            show_compact(ctx, self.current_fps, self.fps_presets()[0], self.write_block_reason(), &mut action);
+            if let Some(action) = action {
+                self.perform(action, ctx, frame);
+            }
+            return;
+        }
+
         CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.heading(APP_TITLE);
             });
-            ui.separator();
-            ui.add_space(10.0);
-            ui.label("Made by abellio");
+            if let Some(release) = &self.available_update {
+                match update_banner::show(ui, release, self.update_progress) {
+                    BannerAction::Idle => {}
+                    BannerAction::Install => {
+                        self.update_progress = Some((0, 0));
+                        self.update_download = Some(update_banner::spawn_download(ctx, release.clone()));
+                    }
+                    BannerAction::Dismiss => self.available_update = None,
+                }
+            }
             ui.horizontal(|ui| {
-                ui.label("Github:");
-                ui.hyperlink("https://github.com/jakubs7");
-                ui.label("");
+                let compact = ui.small_button("Compact mode").on_hover_text("Shrink to a small always-on-top strip");
+                if describe(compact, WidgetType::Button, "Switch to a small always-on-top strip").clicked() {
+                    action = Some(Action::SetCompact(true));
+                }
+                let settings = ui.small_button("Settings");
+                if describe(settings, WidgetType::Button, "Open the preferences window").clicked() {
+                    action = Some(Action::OpenSettings);
+                }
             });
-            ui.add_space(10.0);
-            ui.label("Support my Gacha addiction:");
             ui.horizontal(|ui| {
-                ui.label("ko-fi:");
-                ui.hyperlink("https://ko-fi.com/abellio");
-                ui.label("");
+                ui.selectable_value(&mut self.tab, Tab::Patcher, "Patcher");
+                ui.selectable_value(&mut self.tab, Tab::Graphics, "Settings");
+                ui.selectable_value(&mut self.tab, Tab::RawJson, "Raw JSON");
+                ui.selectable_value(&mut self.tab, Tab::Keys, "Keys");
+                ui.selectable_value(&mut self.tab, Tab::Keybindings, "Keybindings");
+                ui.selectable_value(&mut self.tab, Tab::ConfigTweaks, "Config Tweaks");
+                ui.selectable_value(&mut self.tab, Tab::Launchers, "Launchers");
+                if self.config.sql_console {
+                    ui.selectable_value(&mut self.tab, Tab::Sql, "SQL");
+                }
+                ui.selectable_value(&mut self.tab, Tab::Stats, "Stats");
+                ui.selectable_value(&mut self.tab, Tab::About, "About");
             });
-            ui.add_space(10.0);
+            ui.separator();
+            match self.tab {
+                Tab::Patcher => {}
+                Tab::Graphics => {
+                    let Some(setting) = &self.quality_setting else {
+                        ui.label("Load a configuration file on the Patcher tab first.");
+                        return;
+                    };
+                    let blocked = self.write_block_reason();
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        if let Some(warning) = hdr::warning(setting, &self.graphics_draft, self.os_hdr) {
+                            ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ {}", warning));
+                        }
+                        ui.horizontal(|ui| {
+                            if ui.button("Import preset...").on_hover_text("Review a shared preset file before staging it").clicked() {
+                                action = Some(Action::ImportPreset);
+                            }
+                            if ui.button("Move to another PC...").on_hover_text("Export these settings and import them on another PC").clicked() {
+                                action = Some(Action::MoveSettings);
+                            }
+                            let copy = ui.button("Copy share code").on_hover_text("The FPS limit and graphics settings as one line to paste into Discord");
+                            if describe(copy, WidgetType::Button, "Copy the graphics settings as a share code").clicked() {
+                                action = Some(Action::CopyShareCode);
+                            }
+                            if ui.button("Paste share code...").on_hover_text("Review settings someone shared as a code").clicked() {
+                                action = Some(Action::PasteShareCode);
+                            }
+                        });
+                        let names = self.profile_names.get_or_insert_with(profiles::list);
+                        let conflicts = self.profile_conflicts.get_or_insert_with(profiles::conflicts);
+                        profile_action = profiles_panel::show(ui, &mut self.profile_name, names, conflicts);
+                        ultrawide::show(ui, setting, &mut self.graphics_draft, self.game_version.as_deref());
+                        ui.separator();
+                        match graphics_editor::show(ui, &self.metadata, setting, &mut self.graphics_draft, blocked) {
+                            EditorOutcome::Idle => {}
+                            EditorOutcome::Apply => action = Some(Action::ApplyGraphics),
+                            EditorOutcome::Discard => self.graphics_draft.clear(),
+                        }
+                    });
+                    return;
+                }
+                Tab::RawJson => {
+                    let Some(setting) = &self.quality_setting else {
+                        ui.label("Load a configuration file on the Patcher tab first.");
+                        return;
+                    };
+                    self.advanced.show_switch(ui);
+                    let blocked = self.write_block_reason().or(self.advanced.block_reason());
+                    let editable = self.advanced.is_on();
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        json_tree::show(ui, &self.metadata, setting, &mut self.graphics_draft, self.game_version.as_deref(), editable);
+                        match graphics_editor::show_apply_bar(ui, &self.metadata, setting, &self.graphics_draft, blocked) {
+                            EditorOutcome::Idle => {}
+                            EditorOutcome::Apply => action = Some(Action::ApplyGraphics),
+                            EditorOutcome::Discard => self.graphics_draft.clear(),
+                        }
+                    });
+                    return;
+                }
+                Tab::Keys => {
+                    if self.db_path.is_empty() {
+                        ui.label("Load a configuration file on the Patcher tab first.");
+                        return;
+                    }
+                    self.advanced.show_switch(ui);
+                    let editable = !games::active().has_quality_setting();
+                    let blocked = self.row_write_block_reason().or(self.advanced.block_reason());
+                    let backup_dir = self.backup_dir();
+                    if let Some((level, text)) = self.cleanup.show(ui, &self.db_path, blocked, &backup_dir) {
+                        self.key_browser = KeyBrowser::default();
+                        self.notify(level, text);
+                    }
+                    ui.separator();
+                    let backup_dir = self.config.backup_enabled.then(|| self.backup_dir());
+                    if let Some((level, text)) = self.key_browser.show(ui, &self.db_path, editable, blocked, backup_dir) {
+                        self.notify(level, text);
+                    }
+                    return;
+                }
+                Tab::ConfigTweaks => {
+                    if self.db_path.is_empty() {
+                        ui.label("Load a configuration file on the Patcher tab first.");
+                        return;
+                    }
+                    let blocked = self.game_running.then_some("Close the game first; it may rewrite Engine.ini while running.");
+                    let backup_dir = self.config.backup_enabled.then(|| self.backup_dir());
+                    if let Some((level, text)) = self.config_tweaks.show(ui, &self.db_path, self.quality_setting.as_ref(), blocked, backup_dir) {
+                        self.notify(level, text);
+                    }
+                    return;
+                }
+                Tab::Launchers => {
+                    if self.db_path.is_empty() {
+                        ui.label("Load a configuration file on the Patcher tab first.");
+                        return;
+                    }
+                    if let Some((level, text)) = self.launchers.show(ui, &self.db_path, self.current_fps) {
+                        self.notify(level, text);
+                    }
+                    return;
+                }
+                Tab::Keybindings => {
+                    if self.db_path.is_empty() {
+                        ui.label("Load a configuration file on the Patcher tab first.");
+                        return;
+                    }
+                    let blocked = self.row_write_block_reason();
+                    let backup_dir = self.config.backup_enabled.then(|| self.backup_dir());
+                    if let Some((level, text)) = self.keybindings.show(ui, &self.db_path, blocked, backup_dir) {
+                        self.notify(level, text);
+                    }
+                    return;
+                }
+                Tab::Sql => {
+                    if !self.config.sql_console {
+                        self.tab = Tab::Patcher;
+                        return;
+                    }
+                    if self.db_path.is_empty() {
+                        ui.label("Load a configuration file on the Patcher tab first.");
+                        return;
+                    }
+                    self.advanced.show_switch(ui);
+                    let blocked = self.row_write_block_reason().or(self.advanced.block_reason());
+                    let backup_dir = self.backup_dir();
+                    match self.sql_console.show(ui, &self.db_path, blocked, &backup_dir) {
+                        Some(Ok(write)) => {
+                            self.notify(ToastLevel::Info, format!("Backup saved to {}", write.backup.display()));
+                            self.notify(ToastLevel::Success, format!("SQL statement changed {} row(s).", write.changed));
+                            self.key_browser = KeyBrowser::default();
+                            self.refresh();
+                        }
+                        Some(Err(err)) => self.notify(ToastLevel::Error, format!("Error: {}", err)),
+                        None => {}
+                    }
+                    return;
+                }
+                Tab::Stats => {
+                    let stats = self.stats.get_or_insert_with(stats::load);
+                    let history = self.history.get_or_insert_with(history::load);
+                    if stats_panel::show(ui, stats, history, &self.config.watched) {
+                        self.stats = None;
+                        self.history = None;
+                    }
+                    return;
+                }
+                Tab::About => {
+                    egui::ScrollArea::vertical().show(ui, about::show);
+                    return;
+                }
+            }
+            let status = status_panel::Status {
+                db_path: &self.db_path,
+                db_gone: self.db_gone,
+                game_version: self.game_version.as_deref(),
+                fps: self.current_fps,
+                setting: self.quality_setting.as_ref(),
+                watched: &self.config.watched,
+                watchdog_stopped: self.watchdog_stopped,
+                watchdog_resumes: self.reapplies.frees_at().filter(|_| self.reapplies_paused),
+            };
+            status_panel::show(ui, &status);
+            if self.db_gone {
+                ui.horizontal(|ui| {
+                    ui.colored_label(ToastLevel::Warning.color(), "The configuration file is gone.");
+                    if let Some(found) = &self.db_found {
+                        let switch = ui.button("Use the one found").on_hover_text(found.display().to_string());
+                        if describe(switch, WidgetType::Button, "Switch to the configuration file found in place of the missing one").clicked() {
+                            action = Some(Action::Relocate);
+                        }
+                    } else {
+                        ui.weak("Looking for it again; or locate or browse for it below.");
+                    }
+                });
+            }
+            if let Some(setting) = &self.quality_setting {
+                if pinned::show(ui, &self.metadata, setting, &mut self.config.pinned_keys) {
+                    self.save_config();
+                }
+            }
             ui.separator();
             ui.add_space(10.0);
             ui.label("Steps:");
@@ -192,71 +2799,418 @@ impl App for FPSUnlockerApp {
             ui.label("Select the SQLite database file:");
             ui.add_space(10.0);
             ui.horizontal(|ui| {
-                if ui.button("Locate Configuration File").clicked() {
-                    match get_game_install_path() {
-                        Ok(path) => {
-                            self.db_path = path;
-                            match read_current_fps_setting(&self.db_path) {
-                                Ok(fps) => self.current_fps = Some(fps),
-                                Err(err) => self.status = format!("Error reading FPS setting: {}", err),
-                            }
-                        },
-                        Err(err) => self.status = format!("Error locating game: {}", err),
-                    }
+                let locate = ui.button("Locate Configuration File").on_hover_text("Ctrl+L");
+                if describe(locate, WidgetType::Button, "Locate the game's configuration file automatically").clicked() {
+                    action = Some(Action::Locate);
                 }
-            
-                if ui.button("Browse for Configuration File").clicked() {
-                    if let Some(path) = FileDialog::new().pick_file() {
-                        self.db_path = path.display().to_string();
-                        match read_current_fps_setting(&self.db_path) {
-                            Ok(fps) => self.current_fps = Some(fps),
-                            Err(err) => self.status = format!("Error reading FPS setting: {}", err),
-                        }
-                    }
+                let browse = ui.button("Browse for Configuration File").on_hover_text("Ctrl+O");
+                if describe(browse, WidgetType::Button, "Browse for the LocalStorage.db configuration file").clicked() {
+                    action = Some(Action::Browse);
                 }
-
-                if ui.button("Set FPS to 120").clicked() {
-                    match unlock_fps(&self.db_path) {
-                        Ok(message) => self.status = message,
-                        Err(err) => self.status = format!("Error: {}", err),
+                let scan = ui
+                    .add_enabled(self.scan.is_none(), egui::Button::new("Scan Drives"))
+                    .on_hover_text("Search all drives when the game is not registered");
+                if describe(scan, WidgetType::Button, "Search all drives for the game's configuration file").clicked() {
+                    action = Some(Action::ScanDrives);
+                }
+                let try_sample = ui.button("Try with a Sample").on_hover_text("Practice on a made-up file; each click starts a fresh one");
+                if describe(try_sample, WidgetType::Button, "Load a sample configuration file to try the app safely").clicked() {
+                    action = Some(Action::TrySample);
+                }
+            });
+            if let Some((done, total, current)) = &self.scan_progress {
+                let fraction = if *total == 0 { 0.0 } else { *done as f32 / *total as f32 };
+                ui.add(egui::ProgressBar::new(fraction).show_percentage());
+                ui.horizontal(|ui| {
+                    if ui.small_button("Cancel").clicked() {
+                        action = Some(Action::CancelScan);
+                    }
+                    ui.weak(format!("Searching {}", current));
+                });
+            } else if self.scan.is_some() {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label("Listing drives...");
+                });
+            }
+            for found in self.scan_results.clone() {
+                ui.horizontal(|ui| {
+                    if ui.small_button("Use").clicked() {
+                        self.load_db(found.display().to_string());
+                    }
+                    ui.label(found.display().to_string());
+                });
+            }
+            let blocked = self.write_block_reason();
+            ui.horizontal(|ui| {
+                for (index, fps) in self.fps_presets().iter().enumerate() {
+                    let hint = format!("Ctrl+{}", index + 1);
+                    let preset = ui
+                        .add_enabled(blocked.is_none(), egui::Button::new(format!("Set FPS to {}", fps)))
+                        .on_hover_text(hint)
+                        .on_disabled_hover_text(blocked.unwrap_or_default());
+                    let description = format!("Write a frame-rate limit of {} FPS to the configuration file", fps);
+                    if describe(preset, WidgetType::Button, description).clicked() {
+                        action = Some(Action::ApplyFps(*fps));
                     }
                 }
+                let undo_blocked = blocked.or(self.undo_stack.is_empty().then_some("Nothing to undo yet."));
+                let undo = ui
+                    .add_enabled(undo_blocked.is_none(), egui::Button::new("Undo"))
+                    .on_hover_text("Ctrl+Z")
+                    .on_disabled_hover_text(undo_blocked.unwrap_or_default());
+                if describe(undo, WidgetType::Button, "Undo the last configuration change").clicked() {
+                    action = Some(Action::Undo);
+                }
+                if self.pending_write.is_some() {
+                    ui.spinner();
+                }
             });
+            if self.game_running {
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("The game is running; changes wait until it exits.").color(ui.visuals().warn_fg_color));
+                    let close = ui.small_button("Close game...");
+                    if describe(close, WidgetType::Button, "Close the running game so settings can be written").clicked() {
+                        self.confirm_close_game = true;
+                    }
+                });
+            }
+            if let Some((label, _)) = &self.staged {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Queued: {}", label));
+                    let cancel = ui.small_button("Cancel");
+                    if describe(cancel, WidgetType::Button, "Drop the change waiting for the game to exit").clicked() {
+                        action = Some(Action::DiscardStaged);
+                    }
+                });
+            }
+            if let Some(model) = self.deck {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} detected ({} Hz).", model.name(), model.refresh_hz()));
+                    let review = ui
+                        .add_enabled(self.quality_setting.is_some(), egui::Button::new("Review Deck profile..."))
+                        .on_hover_text(model.quality_preset().description)
+                        .on_disabled_hover_text("Locate or browse for the configuration file first.");
+                    if describe(review, WidgetType::Button, "Review the recommended Steam Deck quality profile").clicked() {
+                        action = Some(Action::ReviewDeckProfile);
+                    }
+                });
+                if deck::in_gamescope() {
+                    ui.weak("Gamescope's frame limit in the Quick Access menu applies on top of this one; set it to Off or to the same value.");
+                }
+            }
             ui.add_space(10.0);
-            ui.label(&self.db_path);
+            let path_caption = ui.label("Configuration file:");
+            describe(ui.label(&self.db_path), WidgetType::Label, format!("Configuration file: {}", self.db_path))
+                .labelled_by(path_caption.id);
+            match &self.write_access {
+                Some(WriteAccess::Writable) | None => {}
+                Some(access @ WriteAccess::NeedsElevation) => {
+                    ui.colored_label(ToastLevel::Warning.color(), access.describe());
+                }
+                Some(access @ WriteAccess::ReadOnly(_)) => {
+                    ui.colored_label(ToastLevel::Error.color(), access.describe());
+                }
+            }
+            if self.cfa_blocked {
+                ui.colored_label(
+                    ToastLevel::Warning.color(),
+                    "Windows Security's Controlled Folder Access protects this folder and blocks WuWa Ploom from changing it.",
+                );
+                ui.horizontal(|ui| {
+                    let allow = ui.button("Allow WuWa Ploom").on_hover_text("Adds it to the allowed apps; needs administrator rights");
+                    if describe(allow, WidgetType::Button, "Allow WuWa Ploom through Controlled Folder Access").clicked() {
+                        action = Some(Action::AllowInDefender);
+                    }
+                    let copy = ui.small_button("Copy command").on_hover_text("To run it yourself in PowerShell as administrator");
+                    if describe(copy, WidgetType::Button, "Copy the PowerShell command that allows WuWa Ploom").clicked() {
+                        action = Some(Action::CopyDefenderCommand);
+                    }
+                });
+                ui.weak("Or open Windows Security > Virus & threat protection > Ransomware protection > Allow an app through Controlled folder access.");
+            }
 
             if let Some(fps) = self.current_fps {
                 ui.separator();
-                ui.label("Current FPS Setting:");
-                ui.label(format!("KeyCustomFrameRate: {}", fps));
+                let fps_caption = ui
+                    .horizontal(|ui| {
+                        let caption = ui.label("Current FPS Setting:");
+                        let refresh = ui.small_button("Refresh").on_hover_text("F5");
+                        if describe(refresh, WidgetType::Button, "Re-read the current frame-rate limit").clicked() {
+                            action = Some(Action::Refresh);
+                        }
+                        let auto = ui
+                            .checkbox(&mut self.config.auto_refresh, "Auto-refresh")
+                            .on_hover_text("Re-read the value whenever the file changes");
+                        if describe(auto, WidgetType::Checkbox, "Automatically re-read the value when the file changes").changed() {
+                            save_config = true;
+                        }
+                        caption
+                    })
+                    .inner;
+                let frame_rate_key = keymap::active().frame_rate;
+                let mut readout = ui.label(format!("{}: {}", frame_rate_key, fps));
+                if let Some(meta) = self.metadata.get(&frame_rate_key) {
+                    readout = readout.on_hover_text(meta.tooltip());
+                }
+                describe(readout, WidgetType::Label, format!("Current frame-rate limit: {} FPS", fps))
+                    .labelled_by(fps_caption.id);
+                if let Some(driver_fps) = self.driver_fps {
+                    ui.horizontal(|ui| {
+                        match driver_fps {
+                            Some(driver_fps) => ui.label(format!("NVIDIA Max Frame Rate: {} FPS", driver_fps)),
+                            None => ui.label("NVIDIA Max Frame Rate: off"),
+                        };
+                        let clear = ui.add_enabled(driver_fps.is_some(), egui::Button::new("Clear").small());
+                        if describe(clear, WidgetType::Button, "Remove the NVIDIA driver's frame-rate limit for the game").clicked() {
+                            action = Some(Action::ClearDriverLimit);
+                        }
+                    });
+                }
+                if let Some(display) = &self.display {
+                    ui.horizontal(|ui| {
+                        if let Some(target) = display.rate_for(fps) {
+                            ui.label(format!("The display runs at {} Hz, below this limit.", display.hz));
+                            let switch = ui.small_button(format!("Switch to {} Hz", target)).on_hover_text("Do this before starting the game");
+                            if describe(switch, WidgetType::Button, format!("Switch the display to {} Hz", target)).clicked() {
+                                action = Some(Action::SwitchRefreshRate(target));
+                            }
+                        } else if self.display_switched {
+                            ui.label(format!("Display switched to {} Hz.", display.hz));
+                        }
+                        if self.display_switched {
+                            let restore = ui.small_button("Restore");
+                            if describe(restore, WidgetType::Button, "Put back the display's own refresh rate").clicked() {
+                                action = Some(Action::RestoreDisplay);
+                            }
+                        }
+                    });
+                }
+                let meta = self.metadata.get(&frame_rate_key);
+                let (min, max) = (meta.and_then(|meta| meta.min).map(|min| min as i64), meta.and_then(|meta| meta.max).map(|max| max as i64));
+                let vrr = self.config.vrr_mode.then_some(self.display.as_ref()).flatten();
+                if let Some((display, cap)) = vrr.and_then(|display| Some((display, display::vrr_cap(display.hz, min, max)?))) {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("VRR limit for {} Hz: {} FPS", display.hz, cap)).on_hover_text(display.vrr_ladder(min, max));
+                        let blocked = self.write_block_reason().or((fps == cap).then_some("The limit is already set."));
+                        let apply = ui
+                            .add_enabled(blocked.is_none(), egui::Button::new("Apply").small())
+                            .on_hover_text("Also sets RTSS and the NVIDIA driver when those are turned on in Settings")
+                            .on_disabled_hover_text(blocked.unwrap_or_default());
+                        if describe(apply, WidgetType::Button, format!("Write a frame-rate limit of {} FPS for VRR", cap)).clicked() {
+                            action = Some(Action::ApplyFps(cap));
+                        }
+                    });
+                }
+                let active_plan = self.power_schemes.iter().find(|scheme| scheme.active);
+                if let Some(active_plan) = active_plan {
+                    ui.horizontal(|ui| {
+                        if self.power_restore.is_some() {
+                            ui.label(format!("Power plan: {} until the game exits.", active_plan.name));
+                            let restore = ui.small_button("Restore");
+                            if describe(restore, WidgetType::Button, "Put back your own power plan").clicked() {
+                                action = Some(Action::RestorePowerPlan);
+                            }
+                        } else if let Some(fast) = power::fastest(&self.power_schemes).filter(|_| !active_plan.is_fast()) {
+                            ui.label(format!("Power plan: {}", active_plan.name))
+                                .on_hover_text("Laptops often hold the GPU back on it, which looks like the unlock not working");
+                            let switch = ui.small_button(format!("Use {} while playing", fast.name));
+                            if describe(switch, WidgetType::Button, format!("Switch to the {} power plan until the game exits", fast.name)).clicked() {
+                                action = Some(Action::SwitchPowerPlan);
+                            }
+                        }
+                    });
+                }
+                if self.game_running {
+                    ui.horizontal(|ui| {
+                        if self.verify.is_some() {
+                            ui.spinner();
+                            ui.label(format!("Measuring the frame rate for {} seconds; keep playing…", presentmon::CAPTURE_SECONDS));
+                        } else {
+                            let verify = ui.small_button("Verify unlock").on_hover_text("Measures the frame rate the game presents with PresentMon");
+                            if describe(verify, WidgetType::Button, "Measure the game's frame rate").clicked() {
+                                action = Some(Action::VerifyUnlock);
+                            }
+                        }
+                    });
+                }
+                if let Some(verified) = &self.verified {
+                    ui.label(verified);
+                }
                 if fps == 120 {
                     ui.label("FPS is already set to 120. No need to patch.");
                 }
+                if let Some(setting) = &self.quality_setting {
+                    if let Some((label, patch)) = graphics_editor::show_comfort(ui, &self.metadata, setting, blocked) {
+                        comfort_write = Some(PendingWrite { label, patch, undo: false });
+                    }
+                    show_settings_overview(ui, &self.metadata, setting);
+                    if watch::show(ui, &mut self.config.watched, &self.metadata, setting) {
+                        save_config = true;
+                    }
+                }
+            }
+            ui.add_space(10.0);
+            ui.separator();
+            ui.horizontal(|ui| {
+                let label = if self.diagnosing.is_some() { "Checking..." } else { "Check my setup" };
+                let diagnose = ui.add_enabled(self.diagnosing.is_none(), egui::Button::new(label));
+                if describe(diagnose, WidgetType::Button, "Run setup diagnostics").clicked() {
+                    action = Some(Action::Diagnose);
+                }
+                let copy = ui.button("Copy report").on_hover_text("Redacted report for bug reports");
+                if describe(copy, WidgetType::Button, "Copy a redacted diagnostic report to the clipboard").clicked() {
+                    action = Some(Action::CopyReport);
+                }
+                let save = ui.button("Save report...");
+                if describe(save, WidgetType::Button, "Save a redacted diagnostic report to a file").clicked() {
+                    action = Some(Action::SaveReport);
+                }
+            });
+            ui.horizontal(|ui| {
+                let loaded = self.quality_setting.is_some();
+                let copy = ui
+                    .add_enabled(loaded, egui::Button::new("Copy inspection report"))
+                    .on_hover_text("Every setting with its value and out-of-range flags, as Markdown for forum posts")
+                    .on_disabled_hover_text("Locate or browse for the configuration file first.");
+                if describe(copy, WidgetType::Button, "Copy an inspection report of the current settings").clicked() {
+                    action = Some(Action::CopyInspection);
+                }
+                let save = ui
+                    .add_enabled(loaded, egui::Button::new("Save inspection report..."))
+                    .on_hover_text("As Markdown or HTML")
+                    .on_disabled_hover_text("Locate or browse for the configuration file first.");
+                if describe(save, WidgetType::Button, "Save an inspection report of the current settings").clicked() {
+                    action = Some(Action::SaveInspection);
+                }
+            });
+            if !self.diagnostics.is_empty() {
+                show_diagnostics(ui, &self.diagnostics);
+            }
+            if self.installs.len() > 1 {
+                let blocked = self.row_write_block_reason();
+                if let Some(from) = installs_panel::show(ui, &self.installs, &self.installs_diverging, Path::new(&self.db_path), blocked) {
+                    action = Some(Action::SyncInstalls(from));
+                }
             }
+            egui::CollapsingHeader::new("Troubleshoot").show(ui, |ui| {
+                ui.label("What's going wrong?");
+                for symptom in Symptom::ALL {
+                    let selected = self.troubleshooting.as_ref().is_some_and(|(current, _)| *current == symptom);
+                    if ui.selectable_label(selected, symptom.title()).clicked() {
+                        action = Some(Action::Troubleshoot(symptom));
+                    }
+                }
+                if let Some((symptom, findings)) = &self.troubleshooting {
+                    ui.separator();
+                    if let Some(fix) = troubleshoot::show(ui, findings) {
+                        action = Some(Action::ApplyFix(fix));
+                    }
+                    if ui.small_button("Check again").clicked() {
+                        action = Some(Action::Troubleshoot(*symptom));
+                    }
+                }
+            });
+
             ui.add_space(10.0);
-            ui.label(&self.status);
+            show_history(ui, &self.toasts, &mut self.show_history);
         });
+
+        show_shortcut_hints(ctx, self.fps_presets());
+        show_drop_target(ctx);
+        self.show_confirm_write(ctx);
+        self.show_confirm_close(ctx, frame);
+        self.show_confirm_sync(ctx);
+        self.show_confirm_rtss(ctx);
+        self.advanced.show_confirm(ctx);
+        self.show_confirm_close_game(ctx);
+        self.show_preset_review(ctx);
+        self.show_transfer(ctx);
+        self.show_paste_share_code(ctx);
+        if !self.whats_new.is_empty() && !whats_new::show(ctx, &self.whats_new) {
+            self.whats_new.clear();
+        }
+        self.show_settings(ctx);
+        self.toasts.show(ctx);
+
+        if save_config {
+            self.save_config();
+        }
+        if let Some(write) = comfort_write {
+            self.request_write(ctx, write);
+        }
+        if let Some(profile_action) = profile_action {
+            self.handle_profile(profile_action);
+        }
+
+        if let Some(action) = action {
+            self.perform(action, ctx, frame);
+        }
     }
 }
 
 fn main() {
-    set_console_title("WuWa Ploom FPS Unlock");
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let _log_guard = logging::init(args.iter().any(|arg| arg == "--verbose"));
+    tracing::info!(version = env!("CARGO_PKG_VERSION"), "starting");
+    crash::install_hook();
+
+    if let [flag, target, ..] = args.as_slice() {
+        if flag == update::REPLACE_ARG {
+            // We are the freshly downloaded version; a failure is logged and left for the old
+            // version, which finish_replace starts again, to report.
+            let _ = update::finish_replace(Path::new(target));
+            return;
+        }
+    }
+    if let [flag, request, result, ..] = args.as_slice() {
+        if flag == access::ELEVATED_WRITE_ARG {
+            // We are the elevated helper of another window's write.
+            let code = access::serve_elevated(Path::new(request), Path::new(result));
+            drop(_log_guard);
+            std::process::exit(code);
+        }
+    }
+
+    let primary = match instance::acquire() {
+        Instance::Primary(primary) => primary,
+        Instance::Secondary => {
+            // Another window already owns the DB; hand it our arguments instead of racing it.
+            let _ = instance::forward_to_primary(&args);
+            platform::native().focus_window(WINDOW_TITLE);
+            return;
+        }
+    };
+
+    cosmetic::set_console_title("WuWa Ploom FPS Unlock");
 
-    let native_options = NativeOptions::default();
+    let config = Config::load();
+    games::set_active(&config.game);
+    profiles::set_sync_folder(config.sync_folder());
+    let native_options = NativeOptions {
+        drag_and_drop_support: true,
+        decorated: !config.custom_title_bar,
+        ..Default::default()
+    };
     eframe::run_native(
-        "WuWa Ploom Tools",
+        WINDOW_TITLE,
         native_options,
-        Box::new(|_cc: &CreationContext| Box::new(FPSUnlockerApp::default())),
+        Box::new(move |cc: &CreationContext| {
+            apply_theme(&cc.egui_ctx, config.theme);
+            let native_scale = cc.integration_info.native_pixels_per_point;
+            apply_scale(&cc.egui_ctx, config.large_ui, native_scale);
+            let mut app = FPSUnlockerApp {
+                deck: deck::detect(),
+                native_scale,
+                frameless: config.custom_title_bar,
+                metadata: Metadata::load(config.language),
+                forwarded_args: primary.listen(cc.egui_ctx.clone()),
+                launched_minimized: args.iter().any(|arg| arg == autostart::MINIMIZED_ARG),
+                config,
+                ..Default::default()
+            };
+            app.handle_args(&args);
+            Box::new(app)
+        }),
     );
-
-    let hwnd = get_hwnd("WuWa Ploom Tools");
-    if !hwnd.is_null() {
-        let icon = load_icon("misc/ploom.ico");
-        if icon.is_null() {
-            eprintln!("Failed to load icon.");
-        } else {
-            set_window_icon(hwnd, icon);
-        }
-        remove_window_icon(hwnd);
-    }
 }
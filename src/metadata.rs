@@ -0,0 +1,235 @@
+//! What each GameQualitySetting key means and which values it accepts, from a table embedded
+//! at build time and overridable from the app folder. Frontends check values against it before
+//! they are written.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::paths::app_dir;
+pub use crate::update::parse_version;
+
+const METADATA_EN: &str = include_str!("../misc/key_metadata.en.json");
+/// Same format as the embedded table; lets new keys be described without a new release.
+const OVERRIDE_FILE: &str = "key_metadata.json";
+
+/// UI languages with a complete string table; only English ships today.
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Language {
+    English,
+}
+
+impl Language {
+    pub const ALL: [Language; 1] = [Language::English];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+        }
+    }
+}
+
+/// Which section of the settings editor a key is shown in.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    #[default]
+    Graphics,
+    Audio,
+    Controls,
+    Display,
+}
+
+impl Category {
+    pub const ALL: [Category; 4] = [Category::Graphics, Category::Display, Category::Audio, Category::Controls];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Category::Graphics => "Graphics",
+            Category::Audio => "Audio",
+            Category::Controls => "Controls",
+            Category::Display => "Display and HDR",
+        }
+    }
+}
+
+#[derive(Clone, Deserialize)]
+pub struct KeyOption {
+    pub value: i64,
+    pub label: String,
+    /// Short quality/performance trade-off of picking this option.
+    #[serde(default)]
+    pub hint: String,
+}
+
+/// What a GameQualitySetting key means and which values it accepts.
+#[derive(Clone, Deserialize)]
+pub struct KeyMeta {
+    pub key: String,
+    pub label: String,
+    pub description: String,
+    #[serde(default)]
+    pub category: Category,
+    /// Stored as a fractional number rather than an integer level.
+    #[serde(default)]
+    pub float: bool,
+    #[serde(default)]
+    pub min: Option<f64>,
+    #[serde(default)]
+    pub max: Option<f64>,
+    /// Increment for typed numeric entry; keys with a step get a drag field instead of a slider.
+    #[serde(default)]
+    pub step: Option<f64>,
+    #[serde(default)]
+    pub options: Vec<KeyOption>,
+    /// First client version that stores the key, e.g. "1.1".
+    #[serde(default)]
+    pub since: Option<String>,
+}
+
+/// What validation found wrong with a value about to be written.
+pub enum Problem {
+    /// Outside what the game accepts; the write is refused.
+    Invalid(String),
+    /// Probably harmless, but worth telling the user.
+    Unavailable(String),
+}
+
+impl KeyMeta {
+    pub fn range_text(&self) -> String {
+        if !self.options.is_empty() {
+            let options: Vec<String> = self.options.iter().map(|option| format!("{} = {}", option.value, option.label)).collect();
+            return options.join(", ");
+        }
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("{} to {}", min, max),
+            (Some(min), None) => format!("at least {}", min),
+            (None, Some(max)) => format!("at most {}", max),
+            (None, None) => "unrestricted".into(),
+        }
+    }
+
+    /// Checks type, range and version availability. A `Null` value means removal and always passes.
+    pub fn check(&self, value: &Value, game_version: Option<&str>) -> Option<Problem> {
+        if value.is_null() {
+            return None;
+        }
+        let Some(number) = value.as_f64() else {
+            return Some(Problem::Invalid(format!("{} must be a number, not {}", self.key, value)));
+        };
+        if !(self.float || value.is_i64() || value.is_u64()) {
+            return Some(Problem::Invalid(format!("{} must be a whole number, not {}", self.key, value)));
+        }
+        let in_range = if self.options.is_empty() {
+            self.min.is_none_or(|min| number >= min) && self.max.is_none_or(|max| number <= max)
+        } else {
+            self.options.iter().any(|option| option.value as f64 == number)
+        };
+        if !in_range {
+            return Some(Problem::Invalid(format!("{} = {} is outside {}", self.key, value, self.range_text())));
+        }
+        let since = parse_version(self.since.as_deref()?);
+        let installed = parse_version(game_version?);
+        (!installed.is_empty() && installed < since).then(|| {
+            let since = self.since.clone().unwrap_or_default();
+            Problem::Unavailable(format!("{} needs client {} or newer; the installed client may ignore it", self.key, since))
+        })
+    }
+
+    pub fn tooltip(&self) -> String {
+        let mut tooltip = format!("{}\n\nValid values: {}", self.description, self.range_text());
+        for option in self.options.iter().filter(|option| !option.hint.is_empty()) {
+            tooltip.push_str(&format!("\n{}: {}", option.label, option.hint));
+        }
+        tooltip
+    }
+
+    pub fn option(&self, value: &Value) -> Option<&KeyOption> {
+        let value = value.as_i64()?;
+        self.options.iter().find(|option| option.value == value)
+    }
+}
+
+#[derive(Default)]
+pub struct Metadata {
+    keys: Vec<KeyMeta>,
+    /// What happened to each override file that was found, for the launch notifications.
+    notes: Vec<OverrideNote>,
+}
+
+pub struct OverrideNote {
+    pub loaded: bool,
+    pub text: String,
+}
+
+/// Places checked for a community-maintained table, later ones winning: next to the
+/// executable, then the app folder.
+fn override_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(dir) = env::current_exe().ok().as_deref().and_then(Path::parent) {
+        paths.push(dir.join(OVERRIDE_FILE));
+    }
+    paths.push(app_dir().join(OVERRIDE_FILE));
+    paths
+}
+
+impl Metadata {
+    pub fn load(language: Language) -> Self {
+        let source = match language {
+            Language::English => METADATA_EN,
+        };
+        // The table is embedded at compile time, so a parse failure is a packaging bug.
+        let keys = serde_json::from_str(source).expect("embedded key metadata is valid JSON");
+        let mut metadata = Self { keys, notes: Vec::new() };
+        for path in override_paths() {
+            metadata.merge_file(&path);
+        }
+        metadata
+    }
+
+    /// Entries in the file replace embedded ones with the same key; new keys are appended.
+    fn merge_file(&mut self, path: &Path) {
+        let Ok(text) = fs::read_to_string(path) else {
+            return;
+        };
+        let entries: Vec<KeyMeta> = match serde_json::from_str(&text) {
+            Ok(entries) => entries,
+            Err(err) => {
+                self.notes.push(OverrideNote { loaded: false, text: format!("Ignored {}: {}", path.display(), err) });
+                return;
+            }
+        };
+        let count = entries.len();
+        for entry in entries {
+            match self.keys.iter_mut().find(|meta| meta.key == entry.key) {
+                Some(existing) => *existing = entry,
+                None => self.keys.push(entry),
+            }
+        }
+        let text = format!("Loaded {} key description(s) from {}", count, path.display());
+        self.notes.push(OverrideNote { loaded: true, text });
+    }
+
+    pub fn notes(&self) -> &[OverrideNote] {
+        &self.notes
+    }
+
+    pub fn keys(&self) -> &[KeyMeta] {
+        &self.keys
+    }
+
+    pub fn get(&self, key: &str) -> Option<&KeyMeta> {
+        self.keys.iter().find(|meta| meta.key == key)
+    }
+
+    /// Problems with every described field of `patch`; undescribed keys are not checked.
+    pub fn check_patch(&self, patch: &Map<String, Value>, game_version: Option<&str>) -> Vec<Problem> {
+        patch
+            .iter()
+            .filter_map(|(key, value)| self.get(key)?.check(value, game_version))
+            .collect()
+    }
+}
@@ -0,0 +1,38 @@
+//! Announcements from background work: the watchdog re-applying keys, the check after the game
+//! starts, updates, and backups.
+//! They always land in the in-app toasts and history; while the window is in the background
+//! they also go to the system, when the user allows it and the system can show them.
+
+use crate::platform;
+use crate::WINDOW_TITLE;
+
+/// The subsystem a notification comes from; it titles the system notification.
+#[derive(Clone, Copy)]
+pub enum Source {
+    Watchdog,
+    /// The read-back of a new FPS limit after the game started.
+    ReadBack,
+    Updater,
+    Backup,
+}
+
+impl Source {
+    pub fn title(self) -> &'static str {
+        match self {
+            Source::Watchdog => "Watched keys",
+            Source::ReadBack => "FPS limit check",
+            Source::Updater => "Update available",
+            Source::Backup => "Backup",
+        }
+    }
+}
+
+/// Sends `text` to the system if `enabled` and the window is not `focused`; returns whether it went.
+pub fn send_system(enabled: bool, focused: bool, source: Source, text: &str) -> bool {
+    enabled && !focused && platform::native().notify(WINDOW_TITLE, source.title(), text)
+}
+
+/// Takes down the tray icon system notifications leave behind.
+pub fn clear() {
+    platform::native().clear_notifications(WINDOW_TITLE);
+}
@@ -0,0 +1,330 @@
+//! The NVIDIA driver's per-application "Max Frame Rate", read and set through NVAPI's driver
+//! settings (DRS). With both the driver and the game capping frames, the lower cap wins and
+//! changing one looks broken, so the app can keep the driver's cap equal to the game's.
+//!
+//! Profiles are keyed by the client's full path. Its file name, "Client-Win64-Shipping.exe", is
+//! shared by many Unreal games, so a profile for the bare name is read but never changed.
+
+use std::io;
+use std::path::Path;
+
+use wuwa_ploom_core::{MyError, Result};
+
+/// `FRL_FPS_ID`, "Max Frame Rate" in the NVIDIA Control Panel; 0 means no limit.
+#[cfg(windows)]
+const FRL_FPS_ID: u32 = 0x1083_5002;
+
+/// The driver's frame-rate limit for the executable at `exe`, from whichever profile the driver
+/// applies to it; `None` when it sets none.
+#[cfg(windows)]
+pub fn max_frame_rate(exe: &Path) -> Result<Option<u32>> {
+    let session = nvapi::Session::open()?;
+    let Some((profile, _)) = session.find_profile(&exe.to_string_lossy())? else {
+        return Ok(None);
+    };
+    Ok(session.get_u32(profile, FRL_FPS_ID)?.filter(|&fps| fps > 0))
+}
+
+/// Sets the driver's frame-rate limit for the executable at `exe` to `fps`, or clears it with
+/// `None`, in a profile for that full path; the driver prefers it over one for the bare name.
+/// Saving driver settings needs administrator rights, reported as `MyError::Permission`.
+#[cfg(windows)]
+pub fn set_max_frame_rate(exe: &Path, fps: Option<u32>) -> Result<()> {
+    let session = nvapi::Session::open()?;
+    let path = exe.to_string_lossy();
+    let found = session.find_profile(&path)?;
+    let own = found.as_ref().filter(|(_, app)| app.eq_ignore_ascii_case(&path)).map(|(profile, _)| *profile);
+    match (own, fps) {
+        (Some(profile), None) => session.delete(profile, FRL_FPS_ID)?,
+        (Some(profile), Some(fps)) => session.set_u32(profile, FRL_FPS_ID, fps)?,
+        (None, None) if found.is_none() => return Ok(()),
+        // A profile of our own without the setting lifts the bare name's limit for this install.
+        (None, fps) => {
+            let profile = session.create_profile(&format!("WuWa Ploom - {}", path), &path)?;
+            if let Some(fps) = fps {
+                session.set_u32(profile, FRL_FPS_ID, fps)?;
+            }
+        }
+    }
+    session.save()?;
+    tracing::info!(exe = %path, ?fps, "set NVIDIA max frame rate");
+    Ok(())
+}
+
+#[cfg(not(windows))]
+pub fn max_frame_rate(_exe: &Path) -> Result<Option<u32>> {
+    Err(unavailable())
+}
+
+#[cfg(not(windows))]
+pub fn set_max_frame_rate(_exe: &Path, _fps: Option<u32>) -> Result<()> {
+    Err(unavailable())
+}
+
+fn unavailable() -> MyError {
+    MyError::Io(io::Error::other("NVAPI is not available; is an NVIDIA driver installed?"))
+}
+
+/// Just enough of NVAPI's driver-settings interface, loaded from the driver's DLL at runtime.
+/// Layouts and function ids follow `nvapi.h` and `nvapi_interface.h`.
+#[cfg(windows)]
+mod nvapi {
+    use std::ffi::c_void;
+    use std::{io, mem};
+
+    use winapi::um::libloaderapi::{GetProcAddress, LoadLibraryA};
+
+    use super::unavailable;
+    use wuwa_ploom_core::{MyError, Result};
+
+    type Handle = *mut c_void;
+    type Status = i32;
+
+    const OK: Status = 0;
+    const INVALID_USER_PRIVILEGE: Status = -137;
+    const SETTING_NOT_FOUND: Status = -160;
+    const EXECUTABLE_NOT_FOUND: Status = -166;
+
+    const INITIALIZE: u32 = 0x0150_E828;
+    const CREATE_SESSION: u32 = 0x0694_D52E;
+    const DESTROY_SESSION: u32 = 0xDAD9_CFF8;
+    const LOAD_SETTINGS: u32 = 0x375D_BD6B;
+    const SAVE_SETTINGS: u32 = 0xFCBC_7E14;
+    const FIND_APPLICATION_BY_NAME: u32 = 0xEEE5_66B2;
+    const CREATE_PROFILE: u32 = 0xCC17_6068;
+    const CREATE_APPLICATION: u32 = 0x4347_A9DE;
+    const GET_SETTING: u32 = 0x73BF_8338;
+    const SET_SETTING: u32 = 0x577D_D202;
+    const DELETE_PROFILE_SETTING: u32 = 0xE4A2_6362;
+
+    const NAME_LEN: usize = 2048;
+    /// `NVDRS_DWORD_TYPE`
+    const DWORD_TYPE: u32 = 0;
+
+    /// `NVDRS_SETTING_V1`; the value unions are as large as `NVDRS_BINARY_SETTING`.
+    #[repr(C)]
+    struct Setting {
+        version: u32,
+        name: [u16; NAME_LEN],
+        id: u32,
+        kind: u32,
+        location: u32,
+        is_current_predefined: u32,
+        is_predefined_valid: u32,
+        predefined: [u32; 1025],
+        current: [u32; 1025],
+    }
+
+    /// `NVDRS_APPLICATION_V2`
+    #[repr(C)]
+    struct Application {
+        version: u32,
+        is_predefined: u32,
+        app_name: [u16; NAME_LEN],
+        friendly_name: [u16; NAME_LEN],
+        launcher: [u16; NAME_LEN],
+        file_in_folder: [u16; NAME_LEN],
+    }
+
+    /// `NVDRS_PROFILE_V1`
+    #[repr(C)]
+    struct Profile {
+        version: u32,
+        name: [u16; NAME_LEN],
+        gpu_support: u32,
+        is_predefined: u32,
+        apps: u32,
+        settings: u32,
+    }
+
+    // The driver checks the size encoded in each struct's version.
+    const _: () = assert!(mem::size_of::<Setting>() == 12320);
+    const _: () = assert!(mem::size_of::<Application>() == 16392);
+    const _: () = assert!(mem::size_of::<Profile>() == 4116);
+
+    /// `MAKE_NVAPI_VERSION`
+    const fn version<T>(number: u32) -> u32 {
+        mem::size_of::<T>() as u32 | (number << 16)
+    }
+
+    fn wide(text: &str) -> [u16; NAME_LEN] {
+        let mut buffer = [0; NAME_LEN];
+        for (slot, unit) in buffer.iter_mut().zip(text.encode_utf16().take(NAME_LEN - 1)) {
+            *slot = unit;
+        }
+        buffer
+    }
+
+    fn narrow(buffer: &[u16]) -> String {
+        let len = buffer.iter().position(|&unit| unit == 0).unwrap_or(buffer.len());
+        String::from_utf16_lossy(&buffer[..len])
+    }
+
+    fn check(status: Status, what: &str) -> Result<()> {
+        match status {
+            OK => Ok(()),
+            INVALID_USER_PRIVILEGE => Err(MyError::Permission(format!("{}: changing NVIDIA driver settings needs administrator rights", what))),
+            _ => Err(MyError::Io(io::Error::other(format!("{} failed with NVAPI status {}", what, status)))),
+        }
+    }
+
+    /// Boxed, as the structs are several KiB each.
+    fn zeroed<T>() -> Box<T> {
+        // SAFETY: only used for the plain-data `repr(C)` structs above, for which all zeroes is valid.
+        unsafe { Box::new(mem::zeroed()) }
+    }
+
+    type QueryInterface = unsafe extern "C" fn(u32) -> *mut c_void;
+
+    struct Api {
+        query: QueryInterface,
+    }
+
+    impl Api {
+        fn load() -> Result<Self> {
+            let library = if cfg!(target_pointer_width = "64") { c"nvapi64.dll" } else { c"nvapi.dll" };
+            // SAFETY: C string arguments; the DLL stays loaded for the process.
+            unsafe {
+                let module = LoadLibraryA(library.as_ptr());
+                if module.is_null() {
+                    return Err(unavailable());
+                }
+                let query = GetProcAddress(module, c"nvapi_QueryInterface".as_ptr());
+                if query.is_null() {
+                    return Err(unavailable());
+                }
+                Ok(Self { query: mem::transmute::<*mut winapi::shared::minwindef::__some_function, QueryInterface>(query) })
+            }
+        }
+
+        /// The function with `id`, as `F`, which must match its C signature.
+        unsafe fn function<F: Copy>(&self, id: u32) -> Result<F> {
+            let pointer = (self.query)(id);
+            if pointer.is_null() {
+                return Err(MyError::Io(io::Error::other(format!("this driver lacks NVAPI function {:#x}", id))));
+            }
+            Ok(mem::transmute_copy(&pointer))
+        }
+    }
+
+    /// A loaded copy of the driver settings; changes take effect on `save`.
+    pub struct Session {
+        api: Api,
+        handle: Handle,
+    }
+
+    impl Session {
+        pub fn open() -> Result<Self> {
+            let api = Api::load()?;
+            // SAFETY: signatures as declared in nvapi.h.
+            unsafe {
+                let initialize: unsafe extern "C" fn() -> Status = api.function(INITIALIZE)?;
+                check(initialize(), "NvAPI_Initialize")?;
+                let create: unsafe extern "C" fn(*mut Handle) -> Status = api.function(CREATE_SESSION)?;
+                let mut handle = std::ptr::null_mut();
+                check(create(&mut handle), "NvAPI_DRS_CreateSession")?;
+                let session = Self { api, handle };
+                let load: unsafe extern "C" fn(Handle) -> Status = session.api.function(LOAD_SETTINGS)?;
+                check(load(session.handle), "NvAPI_DRS_LoadSettings")?;
+                Ok(session)
+            }
+        }
+
+        /// The profile the driver applies to `exe`, if any, with the application name that
+        /// matched: the full path, or only the file name.
+        pub fn find_profile(&self, exe: &str) -> Result<Option<(Handle, String)>> {
+            let name = wide(exe);
+            let mut application = zeroed::<Application>();
+            application.version = version::<Application>(2);
+            let mut profile = std::ptr::null_mut();
+            // SAFETY: signature as declared in nvapi.h; the buffers outlive the call.
+            let status = unsafe {
+                let find: unsafe extern "C" fn(Handle, *const u16, *mut Handle, *mut Application) -> Status =
+                    self.api.function(FIND_APPLICATION_BY_NAME)?;
+                find(self.handle, name.as_ptr(), &mut profile, &mut *application)
+            };
+            match status {
+                EXECUTABLE_NOT_FOUND => Ok(None),
+                status => check(status, "NvAPI_DRS_FindApplicationByName").map(|()| Some((profile, narrow(&application.app_name)))),
+            }
+        }
+
+        /// A new profile named `name` holding `exe`.
+        pub fn create_profile(&self, name: &str, exe: &str) -> Result<Handle> {
+            let mut profile = zeroed::<Profile>();
+            profile.version = version::<Profile>(1);
+            profile.name = wide(name);
+            let mut application = zeroed::<Application>();
+            application.version = version::<Application>(2);
+            application.app_name = wide(exe);
+            let mut handle = std::ptr::null_mut();
+            // SAFETY: signatures as declared in nvapi.h; the buffers outlive the calls.
+            unsafe {
+                let create: unsafe extern "C" fn(Handle, *mut Profile, *mut Handle) -> Status = self.api.function(CREATE_PROFILE)?;
+                check(create(self.handle, &mut *profile, &mut handle), "NvAPI_DRS_CreateProfile")?;
+                let add: unsafe extern "C" fn(Handle, Handle, *mut Application) -> Status = self.api.function(CREATE_APPLICATION)?;
+                check(add(self.handle, handle, &mut *application), "NvAPI_DRS_CreateApplication")?;
+            }
+            Ok(handle)
+        }
+
+        pub fn get_u32(&self, profile: Handle, id: u32) -> Result<Option<u32>> {
+            let mut setting = zeroed::<Setting>();
+            setting.version = version::<Setting>(1);
+            // SAFETY: signature as declared in nvapi.h; the buffer outlives the call.
+            let status = unsafe {
+                let get: unsafe extern "C" fn(Handle, Handle, u32, *mut Setting) -> Status = self.api.function(GET_SETTING)?;
+                get(self.handle, profile, id, &mut *setting)
+            };
+            match status {
+                SETTING_NOT_FOUND => Ok(None),
+                status => check(status, "NvAPI_DRS_GetSetting").map(|()| Some(setting.current[0])),
+            }
+        }
+
+        pub fn set_u32(&self, profile: Handle, id: u32, value: u32) -> Result<()> {
+            let mut setting = zeroed::<Setting>();
+            setting.version = version::<Setting>(1);
+            setting.id = id;
+            setting.kind = DWORD_TYPE;
+            setting.current[0] = value;
+            // SAFETY: signature as declared in nvapi.h; the buffer outlives the call.
+            unsafe {
+                let set: unsafe extern "C" fn(Handle, Handle, *mut Setting) -> Status = self.api.function(SET_SETTING)?;
+                check(set(self.handle, profile, &mut *setting), "NvAPI_DRS_SetSetting")
+            }
+        }
+
+        /// Back to the driver's default for the profile; fine if it was not set.
+        pub fn delete(&self, profile: Handle, id: u32) -> Result<()> {
+            // SAFETY: signature as declared in nvapi.h.
+            let status = unsafe {
+                let delete: unsafe extern "C" fn(Handle, Handle, u32) -> Status = self.api.function(DELETE_PROFILE_SETTING)?;
+                delete(self.handle, profile, id)
+            };
+            match status {
+                SETTING_NOT_FOUND => Ok(()),
+                status => check(status, "NvAPI_DRS_DeleteProfileSetting"),
+            }
+        }
+
+        pub fn save(&self) -> Result<()> {
+            // SAFETY: signature as declared in nvapi.h.
+            unsafe {
+                let save: unsafe extern "C" fn(Handle) -> Status = self.api.function(SAVE_SETTINGS)?;
+                check(save(self.handle), "NvAPI_DRS_SaveSettings")
+            }
+        }
+    }
+
+    impl Drop for Session {
+        fn drop(&mut self) {
+            // SAFETY: signature as declared in nvapi.h; the handle came from CreateSession.
+            unsafe {
+                if let Ok(destroy) = self.api.function::<unsafe extern "C" fn(Handle) -> Status>(DESTROY_SESSION) {
+                    destroy(self.handle);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use std::env;
+use std::path::PathBuf;
+
+/// `%APPDATA%\wuwa-ploom`, where the app keeps its own files.
+#[cfg(windows)]
+pub fn app_dir() -> PathBuf {
+    env::var_os("APPDATA")
+        .map(PathBuf::from)
+        .unwrap_or_else(env::temp_dir)
+        .join("wuwa-ploom")
+}
+
+/// `$XDG_CONFIG_HOME/wuwa-ploom`, falling back to `~/.config/wuwa-ploom`.
+#[cfg(not(windows))]
+pub fn app_dir() -> PathBuf {
+    env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .unwrap_or_else(env::temp_dir)
+        .join("wuwa-ploom")
+}
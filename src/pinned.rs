@@ -0,0 +1,54 @@
+use eframe::egui;
+use serde_json::Value;
+
+use crate::metadata::Metadata;
+
+/// Keys pinned until the user picks their own: resolution and the graphics preset. The FPS limit
+/// and VSync are in the status above already.
+pub fn default_keys() -> Vec<String> {
+    ["KeyPcResolutionWidth", "KeyPcResolutionHeight", "KeyQualityLevel"].map(String::from).to_vec()
+}
+
+/// Live readouts of the GameQualitySetting fields in `pinned`, with controls to pin and unpin
+/// them. Returns whether `pinned` changed.
+pub fn show(ui: &mut egui::Ui, metadata: &Metadata, setting: &Value, pinned: &mut Vec<String>) -> bool {
+    let mut unpin = None;
+    let mut pin = None;
+    egui::CollapsingHeader::new("Pinned keys").default_open(true).show(ui, |ui| {
+        egui::Grid::new("pinned_grid").num_columns(3).show(ui, |ui| {
+            for (index, key) in pinned.iter().enumerate() {
+                let meta = metadata.get(key);
+                let name = ui.label(meta.map_or(key.as_str(), |meta| meta.label.as_str()));
+                if meta.is_some() {
+                    name.on_hover_text(key);
+                }
+                match setting.get(key) {
+                    None => ui.weak("not set"),
+                    Some(value) => match meta.and_then(|meta| meta.option(value)) {
+                        Some(option) => ui.label(format!("{} ({})", option.label, value)),
+                        None => ui.label(value.to_string()),
+                    },
+                };
+                if ui.small_button("✖").on_hover_text("Unpin").clicked() {
+                    unpin = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        let unpinned: Vec<&String> = setting.as_object().into_iter().flat_map(|fields| fields.keys()).filter(|key| !pinned.contains(key)).collect();
+        egui::ComboBox::from_id_source("pin_key").selected_text("Pin a key").show_ui(ui, |ui| {
+            for key in unpinned {
+                let label = metadata.get(key).map_or(key.clone(), |meta| format!("{} ({})", meta.label, key));
+                if ui.selectable_label(false, label).clicked() {
+                    pin = Some(key.clone());
+                }
+            }
+        });
+    });
+    let changed = unpin.is_some() || pin.is_some();
+    if let Some(index) = unpin {
+        pinned.remove(index);
+    }
+    pinned.extend(pin);
+    changed
+}
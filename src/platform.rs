@@ -0,0 +1,227 @@
+//! Everything that talks to the operating system directly, behind one trait so the rest
+//! of the core builds and runs its tests anywhere.
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, System, UpdateKind};
+
+use crate::registry::Location;
+
+#[cfg(not(windows))]
+mod generic;
+#[cfg(windows)]
+mod windows;
+
+#[cfg(not(windows))]
+pub use self::generic::GenericPlatform;
+#[cfg(windows)]
+pub use self::windows::{find_window, WindowsPlatform};
+
+/// A running process as the platform reports it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// Executable name, e.g. "Client-Win64-Shipping.exe"; Linux truncates it to 15 bytes.
+    pub name: String,
+    /// Full path of the executable, when the OS lets us see it.
+    pub exe: Option<PathBuf>,
+}
+
+impl ProcessInfo {
+    pub fn new(pid: u32, name: &str) -> Self {
+        Self { pid, name: name.to_string(), exe: None }
+    }
+}
+
+/// Scheduling priority the game can be given.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Priority {
+    #[default]
+    Normal,
+    AboveNormal,
+    High,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 3] = [Priority::Normal, Priority::AboveNormal, Priority::High];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Priority::Normal => "Normal",
+            Priority::AboveNormal => "Above normal",
+            Priority::High => "High",
+        }
+    }
+}
+
+pub trait Platform: Sync {
+    /// String value `value` of the registry key at `location`.
+    fn registry_string(&self, location: &Location, value: &str) -> Option<String>;
+    /// Sets string value `value` of the registry key at `location`, creating the key, or deletes
+    /// the value when `data` is `None`. False if that failed; deleting a missing value succeeds.
+    fn set_registry_string(&self, location: &Location, value: &str, data: Option<&str>) -> bool;
+    /// All running processes.
+    fn processes(&self) -> Vec<ProcessInfo>;
+    /// Kills process `pid`; false if it is gone or we may not.
+    fn terminate(&self, pid: u32) -> bool;
+    /// Gives process `pid` `priority` and, when set, the CPU `affinity` mask (bit n is logical
+    /// CPU n); false if either could not be set.
+    fn tune_process(&self, pid: u32, priority: Priority, affinity: Option<u64>) -> bool;
+    /// Whether this process runs with administrator rights.
+    fn is_elevated(&self) -> bool;
+    /// Whether `run_elevated` can gain rights this process lacks.
+    fn can_elevate(&self) -> bool;
+    /// Runs `program` with administrator rights, asking the user, and waits for it. `None` if it
+    /// could not start, including when the user declined.
+    fn run_elevated(&self, program: &Path, args: &[String]) -> Option<i32>;
+    /// Shows a system notification on behalf of the window titled `window_title` and draws
+    /// attention to the window. Returns false if the system could not show it.
+    fn notify(&self, window_title: &str, title: &str, text: &str) -> bool;
+    /// Removes whatever `notify` left behind, such as a tray icon.
+    fn clear_notifications(&self, window_title: &str);
+    /// Returns false if the window or the icon could not be found.
+    fn set_window_icon(&self, window_title: &str, icon_path: &Path) -> bool;
+    fn minimize_window(&self, window_title: &str);
+    /// Restores the window if minimized and brings it to the front.
+    fn focus_window(&self, window_title: &str);
+}
+
+/// Platform for tests: answers from its fields and records notifications and kills instead of
+/// acting on them.
+#[derive(Default)]
+pub struct StubPlatform {
+    /// (key, value name, data) triples served by `registry_string`; keys match case-insensitively.
+    pub registry: Vec<(Location, String, String)>,
+    pub processes: Vec<ProcessInfo>,
+    pub elevated: bool,
+    pub notifications: Mutex<Vec<String>>,
+    /// Pids passed to `terminate`.
+    pub terminated: Mutex<Vec<u32>>,
+    /// Calls to `tune_process`.
+    pub tuned: Mutex<Vec<(u32, Priority, Option<u64>)>>,
+    /// Calls to `set_registry_string`, which `registry_string` sees over `registry`.
+    pub registry_writes: Mutex<Vec<(Location, String, Option<String>)>>,
+}
+
+impl StubPlatform {
+    pub const fn new() -> Self {
+        Self {
+            registry: Vec::new(),
+            processes: Vec::new(),
+            elevated: false,
+            notifications: Mutex::new(Vec::new()),
+            terminated: Mutex::new(Vec::new()),
+            tuned: Mutex::new(Vec::new()),
+            registry_writes: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+impl Platform for StubPlatform {
+    fn registry_string(&self, location: &Location, value: &str) -> Option<String> {
+        let written = self.registry_writes.lock().ok().and_then(|writes| {
+            let found = writes.iter().rev().find(|(at, name, _)| at == location && name == value);
+            found.map(|(_, _, data)| data.clone())
+        });
+        if let Some(data) = written {
+            return data;
+        }
+        self.registry
+            .iter()
+            .find(|(at, name, _)| {
+                at.hive == location.hive && at.view == location.view && at.key.eq_ignore_ascii_case(&location.key) && name == value
+            })
+            .map(|(_, _, data)| data.clone())
+    }
+
+    fn set_registry_string(&self, location: &Location, value: &str, data: Option<&str>) -> bool {
+        if let Ok(mut writes) = self.registry_writes.lock() {
+            writes.push((location.clone(), value.to_string(), data.map(str::to_string)));
+        }
+        true
+    }
+
+    fn processes(&self) -> Vec<ProcessInfo> {
+        self.processes.clone()
+    }
+
+    fn terminate(&self, pid: u32) -> bool {
+        if let Ok(mut terminated) = self.terminated.lock() {
+            terminated.push(pid);
+        }
+        self.processes.iter().any(|process| process.pid == pid)
+    }
+
+    fn tune_process(&self, pid: u32, priority: Priority, affinity: Option<u64>) -> bool {
+        if let Ok(mut tuned) = self.tuned.lock() {
+            tuned.push((pid, priority, affinity));
+        }
+        self.processes.iter().any(|process| process.pid == pid)
+    }
+
+    fn is_elevated(&self) -> bool {
+        self.elevated
+    }
+
+    fn can_elevate(&self) -> bool {
+        !self.elevated
+    }
+
+    fn run_elevated(&self, _program: &Path, _args: &[String]) -> Option<i32> {
+        None
+    }
+
+    fn notify(&self, _window_title: &str, title: &str, text: &str) -> bool {
+        if let Ok(mut notifications) = self.notifications.lock() {
+            notifications.push(format!("{}: {}", title, text));
+        }
+        true
+    }
+
+    fn clear_notifications(&self, _window_title: &str) {}
+
+    fn set_window_icon(&self, _window_title: &str, _icon_path: &Path) -> bool {
+        false
+    }
+
+    fn minimize_window(&self, _window_title: &str) {}
+
+    fn focus_window(&self, _window_title: &str) {}
+}
+
+/// Process list from sysinfo, shared by the native platforms.
+fn system_processes() -> Vec<ProcessInfo> {
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessRefreshKind::new().with_exe(UpdateKind::OnlyIfNotSet));
+    system
+        .processes()
+        .iter()
+        .map(|(pid, process)| ProcessInfo {
+            pid: pid.as_u32(),
+            name: process.name().to_string(),
+            exe: process.exe().map(Path::to_path_buf),
+        })
+        .collect()
+}
+
+fn terminate_process(pid: u32) -> bool {
+    let mut system = System::new();
+    let pid = Pid::from_u32(pid);
+    system.refresh_process_specifics(pid, ProcessRefreshKind::new()) && system.process(pid).is_some_and(|process| process.kill())
+}
+
+/// The implementation for the system the app was built for.
+pub fn native() -> &'static dyn Platform {
+    #[cfg(windows)]
+    {
+        static NATIVE: WindowsPlatform = WindowsPlatform;
+        &NATIVE
+    }
+    #[cfg(not(windows))]
+    {
+        static NATIVE: GenericPlatform = GenericPlatform;
+        &NATIVE
+    }
+}
@@ -0,0 +1,65 @@
+use std::path::Path;
+use std::process::Command;
+
+use tracing::debug;
+
+use super::{Platform, Priority, ProcessInfo};
+use crate::registry::Location;
+
+/// Linux and other systems: real process handling, desktop notifications, no registry (Wine
+/// prefixes are read directly by `wine`), and no window management beyond what eframe does.
+pub struct GenericPlatform;
+
+impl Platform for GenericPlatform {
+    fn registry_string(&self, _location: &Location, _value: &str) -> Option<String> {
+        None
+    }
+
+    fn set_registry_string(&self, _location: &Location, _value: &str, _data: Option<&str>) -> bool {
+        false
+    }
+
+    fn processes(&self) -> Vec<ProcessInfo> {
+        super::system_processes()
+    }
+
+    fn terminate(&self, pid: u32) -> bool {
+        super::terminate_process(pid)
+    }
+
+    /// Raising priority needs root here, and Wine maps priorities its own way.
+    fn tune_process(&self, pid: u32, _priority: Priority, _affinity: Option<u64>) -> bool {
+        debug!(pid, "process tuning is not supported on this system");
+        false
+    }
+
+    fn is_elevated(&self) -> bool {
+        false
+    }
+
+    /// There is no consent prompt to go through; users fix file ownership themselves.
+    fn can_elevate(&self) -> bool {
+        false
+    }
+
+    fn run_elevated(&self, _program: &Path, _args: &[String]) -> Option<i32> {
+        None
+    }
+
+    /// Through `notify-send`, which most desktops ship; false where it is missing.
+    fn notify(&self, window_title: &str, title: &str, text: &str) -> bool {
+        let status = Command::new("notify-send").arg(format!("--app-name={}", window_title)).args([title, text]).status();
+        debug!(?status, "notify-send");
+        status.is_ok_and(|status| status.success())
+    }
+
+    fn clear_notifications(&self, _window_title: &str) {}
+
+    fn set_window_icon(&self, _window_title: &str, _icon_path: &Path) -> bool {
+        false
+    }
+
+    fn minimize_window(&self, _window_title: &str) {}
+
+    fn focus_window(&self, _window_title: &str) {}
+}
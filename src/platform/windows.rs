@@ -0,0 +1,245 @@
+use std::ffi::OsStr;
+use std::mem;
+use std::os::windows::ffi::OsStrExt;
+use std::path::Path;
+use std::ptr;
+
+use winapi::shared::windef::{HICON, HWND};
+use winapi::um::handleapi::CloseHandle;
+use winapi::um::processthreadsapi::{GetCurrentProcess, GetExitCodeProcess, OpenProcess, OpenProcessToken, SetPriorityClass};
+use winapi::um::securitybaseapi::GetTokenInformation;
+use winapi::um::shellapi::{
+    ShellExecuteExW, Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_TIP, NIIF_INFO, NIM_ADD, NIM_DELETE, NIM_MODIFY, NOTIFYICONDATAW,
+    SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW,
+};
+use winapi::um::synchapi::WaitForSingleObject;
+use winapi::um::winbase::{ABOVE_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS, INFINITE, NORMAL_PRIORITY_CLASS};
+use winapi::um::winnt::{TokenElevation, HANDLE, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION, TOKEN_ELEVATION, TOKEN_QUERY};
+use winapi::um::winuser::{
+    FindWindowW, FlashWindowEx, GetClassLongPtrW, GetForegroundWindow, IsIconic, LoadImageW, SetClassLongPtrW, SetForegroundWindow, ShowWindow,
+    FLASHWINFO, FLASHW_ALL, FLASHW_TIMERNOFG, GCLP_HICON, GCLP_HICONSM, IMAGE_ICON, LR_DEFAULTSIZE, LR_LOADFROMFILE, SW_HIDE,
+    SW_MINIMIZE, SW_RESTORE,
+};
+use winreg::enums::{HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE, KEY_READ, KEY_WOW64_32KEY, KEY_WOW64_64KEY, KEY_WRITE};
+use winreg::RegKey;
+
+use super::{Platform, Priority, ProcessInfo};
+use crate::registry::{Hive, Location, View};
+
+pub struct WindowsPlatform;
+
+// winapi declares the mask as a DWORD, which would cut it to the first 32 CPUs.
+#[link(name = "kernel32")]
+extern "system" {
+    fn SetProcessAffinityMask(process: HANDLE, mask: usize) -> i32;
+}
+
+/// Our one tray icon; it carries the notifications and goes away in `clear_notifications`.
+const NOTIFY_ICON_ID: u32 = 1;
+
+fn wide(text: &OsStr) -> Vec<u16> {
+    text.encode_wide().chain(Some(0)).collect()
+}
+
+/// Copies `text` into a fixed-size UTF-16 buffer, cut to fit with room for the terminator.
+fn copy_wide(buffer: &mut [u16], text: &str) {
+    let units = text.encode_utf16().take(buffer.len() - 1).chain(Some(0));
+    for (slot, unit) in buffer.iter_mut().zip(units) {
+        *slot = unit;
+    }
+}
+
+fn notify_icon_data(hwnd: HWND) -> NOTIFYICONDATAW {
+    let mut data: NOTIFYICONDATAW = unsafe { mem::zeroed() };
+    data.cbSize = mem::size_of::<NOTIFYICONDATAW>() as u32;
+    data.hWnd = hwnd;
+    data.uID = NOTIFY_ICON_ID;
+    data
+}
+
+/// The hive of `location` and the access flag selecting its view.
+fn registry_root(location: &Location) -> (RegKey, u32) {
+    let hive = match location.hive {
+        Hive::LocalMachine => HKEY_LOCAL_MACHINE,
+        Hive::CurrentUser => HKEY_CURRENT_USER,
+    };
+    let view = match location.view {
+        View::Registry64 => KEY_WOW64_64KEY,
+        View::Registry32 => KEY_WOW64_32KEY,
+    };
+    (RegKey::predef(hive), view)
+}
+
+pub fn find_window(title: &str) -> HWND {
+    let wide_title = wide(OsStr::new(title));
+    unsafe { FindWindowW(ptr::null(), wide_title.as_ptr()) }
+}
+
+impl Platform for WindowsPlatform {
+    fn registry_string(&self, location: &Location, value: &str) -> Option<String> {
+        let (hive, view) = registry_root(location);
+        hive.open_subkey_with_flags(&location.key, KEY_READ | view).ok()?.get_value(value).ok()
+    }
+
+    fn set_registry_string(&self, location: &Location, value: &str, data: Option<&str>) -> bool {
+        let (hive, view) = registry_root(location);
+        match data {
+            Some(data) => hive.create_subkey_with_flags(&location.key, KEY_WRITE | view).and_then(|(key, _)| key.set_value(value, &data)).is_ok(),
+            None => match hive.open_subkey_with_flags(&location.key, KEY_WRITE | view).and_then(|key| key.delete_value(value)) {
+                Ok(()) => true,
+                Err(err) => err.kind() == std::io::ErrorKind::NotFound,
+            },
+        }
+    }
+
+    fn processes(&self) -> Vec<ProcessInfo> {
+        super::system_processes()
+    }
+
+    fn terminate(&self, pid: u32) -> bool {
+        super::terminate_process(pid)
+    }
+
+    fn tune_process(&self, pid: u32, priority: Priority, affinity: Option<u64>) -> bool {
+        let class = match priority {
+            Priority::Normal => NORMAL_PRIORITY_CLASS,
+            Priority::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+            Priority::High => HIGH_PRIORITY_CLASS,
+        };
+        unsafe {
+            let process = OpenProcess(PROCESS_SET_INFORMATION | PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if process.is_null() {
+                return false;
+            }
+            let mut ok = SetPriorityClass(process, class) != 0;
+            if let Some(mask) = affinity {
+                ok &= SetProcessAffinityMask(process, mask as usize) != 0;
+            }
+            CloseHandle(process);
+            ok
+        }
+    }
+
+    fn is_elevated(&self) -> bool {
+        unsafe {
+            let mut token: HANDLE = ptr::null_mut();
+            if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+                return false;
+            }
+            let mut elevation: TOKEN_ELEVATION = mem::zeroed();
+            let mut size = 0;
+            let ok = GetTokenInformation(
+                token,
+                TokenElevation,
+                &mut elevation as *mut _ as *mut _,
+                mem::size_of::<TOKEN_ELEVATION>() as u32,
+                &mut size,
+            );
+            CloseHandle(token);
+            ok != 0 && elevation.TokenIsElevated != 0
+        }
+    }
+
+    fn can_elevate(&self) -> bool {
+        !self.is_elevated()
+    }
+
+    /// Through the `runas` verb, which shows the UAC prompt.
+    fn run_elevated(&self, program: &Path, args: &[String]) -> Option<i32> {
+        let verb = wide(OsStr::new("runas"));
+        let file = wide(program.as_os_str());
+        let quoted: Vec<String> = args.iter().map(|arg| format!("\"{}\"", arg)).collect();
+        let parameters = wide(OsStr::new(&quoted.join(" ")));
+        unsafe {
+            let mut info: SHELLEXECUTEINFOW = mem::zeroed();
+            info.cbSize = mem::size_of::<SHELLEXECUTEINFOW>() as u32;
+            info.fMask = SEE_MASK_NOCLOSEPROCESS;
+            info.lpVerb = verb.as_ptr();
+            info.lpFile = file.as_ptr();
+            info.lpParameters = parameters.as_ptr();
+            info.nShow = SW_HIDE;
+            if ShellExecuteExW(&mut info) == 0 || info.hProcess.is_null() {
+                return None;
+            }
+            WaitForSingleObject(info.hProcess, INFINITE);
+            let mut code = 0;
+            let ok = GetExitCodeProcess(info.hProcess, &mut code);
+            CloseHandle(info.hProcess);
+            (ok != 0).then_some(code as i32)
+        }
+    }
+
+    /// A balloon from a tray icon, which Windows 10 and later show as a toast, and a flashing
+    /// taskbar button.
+    fn notify(&self, window_title: &str, title: &str, text: &str) -> bool {
+        let hwnd = find_window(window_title);
+        if hwnd.is_null() {
+            return false;
+        }
+        let mut data = notify_icon_data(hwnd);
+        data.uFlags = NIF_ICON | NIF_TIP | NIF_INFO;
+        data.dwInfoFlags = NIIF_INFO;
+        copy_wide(&mut data.szTip, window_title);
+        copy_wide(&mut data.szInfoTitle, title);
+        copy_wide(&mut data.szInfo, text);
+        unsafe {
+            data.hIcon = GetClassLongPtrW(hwnd, GCLP_HICONSM) as HICON;
+            if GetForegroundWindow() != hwnd {
+                let mut info = FLASHWINFO {
+                    cbSize: mem::size_of::<FLASHWINFO>() as u32,
+                    hwnd,
+                    dwFlags: FLASHW_ALL | FLASHW_TIMERNOFG,
+                    uCount: 0,
+                    dwTimeout: 0,
+                };
+                FlashWindowEx(&mut info);
+            }
+            Shell_NotifyIconW(NIM_MODIFY, &mut data) != 0 || Shell_NotifyIconW(NIM_ADD, &mut data) != 0
+        }
+    }
+
+    fn clear_notifications(&self, window_title: &str) {
+        let hwnd = find_window(window_title);
+        if !hwnd.is_null() {
+            unsafe { Shell_NotifyIconW(NIM_DELETE, &mut notify_icon_data(hwnd)) };
+        }
+    }
+
+    fn set_window_icon(&self, window_title: &str, icon_path: &Path) -> bool {
+        let hwnd = find_window(window_title);
+        if hwnd.is_null() {
+            return false;
+        }
+        let wide_path = wide(icon_path.as_os_str());
+        unsafe {
+            let icon = LoadImageW(ptr::null_mut(), wide_path.as_ptr(), IMAGE_ICON, 0, 0, LR_DEFAULTSIZE | LR_LOADFROMFILE) as HICON;
+            if icon.is_null() {
+                return false;
+            }
+            SetClassLongPtrW(hwnd, GCLP_HICON, icon as isize);
+            SetClassLongPtrW(hwnd, GCLP_HICONSM, icon as isize);
+        }
+        true
+    }
+
+    fn minimize_window(&self, window_title: &str) {
+        let hwnd = find_window(window_title);
+        if !hwnd.is_null() {
+            unsafe {
+                ShowWindow(hwnd, SW_MINIMIZE);
+            }
+        }
+    }
+
+    fn focus_window(&self, window_title: &str) {
+        let hwnd = find_window(window_title);
+        if !hwnd.is_null() {
+            unsafe {
+                if IsIconic(hwnd) != 0 {
+                    ShowWindow(hwnd, SW_RESTORE);
+                }
+                SetForegroundWindow(hwnd);
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! The Windows power plan. Laptops on Balanced or a power-saving plan often hold the GPU back
+//! far enough that an unlocked frame rate looks like it didn't take, so the app offers to run
+//! the game on High performance or Ultimate Performance and put the user's plan back afterwards.
+//! Plans are read and switched with `powercfg`, whose output is localized and in the console's
+//! OEM code page. Plans are told apart by GUID alone; names are only shown.
+
+use std::io;
+use std::process::Command;
+
+use tracing::{info, instrument};
+
+use crate::{MyError, Result};
+
+/// Windows' built-in High performance plan.
+pub const HIGH_PERFORMANCE: &str = "8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c";
+/// The Ultimate Performance template. Copies made from it get their own GUID and count as any
+/// other plan: only their name, which is localized and can be edited, would give them away.
+pub const ULTIMATE_PERFORMANCE: &str = "e9a42b02-d5df-448d-aa00-03f14749eb61";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scheme {
+    pub guid: String,
+    pub name: String,
+    pub active: bool,
+}
+
+impl Scheme {
+    /// Whether the plan runs the hardware at full speed.
+    pub fn is_fast(&self) -> bool {
+        self.rank().is_some()
+    }
+
+    /// Ultimate Performance before High performance.
+    fn rank(&self) -> Option<u8> {
+        match self.guid.as_str() {
+            ULTIMATE_PERFORMANCE => Some(0),
+            HIGH_PERFORMANCE => Some(1),
+            _ => None,
+        }
+    }
+}
+
+fn is_guid(text: &str) -> bool {
+    text.len() == 36
+        && text.char_indices().all(|(i, c)| if matches!(i, 8 | 13 | 18 | 23) { c == '-' } else { c.is_ascii_hexdigit() })
+}
+
+/// The plans in `powercfg /list` output; the active one ends with `*`.
+pub fn parse_list(text: &str) -> Vec<Scheme> {
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let guid = line.split_whitespace().find(|word| is_guid(word))?.to_ascii_lowercase();
+            let name = line.find('(').zip(line.rfind(')')).and_then(|(open, close)| line.get(open + 1..close)).unwrap_or(&guid).to_string();
+            Some(Scheme { guid, name, active: line.ends_with('*') })
+        })
+        .collect()
+}
+
+/// The fastest plan in `schemes`.
+pub fn fastest(schemes: &[Scheme]) -> Option<&Scheme> {
+    schemes.iter().filter_map(|scheme| Some((scheme.rank()?, scheme))).min_by_key(|(rank, _)| *rank).map(|(_, scheme)| scheme)
+}
+
+fn powercfg(args: &[&str]) -> Result<String> {
+    if !cfg!(windows) {
+        return Err(MyError::Io(io::Error::from(io::ErrorKind::Unsupported)));
+    }
+    let mut command = Command::new("powercfg");
+    command.args(args);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command.output()?;
+    let stdout = decode_oem(&output.stdout);
+    if !output.status.success() {
+        return Err(MyError::Io(io::Error::other(format!("powercfg failed: {}", stdout.trim()))));
+    }
+    Ok(stdout)
+}
+
+/// Console output in the OEM code page, e.g. 850 for a German Windows.
+#[cfg(windows)]
+fn decode_oem(bytes: &[u8]) -> String {
+    use winapi::um::stringapiset::MultiByteToWideChar;
+    use winapi::um::winnls::GetOEMCP;
+
+    let Ok(len) = i32::try_from(bytes.len()) else {
+        return String::from_utf8_lossy(bytes).into_owned();
+    };
+    if len == 0 {
+        return String::new();
+    }
+    // SAFETY: the first call only measures; the second writes at most `wide.len()` units.
+    unsafe {
+        let code_page = GetOEMCP();
+        let needed = MultiByteToWideChar(code_page, 0, bytes.as_ptr().cast(), len, std::ptr::null_mut(), 0);
+        if needed <= 0 {
+            return String::from_utf8_lossy(bytes).into_owned();
+        }
+        let mut wide = vec![0u16; needed as usize];
+        let written = MultiByteToWideChar(code_page, 0, bytes.as_ptr().cast(), len, wide.as_mut_ptr(), needed);
+        String::from_utf16_lossy(&wide[..written.max(0) as usize])
+    }
+}
+
+#[cfg(not(windows))]
+fn decode_oem(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+/// Every power plan on this PC; empty where there are none to pick, e.g. off Windows.
+pub fn schemes() -> Vec<Scheme> {
+    powercfg(&["/list"]).map(|text| parse_list(&text)).unwrap_or_default()
+}
+
+#[instrument(err(level = "warn"))]
+pub fn set_active(guid: &str) -> Result<()> {
+    powercfg(&["/setactive", guid])?;
+    info!(guid, "switched power plan");
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Measuring the frame rate the game actually presents, to confirm a written limit took effect
+//! in-engine. PresentMon (Intel's open-source ETW frame capture tool) does the capture; we run
+//! it for a few seconds against the game's processes and read its CSV. It is not bundled, so
+//! users drop it next to the app or in the app folder.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::{info, instrument};
+
+use crate::paths::app_dir;
+use crate::{MyError, Result};
+
+/// Where PresentMon releases are published, for the message when it is missing.
+pub const DOWNLOAD_URL: &str = "https://github.com/GameTechDev/PresentMon/releases";
+/// Seconds sampled by default; long enough to smooth out loading hitches.
+pub const CAPTURE_SECONDS: u32 = 10;
+/// The limit the client enforces when its frame-rate setting is not unlocked.
+const STOCK_CAP: f64 = 60.0;
+/// How close to the limit counts as reaching it.
+const TOLERANCE: f64 = 0.05;
+/// Frame-time columns, newest PresentMon first.
+const FRAME_TIME_COLUMNS: &[&str] = &["MsBetweenPresents", "FrameTime"];
+
+/// Frame rates seen during a capture.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrameSample {
+    pub frames: usize,
+    pub average_fps: f64,
+    /// The frame rate of the slowest 1% of frames.
+    pub low_fps: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// Running at the limit.
+    AtLimit,
+    /// Above the stock cap but short of the limit: unlocked, held back by the hardware.
+    Unlocked,
+    /// At or below the stock cap although the limit is higher; the game has not picked it up.
+    Capped,
+    /// Faster than the limit, which something else overrides.
+    AboveLimit,
+}
+
+impl FrameSample {
+    pub fn verdict(&self, limit: i64) -> Verdict {
+        let limit = limit as f64;
+        if self.average_fps > limit * (1.0 + TOLERANCE) {
+            Verdict::AboveLimit
+        } else if self.average_fps >= limit * (1.0 - TOLERANCE) {
+            Verdict::AtLimit
+        } else if limit > STOCK_CAP && self.average_fps <= STOCK_CAP * (1.0 + TOLERANCE) {
+            Verdict::Capped
+        } else {
+            Verdict::Unlocked
+        }
+    }
+
+    /// One line for the UI.
+    pub fn describe(&self, limit: i64) -> String {
+        let measured = format!("Measured {:.0} FPS on average ({:.0} FPS 1% low)", self.average_fps, self.low_fps);
+        match self.verdict(limit) {
+            Verdict::AtLimit => format!("{}: the {} FPS limit is in effect.", measured, limit),
+            Verdict::Unlocked => format!("{}: unlocked, but the PC does not reach {} FPS here.", measured, limit),
+            Verdict::Capped => format!("{}: still at the stock cap. Restart the game to pick up {} FPS.", measured, limit),
+            Verdict::AboveLimit => format!("{}: above the {} FPS limit, so the game is not limiting frames.", measured, limit),
+        }
+    }
+}
+
+/// Frame rates in PresentMon CSV output; `None` when it holds no frames.
+pub fn parse_csv(text: &str) -> Option<FrameSample> {
+    let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<&str> = lines.next()?.split(',').map(str::trim).collect();
+    let column = FRAME_TIME_COLUMNS.iter().find_map(|name| header.iter().position(|column| column.eq_ignore_ascii_case(name)))?;
+    let mut frame_times: Vec<f64> = lines
+        .filter_map(|line| line.split(',').nth(column)?.trim().parse::<f64>().ok())
+        .filter(|ms| *ms > 0.0 && ms.is_finite())
+        .collect();
+    if frame_times.is_empty() {
+        return None;
+    }
+    let total: f64 = frame_times.iter().sum();
+    frame_times.sort_by(|a, b| b.total_cmp(a));
+    let slowest = &frame_times[..frame_times.len().div_ceil(100)];
+    let slowest_mean = slowest.iter().sum::<f64>() / slowest.len() as f64;
+    Some(FrameSample {
+        frames: frame_times.len(),
+        average_fps: 1000.0 * frame_times.len() as f64 / total,
+        low_fps: 1000.0 / slowest_mean,
+    })
+}
+
+/// A PresentMon executable in `dir`, named as its releases are (`PresentMon-2.3.0-x64.exe`).
+pub fn find_in(dir: &Path) -> Option<PathBuf> {
+    let mut found: Vec<PathBuf> = fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default().to_ascii_lowercase();
+            name.starts_with("presentmon") && name.ends_with(".exe") && path.is_file()
+        })
+        .collect();
+    found.sort_by_key(|path| version_of(path));
+    found.pop()
+}
+
+/// The version numbers in a name such as `PresentMon-2.10.0-x64.exe`, compared as numbers so
+/// 2.10 sorts after 2.9; names without one sort first.
+fn version_of(path: &Path) -> Vec<u64> {
+    let name = path.file_stem().and_then(|name| name.to_str()).unwrap_or_default();
+    name.split('-').find(|part| part.starts_with(|c: char| c.is_ascii_digit())).map_or_else(Vec::new, |version| {
+        version.split('.').map(|part| part.parse().unwrap_or_default()).collect()
+    })
+}
+
+/// PresentMon next to the app, in the app folder, or on the PATH.
+pub fn find() -> Option<PathBuf> {
+    let exe_dir = env::current_exe().ok().and_then(|exe| exe.parent().map(Path::to_path_buf));
+    let path_dirs = env::var_os("PATH").map(|path| env::split_paths(&path).collect::<Vec<_>>()).unwrap_or_default();
+    exe_dir.into_iter().chain([app_dir()]).chain(path_dirs).find_map(|dir| find_in(&dir))
+}
+
+/// Runs `presentmon` for `seconds` against `process_names` and reads what it saw. ETW capture
+/// needs administrator rights or membership in Performance Log Users.
+#[instrument(err(level = "warn"))]
+pub fn capture(presentmon: &Path, process_names: &[&str], seconds: u32) -> Result<FrameSample> {
+    let mut command = Command::new(presentmon);
+    for name in process_names {
+        command.args(["--process_name", name]);
+    }
+    command.args(["--output_stdout", "--stop_existing_session", "--session_name", "wuwa-ploom", "--terminate_after_timed"]);
+    command.args(["--timed", &seconds.to_string()]);
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    let output = command.output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if let Some(sample) = parse_csv(&stdout) {
+        info!(?sample, "captured frame rate");
+        return Ok(sample);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.to_ascii_lowercase().contains("access denied") || stderr.contains("administrator") {
+        return Err(MyError::Permission("PresentMon needs administrator rights to capture frames".into()));
+    }
+    let detail = stderr.lines().find(|line| !line.trim().is_empty()).unwrap_or("no frames from the game were seen");
+    Err(MyError::Io(io::Error::other(format!("PresentMon: {}", detail.trim()))))
+}
@@ -0,0 +1,110 @@
+use std::collections::BTreeSet;
+
+use eframe::egui;
+use serde_json::Value;
+
+use crate::db::Patch;
+use crate::diff_view;
+use crate::json_diff;
+use crate::metadata::{Metadata, Problem};
+use crate::presets::{self, PresetFile};
+
+/// An imported preset being reviewed; only ticked keys are staged.
+pub struct PresetReview {
+    preset: PresetFile,
+    selected: BTreeSet<String>,
+    /// Settings moved from another PC, whose resolution and display keys start unticked.
+    transfer: bool,
+}
+
+pub enum ReviewOutcome {
+    Open,
+    Stage(Patch),
+    Close,
+}
+
+impl PresetReview {
+    /// Starts with every key ticked except the ones that fail validation.
+    pub fn new(preset: PresetFile, metadata: &Metadata, game_version: Option<&str>) -> Self {
+        let selected = preset
+            .settings
+            .iter()
+            .filter(|(key, value)| {
+                !matches!(metadata.get(key).and_then(|meta| meta.check(value, game_version)), Some(Problem::Invalid(_)))
+            })
+            .map(|(key, _)| key.clone())
+            .collect();
+        Self { preset, selected, transfer: false }
+    }
+
+    /// `new` for a file exported on another PC: keys tied to that PC's monitor start unticked
+    /// and are labelled, so this PC keeps its own.
+    pub fn transfer(preset: PresetFile, metadata: &Metadata, game_version: Option<&str>) -> Self {
+        let mut review = Self::new(preset, metadata, game_version);
+        review.selected.retain(|key| !presets::is_machine_specific(key));
+        review.transfer = true;
+        review
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, metadata: &Metadata, setting: &Value, game_version: Option<&str>) -> ReviewOutcome {
+        let mut outcome = ReviewOutcome::Open;
+        egui::Window::new(format!("Preset: {}", self.preset.name)).collapsible(false).show(ctx, |ui| {
+            if !self.preset.author.is_empty() {
+                ui.label(format!("By {}", self.preset.author));
+            }
+            if !self.preset.description.is_empty() {
+                ui.label(&self.preset.description);
+            }
+            if self.transfer {
+                ui.label("Keys marked \"other PC\" describe the monitor it was exported on and are unticked; tick them to copy them anyway.");
+            }
+            ui.separator();
+            egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                egui::Grid::new("preset_review").striped(true).num_columns(3).show(ui, |ui| {
+                    for (key, value) in &self.preset.settings {
+                        let meta = metadata.get(key);
+                        let label = meta.map_or(key.as_str(), |meta| meta.label.as_str());
+                        let problem = meta.and_then(|meta| meta.check(value, game_version));
+                        let invalid = matches!(problem, Some(Problem::Invalid(_)));
+                        let mut ticked = self.selected.contains(key);
+                        let toggle = ui.add_enabled(!invalid, egui::Checkbox::new(&mut ticked, label)).on_hover_text(key);
+                        if toggle.changed() {
+                            if ticked {
+                                self.selected.insert(key.clone());
+                            } else {
+                                self.selected.remove(key);
+                            }
+                        }
+                        diff_view::show_one(ui, key, &json_diff::member(setting.get(key), Some(value)));
+                        match problem {
+                            Some(Problem::Invalid(text)) => ui.colored_label(ui.visuals().error_fg_color, text),
+                            Some(Problem::Unavailable(text)) => ui.colored_label(ui.visuals().warn_fg_color, text),
+                            None if self.transfer && presets::is_machine_specific(key) => ui.weak("other PC"),
+                            None if metadata.get(key).is_none() => ui.weak("undescribed key"),
+                            None => ui.label(""),
+                        };
+                        ui.end_row();
+                    }
+                });
+            });
+            ui.separator();
+            ui.horizontal(|ui| {
+                let stage = ui.add_enabled(!self.selected.is_empty(), egui::Button::new(format!("Stage {} key(s)", self.selected.len())));
+                if stage.on_hover_text("Adds the ticked keys to the Settings tab draft for review").clicked() {
+                    let patch = self
+                        .preset
+                        .settings
+                        .iter()
+                        .filter(|(key, _)| self.selected.contains(*key))
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect();
+                    outcome = ReviewOutcome::Stage(patch);
+                }
+                if ui.button("Close").clicked() {
+                    outcome = ReviewOutcome::Close;
+                }
+            });
+        });
+        outcome
+    }
+}
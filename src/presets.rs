@@ -0,0 +1,126 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+
+use serde_json::Value;
+use tracing::{instrument, warn};
+
+use crate::db::Patch;
+use crate::schema::{self, Migration};
+use crate::store;
+use crate::{MyError, Result};
+
+/// Upgrades for preset and profile files, oldest first; see `schema::migrate`.
+const MIGRATIONS: &[Migration] = &[schema::unversioned_to_v1];
+pub const PRESET_VERSION: u64 = MIGRATIONS.len() as u64;
+/// Starts every share code, naming its format.
+const SHARE_PREFIX: &str = "WWP1.";
+/// Settings blobs are a few kilobytes; anything inflating past this is not a share code.
+const MAX_SHARED_BYTES: u64 = 256 * 1024;
+/// Keys that describe this PC's monitor rather than a taste in graphics: the resolution, which
+/// display the game opens on and HDR calibration. Listed by name, as brightness and UI options
+/// share words with them.
+const MACHINE_SPECIFIC: &[&str] =
+    &["KeyPcResolutionWidth", "KeyPcResolutionHeight", "KeyPcDisplayIndex", "KeyHdrEnable", "KeyHdrMaxBrightness", "KeyHdrUiBrightness"];
+
+/// A shared settings file: a few descriptive fields and the GameQualitySetting fields it sets.
+#[derive(Serialize, Deserialize)]
+pub struct PresetFile {
+    #[serde(default)]
+    pub version: u64,
+    pub name: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub description: String,
+    pub settings: Patch,
+}
+
+/// Whether `key` only makes sense on the PC it was saved on, so a transfer leaves it alone.
+pub fn is_machine_specific(key: &str) -> bool {
+    MACHINE_SPECIFIC.contains(&key)
+}
+
+/// The whole GameQualitySetting blob as a preset called `name`.
+pub fn from_setting(name: &str, setting: &Value) -> Result<PresetFile> {
+    let settings = setting
+        .as_object()
+        .cloned()
+        .ok_or_else(|| MyError::Schema("GameQualitySetting is not a JSON object".into()))?;
+    Ok(PresetFile { version: PRESET_VERSION, name: name.trim().to_string(), author: String::new(), description: String::new(), settings })
+}
+
+/// This PC's name, for telling transfer files apart.
+pub fn machine_name() -> String {
+    env::var("COMPUTERNAME").or_else(|_| env::var("HOSTNAME")).unwrap_or_else(|_| "this PC".to_string())
+}
+
+/// Writes every setting to `path` for importing on another PC.
+#[instrument(skip(setting), err(level = "warn"))]
+pub fn export_transfer(path: &Path, setting: &Value) -> Result<()> {
+    let machine = machine_name();
+    let mut preset = from_setting(&format!("Settings from {}", machine), setting)?;
+    preset.author = machine;
+    preset.description = "Every graphics setting, exported for another PC. Resolution and display keys stay unticked on import.".into();
+    store::write_json(path, &preset)
+}
+
+/// `preset` as one line of text to paste into a chat: `WWP1.` and the deflated JSON in URL-safe
+/// base64.
+pub fn to_share_code(preset: &PresetFile) -> Result<String> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&serde_json::to_vec(preset)?)?;
+    Ok(format!("{}{}", SHARE_PREFIX, URL_SAFE_NO_PAD.encode(encoder.finish()?)))
+}
+
+/// The preset in a pasted message: a share code anywhere in `text`, or a preset file's JSON.
+pub fn from_share_code(text: &str) -> Result<PresetFile> {
+    let origin = Path::new("pasted text");
+    let Some(start) = text.find(SHARE_PREFIX) else {
+        let json = text.trim().trim_matches('`').trim_start_matches("json").trim();
+        let value = serde_json::from_str(json).map_err(|_| MyError::Schema("no settings code or preset JSON found".into()))?;
+        return preset_from_value(value, origin);
+    };
+    let code: String = text[start + SHARE_PREFIX.len()..]
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+        .collect();
+    let compressed = URL_SAFE_NO_PAD.decode(code).map_err(|err| MyError::Schema(format!("damaged settings code: {}", err)))?;
+    let mut json = Vec::new();
+    DeflateDecoder::new(compressed.as_slice()).take(MAX_SHARED_BYTES).read_to_end(&mut json)?;
+    preset_from_value(serde_json::from_slice(&json)?, origin)
+}
+
+pub fn read_preset(path: &Path) -> Result<PresetFile> {
+    file_exists_at(path)?;
+    preset_from_value(serde_json::from_str(&fs::read_to_string(path)?)?, path)
+}
+
+/// `read_preset` for JSON already read from `path`.
+pub fn preset_from_value(mut value: Value, path: &Path) -> Result<PresetFile> {
+    if let Err(err) = schema::migrate(&mut value, MIGRATIONS) {
+        // Unknown newer fields are ignored; the settings themselves are still worth reading.
+        warn!(path = %path.display(), %err, "reading preset anyway");
+    }
+    let preset: PresetFile = serde_json::from_value(value)?;
+    if preset.settings.is_empty() {
+        return Err(MyError::Schema("the preset sets no keys".into()));
+    }
+    Ok(preset)
+}
+
+fn file_exists_at(path: &Path) -> Result<()> {
+    if path.is_file() {
+        Ok(())
+    } else {
+        Err(MyError::Io(io::Error::new(io::ErrorKind::NotFound, format!("{} does not exist", path.display()))))
+    }
+}
@@ -0,0 +1,78 @@
+//! Rows of LocalStorage that identify the player's account or this device. The client keeps
+//! them next to the settings, so the Keys tab points them out for users who want to know what
+//! is stored locally. They are shown, never edited: the game needs them to sign in and to
+//! recognise the device, and deleting one can log the player out or trigger a new device check.
+
+/// What a row identifies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Identifier {
+    Account,
+    Device,
+}
+
+impl Identifier {
+    pub fn name(self) -> &'static str {
+        match self {
+            Identifier::Account => "account identifier",
+            Identifier::Device => "device identifier",
+        }
+    }
+
+    pub fn warning(self) -> &'static str {
+        match self {
+            Identifier::Account => "The game signs in with this row. Don't delete or change it, or you may be logged out or lose the saved login.",
+            Identifier::Device => "The game recognises this PC by this row. Don't delete or change it, or the next login may ask to verify the device.",
+        }
+    }
+}
+
+/// Words in key names, whole or joined with their neighbour (`user` `id` -> `userid`).
+const ACCOUNT_WORDS: &[&str] = &["account", "accountid", "uid", "userid", "username", "playerid", "roleid", "openid", "token", "login", "sdkuid"];
+const DEVICE_WORDS: &[&str] = &["device", "deviceid", "uuid", "guid", "machineid", "hwid", "androidid", "idfa", "idfv"];
+
+/// `KeyAccountId` -> `key`, `account`, `id`; also splits on `_`, `.`, `-` and digits.
+fn words(key: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut previous_lower = false;
+    for c in key.chars() {
+        if !c.is_ascii_alphabetic() {
+            words.push(std::mem::take(&mut word));
+            previous_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && previous_lower {
+            words.push(std::mem::take(&mut word));
+        }
+        previous_lower = c.is_ascii_lowercase();
+        word.push(c.to_ascii_lowercase());
+    }
+    words.push(word);
+    words.retain(|word| !word.is_empty());
+    words
+}
+
+/// Whether the row `key` holds an account or device identifier, judged by its name.
+pub fn classify(key: &str) -> Option<Identifier> {
+    let words = words(key);
+    let joined = words.windows(2).map(|pair| pair.concat());
+    let candidates: Vec<String> = words.iter().cloned().chain(joined).collect();
+    let has = |list: &[&str]| candidates.iter().any(|word| list.contains(&word.as_str()));
+    if has(DEVICE_WORDS) {
+        Some(Identifier::Device)
+    } else if has(ACCOUNT_WORDS) {
+        Some(Identifier::Account)
+    } else {
+        None
+    }
+}
+
+/// `value` with all but its first and last two characters hidden, for showing on screen.
+pub fn mask(value: &str) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.len() <= 8 {
+        return "•".repeat(chars.len());
+    }
+    let hidden = "•".repeat(chars.len() - 4);
+    format!("{}{}{}", chars[..2].iter().collect::<String>(), hidden, chars[chars.len() - 2..].iter().collect::<String>())
+}
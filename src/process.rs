@@ -0,0 +1,133 @@
+//! Watching the game client: whether it runs, where its executable is, and closing it.
+
+use std::path::Path;
+
+use tracing::{info, instrument, warn};
+
+use crate::detect::db_path_for_exe;
+use crate::games;
+use crate::platform::{Platform, Priority, ProcessInfo};
+
+/// Longest process name Linux reports; Wine processes show up cut to this length.
+const TRUNCATED_NAME_LEN: usize = 15;
+
+/// Whether `name` is one of the active game's executables.
+pub fn is_game_process_name(name: &str) -> bool {
+    games::active().process_names().iter().any(|game| {
+        name.eq_ignore_ascii_case(game)
+            || (name.len() == TRUNCATED_NAME_LEN && game.get(..TRUNCATED_NAME_LEN).is_some_and(|start| name.eq_ignore_ascii_case(start)))
+    })
+}
+
+pub fn game_processes(platform: &dyn Platform) -> Vec<ProcessInfo> {
+    platform.processes().into_iter().filter(|process| is_game_process_name(&process.name)).collect()
+}
+
+pub fn is_game_running(platform: &dyn Platform) -> bool {
+    !game_processes(platform).is_empty()
+}
+
+/// Kills the game processes running from the install that keeps `db_path` and returns how many
+/// went; another install, or a process whose executable can't be seen, is left running. Callers
+/// confirm with the user first: the game loses unsaved state and skips writing its settings on
+/// exit.
+#[instrument(skip(platform))]
+pub fn terminate_game(platform: &dyn Platform, db_path: &Path) -> usize {
+    let processes: Vec<ProcessInfo> = game_processes(platform)
+        .into_iter()
+        .filter(|process| process.exe.as_deref().and_then(db_path_for_exe).is_some_and(|db| db == db_path))
+        .collect();
+    let killed = processes.iter().filter(|process| platform.terminate(process.pid)).count();
+    if killed < processes.len() {
+        warn!(killed, found = processes.len(), "could not close every game process");
+    }
+    killed
+}
+
+/// The affinity mask for a core list such as `0-5,8`; `None` when empty or not a valid list.
+/// Only the first 64 logical CPUs can be picked.
+pub fn core_mask(text: &str) -> Option<u64> {
+    let mut mask = 0u64;
+    for part in text.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let (first, last) = part.split_once('-').unwrap_or((part, part));
+        let (first, last): (u32, u32) = (first.trim().parse().ok()?, last.trim().parse().ok()?);
+        if first > last || last >= u64::BITS {
+            return None;
+        }
+        for core in first..=last {
+            mask |= 1 << core;
+        }
+    }
+    (mask != 0).then_some(mask)
+}
+
+/// Gives the game processes in `pids` not yet in `tuned` the priority and affinity, then adds
+/// them there; returns the ones that refused, e.g. protected by anti-cheat.
+#[instrument(skip(platform, tuned))]
+pub fn tune_game(platform: &dyn Platform, pids: &[u32], tuned: &mut Vec<u32>, priority: Priority, affinity: Option<u64>) -> Vec<u32> {
+    let mut refused = Vec::new();
+    for &pid in pids {
+        if tuned.contains(&pid) {
+            continue;
+        }
+        tuned.push(pid);
+        if platform.tune_process(pid, priority, affinity) {
+            info!(pid, ?priority, affinity, "tuned game process");
+        } else {
+            warn!(pid, "could not tune game process");
+            refused.push(pid);
+        }
+    }
+    refused
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GameEvent {
+    Started(ProcessInfo),
+    Stopped,
+}
+
+/// Remembers whether the game ran at the last poll, so callers see starts and stops as events.
+#[derive(Default)]
+pub struct GameWatcher {
+    running: Option<ProcessInfo>,
+    /// Every game process seen at the last poll.
+    pids: Vec<u32>,
+}
+
+impl GameWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The game process seen at the last poll; the one with a known executable path if several run.
+    pub fn running(&self) -> Option<&ProcessInfo> {
+        self.running.as_ref()
+    }
+
+    /// Every game process seen at the last poll; the client may start a moment after its launcher.
+    pub fn pids(&self) -> &[u32] {
+        &self.pids
+    }
+
+    pub fn poll(&mut self, platform: &dyn Platform) -> Option<GameEvent> {
+        let mut found = game_processes(platform);
+        self.pids = found.iter().map(|process| process.pid).collect();
+        found.sort_by_key(|process| process.exe.is_none());
+        match (self.running.take(), found.into_iter().next()) {
+            (None, Some(process)) => {
+                info!(pid = process.pid, exe = ?process.exe, "game started");
+                self.running = Some(process.clone());
+                Some(GameEvent::Started(process))
+            }
+            (Some(_), None) => {
+                info!("game stopped");
+                Some(GameEvent::Stopped)
+            }
+            (_, now) => {
+                self.running = now;
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use serde_json::Value;
+use tracing::{info, instrument};
+
+use crate::paths::app_dir;
+use crate::presets::{self, PresetFile};
+use crate::store;
+use crate::{MyError, Result};
+
+static SYNC_FOLDER: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Keeps profiles in `folder`, e.g. inside OneDrive, Dropbox or Syncthing, so other PCs see
+/// them; `None` keeps them in the app folder.
+pub fn set_sync_folder(folder: Option<PathBuf>) {
+    if let Ok(mut current) = SYNC_FOLDER.write() {
+        *current = folder;
+    }
+}
+
+/// Profiles are preset files, so a saved profile can also be shared as a preset. They live in
+/// the sync folder when one is set.
+pub fn profiles_dir() -> PathBuf {
+    let folder = SYNC_FOLDER.read().ok().and_then(|folder| folder.clone());
+    folder.unwrap_or_else(app_dir).join("profiles")
+}
+
+/// Keeps names usable as Windows file names.
+fn file_name(name: &str) -> String {
+    let cleaned: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == ' ' || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    format!("{}.json", cleaned)
+}
+
+pub fn list() -> Vec<String> {
+    let Ok(entries) = fs::read_dir(profiles_dir()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "json"))
+        .filter_map(|path| read(&path).ok())
+        .map(|preset| preset.name)
+        .collect();
+    names.sort();
+    // Conflicted copies carry the same name; see `conflicts`.
+    names.dedup();
+    names
+}
+
+/// Stores the whole GameQualitySetting blob under `name`, replacing a profile of the same name.
+#[instrument(skip(setting), err(level = "warn"))]
+pub fn save(name: &str, setting: &Value) -> Result<PathBuf> {
+    let preset = presets::from_setting(name, setting)?;
+    let dir = profiles_dir();
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(file_name(name));
+    store::write_json(&path, &preset)?;
+    Ok(path)
+}
+
+pub fn load(name: &str) -> Result<PresetFile> {
+    read(&profiles_dir().join(file_name(name)))
+}
+
+/// A profile that can't be parsed is an error but stays where it is: in a sync folder it may be
+/// half-synced, or belong to another PC, so listing skips it instead of renaming it.
+fn read(path: &Path) -> Result<PresetFile> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(MyError::Io(io::Error::new(io::ErrorKind::NotFound, format!("{} does not exist", path.display()))));
+        }
+        Err(err) => return Err(err.into()),
+    };
+    presets::preset_from_value(serde_json::from_str(&text)?, path)
+}
+
+/// Copies the profiles in `from` that `to` lacks, e.g. when moving to a sync folder; returns
+/// how many.
+#[instrument(err(level = "warn"))]
+pub fn copy_missing(from: &Path, to: &Path) -> Result<usize> {
+    let Ok(entries) = fs::read_dir(from) else {
+        return Ok(0);
+    };
+    fs::create_dir_all(to)?;
+    let mut copied = 0;
+    for path in entries.flatten().map(|entry| entry.path()).filter(|path| path.extension().is_some_and(|extension| extension == "json")) {
+        let target = to.join(path.file_name().unwrap_or_default());
+        if !target.exists() {
+            fs::copy(&path, &target)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// A profile with several files, as sync tools leave behind when two PCs changed it before
+/// syncing: `High (PC's conflicted copy 2024-05-01).json` from Dropbox, `High-DESKTOP.json` from
+/// OneDrive, `High.sync-conflict-20240501-101500-ABCDEFG.json` from Syncthing. Every copy still
+/// holds the profile's name, which is how they are found whatever the tool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    pub name: String,
+    /// Each file with when it was last changed, newest first.
+    pub versions: Vec<(PathBuf, SystemTime)>,
+}
+
+pub fn conflicts() -> Vec<Conflict> {
+    conflicts_in(&profiles_dir())
+}
+
+pub fn conflicts_in(dir: &Path) -> Vec<Conflict> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut conflicts: Vec<Conflict> = Vec::new();
+    for path in entries.flatten().map(|entry| entry.path()).filter(|path| path.extension().is_some_and(|extension| extension == "json")) {
+        let Ok(preset) = read(&path) else {
+            continue;
+        };
+        let modified = fs::metadata(&path).and_then(|metadata| metadata.modified()).unwrap_or(SystemTime::UNIX_EPOCH);
+        match conflicts.iter_mut().find(|conflict| conflict.name == preset.name) {
+            Some(conflict) => conflict.versions.push((path, modified)),
+            None => conflicts.push(Conflict { name: preset.name, versions: vec![(path, modified)] }),
+        }
+    }
+    conflicts.retain(|conflict| conflict.versions.len() > 1);
+    for conflict in &mut conflicts {
+        conflict.versions.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    }
+    conflicts.sort_by(|a, b| a.name.cmp(&b.name));
+    conflicts
+}
+
+/// Makes `keep`, one of `conflict`'s files, the profile and deletes the other versions.
+#[instrument(skip(conflict), fields(name = %conflict.name), err(level = "warn"))]
+pub fn resolve(conflict: &Conflict, keep: &Path) -> Result<()> {
+    let dir = keep.parent().unwrap_or(Path::new("."));
+    let target = dir.join(file_name(&conflict.name));
+    if keep != target {
+        store::write_atomic(&target, &fs::read(keep)?)?;
+    }
+    for (path, _) in conflict.versions.iter().filter(|(path, _)| *path != target) {
+        fs::remove_file(path)?;
+    }
+    info!(kept = %keep.display(), "resolved sync conflict");
+    Ok(())
+}
+
+#[instrument(err(level = "warn"))]
+pub fn delete(name: &str) -> Result<()> {
+    fs::remove_file(profiles_dir().join(file_name(name)))?;
+    Ok(())
+}
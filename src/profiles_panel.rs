@@ -0,0 +1,67 @@
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use eframe::egui;
+use wuwa_ploom_core::profiles::Conflict;
+
+pub enum ProfileAction {
+    Save,
+    Restore(String),
+    Delete(String),
+    /// Keep this version of the conflict and drop the others.
+    Resolve(Conflict, PathBuf),
+}
+
+fn age(modified: SystemTime) -> String {
+    let minutes = SystemTime::now().duration_since(modified).map(|age| age.as_secs() / 60).unwrap_or_default();
+    match minutes {
+        0 => "just now".into(),
+        1..=59 => format!("{} min ago", minutes),
+        60..=2879 => format!("{} h ago", minutes / 60),
+        _ => format!("{} days ago", minutes / 1440),
+    }
+}
+
+fn show_conflict(ui: &mut egui::Ui, conflict: &Conflict, action: &mut Option<ProfileAction>) {
+    ui.colored_label(ui.visuals().warn_fg_color, format!("⚠ \"{}\" was changed on more than one PC before syncing:", conflict.name));
+    for (index, (path, modified)) in conflict.versions.iter().enumerate() {
+        ui.horizontal(|ui| {
+            let file = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            ui.label(format!("{} ({}{})", file, age(*modified), if index == 0 { ", newest" } else { "" }));
+            if ui.small_button("Keep this one").on_hover_text("Deletes the other versions").clicked() {
+                *action = Some(ProfileAction::Resolve(conflict.clone(), path.clone()));
+            }
+        });
+    }
+}
+
+pub fn show(ui: &mut egui::Ui, new_name: &mut String, names: &[String], conflicts: &[Conflict]) -> Option<ProfileAction> {
+    let mut action = None;
+    egui::CollapsingHeader::new(format!("Profiles ({})", names.len())).show(ui, |ui| {
+        for conflict in conflicts {
+            show_conflict(ui, conflict, &mut action);
+        }
+        ui.horizontal(|ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(new_name);
+            let save = ui
+                .add_enabled(!new_name.trim().is_empty(), egui::Button::new("Save current settings"))
+                .on_hover_text("Capture the stored settings, e.g. to restore them after a game update resets them");
+            if save.clicked() {
+                action = Some(ProfileAction::Save);
+            }
+        });
+        for name in names {
+            ui.horizontal(|ui| {
+                ui.label(name);
+                if ui.small_button("Restore...").clicked() {
+                    action = Some(ProfileAction::Restore(name.clone()));
+                }
+                if ui.small_button("Delete").clicked() {
+                    action = Some(ProfileAction::Delete(name.clone()));
+                }
+            });
+        }
+    });
+    action
+}
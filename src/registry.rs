@@ -0,0 +1,103 @@
+//! Where the launcher may have registered the game: machine-wide or per user, in either
+//! registry view, under the value names different launcher versions used. Lookups return every
+//! hit with where it came from, so detection can take the first and diagnostics can list them all.
+
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hive {
+    LocalMachine,
+    CurrentUser,
+}
+
+/// Half of the registry on 64-bit Windows. 32-bit programs such as the launcher see `Registry32`,
+/// which lives under `WOW6432Node` in the 64-bit view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum View {
+    Registry64,
+    Registry32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Location {
+    pub hive: Hive,
+    pub view: View,
+    /// Path below the hive, without any `WOW6432Node` part; `view` selects that.
+    pub key: String,
+}
+
+impl Location {
+    pub fn new(hive: Hive, view: View, key: &str) -> Self {
+        Self { hive, view, key: key.to_string() }
+    }
+
+    /// `HKEY_LOCAL_MACHINE\key` as a 64-bit program sees it.
+    pub fn machine(key: &str) -> Self {
+        Self::new(Hive::LocalMachine, View::Registry64, key)
+    }
+
+    /// The key as spelled in the 64-bit view: machine-wide `Software` keys of the 32-bit view
+    /// gain a `WOW6432Node` part. Per-user keys are shared between the views.
+    pub fn redirected_key(&self) -> String {
+        let software = self.key.get(..9).filter(|start| start.eq_ignore_ascii_case("SOFTWARE\\"));
+        match (self.hive, self.view, software) {
+            (Hive::LocalMachine, View::Registry32, Some(start)) => format!("{}WOW6432Node\\{}", start, &self.key[9..]),
+            _ => self.key.clone(),
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hive = match self.hive {
+            Hive::LocalMachine => "HKLM",
+            Hive::CurrentUser => "HKCU",
+        };
+        write!(f, "{}\\{}", hive, self.redirected_key())
+    }
+}
+
+/// A value found in the registry and where it was.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    pub location: Location,
+    pub value_name: String,
+    pub data: String,
+}
+
+impl fmt::Display for Candidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {} ({})", self.value_name, self.data, self.location)
+    }
+}
+
+/// Names the install folder has been stored under, newest launcher first.
+pub const INSTALL_PATH_NAMES: &[&str] = &["InstallPath", "InstallLocation"];
+pub const VERSION_NAMES: &[&str] = &["DisplayVersion", "Version"];
+
+/// Every place the launcher may have written one of `keys`, most likely first: machine-wide in
+/// the 32-bit view where it normally writes, then the 64-bit view, then per-user installs.
+pub fn locations(keys: &[&str]) -> Vec<Location> {
+    let places = [(Hive::LocalMachine, View::Registry32), (Hive::LocalMachine, View::Registry64), (Hive::CurrentUser, View::Registry64)];
+    places
+        .iter()
+        .flat_map(|&(hive, view)| keys.iter().map(move |key| Location::new(hive, view, key)))
+        .collect()
+}
+
+/// Where the active game may be registered.
+pub fn game_locations() -> Vec<Location> {
+    crate::games::active().registry_locations()
+}
+
+/// Every non-empty value named one of `names` at `locations`, in order, read through `read`.
+pub fn candidates(read: impl Fn(&Location, &str) -> Option<String>, locations: &[Location], names: &[&str]) -> Vec<Candidate> {
+    locations
+        .iter()
+        .flat_map(|location| names.iter().map(move |name| (location, *name)))
+        .filter_map(|(location, name)| {
+            let data = read(location, name).filter(|data| !data.trim().is_empty())?;
+            Some(Candidate { location: location.clone(), value_name: name.to_string(), data })
+        })
+        .collect()
+}
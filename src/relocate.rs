@@ -0,0 +1,26 @@
+//! Recovering when the loaded LocalStorage.db disappears, as it does when the game is moved to
+//! another drive or reinstalled. Instead of every action failing on the missing file, the app
+//! looks for the install again and offers the file it finds. Backups and the change history are
+//! kept in the app folder rather than next to the file, so they carry over as they are; what the
+//! app remembers by path is moved to the new one.
+
+use std::path::{Path, PathBuf};
+
+use tracing::info;
+
+/// Where the configuration file that was at `gone` is likely to be now: the install detection
+/// found, else another known file. `None` while neither exists.
+pub fn replacement(gone: &str, detected: Option<&str>, known: &[PathBuf]) -> Option<PathBuf> {
+    let gone = Path::new(gone);
+    detected.map(PathBuf::from).into_iter().chain(known.iter().cloned()).find(|path| path != gone && path.is_file())
+}
+
+/// Replaces `gone` in `remembered` with `found`, keeping its place and listing `found` once.
+pub fn migrate(remembered: &mut Vec<String>, gone: &str, found: &str) {
+    remembered.retain(|path| path != found);
+    match remembered.iter_mut().find(|path| *path == gone) {
+        Some(path) => *path = found.to_string(),
+        None => remembered.push(found.to_string()),
+    }
+    info!(gone, found, "moved to a relocated configuration file");
+}
@@ -0,0 +1,96 @@
+use std::env;
+
+use crate::db;
+use crate::diagnostics::CheckResult;
+use crate::detect::get_game_install_path;
+use crate::platform;
+use crate::registry::Location;
+
+pub fn os_version() -> String {
+    const KEY: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion";
+    let platform = platform::native();
+    let location = Location::machine(KEY);
+    let Some(product) = platform.registry_string(&location, "ProductName") else {
+        return env::consts::OS.into();
+    };
+    let display = platform.registry_string(&location, "DisplayVersion").unwrap_or_default();
+    let build = platform.registry_string(&location, "CurrentBuild").unwrap_or_default();
+    format!("{} {} (build {})", product, display, build)
+}
+
+/// Replaces the user's profile directory and account name so reports can be pasted publicly.
+pub fn redact(text: &str) -> String {
+    let mut text = text.to_string();
+    for (var, placeholder) in [("USERPROFILE", "%USERPROFILE%"), ("HOME", "~")] {
+        if let Ok(profile) = env::var(var) {
+            if profile.len() > 1 {
+                text = text.replace(&profile, placeholder);
+            }
+        }
+    }
+    if let Ok(user) = env::var("USERNAME").or_else(|_| env::var("USER")) {
+        if user.len() > 2 {
+            text = text.replace(&user, "<user>");
+        }
+    }
+    text
+}
+
+fn schema_probe(db_path: &str, out: &mut String) {
+    match db::list_keys(db_path, "", usize::MAX) {
+        Ok(listing) => {
+            out.push_str(&format!("  LocalStorage rows: {}\n", listing.total));
+            for entry in listing.entries {
+                out.push_str(&format!("    {} ({} bytes)\n", entry.key, entry.size));
+            }
+        }
+        Err(err) => out.push_str(&format!("  LocalStorage query failed: {}\n", err)),
+    }
+    match db::read_quality_setting_file(db_path) {
+        Ok(setting) => {
+            out.push_str("  GameQualitySetting:\n");
+            if let Some(fields) = setting.as_object() {
+                for (key, value) in fields {
+                    out.push_str(&format!("    {} = {}\n", key, value));
+                }
+            }
+        }
+        Err(err) => out.push_str(&format!("  GameQualitySetting unreadable: {}\n", err)),
+    }
+}
+
+pub fn build_report(db_path: &str, diagnostics: &[CheckResult], log: &[String]) -> String {
+    let mut out = String::new();
+    out.push_str("WuWa Ploom diagnostic report\n");
+    out.push_str(&format!("App version: {}\n", env!("CARGO_PKG_VERSION")));
+    out.push_str(&format!("OS: {}\n", os_version()));
+
+    out.push_str("\nPaths:\n");
+    match get_game_install_path() {
+        Ok(path) => out.push_str(&format!("  detected: {}\n", path)),
+        Err(err) => out.push_str(&format!("  detected: none ({})\n", err)),
+    }
+    out.push_str(&format!("  selected: {}\n", if db_path.is_empty() { "none" } else { db_path }));
+
+    out.push_str("\nSchema probe:\n");
+    if db_path.is_empty() {
+        out.push_str("  skipped, no database selected\n");
+    } else {
+        schema_probe(db_path, &mut out);
+    }
+
+    out.push_str("\nChecks:\n");
+    if diagnostics.is_empty() {
+        out.push_str("  not run\n");
+    }
+    for result in diagnostics {
+        out.push_str(&format!("  [{}] {}: {}\n", result.status.label(), result.name, result.detail));
+    }
+
+    out.push_str("\nRecent activity:\n");
+    for line in log {
+        out.push_str(&format!("  {}\n", line));
+    }
+
+    redact(&out)
+}
@@ -0,0 +1,68 @@
+//! Frame-rate limits in RivaTuner Statistics Server profiles. RTSS caps frame times more evenly
+//! than the game's own limiter, which VRR displays benefit from. Profiles are INI files named
+//! after the game's executable in RTSS's `Profiles` folder; only the `Limit` line is touched.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use tracing::{info, instrument};
+
+use crate::platform::Platform;
+use crate::registry::{self, Location};
+use crate::{ini, store, Result};
+
+const INSTALL_KEY: &str = "SOFTWARE\\Unwinder\\RTSS";
+const INSTALL_VALUE: &str = "InstallDir";
+/// Where the installer puts RTSS unless told otherwise.
+const DEFAULT_DIRS: &[&str] = &["C:\\Program Files (x86)\\RivaTuner Statistics Server", "C:\\Program Files\\RivaTuner Statistics Server"];
+const SECTION: &str = "Framerate";
+const LIMIT: &str = "Limit";
+
+/// RTSS's install folder: from its registry entry, else a default folder that has profiles.
+pub fn find_install(platform: &dyn Platform) -> Option<PathBuf> {
+    let read = |location: &Location, name: &str| platform.registry_string(location, name);
+    let registered = registry::candidates(read, &registry::locations(&[INSTALL_KEY]), &[INSTALL_VALUE]);
+    registered
+        .into_iter()
+        .map(|candidate| PathBuf::from(candidate.data))
+        .chain(DEFAULT_DIRS.iter().map(PathBuf::from))
+        .find(|dir| dir.join("Profiles").is_dir())
+}
+
+/// The profile RTSS applies to `exe`.
+pub fn profile_path(install: &Path, exe: &str) -> PathBuf {
+    install.join("Profiles").join(format!("{}.cfg", exe))
+}
+
+/// The limit in `text`'s `[Framerate]` section; `None` if unset or 0 (no limit).
+pub fn read_limit(text: &str) -> Option<i64> {
+    ini::get(text, SECTION, LIMIT)?.parse().ok().filter(|&limit| limit > 0)
+}
+
+/// `text` with the `[Framerate]` limit set to `fps`, adding the section or line if missing and
+/// leaving everything else as it was.
+pub fn set_limit(text: &str, fps: i64) -> String {
+    ini::set(text, SECTION, LIMIT, &fps.to_string())
+}
+
+/// The limit currently in `exe`'s profile, if it has one.
+pub fn current_limit(install: &Path, exe: &str) -> Option<i64> {
+    fs::read_to_string(profile_path(install, exe)).ok().as_deref().and_then(read_limit)
+}
+
+/// Creates or updates `exe`'s profile with a limit of `fps`. RTSS under Program Files may need
+/// administrator rights, which surface as `MyError::Permission`. A profile that exists but can't
+/// be read is an error rather than a blank one, which would drop the user's other RTSS settings.
+#[instrument(err(level = "warn"))]
+pub fn write_limit(install: &Path, exe: &str, fps: i64) -> Result<PathBuf> {
+    let path = profile_path(install, exe);
+    let text = match fs::read_to_string(&path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    store::write_atomic(&path, set_limit(&text, fps).as_bytes())?;
+    info!(path = %path.display(), fps, "wrote RTSS limit");
+    Ok(path)
+}
@@ -0,0 +1,78 @@
+//! A made-up LocalStorage.db to try the app on. It holds the rows the app works with (the
+//! settings row at 60 FPS, an input table, a cached row, account and device identifiers with
+//! fake values), so every operation can be tried, and undone or broken, without the game's own
+//! file. The sample lives in the app folder, never next to the game.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{params, Connection};
+use tracing::info;
+
+use crate::db::{self, Db};
+use crate::paths::app_dir;
+use crate::Result;
+
+/// The sample's rows, as the game stores them: key and JSON text.
+pub const ROWS: &[(&str, &str)] = &[
+    (
+        "GameQualitySetting",
+        r#"{"KeyCustomFrameRate":60,"KeyPcVsync":1,"KeyQualityLevel":3,"KeyNewShadowQuality":3,"KeyFsrEnable":1,"KeyFsrMode":1,"KeyHdrEnable":0,"KeyBrightness":1.0,"KeyPcResolutionWidth":1920,"KeyPcResolutionHeight":1080}"#,
+    ),
+    ("PlayerName", r#""Rover""#),
+    ("SdkUid", r#""sample-0000-account""#),
+    ("DeviceId", r#""sample-0000-device""#),
+    ("InputKeymap", r#"{"Move":{"Forward":"W","Back":"S","Left":"A","Right":"D"},"Jump":"SpaceBar","Dash":"LeftShift"}"#),
+    ("NoticeCache", r#"{"LastNotice":0}"#),
+];
+
+/// Where the sample is kept.
+pub fn path() -> PathBuf {
+    app_dir().join("sample").join("LocalStorage.db")
+}
+
+/// Where backups of the sample go, apart from the game's.
+pub fn backup_dir() -> PathBuf {
+    app_dir().join("sample").join("backups")
+}
+
+/// Whether `db_path` is the sample rather than a game's file.
+pub fn is_sample(db_path: &str) -> bool {
+    Path::new(db_path) == path()
+}
+
+/// Writes a new sample to `db_path`, replacing whatever was there.
+pub fn create_at(db_path: &str) -> Result<()> {
+    db::wait(Db::new(db_path).submit_file(|db_path| {
+        let path = Path::new(db_path);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        let conn = Connection::open(path)?;
+        conn.execute_batch("CREATE TABLE LocalStorage (key TEXT PRIMARY KEY, value TEXT);")?;
+        for (key, value) in ROWS {
+            conn.execute("INSERT INTO LocalStorage (key, value) VALUES (?1, ?2);", params![key, value])?;
+        }
+        info!(db_path, "created sample database");
+        Ok(())
+    }))
+}
+
+/// A fresh sample in the app folder, as new users start with.
+pub fn reset() -> Result<String> {
+    let db_path = path().display().to_string();
+    create_at(&db_path)?;
+    Ok(db_path)
+}
+
+/// The sample in the app folder, created on first use; changes made to it are kept.
+pub fn existing_or_new() -> Result<String> {
+    if path().is_file() {
+        Ok(path().display().to_string())
+    } else {
+        reset()
+    }
+}
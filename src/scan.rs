@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use crate::detect;
+use crate::tasks::{self, Reporter, Task};
+
+pub enum ScanUpdate {
+    Progress { done: usize, total: usize, current: String },
+    Finished(Vec<PathBuf>),
+}
+
+fn run(reporter: Reporter<ScanUpdate>) {
+    let found = detect::scan_drives(|done, total, root| {
+        reporter.send(ScanUpdate::Progress { done, total, current: root.display().to_string() })
+    });
+    reporter.send(ScanUpdate::Finished(found));
+}
+
+/// Searches every drive for game installs in the background, reporting progress as it goes.
+/// Dropping the task stops the search at the next folder.
+pub fn spawn_scan(ctx: &egui::Context) -> Task<ScanUpdate> {
+    tasks::spawn(ctx, "drive-scan", run)
+}
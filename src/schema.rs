@@ -0,0 +1,45 @@
+//! Versioning for the JSON files the app writes itself (config, profiles and presets). Each file
+//! carries a `version` field; older files are upgraded one step at a time when read.
+
+use serde_json::{Map, Value};
+use tracing::info;
+
+use crate::{MyError, Result};
+
+pub const VERSION_FIELD: &str = "version";
+
+/// Upgrades a file's top-level object from one version to the next.
+pub type Migration = fn(&mut Map<String, Value>);
+
+/// Version stamped in `value`; files from before versioning count as 0.
+pub fn file_version(value: &Value) -> u64 {
+    value.get(VERSION_FIELD).and_then(Value::as_u64).unwrap_or(0)
+}
+
+/// Runs `migrations[v]` for every version `v` from the file's up to `migrations.len()`, which is
+/// the current version, and stamps the result. Fails without touching `value` if it was written
+/// by a newer release, so callers can decide whether to read it anyway.
+pub fn migrate(value: &mut Value, migrations: &[Migration]) -> Result<u64> {
+    let current = migrations.len() as u64;
+    let from = file_version(value);
+    if from > current {
+        return Err(MyError::Schema(format!(
+            "written by a newer version of the app (format {}, this build reads up to {})",
+            from, current
+        )));
+    }
+    let fields = value
+        .as_object_mut()
+        .ok_or_else(|| MyError::Schema("expected a JSON object".into()))?;
+    for migration in &migrations[from as usize..] {
+        migration(fields);
+    }
+    if from < current {
+        info!(from, to = current, "migrated file format");
+    }
+    fields.insert(VERSION_FIELD.into(), Value::from(current));
+    Ok(from)
+}
+
+/// For version 0 -> 1: files from before versioning already have the version 1 layout.
+pub fn unversioned_to_v1(_fields: &mut Map<String, Value>) {}
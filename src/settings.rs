@@ -0,0 +1,123 @@
+//! GameQualitySetting as a typed struct, so code that reads or builds settings gets field names
+//! checked by the compiler. Every known field is optional and left out again when absent; fields
+//! we don't model land in `other` and are written back untouched.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::db::Patch;
+use crate::{MyError, Result};
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GameQualitySetting {
+    // Graphics
+    #[serde(rename = "KeyCustomFrameRate", default, skip_serializing_if = "Option::is_none")]
+    pub custom_frame_rate: Option<i64>,
+    #[serde(rename = "KeyPcVsync", default, skip_serializing_if = "Option::is_none")]
+    pub pc_vsync: Option<i64>,
+    #[serde(rename = "KeyQualityLevel", default, skip_serializing_if = "Option::is_none")]
+    pub quality_level: Option<i64>,
+    #[serde(rename = "KeyNewShadowQuality", default, skip_serializing_if = "Option::is_none")]
+    pub new_shadow_quality: Option<i64>,
+    #[serde(rename = "KeyNiagaraQuality", default, skip_serializing_if = "Option::is_none")]
+    pub niagara_quality: Option<i64>,
+    #[serde(rename = "KeyImageDetail", default, skip_serializing_if = "Option::is_none")]
+    pub image_detail: Option<i64>,
+    #[serde(rename = "KeyAntiAliasing", default, skip_serializing_if = "Option::is_none")]
+    pub anti_aliasing: Option<i64>,
+    #[serde(rename = "KeyAntiAliasingMode", default, skip_serializing_if = "Option::is_none")]
+    pub anti_aliasing_mode: Option<i64>,
+    #[serde(rename = "KeyRenderScale", default, skip_serializing_if = "Option::is_none")]
+    pub render_scale: Option<i64>,
+    #[serde(rename = "KeySceneAo", default, skip_serializing_if = "Option::is_none")]
+    pub scene_ao: Option<i64>,
+    #[serde(rename = "KeyVolumeFog", default, skip_serializing_if = "Option::is_none")]
+    pub volume_fog: Option<i64>,
+    #[serde(rename = "KeyVolumeLight", default, skip_serializing_if = "Option::is_none")]
+    pub volume_light: Option<i64>,
+    #[serde(rename = "KeyMotionBlur", default, skip_serializing_if = "Option::is_none")]
+    pub motion_blur: Option<i64>,
+    #[serde(rename = "KeyStreamLevel", default, skip_serializing_if = "Option::is_none")]
+    pub stream_level: Option<i64>,
+    #[serde(rename = "KeyBloomEnable", default, skip_serializing_if = "Option::is_none")]
+    pub bloom_enable: Option<i64>,
+    #[serde(rename = "KeyNvidiaReflex", default, skip_serializing_if = "Option::is_none")]
+    pub nvidia_reflex: Option<i64>,
+    #[serde(rename = "KeyBrightness", default, skip_serializing_if = "Option::is_none")]
+    pub brightness: Option<f64>,
+    #[serde(rename = "KeyGamma", default, skip_serializing_if = "Option::is_none")]
+    pub gamma: Option<f64>,
+    #[serde(rename = "KeySharpness", default, skip_serializing_if = "Option::is_none")]
+    pub sharpness: Option<f64>,
+    #[serde(rename = "KeyNvidiaSuperSamplingEnable", default, skip_serializing_if = "Option::is_none")]
+    pub nvidia_super_sampling_enable: Option<i64>,
+    #[serde(rename = "KeyNvidiaSuperSamplingMode", default, skip_serializing_if = "Option::is_none")]
+    pub nvidia_super_sampling_mode: Option<i64>,
+    #[serde(rename = "KeyFsrEnable", default, skip_serializing_if = "Option::is_none")]
+    pub fsr_enable: Option<i64>,
+    #[serde(rename = "KeyFsrMode", default, skip_serializing_if = "Option::is_none")]
+    pub fsr_mode: Option<i64>,
+    #[serde(rename = "KeyXessEnable", default, skip_serializing_if = "Option::is_none")]
+    pub xess_enable: Option<i64>,
+    #[serde(rename = "KeyXessQuality", default, skip_serializing_if = "Option::is_none")]
+    pub xess_quality: Option<i64>,
+
+    // Audio
+    #[serde(rename = "KeyMasterVolume", default, skip_serializing_if = "Option::is_none")]
+    pub master_volume: Option<i64>,
+    #[serde(rename = "KeyMusicVolume", default, skip_serializing_if = "Option::is_none")]
+    pub music_volume: Option<i64>,
+    #[serde(rename = "KeySFXVolume", default, skip_serializing_if = "Option::is_none")]
+    pub sfx_volume: Option<i64>,
+    #[serde(rename = "KeyVoiceVolume", default, skip_serializing_if = "Option::is_none")]
+    pub voice_volume: Option<i64>,
+    #[serde(rename = "KeyVoiceLanguage", default, skip_serializing_if = "Option::is_none")]
+    pub voice_language: Option<i64>,
+
+    // Controls
+    #[serde(rename = "KeyHorizontalViewSensitivity", default, skip_serializing_if = "Option::is_none")]
+    pub horizontal_view_sensitivity: Option<f64>,
+    #[serde(rename = "KeyVerticalViewSensitivity", default, skip_serializing_if = "Option::is_none")]
+    pub vertical_view_sensitivity: Option<f64>,
+    #[serde(rename = "KeyAimHorizontalViewSensitivity", default, skip_serializing_if = "Option::is_none")]
+    pub aim_horizontal_view_sensitivity: Option<f64>,
+    #[serde(rename = "KeyAimVerticalViewSensitivity", default, skip_serializing_if = "Option::is_none")]
+    pub aim_vertical_view_sensitivity: Option<f64>,
+    #[serde(rename = "KeyMouseAcceleration", default, skip_serializing_if = "Option::is_none")]
+    pub mouse_acceleration: Option<i64>,
+
+    // Display
+    #[serde(rename = "KeyHdrEnable", default, skip_serializing_if = "Option::is_none")]
+    pub hdr_enable: Option<i64>,
+    #[serde(rename = "KeyHdrMaxBrightness", default, skip_serializing_if = "Option::is_none")]
+    pub hdr_max_brightness: Option<i64>,
+    #[serde(rename = "KeyHdrUiBrightness", default, skip_serializing_if = "Option::is_none")]
+    pub hdr_ui_brightness: Option<i64>,
+
+    /// Everything else in the blob, including keys added by later game versions.
+    #[serde(flatten)]
+    pub other: Map<String, Value>,
+}
+
+impl GameQualitySetting {
+    pub fn from_value(value: &Value) -> Result<Self> {
+        Ok(Self::deserialize(value)?)
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::to_value(self).unwrap_or(Value::Null)
+    }
+
+    /// The fields that are set, as a patch; handy for building presets from a partial struct.
+    pub fn to_patch(&self) -> Patch {
+        match self.to_value() {
+            Value::Object(fields) => fields,
+            _ => Patch::new(),
+        }
+    }
+
+    pub fn frame_rate(&self) -> Result<i64> {
+        self.custom_frame_rate
+            .ok_or_else(|| MyError::Schema("KeyCustomFrameRate not found or not an integer".into()))
+    }
+}
@@ -0,0 +1,193 @@
+use eframe::egui;
+use rfd::FileDialog;
+
+use wuwa_ploom_core::autostart::State;
+use wuwa_ploom_core::games;
+use wuwa_ploom_core::platform::Priority;
+use wuwa_ploom_core::process;
+
+use crate::config::{Config, Language, Theme};
+
+pub enum SettingsOutcome {
+    Open,
+    Save,
+    Cancel,
+    /// The Start with Windows switch was flipped; it takes effect right away, not on Save.
+    StartWithWindows(bool),
+}
+
+/// Edits `draft` in a preferences window; the caller decides what Save and Cancel mean.
+/// `autostart` is the Run entry as last read, `None` where there is no such thing.
+pub fn show(ctx: &egui::Context, draft: &mut Config, autostart: Option<&State>) -> SettingsOutcome {
+    let mut outcome = SettingsOutcome::Open;
+    egui::Window::new("Settings").collapsible(false).resizable(false).show(ctx, |ui| {
+        let current = games::by_id(&draft.game).map_or("", |game| game.name());
+        egui::ComboBox::from_label("Game").selected_text(current).show_ui(ui, |ui| {
+            for game in games::ALL {
+                ui.selectable_value(&mut draft.game, game.id().to_string(), game.name());
+            }
+        });
+
+        ui.separator();
+        ui.heading("Backups");
+        ui.checkbox(&mut draft.backup_enabled, "Back up the database before every write");
+        ui.horizontal(|ui| {
+            ui.label("Backup folder:");
+            let shown = if draft.backup_dir.is_empty() {
+                draft.backup_dir().display().to_string()
+            } else {
+                draft.backup_dir.clone()
+            };
+            ui.label(shown);
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Change...").clicked() {
+                if let Some(dir) = FileDialog::new().pick_folder() {
+                    draft.backup_dir = dir.display().to_string();
+                }
+            }
+            if ui.button("Use default").clicked() {
+                draft.backup_dir.clear();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Space for backups:");
+            ui.add(egui::DragValue::new(&mut draft.backup_budget_mb).clamp_range(0..=100_000).suffix(" MB"));
+            ui.weak("0 keeps every backup");
+        })
+        .response
+        .on_hover_text("When backups take more, the oldest are deleted");
+
+        ui.separator();
+        ui.heading("Sync between PCs");
+        ui.label("Keep profiles, and backups unless a backup folder is set above, in a folder that OneDrive, Dropbox or Syncthing syncs.");
+        ui.horizontal(|ui| {
+            ui.label("Sync folder:");
+            ui.label(if draft.sync_folder.is_empty() { "none" } else { draft.sync_folder.as_str() });
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Choose...").clicked() {
+                if let Some(dir) = FileDialog::new().pick_folder() {
+                    draft.sync_folder = dir.display().to_string();
+                }
+            }
+            if ui.add_enabled(!draft.sync_folder.is_empty(), egui::Button::new("Stop syncing")).clicked() {
+                draft.sync_folder.clear();
+            }
+        });
+
+        ui.separator();
+        ui.heading("Appearance");
+        ui.horizontal(|ui| {
+            ui.label("Theme:");
+            ui.selectable_value(&mut draft.theme, Theme::Dark, "Dark");
+            ui.selectable_value(&mut draft.theme, Theme::Light, "Light");
+        });
+        ui.checkbox(&mut draft.custom_title_bar, "Custom title bar (takes effect after restart)");
+        ui.checkbox(&mut draft.large_ui, "Enlarged UI for handheld and TV screens")
+            .on_hover_text("Always on in Steam's Game Mode and Big Picture");
+        egui::ComboBox::from_label("Language")
+            .selected_text(draft.language.name())
+            .show_ui(ui, |ui| {
+                for language in Language::ALL {
+                    ui.selectable_value(&mut draft.language, language, language.name());
+                }
+            });
+
+        ui.separator();
+        ui.heading("Behaviour");
+        ui.checkbox(&mut draft.start_minimized, "Start minimized");
+        if let Some(state) = autostart {
+            let mut enabled = *state == State::On;
+            let toggle = ui.checkbox(&mut enabled, "Start with Windows").on_hover_text("Starts minimized and keeps watched keys re-applied");
+            if toggle.changed() {
+                outcome = SettingsOutcome::StartWithWindows(enabled);
+            }
+            if let State::Elsewhere(entry) = state {
+                ui.colored_label(ui.visuals().warn_fg_color, format!("Windows starts another copy: {}", entry));
+                if ui.small_button("Start this copy instead").clicked() {
+                    outcome = SettingsOutcome::StartWithWindows(true);
+                }
+            }
+        }
+        ui.checkbox(&mut draft.auto_detect_on_launch, "Locate the configuration file on launch");
+        ui.checkbox(&mut draft.confirm_before_write, "Ask for confirmation before writing");
+        ui.checkbox(&mut draft.auto_refresh, "Auto-refresh the current FPS readout");
+        ui.checkbox(&mut draft.check_updates, "Check GitHub for a new version at startup");
+        ui.checkbox(&mut draft.system_notifications, "Show system notifications while in the background")
+            .on_hover_text("For re-applied watched keys, new versions and backups");
+        ui.checkbox(&mut draft.vrr_mode, "VRR mode: suggest a limit 3 FPS below the refresh rate (G-Sync/FreeSync)")
+            .on_hover_text("Keeps frames inside the VRR range, below where VSync or tearing sets in; the two options below carry the limit to RTSS and the driver");
+        ui.checkbox(&mut draft.rtss_profile, "Offer to match the RivaTuner (RTSS) frame limit after changing FPS")
+            .on_hover_text("RTSS paces frames more evenly than the game's limiter, which suits VRR displays");
+        ui.checkbox(&mut draft.nvidia_frame_limit, "Keep the NVIDIA driver's Max Frame Rate equal to the FPS limit")
+            .on_hover_text("Otherwise the lower of the two caps wins; changing it needs administrator rights");
+        ui.checkbox(&mut draft.sync_game_user_settings, "Keep GameUserSettings.ini in line with the database after writes")
+            .on_hover_text("Frame-rate limit and resolution; some client versions snap back to the INI's values");
+        ui.checkbox(&mut draft.read_back_after_launch, "Check that a new FPS limit survives the first minutes of play")
+            .on_hover_text("The game may put its own limit back when it starts or when its settings menu opens");
+        ui.checkbox(&mut draft.restore_refresh_rate, "Restore the display's refresh rate when the game exits")
+            .on_hover_text("After switching it from the Patcher tab; it is always restored when WuWa Ploom closes");
+        ui.checkbox(&mut draft.power_plan_while_playing, "Use the High performance power plan while the game runs")
+            .on_hover_text("Laptops on Balanced often hold the GPU back; your plan comes back when the game exits");
+        egui::ComboBox::from_label("Game priority when it starts")
+            .selected_text(draft.game_priority.name())
+            .show_ui(ui, |ui| {
+                for priority in Priority::ALL {
+                    ui.selectable_value(&mut draft.game_priority, priority, priority.name());
+                }
+            })
+            .response
+            .on_hover_text("High can reduce stutter at high frame caps; Normal leaves the game alone");
+        ui.horizontal(|ui| {
+            ui.label("Game CPU cores:");
+            ui.add(egui::TextEdit::singleline(&mut draft.game_affinity).hint_text("all").desired_width(80.0))
+                .on_hover_text("A list such as 0-7 or 0-5,8; applied when the game starts");
+            if !draft.game_affinity.trim().is_empty() && process::core_mask(&draft.game_affinity).is_none() {
+                ui.colored_label(ui.visuals().error_fg_color, "Not a list of cores");
+            }
+        });
+        ui.checkbox(&mut draft.discord_presence, "Show in Discord while watched keys are being kept")
+            .on_hover_text("A status such as \"FPS unlock active: 165\" while the app runs and re-applies watched keys");
+        if draft.discord_presence {
+            ui.horizontal(|ui| {
+                ui.label("Discord application ID:");
+                ui.text_edit_singleline(&mut draft.discord_app_id)
+                    .on_hover_text("From the Discord Developer Portal; its name is what Discord shows you playing");
+            });
+        }
+
+        ui.separator();
+        ui.heading("Watchdog");
+        ui.label("How watched keys are put back after the game changes them. Some game screens rewrite the FPS limit each time they open; the limit below stops the two from taking turns.");
+        egui::Grid::new("watchdog_tuning").num_columns(2).show(ui, |ui| {
+            ui.label("Check the file every");
+            ui.add(egui::DragValue::new(&mut draft.watchdog.poll_secs).clamp_range(1..=600).suffix(" s"));
+            ui.end_row();
+            ui.label("Wait after the game's last change");
+            ui.add(egui::DragValue::new(&mut draft.watchdog.debounce_secs).clamp_range(0..=600).suffix(" s"));
+            ui.end_row();
+            ui.label("Re-apply at most");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut draft.watchdog.max_per_hour).clamp_range(0..=1000).suffix(" times an hour"));
+                ui.weak("0 for no limit");
+            });
+            ui.end_row();
+        });
+
+        ui.separator();
+        ui.heading("Advanced");
+        ui.checkbox(&mut draft.sql_console, "I know what I'm doing: show the SQL console");
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                outcome = SettingsOutcome::Save;
+            }
+            if ui.button("Cancel").clicked() {
+                outcome = SettingsOutcome::Cancel;
+            }
+        });
+    });
+    outcome
+}
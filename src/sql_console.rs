@@ -0,0 +1,102 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+use crate::backup;
+use crate::db::{self, QueryResult};
+
+/// Result of a confirmed write statement.
+pub struct SqlWrite {
+    pub changed: usize,
+    pub backup: PathBuf,
+}
+
+/// Free-form SQL against the opened DB. Queries run read-only; writes need an explicit opt-in and a confirmation.
+#[derive(Default)]
+pub struct SqlConsole {
+    sql: String,
+    allow_writes: bool,
+    confirming: bool,
+    result: Option<Result<QueryResult, String>>,
+}
+
+impl SqlConsole {
+    pub fn show(&mut self, ui: &mut egui::Ui, db_path: &str, blocked: Option<&str>, backup_dir: &Path) -> Option<Result<SqlWrite, String>> {
+        ui.label("Queries run on a read-only connection. Tick \"Allow writes\" to run UPDATE, INSERT or DELETE.");
+        ui.add(egui::TextEdit::multiline(&mut self.sql).code_editor().desired_rows(4).desired_width(f32::INFINITY));
+        ui.horizontal(|ui| {
+            if ui.button("Run query").clicked() {
+                self.result = Some(db::run_query(db_path, &self.sql).map_err(|err| err.to_string()));
+            }
+            ui.checkbox(&mut self.allow_writes, "Allow writes");
+            let write_blocked = blocked.or((!self.allow_writes).then_some("Tick \"Allow writes\" first."));
+            let execute = ui
+                .add_enabled(write_blocked.is_none(), egui::Button::new("Execute write..."))
+                .on_hover_text("A backup is always taken first")
+                .on_disabled_hover_text(write_blocked.unwrap_or_default());
+            if execute.clicked() {
+                self.confirming = true;
+            }
+        });
+
+        match &self.result {
+            Some(Ok(result)) => show_table(ui, result),
+            Some(Err(err)) => {
+                ui.colored_label(ui.visuals().error_fg_color, err);
+            }
+            None => {}
+        }
+
+        if !self.confirming {
+            return None;
+        }
+        let mut answer = None;
+        egui::Window::new("Confirm SQL write").collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+            ui.label("Run this statement against the configuration file?");
+            ui.monospace(&self.sql);
+            ui.label("A backup is taken first. This cannot be undone from the app.");
+            if self.sql.to_ascii_lowercase().contains("delete") {
+                let text = "⚠ The game needs some rows, such as its account and device identifiers (marked on the Keys tab). Deleting them can log you out.";
+                ui.colored_label(ui.visuals().warn_fg_color, text);
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Run").clicked() {
+                    answer = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    answer = Some(false);
+                }
+            });
+        });
+        let confirmed = answer?;
+        self.confirming = false;
+        if !confirmed {
+            return None;
+        }
+        Some(
+            backup::backup_db(db_path, backup_dir)
+                .and_then(|backup| Ok(SqlWrite { changed: db::execute_sql(db_path, &self.sql)?, backup }))
+                .map_err(|err| err.to_string()),
+        )
+    }
+}
+
+fn show_table(ui: &mut egui::Ui, result: &QueryResult) {
+    ui.weak(format!("{} row(s)", result.rows.len()));
+    egui::ScrollArea::both().id_source("sql_result").show(ui, |ui| {
+        egui::Grid::new("sql_result_grid").striped(true).num_columns(result.columns.len()).show(ui, |ui| {
+            for column in &result.columns {
+                ui.strong(column);
+            }
+            ui.end_row();
+            for row in &result.rows {
+                for cell in row {
+                    // Long JSON blobs would make the table unreadable; the key browser shows them in full.
+                    let shown: String = cell.chars().take(120).collect();
+                    ui.label(shown).on_hover_text(cell);
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
@@ -0,0 +1,147 @@
+//! A write queued while the game runs. The game keeps LocalStorage.db open and writes its
+//! settings back on exit, so patching the file in place either fails on the lock or is undone.
+//! Instead SQLite's online backup API clones the live file, which works while the game has it
+//! open, the patch goes into the clone, and the clone is copied back over the file through the
+//! same API once the game exits. When the game changed the file after the clone was taken, the
+//! clone is stale; the patch is then applied to the file itself so none of the game's own
+//! changes are lost.
+//!
+//! When only an administrator may write the file, the clone is kept in the app folder and the
+//! patch goes into the file through the elevated helper, like any other write.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use rusqlite::backup::Progress;
+use rusqlite::DatabaseName;
+use tracing::{debug, info, instrument, warn};
+
+use crate::access;
+use crate::db::{self, wait, Db, Patch};
+use crate::paths::app_dir;
+use crate::platform::Platform;
+use crate::{MyError, Result};
+
+/// Tries at putting the write in while the file stays locked; the game may take a few seconds
+/// to let go of it after its window closes.
+const LOCKED_ATTEMPTS: u32 = 5;
+const LOCKED_DELAY: Duration = Duration::from_secs(2);
+
+/// A patch applied to a clone of the database, waiting to go into the file itself.
+#[derive(Debug)]
+pub struct Staged {
+    pub db_path: String,
+    pub clone: PathBuf,
+    pub patch: Patch,
+    /// The values the patch replaced in the clone.
+    pub inverse: Patch,
+    /// When the file last changed before it was cloned.
+    pub cloned_at: Option<SystemTime>,
+    /// Only an administrator may write the file, so the patch goes in through the elevated
+    /// helper.
+    pub elevated: bool,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Finish {
+    /// The file was untouched since the clone, which replaced it.
+    Swapped,
+    /// The game had changed the file, so the patch was applied to it; with the values it
+    /// replaced, if any.
+    Reapplied(Option<Patch>),
+}
+
+/// Where the clone of `db_path` is kept: next to it, so the copy back stays on one drive, or in
+/// the app folder when we may not write next to it.
+pub fn clone_path(db_path: &str, elevated: bool) -> PathBuf {
+    match elevated {
+        false => PathBuf::from(format!("{}.ploom-staged", db_path)),
+        true => app_dir().join("LocalStorage.db.ploom-staged"),
+    }
+}
+
+/// The clone path `replacing` does not use, so a queued write stays whole until the one
+/// replacing it is in place.
+fn free_clone_path(db_path: &str, elevated: bool, replacing: Option<&Staged>) -> PathBuf {
+    let clone = clone_path(db_path, elevated);
+    match replacing {
+        Some(previous) if previous.clone == clone => PathBuf::from(format!("{}-next", clone.display())),
+        _ => clone,
+    }
+}
+
+/// Clones `db_path` with the backup API and applies `patch` to the clone. `None` when the patch
+/// changes nothing. `replacing` is a queued write this one takes over; its clone is left alone,
+/// for the caller to discard once this one is staged.
+#[instrument(skip(patch, replacing), err(level = "warn"))]
+pub fn stage(db_path: &str, patch: &Patch, elevated: bool, replacing: Option<&Staged>) -> Result<Option<Staged>> {
+    let clone = free_clone_path(db_path, elevated, replacing);
+    if let Some(dir) = clone.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let cloned_at = db::modified_time(db_path);
+    let target = clone.clone();
+    Db::new(db_path).run(move |conn| Ok(conn.backup(DatabaseName::Main, &target, None)?))?;
+    let Some(inverse) = db::apply_patch(&clone.display().to_string(), patch)? else {
+        remove_clone(&clone)?;
+        return Ok(None);
+    };
+    info!(clone = %clone.display(), keys = patch.len(), "staged a write for when the game exits");
+    Ok(Some(Staged { db_path: db_path.to_string(), clone, patch: patch.clone(), inverse, cloned_at, elevated }))
+}
+
+/// Puts the staged write into the file, trying again for a while if the file stays locked. The
+/// clone is removed whether or not it worked; on failure the patch is still in the caller's
+/// hands.
+#[instrument(skip(staged, platform), fields(db_path = %staged.db_path), err(level = "warn"))]
+pub fn finish(staged: Staged, platform: &dyn Platform) -> Result<Finish> {
+    let mut attempt = 1;
+    let result = loop {
+        match put_in(&staged, platform) {
+            Err(MyError::Locked(_)) if attempt < LOCKED_ATTEMPTS => {
+                debug!(attempt, "file still locked; trying again");
+                thread::sleep(LOCKED_DELAY);
+                attempt += 1;
+            }
+            result => break result,
+        }
+    };
+    if let Err(err) = remove_clone(&staged.clone) {
+        warn!(%err, clone = %staged.clone.display(), "could not remove the staged clone");
+    }
+    let finish = result?;
+    info!(?finish, "finished a staged write");
+    Ok(finish)
+}
+
+fn put_in(staged: &Staged, platform: &dyn Platform) -> Result<Finish> {
+    if staged.elevated {
+        return Ok(Finish::Reapplied(access::write_elevated(platform, &staged.db_path, &staged.patch)?));
+    }
+    if db::modified_time(&staged.db_path) != staged.cloned_at {
+        return Ok(Finish::Reapplied(db::apply_patch(&staged.db_path, &staged.patch)?));
+    }
+    // Closing the clone's connection folds its log back in, so the file alone is complete.
+    wait(Db::new(&staged.clone.display().to_string()).submit_file(|_| Ok(())))?;
+    let clone = staged.clone.clone();
+    Db::new(&staged.db_path).run(move |conn| Ok(conn.restore(DatabaseName::Main, &clone, None::<fn(Progress)>)?))?;
+    Ok(Finish::Swapped)
+}
+
+/// Drops the staged write and its clone.
+pub fn discard(staged: Staged) -> Result<()> {
+    remove_clone(&staged.clone)
+}
+
+fn remove_clone(clone: &Path) -> Result<()> {
+    wait(Db::new(&clone.display().to_string()).submit_file(|clone| {
+        for path in [clone.to_string(), format!("{}-wal", clone), format!("{}-shm", clone)] {
+            if Path::new(&path).exists() {
+                fs::remove_file(&path)?;
+            }
+        }
+        Ok(())
+    }))
+}
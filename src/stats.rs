@@ -0,0 +1,106 @@
+//! Counters of what the tool did, kept in the app folder and never sent anywhere. They answer
+//! questions like "how often does the game undo my FPS limit?", which decides whether keeping
+//! values re-applied is worth it.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::paths::app_dir;
+use crate::{store, Result};
+
+const STATS_FILE: &str = "stats.json";
+/// Days of per-day counts kept; totals cover everything.
+const KEEP_DAYS: u64 = 90;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// A write of ours changed the database.
+    Patch,
+    /// The game changed a value the user watches or keeps.
+    Revert,
+    Backup,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Counts {
+    pub patches: u64,
+    pub reverts: u64,
+    pub backups: u64,
+}
+
+impl Counts {
+    fn add(&mut self, other: &Counts) {
+        self.patches += other.patches;
+        self.reverts += other.reverts;
+        self.backups += other.backups;
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Stats {
+    pub total: Counts,
+    /// Counts by day since the Unix epoch, in UTC.
+    days: BTreeMap<u64, Counts>,
+}
+
+impl Stats {
+    /// The stats in `path`; empty when there are none yet or the file is damaged.
+    pub fn load_from(path: &Path) -> Self {
+        store::read_json(path).ok().flatten().and_then(|value| serde_json::from_value(value).ok()).unwrap_or_default()
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        store::write_json(path, self)
+    }
+
+    pub fn record(&mut self, event: Event, day: u64) {
+        let delta = match event {
+            Event::Patch => Counts { patches: 1, ..Counts::default() },
+            Event::Revert => Counts { reverts: 1, ..Counts::default() },
+            Event::Backup => Counts { backups: 1, ..Counts::default() },
+        };
+        self.total.add(&delta);
+        self.days.entry(day).or_default().add(&delta);
+        self.days.retain(|kept, _| kept + KEEP_DAYS > day);
+    }
+
+    /// Counts of the `days` days ending with `today`.
+    pub fn recent(&self, days: u64, today: u64) -> Counts {
+        let mut sum = Counts::default();
+        for counts in self.days.range(today.saturating_sub(days.saturating_sub(1))..=today).map(|(_, counts)| counts) {
+            sum.add(counts);
+        }
+        sum
+    }
+}
+
+fn stats_path() -> PathBuf {
+    app_dir().join(STATS_FILE)
+}
+
+/// Today as a day number for `Stats`.
+pub fn today() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|since| since.as_secs() / 86_400).unwrap_or_default()
+}
+
+/// The stats in the app folder.
+pub fn load() -> Stats {
+    Stats::load_from(&stats_path())
+}
+
+/// Adds `event` to the stats in the app folder. Read and written each time, so the app and the
+/// CLI can both count; a failure is only logged, as stats never block the real work.
+pub fn record(event: Event) {
+    let path = stats_path();
+    let mut stats = Stats::load_from(&path);
+    stats.record(event, today());
+    if let Err(err) = stats.save_to(&path) {
+        warn!(%err, ?event, "could not save stats");
+    }
+}
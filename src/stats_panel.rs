@@ -0,0 +1,90 @@
+use std::time::SystemTime;
+
+use eframe::egui;
+use wuwa_ploom_core::history::{self, Author, History};
+use wuwa_ploom_core::stats::{self, Stats};
+
+use crate::watch::WatchedKey;
+
+/// Changes listed, newest first.
+const SHOWN_CHANGES: usize = 50;
+
+/// Local counters for the Stats tab, with a nudge towards re-applying watched keys when the game
+/// keeps undoing them, and the change history below. Returns true when the user asks to reload.
+pub fn show(ui: &mut egui::Ui, stats: &Stats, history: &History, watched: &[WatchedKey]) -> bool {
+    let today = stats::today();
+    let week = stats.recent(7, today);
+    let month = stats.recent(30, today);
+
+    if week.reverts > 0 {
+        ui.strong(format!("The game changed your watched values {} time(s) this week.", week.reverts));
+        if !watched.iter().any(|watch| watch.reassert) {
+            ui.label("Turn on Re-apply for a watched key on the Patcher tab to have it put back automatically.");
+        }
+    } else if watched.is_empty() {
+        ui.label("Watch a key on the Patcher tab to count how often the game changes it.");
+    }
+    ui.add_space(6.0);
+
+    let periods = [week, month, stats.total];
+    let rows = [
+        ("Patches applied", periods.map(|counts| counts.patches)),
+        ("Changes by the game", periods.map(|counts| counts.reverts)),
+        ("Backups taken", periods.map(|counts| counts.backups)),
+    ];
+    egui::Grid::new("stats").striped(true).num_columns(4).show(ui, |ui| {
+        ui.label("");
+        ui.strong("7 days");
+        ui.strong("30 days");
+        ui.strong("All time");
+        ui.end_row();
+        for (label, counts) in rows {
+            ui.label(label);
+            for count in counts {
+                ui.label(count.to_string());
+            }
+            ui.end_row();
+        }
+    });
+    ui.add_space(6.0);
+    ui.weak("Counted on this computer only, by the app and the CLI; nothing is sent anywhere.");
+    let reload = ui.small_button("Reload").clicked();
+    ui.separator();
+    show_history(ui, history);
+    reload
+}
+
+/// `then` as "5 min ago" and the like.
+fn ago(then: u64, now: u64) -> String {
+    match now.saturating_sub(then) {
+        seconds if seconds < 60 => "just now".into(),
+        seconds if seconds < 3600 => format!("{} min ago", seconds / 60),
+        seconds if seconds < 86_400 => format!("{} h ago", seconds / 3600),
+        seconds => format!("{} days ago", seconds / 86_400),
+    }
+}
+
+fn show_history(ui: &mut egui::Ui, history: &History) {
+    ui.strong("Recent changes");
+    if history.changes().is_empty() {
+        ui.label("Nothing yet. Changes to the settings show up here with who made them.");
+        return;
+    }
+    let now = history::unix_time(SystemTime::now());
+    egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+        egui::Grid::new("change_history").striped(true).num_columns(4).show(ui, |ui| {
+            for change in history.changes().iter().rev().take(SHOWN_CHANGES) {
+                ui.label(ago(change.time, now));
+                ui.label(&change.key);
+                ui.label(format!("{} → {}", change.from, change.to));
+                let by = format!("by {}", change.author.label());
+                match change.author {
+                    Author::Tool => ui.label(by),
+                    Author::Game | Author::External => ui.colored_label(ui.visuals().warn_fg_color, by),
+                };
+                ui.end_row();
+            }
+        });
+    });
+    ui.weak("Attributed by matching when the file changed against the app's own writes and when the game last ran.");
+}
@@ -0,0 +1,79 @@
+//! What a write did, as one event that the app's toasts and history, the CLI's output and the
+//! log file all report from. Each frontend used to word the same outcome its own way ("FPS set
+//! to 120." in the app, "Updated 1 key(s)" in the CLI); now the wording and severity live here
+//! and the frontends only decide where the text goes.
+
+use std::path::PathBuf;
+
+use tracing::{error, info, warn};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Success,
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatusEvent {
+    /// A write changed `keys` keys; `label` names it, e.g. "FPS set to 120".
+    Written { label: String, keys: usize },
+    /// A write found every value in place already.
+    AlreadySet { label: String },
+    /// A write waits for the game to exit; see `staged`.
+    Queued { label: String },
+    /// Watched or kept keys the game changed were written back.
+    Reapplied { keys: usize },
+    BackedUp { path: PathBuf },
+    /// A write was not attempted, for `reason`.
+    Refused { reason: String },
+    Failed { error: String },
+}
+
+impl StatusEvent {
+    pub fn severity(&self) -> Severity {
+        match self {
+            StatusEvent::Written { .. } => Severity::Success,
+            StatusEvent::AlreadySet { .. } | StatusEvent::Queued { .. } | StatusEvent::Reapplied { .. } | StatusEvent::BackedUp { .. } => Severity::Info,
+            StatusEvent::Refused { .. } => Severity::Warning,
+            StatusEvent::Failed { .. } => Severity::Error,
+        }
+    }
+
+    /// A stable name for the kind of event, for the log.
+    pub fn action(&self) -> &'static str {
+        match self {
+            StatusEvent::Written { .. } => "written",
+            StatusEvent::AlreadySet { .. } => "already_set",
+            StatusEvent::Queued { .. } => "queued",
+            StatusEvent::Reapplied { .. } => "reapplied",
+            StatusEvent::BackedUp { .. } => "backed_up",
+            StatusEvent::Refused { .. } => "refused",
+            StatusEvent::Failed { .. } => "failed",
+        }
+    }
+
+    /// The event as a sentence for the user.
+    pub fn message(&self) -> String {
+        match self {
+            StatusEvent::Written { label, keys } => format!("{} ({} key(s) changed).", label, keys),
+            StatusEvent::AlreadySet { label } => format!("{}: already set. No need to patch.", label),
+            StatusEvent::Queued { label } => format!("{}: queued. It is written when the game exits.", label),
+            StatusEvent::Reapplied { keys } => format!("Re-applied {} key(s) the game changed.", keys),
+            StatusEvent::BackedUp { path } => format!("Backup saved to {}", path.display()),
+            StatusEvent::Refused { reason } => reason.clone(),
+            StatusEvent::Failed { error } => format!("Error: {}", error),
+        }
+    }
+
+    /// Writes the event to the log at its severity.
+    pub fn log(&self) {
+        let (action, message) = (self.action(), self.message());
+        match self.severity() {
+            Severity::Success | Severity::Info => info!(action, %message, "status"),
+            Severity::Warning => warn!(action, %message, "status"),
+            Severity::Error => error!(action, %message, "status"),
+        }
+    }
+}
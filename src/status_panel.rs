@@ -0,0 +1,81 @@
+use std::time::Instant;
+
+use eframe::egui;
+use serde_json::Value;
+
+use crate::{frame_rate, keymap, sample, watchdog};
+use crate::toasts::ToastLevel;
+use crate::watch::WatchedKey;
+
+/// What the Patcher tab shows at a glance, filled in on launch without any button press.
+pub struct Status<'a> {
+    pub db_path: &'a str,
+    /// `db_path` no longer exists.
+    pub db_gone: bool,
+    pub game_version: Option<&'a str>,
+    pub fps: Option<i64>,
+    pub setting: Option<&'a Value>,
+    pub watched: &'a [WatchedKey],
+    /// A `keep` watchdog gave up after repeated crashes in the last day.
+    pub watchdog_stopped: bool,
+    /// When re-applying watched keys resumes, after it hit the hourly limit.
+    pub watchdog_resumes: Option<Instant>,
+}
+
+pub fn show(ui: &mut egui::Ui, status: &Status) {
+    egui::Grid::new("status_grid").num_columns(2).show(ui, |ui| {
+        ui.label("Install");
+        if status.db_path.is_empty() {
+            ui.colored_label(ToastLevel::Warning.color(), "Not found; locate or browse for it below.");
+        } else if status.db_gone {
+            ui.colored_label(ToastLevel::Warning.color(), format!("{} (gone)", status.db_path));
+        } else if sample::is_sample(status.db_path) {
+            ui.colored_label(ToastLevel::Info.color(), "Sample file; changes don't reach the game. Locate the game's file when done.");
+        } else {
+            ui.label(status.db_path);
+        }
+        ui.end_row();
+
+        ui.label("Game version");
+        ui.label(status.game_version.unwrap_or("Unknown"));
+        ui.end_row();
+
+        ui.label("FPS limit");
+        match status.setting.and_then(|setting| frame_rate::menu_option(setting).warning()) {
+            Some(warning) => {
+                let text = status.fps.map_or("Not saved yet; set 60 in the game first.".into(), |fps| format!("{}, from the in-game option; set 60 in the game first.", fps));
+                ui.colored_label(ToastLevel::Warning.color(), text).on_hover_text(warning);
+            }
+            None => {
+                ui.label(status.fps.map_or("-".into(), |fps| fps.to_string()));
+            }
+        }
+        ui.end_row();
+
+        ui.label("VSync");
+        let vsync = keymap::active().vsync;
+        match status.setting.map(|setting| setting[&vsync].as_i64()) {
+            None => ui.label("-"),
+            Some(Some(0)) => ui.label("Off"),
+            Some(Some(_)) => ui.colored_label(ToastLevel::Warning.color(), "On; it caps the frame rate at the refresh rate."),
+            Some(None) => ui.label("Not in this client version"),
+        };
+        ui.end_row();
+
+        ui.label("Watchdog");
+        let kept = status.watched.iter().filter(|watched| watched.reassert).count();
+        if status.watchdog_stopped {
+            ui.colored_label(ToastLevel::Error.color(), "Stopped after repeated crashes; see the log.");
+        } else if let Some(resumes) = status.watchdog_resumes.filter(|_| kept > 0) {
+            let minutes = watchdog::minutes_until(resumes, Instant::now());
+            ui.colored_label(ToastLevel::Warning.color(), format!("Paused: the game keeps changing keys back. Resumes in {} min.", minutes));
+        } else if kept > 0 {
+            ui.label(format!("Keeping {} key(s)", kept));
+        } else if !status.watched.is_empty() {
+            ui.label(format!("Watching {} key(s)", status.watched.len()));
+        } else {
+            ui.label("Off");
+        }
+        ui.end_row();
+    });
+}
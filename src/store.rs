@@ -0,0 +1,75 @@
+//! Saving and loading the app's own files (config, profiles) so that a crash or power cut mid-save
+//! never leaves them half written. Saves go to a temporary file that replaces the old one only
+//! once complete; a file that still fails to parse is set aside for inspection and the caller
+//! carries on with defaults.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::Result;
+
+/// Replaces `path` with `bytes` in one step: readers see the old content or the new, never a mix.
+pub fn write_atomic(path: &Path, bytes: &[u8]) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp = temp_path(path);
+    let written = File::create(&temp).and_then(|mut file| {
+        file.write_all(bytes)?;
+        // On disk before the rename, or a crash could leave the new name on empty content.
+        file.sync_all()
+    });
+    if let Err(err) = written.and_then(|()| fs::rename(&temp, path)) {
+        let _ = fs::remove_file(&temp);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+/// `value` pretty-printed through `write_atomic`.
+pub fn write_json(path: &Path, value: &impl Serialize) -> Result<()> {
+    write_atomic(path, serde_json::to_string_pretty(value)?.as_bytes())
+}
+
+/// The JSON in `path`, or `None` if there is none. A file that is not valid JSON is renamed by
+/// `set_aside` so the next save doesn't overwrite it; other read failures are errors.
+pub fn read_json(path: &Path) -> Result<Option<Value>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        // Not UTF-8: as damaged as bad JSON.
+        Err(err) if err.kind() == io::ErrorKind::InvalidData => String::new(),
+        Err(err) => return Err(err.into()),
+    };
+    match serde_json::from_str(&text) {
+        Ok(value) => Ok(Some(value)),
+        Err(err) => {
+            let kept = set_aside(path);
+            warn!(path = %path.display(), %err, kept = ?kept.as_ref().map(|kept| kept.display().to_string()), "damaged file; using defaults");
+            Ok(None)
+        }
+    }
+}
+
+/// Renames a damaged file to `<name>.broken-<unix time>`, out of the way of loaders that pick
+/// files by extension. Returns the new path.
+pub fn set_aside(path: &Path) -> Option<PathBuf> {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or_default();
+    let mut name = path.file_name()?.to_os_string();
+    name.push(format!(".broken-{}", secs));
+    let kept = path.with_file_name(name);
+    fs::rename(path, &kept).ok()?;
+    Some(kept)
+}
+
+fn temp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
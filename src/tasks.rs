@@ -0,0 +1,96 @@
+//! Background jobs for the GUI. Each runs on its own thread and reports over a channel that the
+//! app drains once per frame, so slow work never holds up a repaint and nothing but messages
+//! crosses threads.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvError, Sender, TryIter, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+use eframe::egui;
+use tracing::{debug, error};
+
+/// The job's end of a task.
+pub struct Reporter<T> {
+    tx: Sender<T>,
+    ctx: egui::Context,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T> Reporter<T> {
+    /// Delivers `message` and wakes the UI. Returns false once the task was cancelled or dropped,
+    /// which long jobs take as their cue to stop.
+    pub fn send(&self, message: T) -> bool {
+        let delivered = self.tx.send(message).is_ok();
+        self.ctx.request_repaint();
+        delivered && !self.is_cancelled()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// The app's end of a task: polled from `update`, and cancelled when dropped.
+pub struct Task<T> {
+    name: &'static str,
+    rx: Receiver<T>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T> Task<T> {
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.rx.try_recv()
+    }
+
+    /// Waits for the next message; for work that must finish before the app exits.
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.rx.recv()
+    }
+
+    /// Every message sent since the last poll.
+    pub fn try_iter(&self) -> TryIter<'_, T> {
+        self.rx.try_iter()
+    }
+
+    /// Asks the job to stop; it notices the next time it reports.
+    pub fn cancel(&self) {
+        if !self.cancelled.swap(true, Ordering::Relaxed) {
+            debug!(task = self.name, "cancelled");
+        }
+    }
+}
+
+impl<T> Drop for Task<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Runs `job` on a named thread. Messages it sends arrive through the returned task.
+pub fn spawn<T, F>(ctx: &egui::Context, name: &'static str, job: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce(Reporter<T>) + Send + 'static,
+{
+    let (tx, rx) = channel();
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let reporter = Reporter { tx, ctx: ctx.clone(), cancelled: cancelled.clone() };
+    debug!(task = name, "started");
+    // A thread that fails to start just looks like a job that ended without a result.
+    if let Err(err) = thread::Builder::new().name(name.into()).spawn(move || job(reporter)) {
+        error!(task = name, %err, "could not start thread");
+    }
+    Task { name, rx, cancelled }
+}
+
+/// Runs `job` and delivers its return value as the task's only message.
+pub fn spawn_once<T, F>(ctx: &egui::Context, name: &'static str, job: F) -> Task<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    spawn(ctx, name, move |reporter| {
+        reporter.send(job());
+    })
+}
@@ -0,0 +1,121 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use wuwa_ploom_core::status::Severity;
+
+const TOAST_LIFETIME: Duration = Duration::from_secs(4);
+const HISTORY_LEN: usize = 50;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Success,
+    Info,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    pub fn color(self) -> egui::Color32 {
+        match self {
+            ToastLevel::Success => egui::Color32::from_rgb(80, 180, 80),
+            ToastLevel::Info => egui::Color32::from_rgb(120, 160, 220),
+            ToastLevel::Warning => egui::Color32::from_rgb(220, 170, 50),
+            ToastLevel::Error => egui::Color32::from_rgb(220, 80, 80),
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ToastLevel::Success => "OK",
+            ToastLevel::Info => "INFO",
+            ToastLevel::Warning => "WARN",
+            ToastLevel::Error => "ERROR",
+        }
+    }
+}
+
+impl From<Severity> for ToastLevel {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Success => ToastLevel::Success,
+            Severity::Info => ToastLevel::Info,
+            Severity::Warning => ToastLevel::Warning,
+            Severity::Error => ToastLevel::Error,
+        }
+    }
+}
+
+pub struct Toast {
+    pub level: ToastLevel,
+    pub text: String,
+    pub created: Instant,
+}
+
+/// Transient notifications in the window corner, plus a bounded history of everything shown.
+pub struct Toasts {
+    started: Instant,
+    active: Vec<Toast>,
+    history: Vec<Toast>,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            active: Vec::new(),
+            history: Vec::new(),
+        }
+    }
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: ToastLevel, text: impl Into<String>) {
+        let text = text.into();
+        let created = Instant::now();
+        self.active.push(Toast { level, text: text.clone(), created });
+        self.history.push(Toast { level, text, created });
+        if self.history.len() > HISTORY_LEN {
+            self.history.remove(0);
+        }
+    }
+
+    pub fn history(&self) -> &[Toast] {
+        &self.history
+    }
+
+    pub fn format_entry(&self, toast: &Toast) -> String {
+        let elapsed = toast.created.duration_since(self.started);
+        format!("[+{:.1}s] {}: {}", elapsed.as_secs_f32(), toast.level.label(), toast.text)
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        self.active.retain(|toast| toast.created.elapsed() < TOAST_LIFETIME);
+        if self.active.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        egui::Area::new("toasts")
+            .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-10.0, 10.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                for (index, toast) in self.active.iter().enumerate() {
+                    egui::Frame::popup(ui.style()).stroke(egui::Stroke::new(1.0, toast.level.color())).show(ui, |ui| {
+                        ui.horizontal(|ui| {
+                            ui.colored_label(toast.level.color(), toast.level.label());
+                            ui.label(&toast.text);
+                            if ui.small_button("x").on_hover_text("Dismiss").clicked() {
+                                dismissed = Some(index);
+                            }
+                        });
+                    });
+                }
+            });
+        if let Some(index) = dismissed {
+            self.active.remove(index);
+        }
+
+        // Keep repainting so expired toasts disappear without waiting for input.
+        ctx.request_repaint_after(Duration::from_millis(250));
+    }
+}
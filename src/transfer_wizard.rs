@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+
+use eframe::egui;
+
+/// Moving every graphics setting from one PC to another: export on the old PC, carry the file
+/// over, import on the new one.
+#[derive(Default)]
+pub enum TransferWizard {
+    #[default]
+    Start,
+    /// Exported to this file; the next step happens on the other PC.
+    Exported(PathBuf),
+}
+
+pub enum WizardOutcome {
+    Open,
+    Export,
+    Import,
+    Close,
+}
+
+impl TransferWizard {
+    pub fn show(&self, ctx: &egui::Context, sync_folder: Option<&Path>) -> WizardOutcome {
+        let mut outcome = WizardOutcome::Open;
+        egui::Window::new("Move settings to another PC").collapsible(false).resizable(false).show(ctx, |ui| {
+            match self {
+                TransferWizard::Start => {
+                    ui.label("1. On the PC with the settings you want, export them to a file.");
+                    ui.label("2. Copy the file to the other PC.");
+                    ui.label("3. On the other PC, import it and review what changes.");
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        if ui.button("Export from this PC...").on_hover_text("Writes every graphics setting to a file").clicked() {
+                            outcome = WizardOutcome::Export;
+                        }
+                        let import = ui.button("Import on this PC...");
+                        if import.on_hover_text("Resolution and display keys stay unticked so this PC keeps its own").clicked() {
+                            outcome = WizardOutcome::Import;
+                        }
+                    });
+                }
+                TransferWizard::Exported(path) => {
+                    ui.label(format!("Exported to {}", path.display()));
+                    match sync_folder.filter(|folder| path.starts_with(folder)) {
+                        Some(_) => ui.label("It is in your sync folder and will reach your other PCs on its own."),
+                        None => ui.label("Copy it to the other PC, e.g. on a USB stick or through a cloud drive."),
+                    };
+                    ui.label("Then open WuWa Ploom there, choose Move settings to another PC and Import on this PC.");
+                }
+            }
+            ui.separator();
+            if ui.button("Close").clicked() {
+                outcome = WizardOutcome::Close;
+            }
+        });
+        outcome
+    }
+}
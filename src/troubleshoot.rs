@@ -0,0 +1,199 @@
+use std::path::Path;
+
+use eframe::egui;
+use serde_json::Value;
+
+use crate::access::WriteAccess;
+use crate::diagnostics::CheckStatus;
+use crate::watch::WatchedKey;
+use crate::{engine_ini, game_user_settings, keymap};
+
+/// What the user says is wrong; each runs its own checks.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Symptom {
+    FpsReverts,
+    PatchNotApplying,
+}
+
+impl Symptom {
+    pub const ALL: [Symptom; 2] = [Symptom::FpsReverts, Symptom::PatchNotApplying];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Symptom::FpsReverts => "FPS goes back to 60 after launching the game",
+            Symptom::PatchNotApplying => "Patching doesn't apply",
+        }
+    }
+}
+
+/// What the fix button next to a finding does.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Fix {
+    TurnOffVsync,
+    /// Watch the frame-rate key and write it back whenever the game changes it.
+    KeepFrameRate,
+    SyncGameUserSettings,
+    LauncherHooks,
+    CloseGame,
+    AllowInDefender,
+    Locate,
+}
+
+impl Fix {
+    pub fn label(self) -> &'static str {
+        match self {
+            Fix::TurnOffVsync => "Turn VSync off",
+            Fix::KeepFrameRate => "Keep the FPS limit",
+            Fix::SyncGameUserSettings => "Open Config Tweaks",
+            Fix::LauncherHooks => "Open Launchers",
+            Fix::CloseGame => "Close the game...",
+            Fix::AllowInDefender => "Allow WuWa Ploom",
+            Fix::Locate => "Load the detected file",
+        }
+    }
+}
+
+pub struct Finding {
+    pub check: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    pub fix: Option<Fix>,
+}
+
+impl Finding {
+    fn new(check: &'static str, status: CheckStatus, detail: impl Into<String>, fix: Option<Fix>) -> Self {
+        Self { check, status, detail: detail.into(), fix }
+    }
+}
+
+/// The app state the checks look at.
+pub struct Observed<'a> {
+    pub db_path: &'a str,
+    /// The LocalStorage.db detection finds, if any.
+    pub detected: Option<&'a str>,
+    pub setting: Option<&'a Value>,
+    pub game_running: bool,
+    pub write_access: Option<&'a WriteAccess>,
+    pub cfa_blocked: bool,
+    pub watched: &'a [WatchedKey],
+}
+
+pub fn run(symptom: Symptom, observed: &Observed) -> Vec<Finding> {
+    match symptom {
+        Symptom::FpsReverts => fps_reverts(observed),
+        Symptom::PatchNotApplying => patch_not_applying(observed),
+    }
+}
+
+fn fps_reverts(observed: &Observed) -> Vec<Finding> {
+    let Some(setting) = observed.setting else {
+        return vec![Finding::new("Configuration loaded", CheckStatus::Fail, "Load the configuration file first.", Some(Fix::Locate))];
+    };
+    let names = keymap::active();
+    let mut findings = Vec::new();
+    findings.push(match setting[&names.vsync].as_i64() {
+        Some(0) | None => Finding::new("VSync", CheckStatus::Pass, "Off, so it doesn't cap the frame rate.", None),
+        Some(_) => Finding::new(
+            "VSync",
+            CheckStatus::Warn,
+            "On: the game caps the frame rate at the monitor's refresh rate, which can look like the limit went back.",
+            Some(Fix::TurnOffVsync),
+        ),
+    });
+    if let Some(preset) = setting[&names.quality_level].as_i64() {
+        findings.push(Finding::new(
+            "In-game graphics preset",
+            CheckStatus::Warn,
+            format!("Preset {} is stored. Picking a preset or touching FPS in the game's menu rewrites the limit; change single options instead.", preset),
+            None,
+        ));
+    }
+    let kept = observed.watched.iter().any(|watched| watched.key == names.frame_rate && watched.reassert);
+    findings.push(if kept {
+        Finding::new("Watchdog", CheckStatus::Pass, "The FPS limit is written back whenever the game changes it.", None)
+    } else {
+        Finding::new("Watchdog", CheckStatus::Warn, "Nothing puts the FPS limit back when the game resets it.", Some(Fix::KeepFrameRate))
+    });
+    if !kept {
+        findings.push(Finding::new(
+            "Launcher hook",
+            CheckStatus::Warn,
+            "If the app isn't running when you play, a launcher hook can re-apply the limit before every start.",
+            Some(Fix::LauncherHooks),
+        ));
+    }
+    let ini = game_user_settings::path_for_db(Path::new(observed.db_path)).filter(|path| path.is_file());
+    if let Some(text) = ini.and_then(|path| engine_ini::read(&path).ok()) {
+        findings.push(if game_user_settings::mismatches(&text, setting).is_empty() {
+            Finding::new("GameUserSettings.ini", CheckStatus::Pass, "Agrees with the database.", None)
+        } else {
+            Finding::new(
+                "GameUserSettings.ini",
+                CheckStatus::Fail,
+                "Holds a different frame rate or resolution, which the game can snap back to.",
+                Some(Fix::SyncGameUserSettings),
+            )
+        });
+    }
+    findings
+}
+
+fn patch_not_applying(observed: &Observed) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    findings.push(if observed.game_running {
+        Finding::new("Game closed", CheckStatus::Fail, "The game is running and overwrites the file when it exits.", Some(Fix::CloseGame))
+    } else {
+        Finding::new("Game closed", CheckStatus::Pass, "No game process found.", None)
+    });
+    findings.push(match (observed.db_path, observed.detected) {
+        ("", detected) => Finding::new("Configuration loaded", CheckStatus::Fail, "No configuration file is loaded.", detected.map(|_| Fix::Locate)),
+        (loaded, Some(detected)) if !Path::new(loaded).eq(Path::new(detected)) => Finding::new(
+            "Right file",
+            CheckStatus::Warn,
+            format!("The game's file was found at {}, but {} is loaded.", detected, loaded),
+            Some(Fix::Locate),
+        ),
+        (loaded, _) => Finding::new("Right file", CheckStatus::Pass, loaded.to_string(), None),
+    });
+    if let Some(access) = observed.write_access {
+        let status = match access {
+            WriteAccess::Writable => CheckStatus::Pass,
+            WriteAccess::NeedsElevation => CheckStatus::Warn,
+            WriteAccess::ReadOnly(_) => CheckStatus::Fail,
+        };
+        findings.push(Finding::new("Permissions", status, access.describe(), None));
+    }
+    if observed.cfa_blocked {
+        findings.push(Finding::new(
+            "Controlled Folder Access",
+            CheckStatus::Fail,
+            "Windows Security blocks WuWa Ploom from changing files in the game folder.",
+            Some(Fix::AllowInDefender),
+        ));
+    }
+    findings
+}
+
+/// Lists `findings` with a fix button where there is one; returns the fix clicked.
+pub fn show(ui: &mut egui::Ui, findings: &[Finding]) -> Option<Fix> {
+    let mut clicked = None;
+    egui::Grid::new("troubleshoot_grid").striped(true).num_columns(4).show(ui, |ui| {
+        for finding in findings {
+            ui.colored_label(finding.status.color(), finding.status.label());
+            ui.label(finding.check);
+            ui.label(&finding.detail);
+            match finding.fix {
+                Some(fix) if finding.status != CheckStatus::Pass => {
+                    if ui.button(fix.label()).clicked() {
+                        clicked = Some(fix);
+                    }
+                }
+                _ => {
+                    ui.label("");
+                }
+            }
+            ui.end_row();
+        }
+    });
+    clicked
+}
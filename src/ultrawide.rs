@@ -0,0 +1,96 @@
+use eframe::egui;
+use serde_json::Value;
+
+use crate::db::Patch;
+use crate::metadata::parse_version;
+
+const WIDTH_KEY: &str = "KeyPcResolutionWidth";
+const HEIGHT_KEY: &str = "KeyPcResolutionHeight";
+
+struct Preset {
+    aspect: &'static str,
+    width: i64,
+    height: i64,
+}
+
+const PRESETS: &[Preset] = &[
+    Preset { aspect: "21:9", width: 2560, height: 1080 },
+    Preset { aspect: "21:9", width: 3440, height: 1440 },
+    Preset { aspect: "21:9", width: 3840, height: 1600 },
+    Preset { aspect: "32:9", width: 3840, height: 1080 },
+    Preset { aspect: "32:9", width: 5120, height: 1440 },
+];
+
+/// A key the ultrawide presets touch, and the first client version that reads it back on start.
+struct KeySupport {
+    key: &'static str,
+    since: &'static [u32],
+    note: &'static str,
+}
+
+const KEYS: &[KeySupport] = &[
+    KeySupport { key: WIDTH_KEY, since: &[1, 0], note: "Render width." },
+    KeySupport { key: HEIGHT_KEY, since: &[1, 0], note: "Render height." },
+    KeySupport {
+        key: "KeyUltraWideAspectRatio",
+        since: &[1, 1],
+        note: "Unlocks the HUD and cutscene letterboxing for wider than 16:9.",
+    },
+];
+
+/// Whether the installed client reads `support.key`; `None` when the version is unknown.
+fn respected(support: &KeySupport, game_version: Option<&str>) -> Option<bool> {
+    let version = parse_version(game_version?);
+    if version.is_empty() {
+        return None;
+    }
+    Some(version.as_slice() >= support.since)
+}
+
+/// Resolution presets for 21:9 and 32:9 displays. Picking one stages the keys in `draft`.
+pub fn show(ui: &mut egui::Ui, setting: &Value, draft: &mut Patch, game_version: Option<&str>) {
+    egui::CollapsingHeader::new("Ultrawide").show(ui, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            for preset in PRESETS {
+                let text = format!("{} {}x{}", preset.aspect, preset.width, preset.height);
+                if ui.button(text).clicked() {
+                    stage(preset, setting, draft);
+                }
+            }
+        });
+        ui.add_space(4.0);
+        let shown_version = game_version.unwrap_or("unknown");
+        ui.label(format!("Installed client version: {}", shown_version));
+        egui::Grid::new("ultrawide_keys").striped(true).num_columns(3).show(ui, |ui| {
+            for support in KEYS {
+                ui.label(support.key).on_hover_text(support.note);
+                let since: Vec<String> = support.since.iter().map(u32::to_string).collect();
+                let status = if setting.get(support.key).is_none() {
+                    "not stored by this client".to_string()
+                } else {
+                    match respected(support, game_version) {
+                        Some(true) => "respected".to_string(),
+                        Some(false) => format!("ignored before {}", since.join(".")),
+                        None => format!("respected from {}", since.join(".")),
+                    }
+                };
+                ui.label(status);
+                ui.end_row();
+            }
+        });
+    });
+}
+
+fn stage(preset: &Preset, setting: &Value, draft: &mut Patch) {
+    let mut values = vec![(WIDTH_KEY, Value::from(preset.width)), (HEIGHT_KEY, Value::from(preset.height))];
+    if setting.get("KeyUltraWideAspectRatio").is_some() {
+        values.push(("KeyUltraWideAspectRatio", Value::from(1)));
+    }
+    for (key, value) in values {
+        if setting.get(key) == Some(&value) {
+            draft.remove(key);
+        } else {
+            draft.insert(key.to_string(), value);
+        }
+    }
+}
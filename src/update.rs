@@ -0,0 +1,273 @@
+//! Checks GitHub for a newer release and installs it. Stale versions break whenever the game
+//! moves its settings around, so the GUI offers this at startup.
+//!
+//! Installing downloads the release executable, checks it against the release's `SHA256SUMS`,
+//! and starts it with `REPLACE_ARG`: the new program waits until it can overwrite the running
+//! one, copies itself over it, and starts it again. A failed replacement leaves a note that the
+//! next start reports, and that start also clears the downloads left behind.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::{debug, info, instrument, warn};
+
+use crate::paths::app_dir;
+use crate::{MyError, Result};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/jakubs7/wuwa-ploom/releases/latest";
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// Release asset holding `<sha256>  <file name>` lines for the other assets.
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+/// The portable program as attached to releases, which is what the updater swaps in.
+const EXECUTABLE_ASSET: &str = if cfg!(windows) { "wuwa-ploom.exe" } else { "wuwa-ploom" };
+/// Passed to a downloaded program, followed by the path of the program it should replace.
+pub const REPLACE_ARG: &str = "--replace-exe";
+/// How long the new program keeps trying while the old one is still shutting down.
+const REPLACE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Written to the app folder by a replacement that failed, for the next start to report.
+const FAILURE_FILE: &str = "update-failed.txt";
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The release page, for notes and manual downloads.
+    pub html_url: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<Asset>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Asset {
+    pub name: String,
+    pub browser_download_url: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+impl Release {
+    /// The tag without its `v` prefix, e.g. "1.2.0".
+    pub fn version(&self) -> &str {
+        self.tag_name.trim_start_matches('v')
+    }
+
+    /// The downloadable program: an installer or executable for this platform.
+    pub fn installer(&self) -> Option<&Asset> {
+        let suffixes: &[&str] = if cfg!(windows) { &[".msi", ".exe"] } else { &[".tar.gz", ".AppImage"] };
+        suffixes
+            .iter()
+            .find_map(|suffix| self.assets.iter().find(|asset| asset.name.ends_with(suffix)))
+    }
+
+    /// The portable executable, if the release has one and checksums to verify it with.
+    pub fn executable(&self) -> Option<&Asset> {
+        self.asset(CHECKSUMS_ASSET)?;
+        self.asset(EXECUTABLE_ASSET)
+    }
+
+    fn asset(&self, name: &str) -> Option<&Asset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Dotted numeric version, e.g. "1.1.0" -> [1, 1, 0]; stops at the first non-numeric part.
+pub fn parse_version(version: &str) -> Vec<u32> {
+    version.split('.').map_while(|part| part.trim().parse().ok()).collect()
+}
+
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    parse_version(candidate.trim_start_matches('v')) > parse_version(current.trim_start_matches('v'))
+}
+
+fn get(url: &str) -> Result<ureq::Response> {
+    ureq::get(url)
+        .timeout(TIMEOUT)
+        .set("User-Agent", concat!("wuwa-ploom/", env!("CARGO_PKG_VERSION")))
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|err| MyError::Network(err.to_string()))
+}
+
+/// The latest release if it is newer than this build, `None` if we are up to date.
+#[instrument(err(level = "warn"))]
+pub fn check_latest() -> Result<Option<Release>> {
+    let release: Release = get(LATEST_RELEASE_URL)?.into_json()?;
+    info!(latest = release.version(), "checked for updates");
+    Ok(is_newer(release.version(), env!("CARGO_PKG_VERSION")).then_some(release))
+}
+
+/// The hash listed for `file_name` in a `SHA256SUMS` file.
+pub fn listed_checksum(sums: &str, file_name: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+        (name.trim().trim_start_matches('*') == file_name).then(|| hash.to_ascii_lowercase())
+    })
+}
+
+/// Where downloaded programs wait for the replacement; see `remove_downloads`.
+pub fn downloads_dir() -> PathBuf {
+    app_dir().join("updates")
+}
+
+/// Deletes every download in `dir`, returning how many went. Run at start, when any download is
+/// either installed or abandoned; one whose helper is still exiting stays until the next start.
+pub fn remove_downloads(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    let removed = entries.flatten().filter(|entry| fs::remove_file(entry.path()).is_ok()).count();
+    if removed > 0 {
+        info!(removed, "removed old update downloads");
+    }
+    removed
+}
+
+fn file_checksum(path: &Path) -> Result<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Downloads the release executable into `dir`, reporting (bytes done, bytes total) as it goes,
+/// and deletes it again if the download fails or its SHA-256 doesn't match the release's checksum list.
+#[instrument(skip(release, progress), fields(version = release.version()), err(level = "warn"))]
+pub fn download_executable(release: &Release, dir: &Path, mut progress: impl FnMut(u64, u64)) -> Result<PathBuf> {
+    let asset = release.executable().ok_or_else(|| MyError::Update("the release has no executable to install".into()))?;
+    let sums_asset = release.asset(CHECKSUMS_ASSET).ok_or_else(|| MyError::Update(format!("the release has no {}", CHECKSUMS_ASSET)))?;
+    let sums = get(&sums_asset.browser_download_url)?.into_string()?;
+    let expected =
+        listed_checksum(&sums, &asset.name).ok_or_else(|| MyError::Update(format!("{} is not in {}", asset.name, CHECKSUMS_ASSET)))?;
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(format!("{}-{}", release.version(), asset.name));
+    let mut download = || -> Result<String> {
+        let mut reader = get(&asset.browser_download_url)?.into_reader();
+        let mut file = File::create(&path)?;
+        let mut hasher = Sha256::new();
+        let mut buffer = vec![0; 64 * 1024];
+        let mut done = 0;
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            file.write_all(&buffer[..read])?;
+            hasher.update(&buffer[..read]);
+            done += read as u64;
+            progress(done, asset.size);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    };
+    let actual = download().inspect_err(|_| {
+        let _ = fs::remove_file(&path);
+    })?;
+    if actual != expected {
+        let _ = fs::remove_file(&path);
+        return Err(MyError::Update(format!("checksum mismatch for {} (expected {}, got {})", asset.name, expected, actual)));
+    }
+    info!(path = %path.display(), "downloaded and verified update");
+    Ok(path)
+}
+
+/// Starts the downloaded program as the replacement helper for this one; the caller should exit next.
+pub fn start_replace(downloaded: &Path) -> Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(downloaded, fs::Permissions::from_mode(0o755))?;
+    }
+    let current = env::current_exe()?;
+    Command::new(downloaded).arg(REPLACE_ARG).arg(&current).spawn()?;
+    info!(target = %current.display(), "handed over to the update helper");
+    Ok(())
+}
+
+/// Runs in the downloaded program: copies itself next to `target`, checks the copy and moves it
+/// over `target` once the old program has exited and released the file, then starts the program
+/// at `target` again. On failure `target` is left untouched, so that is the version from before,
+/// which reports the note this leaves in the app folder.
+#[instrument(err)]
+pub fn finish_replace(target: &Path) -> Result<()> {
+    let result = env::current_exe().map_err(MyError::from).and_then(|this| install_copy(&this, target));
+    match &result {
+        Ok(()) => info!("update installed; restarting"),
+        Err(err) => note_failure_in(&app_dir(), err),
+    }
+    Command::new(target).spawn()?;
+    result
+}
+
+/// Copies `this`, the verified download, to a file beside `target`, checks the copy against it
+/// and renames it over `target`, so a copy that fails partway never replaces the program.
+pub fn install_copy(this: &Path, target: &Path) -> Result<()> {
+    let mut name = target.file_name().unwrap_or_default().to_os_string();
+    name.push(".new");
+    let staged = target.with_file_name(name);
+    let result = copy_checked(this, &staged).and_then(|()| rename_when_free(&staged, target));
+    if result.is_err() {
+        let _ = fs::remove_file(&staged);
+    }
+    result
+}
+
+fn copy_checked(this: &Path, copy: &Path) -> Result<()> {
+    fs::copy(this, copy)?;
+    if file_checksum(copy)? != file_checksum(this)? {
+        return Err(MyError::Update(format!("the copy at {} does not match the download", copy.display())));
+    }
+    Ok(())
+}
+
+/// Moves `from` over `target`, retrying while the old program still holds it.
+fn rename_when_free(from: &Path, target: &Path) -> Result<()> {
+    let started = Instant::now();
+    loop {
+        match fs::rename(from, target) {
+            Ok(()) => return Ok(()),
+            Err(err) if started.elapsed() < REPLACE_TIMEOUT => {
+                debug!(%err, "target still in use; retrying");
+                thread::sleep(Duration::from_millis(500));
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Leaves `err` in `dir` for `take_failure_from`; only logged if that fails too.
+pub fn note_failure_in(dir: &Path, err: &MyError) {
+    let text = format!("Installing the update failed: {}", err);
+    if let Err(err) = fs::create_dir_all(dir).and_then(|()| fs::write(dir.join(FAILURE_FILE), text)) {
+        warn!(%err, "could not leave the update failure for the next start");
+    }
+}
+
+/// The failure a replacement left in `dir`, removed so it is reported once.
+pub fn take_failure_from(dir: &Path) -> Option<String> {
+    let path = dir.join(FAILURE_FILE);
+    let text = fs::read_to_string(&path).ok()?;
+    let _ = fs::remove_file(&path);
+    Some(text)
+}
+
+/// The failure the last replacement left in the app folder; see `take_failure_from`.
+pub fn take_failure() -> Option<String> {
+    take_failure_from(&app_dir())
+}
@@ -0,0 +1,75 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+use egui::output::OpenUrl;
+use egui::RichText;
+
+use wuwa_ploom_core::update::{self, Release};
+use wuwa_ploom_core::Result;
+
+use crate::tasks::{self, Task};
+
+pub enum DownloadUpdate {
+    Progress(u64, u64),
+    Finished(Result<PathBuf>),
+}
+
+/// Downloads and verifies the release executable in the background, reporting progress as it goes.
+pub fn spawn_download(ctx: &egui::Context, release: Release) -> Task<DownloadUpdate> {
+    tasks::spawn(ctx, "update-download", move |reporter| {
+        let result = update::download_executable(&release, &update::downloads_dir(), |done, total| {
+            reporter.send(DownloadUpdate::Progress(done, total));
+        });
+        reporter.send(DownloadUpdate::Finished(result));
+    })
+}
+
+pub enum BannerAction {
+    Idle,
+    Install,
+    Dismiss,
+}
+
+fn open(ui: &egui::Ui, url: &str) {
+    ui.output().open_url = Some(OpenUrl::new_tab(url));
+}
+
+/// A strip announcing `release`. `progress` is (bytes done, bytes total) while an install downloads.
+pub fn show(ui: &mut egui::Ui, release: &Release, progress: Option<(u64, u64)>) -> BannerAction {
+    let mut action = BannerAction::Idle;
+    egui::Frame::group(ui.style()).show(ui, |ui| {
+        ui.horizontal_wrapped(|ui| {
+            let text = format!("Version {} is available (you have {}).", release.version(), env!("CARGO_PKG_VERSION"));
+            ui.label(RichText::new(text).color(ui.visuals().warn_fg_color));
+            if let Some((done, total)) = progress {
+                let fraction = if total == 0 { 0.0 } else { done as f32 / total as f32 };
+                ui.add(egui::ProgressBar::new(fraction).desired_width(120.0).show_percentage());
+                return;
+            }
+            if release.executable().is_some() {
+                let install = ui.button("Install and restart").on_hover_text("Download, verify the checksum, and replace this program");
+                if install.clicked() {
+                    action = BannerAction::Install;
+                }
+            }
+            if let Some(installer) = release.installer() {
+                let download = ui.button("Download").on_hover_text(format!("{} ({} KB)", installer.name, installer.size / 1024));
+                if download.clicked() {
+                    open(ui, &installer.browser_download_url);
+                }
+            }
+            let notes = ui.button("Release notes");
+            let notes = match &release.body {
+                Some(body) if !body.trim().is_empty() => notes.on_hover_text(body.trim()),
+                _ => notes,
+            };
+            if notes.clicked() {
+                open(ui, &release.html_url);
+            }
+            if ui.small_button("Dismiss").clicked() {
+                action = BannerAction::Dismiss;
+            }
+        });
+    });
+    action
+}
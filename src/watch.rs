@@ -0,0 +1,96 @@
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::db::Patch;
+use crate::metadata::Metadata;
+
+/// A GameQualitySetting field the user wants to keep at `value`.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchedKey {
+    pub key: String,
+    pub value: Value,
+    /// Write `value` back whenever the game changes it.
+    pub reassert: bool,
+}
+
+/// Watched keys whose value went from `before` to something other than what the user set,
+/// with the new value.
+pub fn changed_by_game<'a>(watched: &'a [WatchedKey], before: &Value, after: &'a Value) -> Vec<(&'a WatchedKey, &'a Value)> {
+    watched
+        .iter()
+        .filter_map(|watch| {
+            let now = after.get(&watch.key).unwrap_or(&Value::Null);
+            let was = before.get(&watch.key).unwrap_or(&Value::Null);
+            (now != was && *now != watch.value).then_some((watch, now))
+        })
+        .collect()
+}
+
+/// The patch that puts every re-asserted key back to the user's value; empty when none drifted.
+pub fn reassert_patch(watched: &[WatchedKey], setting: &Value) -> Patch {
+    watched
+        .iter()
+        .filter(|watch| watch.reassert && setting.get(&watch.key).unwrap_or(&Value::Null) != &watch.value)
+        .map(|watch| (watch.key.clone(), watch.value.clone()))
+        .collect()
+}
+
+/// Our own writes move the target, so they are not reported as the game's doing.
+pub fn follow_write(watched: &mut [WatchedKey], patch: &Patch) -> bool {
+    let mut changed = false;
+    for watch in watched.iter_mut() {
+        if let Some(value) = patch.get(&watch.key) {
+            changed |= watch.value != *value;
+            watch.value = value.clone();
+        }
+    }
+    changed
+}
+
+/// Lists watched keys and lets the user add, remove or toggle re-assertion. Returns true on any change.
+pub fn show(ui: &mut egui::Ui, watched: &mut Vec<WatchedKey>, metadata: &Metadata, setting: &Value) -> bool {
+    let mut changed = false;
+    egui::CollapsingHeader::new(format!("Watched keys ({})", watched.len())).show(ui, |ui| {
+        let mut remove = None;
+        egui::Grid::new("watched_keys").striped(true).num_columns(4).show(ui, |ui| {
+            for (index, watch) in watched.iter_mut().enumerate() {
+                let label = metadata.get(&watch.key).map_or(watch.key.as_str(), |meta| meta.label.as_str());
+                ui.label(label).on_hover_text(&watch.key);
+                ui.label(watch.value.to_string());
+                changed |= ui
+                    .checkbox(&mut watch.reassert, "Re-apply")
+                    .on_hover_text("Write this value back whenever the game changes it")
+                    .changed();
+                if ui.small_button("Remove").clicked() {
+                    remove = Some(index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(index) = remove {
+            watched.remove(index);
+            changed = true;
+        }
+
+        let Some(fields) = setting.as_object() else {
+            return;
+        };
+        let mut add = None;
+        egui::ComboBox::from_label("Watch a key").selected_text("Choose...").show_ui(ui, |ui| {
+            for (key, value) in fields {
+                if watched.iter().any(|watch| watch.key == *key) {
+                    continue;
+                }
+                if ui.selectable_label(false, key).clicked() {
+                    add = Some(WatchedKey { key: key.clone(), value: value.clone(), reassert: false });
+                }
+            }
+        });
+        if let Some(watch) = add {
+            watched.push(watch);
+            changed = true;
+        }
+    });
+    changed
+}
@@ -0,0 +1,66 @@
+//! Tuning for the watchdog that writes kept keys back, shared by the app and the CLI's `keep`.
+//! Some game screens rewrite the frame-rate limit each time they open, and re-applying at once
+//! every time fights the game in a loop. So the watchdog waits until the game has left the file
+//! alone for a while, and after too many re-applies in an hour it stops and says so instead.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// The span `Tuning::max_per_hour` counts over.
+pub const WINDOW: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tuning {
+    /// How often the file is checked for changes, in seconds.
+    pub poll_secs: u64,
+    /// How long the game must leave the file alone before kept keys are written back, in seconds.
+    pub debounce_secs: u64,
+    /// Re-applies allowed within an hour; 0 for no limit.
+    pub max_per_hour: u32,
+}
+
+impl Default for Tuning {
+    fn default() -> Self {
+        Self { poll_secs: 3, debounce_secs: 5, max_per_hour: 10 }
+    }
+}
+
+impl Tuning {
+    pub fn poll_interval(&self) -> Duration {
+        Duration::from_secs(self.poll_secs.max(1))
+    }
+
+    pub fn debounce(&self) -> Duration {
+        Duration::from_secs(self.debounce_secs)
+    }
+}
+
+/// Re-applies within the last `WINDOW`.
+#[derive(Debug, Default)]
+pub struct Budget {
+    recent: Vec<Instant>,
+}
+
+impl Budget {
+    /// Whether one more re-apply at `now` stays within `max_per_hour`; it is counted if so.
+    pub fn take(&mut self, now: Instant, max_per_hour: u32) -> bool {
+        self.recent.retain(|&at| now.saturating_duration_since(at) < WINDOW);
+        if max_per_hour > 0 && self.recent.len() >= max_per_hour as usize {
+            return false;
+        }
+        self.recent.push(now);
+        true
+    }
+
+    /// When the oldest counted re-apply leaves the window, freeing room for another.
+    pub fn frees_at(&self) -> Option<Instant> {
+        self.recent.first().map(|&at| at + WINDOW)
+    }
+}
+
+/// Whole minutes from `now` until `at`, rounded up, for "resumes in N min".
+pub fn minutes_until(at: Instant, now: Instant) -> u64 {
+    at.saturating_duration_since(now).as_secs().div_ceil(60)
+}
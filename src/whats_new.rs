@@ -0,0 +1,38 @@
+use eframe::egui;
+use wuwa_ploom_core::changelog::Release;
+
+fn notes(ui: &mut egui::Ui, release: &Release) {
+    for note in &release.notes {
+        ui.label(format!("• {}", note));
+    }
+    if !release.game_settings.is_empty() {
+        ui.label(egui::RichText::new("Game settings").strong());
+        for note in &release.game_settings {
+            ui.label(format!("• {}", note));
+        }
+    }
+}
+
+/// Release notes for `releases`, as a list of collapsible sections.
+pub fn show_releases(ui: &mut egui::Ui, releases: &[&Release]) {
+    for (index, release) in releases.iter().enumerate() {
+        egui::CollapsingHeader::new(format!("Version {}", release.version)).default_open(index == 0).show(ui, |ui| notes(ui, release));
+    }
+}
+
+/// The "what changed" window shown on the first start after an update; `false` once closed.
+pub fn show(ctx: &egui::Context, releases: &[Release]) -> bool {
+    let mut open = true;
+    egui::Window::new("What's new").collapsible(false).show(ctx, |ui| {
+        ui.label(format!("WuWa Ploom was updated to {}. Changes since the version you last ran:", env!("CARGO_PKG_VERSION")));
+        ui.separator();
+        egui::ScrollArea::vertical().max_height(360.0).show(ui, |ui| {
+            show_releases(ui, &releases.iter().collect::<Vec<_>>());
+        });
+        ui.separator();
+        if ui.button("Close").clicked() {
+            open = false;
+        }
+    });
+    open
+}
@@ -0,0 +1,124 @@
+//! Wine and Proton prefixes, where the game runs on Linux and the Steam Deck. Each prefix has
+//! its own `drive_c` and keeps the registry as text in `system.reg` and `user.reg`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::registry::{Hive, Location};
+
+/// Steam installs relative to the home directory: native, the `~/.steam` symlink, and Flatpak.
+const STEAM_ROOTS: &[&str] = &[".local/share/Steam", ".steam/steam", ".var/app/com.valvesoftware.Steam/.local/share/Steam"];
+/// Folders holding one prefix per game: Lutris and Heroic defaults.
+const PREFIX_PARENTS: &[&str] = &["Games", "Games/Heroic/Prefixes"];
+
+pub fn home() -> Option<PathBuf> {
+    env::var_os("HOME").map(PathBuf::from)
+}
+
+fn is_prefix(dir: &Path) -> bool {
+    dir.join("drive_c").is_dir()
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).filter(|path| path.is_dir()).collect();
+    dirs.sort();
+    dirs
+}
+
+/// Library folders listed in `libraryfolders.vdf` lines such as `"path"  "/mnt/sd/SteamLibrary"`.
+fn library_folders(vdf: &str) -> Vec<PathBuf> {
+    vdf.lines()
+        .filter_map(|line| {
+            let rest = line.trim().strip_prefix("\"path\"")?;
+            let path = rest.trim().strip_prefix('"')?.strip_suffix('"')?;
+            Some(PathBuf::from(path.replace("\\\\", "\\")))
+        })
+        .collect()
+}
+
+/// Every Steam library folder (the one containing `steamapps`) under `home`, without duplicates.
+pub fn steam_libraries(home: &Path) -> Vec<PathBuf> {
+    let mut libraries: Vec<PathBuf> = Vec::new();
+    for root in STEAM_ROOTS.iter().map(|root| home.join(root)) {
+        let Ok(root) = root.canonicalize() else {
+            continue;
+        };
+        let vdf = fs::read_to_string(root.join("steamapps").join("libraryfolders.vdf")).unwrap_or_default();
+        for library in std::iter::once(root.clone()).chain(library_folders(&vdf)) {
+            let library = library.canonicalize().unwrap_or(library);
+            if library.join("steamapps").is_dir() && !libraries.contains(&library) {
+                libraries.push(library);
+            }
+        }
+    }
+    libraries
+}
+
+/// Every Wine prefix we know where to look for: `~/.wine`, Proton's `compatdata/*/pfx` in each
+/// Steam library, and Lutris/Heroic game folders.
+pub fn prefixes(home: &Path) -> Vec<PathBuf> {
+    let mut candidates = vec![home.join(".wine")];
+    for library in steam_libraries(home) {
+        candidates.extend(subdirs(&library.join("steamapps").join("compatdata")).into_iter().map(|app| app.join("pfx")));
+    }
+    for parent in PREFIX_PARENTS {
+        candidates.extend(subdirs(&home.join(parent)));
+    }
+    candidates.retain(|dir| is_prefix(dir));
+    candidates
+}
+
+/// Undoes the escaping Wine applies to keys and string data in `.reg` files.
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// String value `value` of the key at `location` as stored in the prefix's `system.reg` or
+/// `user.reg`.
+pub fn registry_string(prefix: &Path, location: &Location, value: &str) -> Option<String> {
+    let file = match location.hive {
+        Hive::LocalMachine => "system.reg",
+        Hive::CurrentUser => "user.reg",
+    };
+    let reg = fs::read_to_string(prefix.join(file)).ok()?;
+    let key = location.redirected_key();
+    let wanted_name = format!("\"{}\"=\"", value);
+    let mut in_key = false;
+    for line in reg.lines() {
+        if let Some(header) = line.strip_prefix('[') {
+            let name = header.split(']').next().unwrap_or_default();
+            in_key = unescape(name).eq_ignore_ascii_case(&key);
+        } else if in_key {
+            if let Some(data) = line.strip_prefix(&wanted_name) {
+                return Some(unescape(data.strip_suffix('"')?));
+            }
+        }
+    }
+    None
+}
+
+/// Where a Windows path such as `C:\Games\Wuthering Waves` lives on the host, via the prefix's
+/// `dosdevices` drive links.
+pub fn host_path(prefix: &Path, windows_path: &str) -> Option<PathBuf> {
+    let (drive, rest) = windows_path.split_once(':')?;
+    let mut path = prefix.join("dosdevices").join(format!("{}:", drive.to_ascii_lowercase()));
+    for part in rest.split('\\').filter(|part| !part.is_empty()) {
+        path.push(part);
+    }
+    Some(path)
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use wuwa_ploom_core::access::{self, WriteAccess};
+use wuwa_ploom_core::db;
+use wuwa_ploom_core::keymap::KeyNames;
+use wuwa_ploom_core::platform::StubPlatform;
+
+use common::{quality_db, TempDir, V1_0_SETTING};
+
+#[test]
+fn fresh_database_is_writable() {
+    let dir = TempDir::new("access");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+
+    assert_eq!(WriteAccess::check(Path::new(&db_path), &StubPlatform::new()), WriteAccess::Writable);
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1, "the probe leaves nothing behind");
+}
+
+#[test]
+fn read_only_file_cannot_be_written_even_elevated() {
+    let dir = TempDir::new("access-ro");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let mut permissions = fs::metadata(&db_path).unwrap().permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(&db_path, permissions).unwrap();
+
+    let access = WriteAccess::check(Path::new(&db_path), &StubPlatform::new());
+
+    assert!(matches!(access, WriteAccess::ReadOnly(_)), "{:?}", access);
+}
+
+#[test]
+fn elevated_helper_applies_the_request() {
+    let dir = TempDir::new("access-helper");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let request = dir.path().join("request.json");
+    let result = dir.path().join("result.json");
+    let patch = db::fps_patch(120);
+    let names = KeyNames::default();
+    fs::write(&request, serde_json::json!({"db_path": db_path, "patch": patch, "game": "wuwa", "names": names}).to_string()).unwrap();
+
+    assert_eq!(access::serve_elevated(&request, &result), 0);
+
+    let outcome: serde_json::Value = serde_json::from_slice(&fs::read(&result).unwrap()).unwrap();
+    assert_eq!(outcome["inverse"], serde_json::json!({"KeyCustomFrameRate": 60}));
+    assert_eq!(db::read_quality_setting_file(&db_path).unwrap()["KeyCustomFrameRate"], 120);
+}
+
+#[test]
+fn elevated_helper_refuses_a_game_it_does_not_know() {
+    let dir = TempDir::new("access-helper-game");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let request = dir.path().join("request.json");
+    let result = dir.path().join("result.json");
+    let names = KeyNames::default();
+    fs::write(&request, serde_json::json!({"db_path": db_path, "patch": db::fps_patch(120), "game": "nope", "names": names}).to_string()).unwrap();
+
+    assert_eq!(access::serve_elevated(&request, &result), 1);
+    assert_eq!(db::read_quality_setting_file(&db_path).unwrap()["KeyCustomFrameRate"], 60);
+}
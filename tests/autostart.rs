@@ -0,0 +1,21 @@
+use std::path::Path;
+
+use wuwa_ploom_core::autostart::{self, State};
+use wuwa_ploom_core::platform::StubPlatform;
+
+#[test]
+fn the_switch_follows_the_run_entry() {
+    let platform = StubPlatform::default();
+    let exe = Path::new("C:\\Tools\\WuWa Ploom\\wuwa-ploom.exe");
+    assert_eq!(autostart::state(&platform, exe), State::Off);
+
+    autostart::set(&platform, exe, true).unwrap();
+    assert_eq!(autostart::state(&platform, exe), State::On);
+    assert_eq!(autostart::command(exe), "\"C:\\Tools\\WuWa Ploom\\wuwa-ploom.exe\" --minimized");
+
+    let moved = Path::new("D:\\Apps\\wuwa-ploom.exe");
+    assert_eq!(autostart::state(&platform, moved), State::Elsewhere(autostart::command(exe)));
+
+    autostart::set(&platform, exe, false).unwrap();
+    assert_eq!(autostart::state(&platform, exe), State::Off);
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use std::fs;
+
+use wuwa_ploom_core::{backup, db};
+
+use common::{quality_db, raw_values, TempDir, V1_0_SETTING};
+
+#[test]
+fn backup_then_restore_round_trips() {
+    let dir = TempDir::new("backup");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let original = raw_values(&db_path, "GameQualitySetting");
+
+    let backup = backup::backup_db(&db_path, &dir.path().join("backups")).unwrap();
+    assert!(backup.starts_with(dir.path().join("backups")));
+    db::apply_patch(&db_path, &db::fps_patch(120)).unwrap();
+    assert_ne!(raw_values(&db_path, "GameQualitySetting"), original);
+
+    backup::restore_db(&backup, &db_path).unwrap();
+    assert_eq!(raw_values(&db_path, "GameQualitySetting"), original);
+}
+
+#[test]
+fn backup_copies_the_write_ahead_log() {
+    let dir = TempDir::new("wal");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    fs::write(format!("{}-wal", db_path), b"log").unwrap();
+
+    fs::write(format!("{}-shm", db_path), b"index").unwrap();
+
+    let backup = backup::backup_db(&db_path, &dir.path().join("backups")).unwrap();
+    assert_eq!(fs::read(format!("{}-wal", backup.display())).unwrap(), b"log");
+    assert_eq!(fs::read(format!("{}-shm", backup.display())).unwrap(), b"index");
+}
+
+#[test]
+fn backups_within_one_second_get_their_own_names() {
+    let dir = TempDir::new("backup-same-second");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let backups = dir.path().join("backups");
+
+    let first = backup::backup_db(&db_path, &backups).unwrap();
+    let second = backup::backup_db(&db_path, &backups).unwrap();
+    let third = backup::backup_db(&db_path, &backups).unwrap();
+    assert!(first != second && second != third && first != third);
+    assert_eq!(backup::snapshots(&backups).len(), 3);
+}
+
+#[test]
+fn restore_drops_a_newer_write_ahead_log() {
+    let dir = TempDir::new("stale-wal");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let backup = backup::backup_db(&db_path, &dir.path().join("backups")).unwrap();
+    fs::write(format!("{}-wal", db_path), b"newer").unwrap();
+
+    backup::restore_db(&backup, &db_path).unwrap();
+    assert!(!dir.path().join("LocalStorage.db-wal").exists());
+}
+
+#[test]
+fn backup_of_missing_db_fails() {
+    let dir = TempDir::new("backup-missing");
+    let missing = dir.path().join("LocalStorage.db").display().to_string();
+    assert!(backup::backup_db(&missing, &dir.path().join("backups")).is_err());
+}
+
+#[test]
+fn pruning_removes_the_oldest_backups_over_budget() {
+    let dir = TempDir::new("prune");
+    let backups = dir.path().join("backups");
+    fs::create_dir_all(&backups).unwrap();
+    fs::write(backups.join("LocalStorage-100.db"), [0; 40]).unwrap();
+    fs::write(backups.join("LocalStorage-100.db-wal"), [0; 10]).unwrap();
+    fs::write(backups.join("Engine-200.ini"), [0; 30]).unwrap();
+    fs::write(backups.join("LocalStorage-300.db"), [0; 40]).unwrap();
+    fs::write(backups.join("notes.txt"), [0; 500]).unwrap();
+
+    let taken: Vec<u64> = backup::snapshots(&backups).iter().map(|snapshot| snapshot.taken).collect();
+    assert_eq!(taken, [100, 200, 300]);
+    let removed = backup::prune(&backups, 80);
+    assert_eq!(removed.iter().map(|snapshot| snapshot.taken).collect::<Vec<_>>(), [100]);
+    assert!(!backups.join("LocalStorage-100.db-wal").exists());
+    // The newest backup of each source stays even over budget.
+    assert!(backup::prune(&backups, 0).is_empty());
+    assert!(backups.join("LocalStorage-300.db").exists() && backups.join("Engine-200.ini").exists());
+    assert!(backups.join("notes.txt").exists());
+}
+
+#[test]
+fn pruning_leaves_other_pcs_backups_alone() {
+    let dir = TempDir::new("prune-other-pc");
+    let root = dir.path().join("backups");
+    let ours = backup::for_this_pc(&root);
+    assert_eq!(ours.parent(), Some(root.as_path()));
+    let theirs = root.join("OTHER-PC");
+    for folder in [&ours, &theirs] {
+        fs::create_dir_all(folder).unwrap();
+        fs::write(folder.join("LocalStorage-100.db"), [0; 40]).unwrap();
+        fs::write(folder.join("LocalStorage-200.db"), [0; 40]).unwrap();
+    }
+
+    assert_eq!(backup::prune(&ours, 0).len(), 1);
+    assert!(theirs.join("LocalStorage-100.db").exists());
+}
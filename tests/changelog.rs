@@ -0,0 +1,43 @@
+use wuwa_ploom_core::changelog;
+
+const NOTES: &str = "# Changelog
+
+Intro text.
+
+## 1.2.0 - 2025-03-01
+- Faster scans.
+- A long note
+  carried onto a second line.
+
+### Game settings
+- Reads KeyCustomFrameRate from the 2.1 client.
+
+## v1.1.0
+- Profiles.
+
+## 1.0.0
+- First release.
+";
+
+#[test]
+fn releases_split_notes_and_game_setting_changes() {
+    let releases = changelog::parse(NOTES);
+    let versions: Vec<&str> = releases.iter().map(|release| release.version.as_str()).collect();
+    assert_eq!(versions, ["1.2.0", "1.1.0", "1.0.0"]);
+    assert_eq!(releases[0].notes, ["Faster scans.", "A long note carried onto a second line."]);
+    assert_eq!(releases[0].game_settings, ["Reads KeyCustomFrameRate from the 2.1 client."]);
+    assert!(releases[1].game_settings.is_empty());
+}
+
+#[test]
+fn only_releases_since_the_last_seen_version_are_shown() {
+    let releases = changelog::parse(NOTES);
+    let shown: Vec<&str> = changelog::since(&releases, "1.0.0", "1.1.0").iter().map(|release| release.version.as_str()).collect();
+    assert_eq!(shown, ["1.1.0"]);
+    assert!(changelog::since(&releases, "1.2.0", "1.2.0").is_empty());
+}
+
+#[test]
+fn the_shipped_changelog_covers_this_version() {
+    assert!(changelog::releases().iter().any(|release| release.version == env!("CARGO_PKG_VERSION")));
+}
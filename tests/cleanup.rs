@@ -0,0 +1,38 @@
+mod common;
+
+use wuwa_ploom_core::cleanup::{self, Transient};
+use wuwa_ploom_core::db;
+
+use common::{fixture_db, TempDir, V1_0_SETTING};
+
+#[test]
+fn patterns_match_whole_names_with_wildcards() {
+    assert!(cleanup::matches("*Cache*", "NoticeCacheData"));
+    assert!(cleanup::matches("*cache", "ResourceCache"));
+    assert!(cleanup::matches("Launch*Time", "LaunchTime"));
+    assert!(!cleanup::matches("Launch*Time", "LaunchTimes"));
+    assert!(!cleanup::matches("Cache", "CacheData"));
+}
+
+#[test]
+fn cleaning_backs_up_and_keeps_settings_and_identifiers() {
+    let dir = TempDir::new("cleanup");
+    let rows = [
+        ("GameQualitySetting", V1_0_SETTING),
+        ("NoticeCache", "[1,2,3]"),
+        ("AccountIdCache", "\"12345678\""),
+        ("PlayerName", "\"Rover\""),
+    ];
+    let db_path = fixture_db(dir.path(), &rows, true);
+
+    let registry = [Transient { pattern: "*Cache".into(), description: String::new() }];
+    let found = cleanup::find_with(&db_path, &registry).unwrap();
+    let keys: Vec<String> = found.into_iter().map(|(entry, _)| entry.key).collect();
+    assert_eq!(keys, ["NoticeCache"]);
+
+    let (backup, removed) = cleanup::clean(&db_path, &dir.path().join("backups"), &keys).unwrap();
+    assert_eq!(removed, 1);
+    assert!(backup.is_file());
+    let left: Vec<String> = db::list_keys(&db_path, "", 10).unwrap().entries.into_iter().map(|entry| entry.key).collect();
+    assert_eq!(left, ["AccountIdCache", "GameQualitySetting", "PlayerName"]);
+}
@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use rusqlite::{params, Connection};
+
+/// A scratch directory under the system temp dir, removed when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let unique = format!("wuwa-ploom-test-{}-{}-{}", name, std::process::id(), NEXT.fetch_add(1, Ordering::Relaxed));
+        let path = std::env::temp_dir().join(unique);
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// GameQualitySetting as an early client stored it: the frame-rate key and a few quality levels.
+pub const V1_0_SETTING: &str = r#"{"KeyCustomFrameRate":60,"KeyPcVsync":1,"KeyQualityLevel":2,"KeyNewShadowQuality":2}"#;
+
+/// A later client with upscaler and HDR keys added.
+pub const V1_2_SETTING: &str = r#"{"KeyCustomFrameRate":60,"KeyPcVsync":0,"KeyQualityLevel":3,"KeyNewShadowQuality":3,"KeyFsrEnable":1,"KeyFsrMode":1,"KeyHdrEnable":0,"KeyBrightness":1.0}"#;
+
+/// Builds a LocalStorage.db with the given rows. `unique_keys` mirrors the game's primary key;
+/// without it duplicate rows can be created, as some damaged files have.
+pub fn fixture_db(dir: &Path, rows: &[(&str, &str)], unique_keys: bool) -> String {
+    let path = dir.join("LocalStorage.db");
+    let conn = Connection::open(&path).unwrap();
+    let schema = if unique_keys {
+        "CREATE TABLE LocalStorage (key TEXT PRIMARY KEY, value TEXT);"
+    } else {
+        "CREATE TABLE LocalStorage (key TEXT, value TEXT);"
+    };
+    conn.execute(schema, []).unwrap();
+    let tx = conn.unchecked_transaction().unwrap();
+    for (key, value) in rows {
+        tx.execute("INSERT INTO LocalStorage (key, value) VALUES (?1, ?2);", params![key, value]).unwrap();
+    }
+    tx.commit().unwrap();
+    path.display().to_string()
+}
+
+pub fn quality_db(dir: &Path, setting: &str) -> String {
+    fixture_db(dir, &[("GameQualitySetting", setting), ("PlayerName", "\"Rover\"")], true)
+}
+
+/// Raw value of every row stored under `key`, in insertion order.
+pub fn raw_values(db_path: &str, key: &str) -> Vec<String> {
+    let conn = Connection::open(db_path).unwrap();
+    let mut stmt = conn.prepare("SELECT value FROM LocalStorage WHERE key = ?1 ORDER BY rowid;").unwrap();
+    let rows = stmt.query_map(params![key], |row| row.get(0)).unwrap();
+    rows.collect::<rusqlite::Result<_>>().unwrap()
+}
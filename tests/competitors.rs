@@ -0,0 +1,31 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use wuwa_ploom_core::competitors::{self, Sign, FLIP_WINDOW};
+
+#[test]
+fn unlocker_names_are_recognised() {
+    assert!(competitors::is_unlocker_name("WuWa-FPS-Unlocker.exe"));
+    assert!(competitors::is_unlocker_name("wuthering_unlock"));
+    assert!(!competitors::is_unlocker_name("wuwa-ploom-fps-unlocker.exe"));
+    assert!(!competitors::is_unlocker_name("Client-Win64-Shipping.exe"));
+
+    let csv = "\"\\Microsoft\\Windows\\Defrag\\ScheduledDefrag\",\"N/A\",\"Ready\"\n\"\\WuWaFpsUnlock\",\"5/1/2024 10:00:00 AM\",\"Ready\"\n";
+    assert_eq!(competitors::parse_scheduled_tasks(csv), ["\\WuWaFpsUnlock"]);
+}
+
+#[test]
+fn frame_rate_lines_in_engine_ini_are_signs() {
+    let text = "[SystemSettings]\nr.Streaming.PoolSize=3000\nt.MaxFPS=144\n";
+    assert_eq!(competitors::ini_signs(text), [Sign::EngineIni { key: "t.MaxFPS".into(), value: "144".into() }]);
+    assert!(competitors::ini_signs("[SystemSettings]\nr.Streaming.PoolSize=3000\n").is_empty());
+}
+
+#[test]
+fn flip_flopping_needs_values_to_come_back() {
+    let at = |seconds: u64| UNIX_EPOCH + Duration::from_secs(1_714_557_600 + seconds);
+    let steady = [(at(0), 60), (at(30), 120), (at(60), 144)];
+    assert!(!competitors::is_flip_flopping(&steady, at(60)));
+    let fighting = [(at(0), 120), (at(30), 60), (at(60), 120), (at(90), 60)];
+    assert!(competitors::is_flip_flopping(&fighting, at(90)));
+    assert!(!competitors::is_flip_flopping(&fighting, at(90) + FLIP_WINDOW));
+}
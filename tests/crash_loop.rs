@@ -0,0 +1,35 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use wuwa_ploom_core::crash_loop::{self, CRASH_MARKER};
+use wuwa_ploom_core::MyError;
+
+const LOG: &str = "\
+2024-05-01T10:00:00.000000Z  INFO wuwa_ploom_cli: wuwa-ploom-cli command=[\"keep\"]
+2024-05-01T10:01:30.250000Z ERROR wuwa_ploom_cli: watchdog crashed reason=database is locked
+2024-05-01T10:03:00.000000Z ERROR wuwa_ploom_cli: watchdog crashed reason=panicked
+garbled line watchdog crashed
+";
+
+#[test]
+fn crash_times_are_read_from_log_lines() {
+    let times = crash_loop::marked_times(LOG, CRASH_MARKER);
+    // 2024-05-01T10:01:30.25Z
+    assert_eq!(times, [UNIX_EPOCH + Duration::from_millis(1_714_557_690_250), UNIX_EPOCH + Duration::from_secs(1_714_557_780)]);
+}
+
+#[test]
+fn a_loop_needs_enough_crashes_close_together() {
+    let mut crashes = crash_loop::marked_times(LOG, CRASH_MARKER);
+    let third = UNIX_EPOCH + Duration::from_secs(1_714_557_900);
+    assert!(!crash_loop::is_loop(&crashes, third));
+    crashes.push(third);
+    assert!(crash_loop::is_loop(&crashes, third));
+    assert!(!crash_loop::is_loop(&crashes, third + crash_loop::WINDOW));
+}
+
+#[test]
+fn a_locked_file_is_not_a_crash() {
+    assert!(!crash_loop::is_crash(&MyError::Locked("database is locked".into())));
+    assert!(crash_loop::is_crash(&MyError::Corruption("file is not a database".into())));
+    assert!(crash_loop::is_crash(&MyError::Io(std::io::Error::other("disk full"))));
+}
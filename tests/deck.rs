@@ -0,0 +1,32 @@
+mod common;
+
+use std::fs;
+
+use wuwa_ploom_core::deck::{self, DeckModel};
+
+use common::TempDir;
+
+fn dmi(name: &str, vendor: &str, product: &str) -> TempDir {
+    let dir = TempDir::new(name);
+    fs::write(dir.path().join("board_vendor"), format!("{}\n", vendor)).unwrap();
+    fs::write(dir.path().join("product_name"), format!("{}\n", product)).unwrap();
+    dir
+}
+
+#[test]
+fn deck_models_come_from_dmi() {
+    assert_eq!(deck::detect_in(dmi("dmi-lcd", "Valve", "Jupiter").path()), Some(DeckModel::Lcd));
+    assert_eq!(deck::detect_in(dmi("dmi-oled", "Valve", "Galileo").path()), Some(DeckModel::Oled));
+    assert_eq!(deck::detect_in(dmi("dmi-pc", "ASUSTeK COMPUTER INC.", "ROG Ally RC71L").path()), None);
+    assert_eq!(deck::detect_in(&TempDir::new("dmi-missing").path().join("id")), None);
+}
+
+#[test]
+fn fps_presets_fit_the_screen() {
+    for model in [DeckModel::Lcd, DeckModel::Oled] {
+        assert_eq!(model.fps_presets()[0], model.refresh_hz());
+        assert!(model.fps_presets().iter().all(|fps| *fps <= model.refresh_hz()));
+        let preset = model.quality_preset();
+        assert_eq!(preset.settings["KeyCustomFrameRate"], model.fps_presets()[1]);
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::{Path, PathBuf};
+
+use wuwa_ploom_core::defender;
+
+const QUERY_OUTPUT: &str = "1\r\n--\r\nD:\\Games\r\n--\r\nC:\\Tools\\other.exe\r\n";
+
+#[test]
+fn settings_are_read_from_the_query_output() {
+    let cfa = defender::parse(QUERY_OUTPUT).unwrap();
+    assert!(cfa.blocks_writes());
+    assert_eq!(cfa.protected, [PathBuf::from("D:\\Games")]);
+    assert_eq!(cfa.allowed, [PathBuf::from("C:\\Tools\\other.exe")]);
+
+    let off = defender::parse("0\n--\n--\n").unwrap();
+    assert!(!off.blocks_writes() && off.protected.is_empty() && off.allowed.is_empty());
+    assert_eq!(defender::parse(""), None);
+}
+
+#[test]
+fn only_protected_folders_block_programs_not_allowed() {
+    let base = Path::new("Users").join("me");
+    let cfa = defender::Cfa { mode: 1, protected: vec![Path::new("Games").join("Protected")], allowed: vec!["allowed.exe".into()] };
+    let defaults = [base.join("Documents")];
+    let us = Path::new("wuwa-ploom.exe");
+
+    assert!(cfa.blocks(&base.join("documents").join("LocalStorage.db"), us, &defaults));
+    assert!(cfa.blocks(&Path::new("games").join("protected").join("LocalStorage.db"), us, &defaults));
+    assert!(!cfa.blocks(&Path::new("Games").join("Other").join("LocalStorage.db"), us, &defaults));
+    assert!(!cfa.blocks(&base.join("Documents").join("LocalStorage.db"), Path::new("Allowed.exe"), &defaults));
+    assert!(!defender::Cfa { mode: 2, ..cfa }.blocks(&base.join("Documents").join("LocalStorage.db"), us, &defaults));
+}
+
+#[test]
+fn the_allow_command_quotes_the_path() {
+    assert_eq!(
+        defender::allow_command(Path::new("C:\\O'Neil\\wuwa-ploom.exe")),
+        "Add-MpPreference -ControlledFolderAccessAllowedApplications 'C:\\O''Neil\\wuwa-ploom.exe'"
+    );
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use std::fs;
+
+use wuwa_ploom_core::detect::{self, db_path_in};
+use wuwa_ploom_core::platform::StubPlatform;
+use wuwa_ploom_core::registry::{Hive, Location, View};
+use wuwa_ploom_core::MyError;
+
+use common::TempDir;
+
+const UNINSTALL_KEY: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\KRInstall Wuthering Waves Overseas";
+
+fn registry_at(hive: Hive, view: View, values: &[(&str, &str)]) -> StubPlatform {
+    let location = Location::new(hive, view, UNINSTALL_KEY);
+    StubPlatform {
+        registry: values.iter().map(|(name, data)| (location.clone(), name.to_string(), data.to_string())).collect(),
+        ..Default::default()
+    }
+}
+
+/// Where the launcher normally registers: machine-wide, 32-bit view.
+fn registry(values: &[(&str, &str)]) -> StubPlatform {
+    registry_at(Hive::LocalMachine, View::Registry32, values)
+}
+
+#[test]
+fn install_path_comes_from_the_launcher_key() {
+    let registry = registry(&[("InstallPath", "D:\\Games\\Wuthering Waves"), ("DisplayVersion", "1.2.0")]);
+
+    let path = detect::install_db_path(&registry).unwrap();
+    assert!(path.starts_with("D:\\Games\\Wuthering Waves"));
+    assert!(path.ends_with("LocalStorage.db"));
+    assert_eq!(detect::game_version(&registry).as_deref(), Some("1.2.0"));
+}
+
+#[test]
+fn missing_launcher_key_is_a_detection_error() {
+    let registry = registry(&[]);
+
+    assert!(matches!(detect::install_db_path(&registry), Err(MyError::Detection(_))));
+    assert_eq!(detect::game_version(&registry), None);
+}
+
+#[test]
+fn per_user_installs_and_older_value_names_are_found() {
+    let registry = registry_at(Hive::CurrentUser, View::Registry64, &[("InstallLocation", "E:\\WuWa"), ("Version", "1.3.0")]);
+
+    let candidates = detect::install_candidates(&registry);
+    assert_eq!(candidates.len(), 1);
+    assert_eq!(candidates[0].location.hive, Hive::CurrentUser);
+    assert_eq!(candidates[0].value_name, "InstallLocation");
+    assert!(detect::install_db_path(&registry).unwrap().starts_with("E:\\WuWa"));
+    assert_eq!(detect::game_version(&registry).as_deref(), Some("1.3.0"));
+}
+
+#[test]
+fn the_32_bit_view_is_spelled_with_wow6432node() {
+    let location = Location::new(Hive::LocalMachine, View::Registry32, UNINSTALL_KEY);
+    assert_eq!(location.redirected_key(), UNINSTALL_KEY.replacen("SOFTWARE\\", "SOFTWARE\\WOW6432Node\\", 1));
+    assert_eq!(Location::new(Hive::CurrentUser, View::Registry32, UNINSTALL_KEY).redirected_key(), UNINSTALL_KEY);
+}
+
+#[test]
+fn scan_finds_installs_below_the_roots() {
+    let dir = TempDir::new("scan");
+    let install = dir.path().join("Games").join("Wuthering Waves");
+    let db = db_path_in(&install);
+    fs::create_dir_all(db.parent().unwrap()).unwrap();
+    fs::write(&db, b"").unwrap();
+    // A game folder without a database is not a hit.
+    fs::create_dir_all(dir.path().join("Other").join("Wuthering Waves Game")).unwrap();
+
+    let roots = vec![dir.path().join("Games"), dir.path().join("Other")];
+    let mut seen = Vec::new();
+    let found = detect::scan_roots(&roots, |done, total, _| {
+        seen.push((done, total));
+        true
+    });
+
+    assert_eq!(found, vec![db]);
+    assert_eq!(seen, vec![(0, 2), (1, 2)]);
+}
+
+#[test]
+fn scan_stops_when_progress_says_so() {
+    let dir = TempDir::new("scan-stop");
+    let db = db_path_in(&dir.path().join("Games"));
+    fs::create_dir_all(db.parent().unwrap()).unwrap();
+    fs::write(&db, b"").unwrap();
+
+    let found = detect::scan_roots(&[dir.path().join("Games")], |_, _, _| false);
+    assert!(found.is_empty());
+}
+
+#[test]
+fn running_client_leads_to_its_database() {
+    let dir = TempDir::new("exe");
+    let db = db_path_in(dir.path());
+    fs::create_dir_all(db.parent().unwrap()).unwrap();
+    let exe = dir.path().join("Wuthering Waves Game").join("Client").join("Binaries").join("Win64").join("Client-Win64-Shipping.exe");
+
+    assert_eq!(detect::db_path_for_exe(&exe), None);
+    fs::write(&db, b"").unwrap();
+    assert_eq!(detect::db_path_for_exe(&exe), Some(db));
+    assert_eq!(detect::db_path_for_exe(&dir.path().join("wine64-preloader")), None);
+}
@@ -0,0 +1,31 @@
+use std::io::Cursor;
+
+use serde_json::json;
+use wuwa_ploom_core::discord;
+
+#[test]
+fn messages_round_trip_with_a_little_endian_header() {
+    let payload = json!({ "v": 1, "client_id": "123" });
+    let frame = discord::encode(0, &payload);
+
+    assert_eq!(&frame[..4], &[0, 0, 0, 0]);
+    assert_eq!(u32::from_le_bytes([frame[4], frame[5], frame[6], frame[7]]) as usize, frame.len() - 8);
+    assert_eq!(discord::decode(&mut Cursor::new(frame)).unwrap(), (0, payload));
+
+    let mut oversized = discord::encode(1, &json!({}));
+    oversized[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(discord::decode(&mut Cursor::new(oversized)).is_err());
+}
+
+#[test]
+fn activities_show_the_status_or_clear_it() {
+    let shown = discord::activity(42, Some("FPS unlock active: 165"), 1_700_000_000, 3);
+    assert_eq!(shown["cmd"], "SET_ACTIVITY");
+    assert_eq!(shown["nonce"], "3");
+    assert_eq!(shown["args"]["pid"], 42);
+    assert_eq!(shown["args"]["activity"]["details"], "FPS unlock active: 165");
+    assert_eq!(shown["args"]["activity"]["timestamps"]["start"], 1_700_000_000);
+
+    let cleared = discord::activity(42, None, 1_700_000_000, 4);
+    assert!(cleared["args"]["activity"].is_null());
+}
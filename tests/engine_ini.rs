@@ -0,0 +1,55 @@
+mod common;
+
+use std::fs;
+
+use wuwa_ploom_core::engine_ini::{self, SYSTEM_SETTINGS, TWEAKS};
+use wuwa_ploom_core::ini;
+
+use common::TempDir;
+
+const ENGINE_INI: &str = "[Core.System]\r\nPaths=../../../Engine/Content\r\n\r\n[SystemSettings]\r\n; kept\r\nr.Streaming.PoolSize=1000\r\nr.Foo=1\r\n";
+
+#[test]
+fn set_replaces_in_place_and_keeps_comments_and_line_endings() {
+    let updated = ini::set(ENGINE_INI, SYSTEM_SETTINGS, "r.streaming.poolsize", "3072");
+    assert_eq!(updated, ENGINE_INI.replace("r.Streaming.PoolSize=1000", "r.streaming.poolsize=3072"));
+    assert_eq!(ini::get(&updated, "systemsettings", "R.Streaming.PoolSize").as_deref(), Some("3072"));
+
+    let added = ini::set("", "/Script/Engine.Engine", "bSmoothFrameRate", "False");
+    assert_eq!(added, "[/Script/Engine.Engine]\nbSmoothFrameRate=False\n");
+}
+
+#[test]
+fn removing_the_last_entry_drops_the_section() {
+    let without_pool = ini::remove(ENGINE_INI, SYSTEM_SETTINGS, "r.Streaming.PoolSize");
+    assert_eq!(ini::entries(&without_pool, SYSTEM_SETTINGS), [("r.Foo".to_string(), "1".to_string())]);
+
+    let text = "[Core.System]\nPaths=x\n\n[SystemSettings]\nr.Foo=1\n";
+    assert_eq!(ini::remove(text, SYSTEM_SETTINGS, "r.Foo"), "[Core.System]\nPaths=x\n");
+}
+
+#[test]
+fn engine_ini_sits_beside_the_database_and_edits_are_backed_up() {
+    let dir = TempDir::new("engine-ini");
+    let saved = dir.path().join("Client").join("Saved");
+    let db_path = saved.join("LocalStorage").join("LocalStorage.db");
+    let ini_dir = saved.join("Config").join("WindowsNoEditor");
+    fs::create_dir_all(&ini_dir).unwrap();
+    let path = engine_ini::path_for_db(&db_path).unwrap();
+    assert_eq!(path, ini_dir.join("Engine.ini"));
+
+    // A missing file is created without a backup.
+    let tweak = &TWEAKS[0];
+    assert_eq!(engine_ini::edit(&path, tweak.section, tweak.key, Some(tweak.value), Some(&dir.path().join("backups"))).unwrap(), None);
+    assert_eq!(engine_ini::current(&engine_ini::read(&path).unwrap(), tweak).as_deref(), Some(tweak.value));
+
+    let backup = engine_ini::edit(&path, tweak.section, tweak.key, None, Some(&dir.path().join("backups"))).unwrap().unwrap();
+    assert!(fs::read_to_string(&backup).unwrap().contains(tweak.key));
+    assert_eq!(engine_ini::read(&path).unwrap(), "");
+
+    // A second edit within the same second keeps the first backup.
+    engine_ini::edit(&path, tweak.section, tweak.key, Some(tweak.value), None).unwrap();
+    let again = engine_ini::edit(&path, tweak.section, tweak.key, None, Some(&dir.path().join("backups"))).unwrap().unwrap();
+    assert_ne!(again, backup);
+    assert!(backup.exists());
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use rusqlite::{ffi, Connection};
+use wuwa_ploom_core::db;
+use wuwa_ploom_core::MyError;
+
+use common::{fixture_db, quality_db, TempDir, V1_0_SETTING};
+
+#[test]
+fn missing_database_is_a_detection_error() {
+    let dir = TempDir::new("err-missing");
+    let db_path = dir.path().join("LocalStorage.db").display().to_string();
+
+    let err = db::read_quality_setting_file(&db_path).unwrap_err();
+    assert!(matches!(err, MyError::Detection(_)));
+    assert!(err.to_string().ends_with(err.hint()));
+}
+
+#[test]
+fn held_database_is_locked() {
+    let dir = TempDir::new("err-locked");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let holder = Connection::open(&db_path).unwrap();
+    holder.execute_batch("BEGIN EXCLUSIVE;").unwrap();
+
+    assert!(matches!(db::apply_patch(&db_path, &db::fps_patch(120)), Err(MyError::Locked(_))));
+}
+
+#[test]
+fn bad_blobs_are_told_apart() {
+    let (first, second) = (TempDir::new("err-malformed"), TempDir::new("err-norow"));
+    let malformed = quality_db(first.path(), r#"{"KeyCustomFrameRate":60,"#);
+    let missing_row = fixture_db(second.path(), &[("PlayerName", "\"Rover\"")], true);
+
+    assert!(matches!(db::read_quality_setting_file(&malformed), Err(MyError::Corruption(_))));
+    assert!(matches!(db::read_quality_setting_file(&missing_row), Err(MyError::Schema(_))));
+}
+
+#[test]
+fn text_file_is_not_a_database() {
+    let dir = TempDir::new("err-notdb");
+    let path = dir.path().join("LocalStorage.db");
+    std::fs::write(&path, "not a database, just some text that is long enough to have a header").unwrap();
+
+    let err = db::read_quality_setting_file(&path.display().to_string()).unwrap_err();
+    assert!(matches!(err, MyError::Corruption(_)), "{}", err);
+}
+
+#[test]
+fn a_full_disk_is_not_a_damaged_file() {
+    let failure = |code| MyError::from(rusqlite::Error::SqliteFailure(ffi::Error::new(code), None));
+
+    assert!(matches!(failure(ffi::SQLITE_FULL), MyError::Io(_)));
+    assert!(matches!(failure(ffi::SQLITE_IOERR), MyError::Io(_)));
+    assert!(matches!(failure(ffi::SQLITE_NOMEM), MyError::Io(_)));
+    assert!(matches!(failure(ffi::SQLITE_CORRUPT), MyError::Corruption(_)));
+}
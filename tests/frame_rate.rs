@@ -0,0 +1,23 @@
+mod common;
+
+use serde_json::{json, Value};
+use wuwa_ploom_core::frame_rate::{self, MenuOption};
+
+use common::V1_0_SETTING;
+
+#[test]
+fn the_frame_rate_key_tells_the_menu_option() {
+    let setting: Value = serde_json::from_str(V1_0_SETTING).unwrap();
+    assert_eq!(frame_rate::menu_option(&setting), MenuOption::Expected);
+    assert_eq!(frame_rate::menu_option(&json!({"KeyCustomFrameRate": 45})), MenuOption::Other(45));
+    assert_eq!(frame_rate::menu_option(&json!({"KeyCustomFrameRate": 120})), MenuOption::Patched(120));
+    assert_eq!(frame_rate::menu_option(&json!({"KeyPcVsync": 0})), MenuOption::Unset);
+}
+
+#[test]
+fn only_a_menu_option_other_than_60_warns() {
+    assert!(MenuOption::Expected.warning().is_none());
+    assert!(MenuOption::Patched(90).warning().is_none());
+    assert!(MenuOption::Other(30).warning().unwrap().contains("on 30, not 60"));
+    assert!(MenuOption::Unset.warning().is_some());
+}
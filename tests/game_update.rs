@@ -0,0 +1,41 @@
+mod common;
+
+use std::fs;
+
+use common::TempDir;
+use wuwa_ploom_core::game_update::{self, UpdateSign};
+use wuwa_ploom_core::platform::{ProcessInfo, StubPlatform};
+
+#[test]
+fn updaters_only_count_when_running_from_the_install() {
+    let dir = TempDir::new("game_update_process");
+    let install = dir.path().join("Wuthering Waves");
+    let db = install.join("Wuthering Waves Game").join("Client").join("Saved").join("LocalStorage").join("LocalStorage.db");
+    let mut launcher = ProcessInfo::new(100, "launcher.exe");
+    launcher.exe = Some(install.join("launcher.exe"));
+    let mut elsewhere = ProcessInfo::new(101, "GpuDriverUpdate.exe");
+    elsewhere.exe = Some(dir.path().join("Drivers").join("GpuDriverUpdate.exe"));
+    let mut platform = StubPlatform { processes: vec![launcher, elsewhere, ProcessInfo::new(102, "Setup-Installer.exe")], ..Default::default() };
+    assert_eq!(game_update::in_progress(&platform, &db), None);
+
+    let mut updater = ProcessInfo::new(103, "KRUpdater.exe");
+    updater.exe = Some(install.join("KRUpdater.exe"));
+    platform.processes.push(updater);
+    assert_eq!(game_update::in_progress(&platform, &db), Some(UpdateSign::Updater("KRUpdater.exe".into())));
+}
+
+#[test]
+fn half_downloaded_paks_mean_an_update() {
+    let dir = TempDir::new("game_update_files");
+    let game = dir.path().join("Wuthering Waves Game");
+    let paks = game.join("Client").join("Content").join("Paks");
+    fs::create_dir_all(&paks).unwrap();
+    fs::write(paks.join("pakchunk0-WindowsNoEditor.pak"), "").unwrap();
+    let db = game.join("Client").join("Saved").join("LocalStorage").join("LocalStorage.db");
+    assert_eq!(game_update::in_progress(&StubPlatform::default(), &db), None);
+
+    fs::write(paks.join("pakchunk1-WindowsNoEditor.pak.download"), "").unwrap();
+    assert_eq!(game_update::partial_files(&game), [paks.join("pakchunk1-WindowsNoEditor.pak.download")]);
+    assert!(game_update::in_progress(&StubPlatform::default(), &db).is_some());
+    assert!(!game_update::is_partial_file("LocalStorage.db"));
+}
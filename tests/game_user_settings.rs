@@ -0,0 +1,36 @@
+mod common;
+
+use std::fs;
+
+use serde_json::json;
+use wuwa_ploom_core::game_user_settings;
+
+use common::TempDir;
+
+const USER_SETTINGS: &str = "[/Script/Engine.GameUserSettings]\r\nbUseVSync=False\r\nResolutionSizeX=1920\r\nResolutionSizeY=1080\r\nFrameRateLimit=60.000000\r\n";
+
+#[test]
+fn values_compare_as_numbers() {
+    let setting = json!({"KeyCustomFrameRate": 60, "KeyPcResolutionWidth": 1920});
+    assert!(game_user_settings::mismatches(USER_SETTINGS, &setting).is_empty());
+
+    let setting = json!({"KeyCustomFrameRate": 120, "KeyPcResolutionWidth": 2560});
+    let keys: Vec<_> = game_user_settings::mismatches(USER_SETTINGS, &setting).iter().map(|mismatch| mismatch.pair.ini_key).collect();
+    assert_eq!(keys, ["FrameRateLimit", "ResolutionSizeX"]);
+}
+
+#[test]
+fn sync_writes_the_database_values_in_the_ini_format() {
+    let dir = TempDir::new("game-user-settings");
+    let path = dir.path().join("GameUserSettings.ini");
+    fs::write(&path, USER_SETTINGS).unwrap();
+    let setting = json!({"KeyCustomFrameRate": 120, "KeyPcResolutionWidth": 2560, "KeyPcResolutionHeight": 1080});
+
+    let (changed, backup) = game_user_settings::sync(&path, &setting, Some(&dir.path().join("backups"))).unwrap();
+    assert_eq!(changed, 2);
+    assert_eq!(fs::read_to_string(backup.unwrap()).unwrap(), USER_SETTINGS);
+    let expected = USER_SETTINGS.replace("FrameRateLimit=60.000000", "FrameRateLimit=120.000000").replace("ResolutionSizeX=1920", "ResolutionSizeX=2560");
+    assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+
+    assert_eq!(game_user_settings::sync(&path, &setting, None).unwrap(), (0, None));
+}
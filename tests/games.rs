@@ -0,0 +1,37 @@
+use std::path::Path;
+
+use wuwa_ploom_core::games::{self, GameAdapter, WutheringWaves};
+
+#[test]
+fn adapters_have_distinct_ids_and_wuthering_waves_is_the_default() {
+    let mut ids: Vec<&str> = games::ALL.iter().map(|game| game.id()).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), games::ALL.len());
+    assert_eq!(games::active().id(), "wuwa");
+    assert_eq!(games::by_id("WUWA").map(|game| game.name()), Some("Wuthering Waves"));
+    assert!(games::by_id("genshin").is_none());
+}
+
+#[test]
+fn each_adapter_uses_its_own_folders_and_keys() {
+    let db = WutheringWaves.db_path_in(Path::new("Games"));
+
+    assert!(db.starts_with(Path::new("Games").join("Wuthering Waves Game")));
+    assert!(db.ends_with("LocalStorage.db"));
+    assert!(WutheringWaves.registry_locations().iter().all(|location| location.key.contains("Wuthering Waves")));
+    assert_eq!(WutheringWaves.key_names(None).setting_row, "GameQualitySetting");
+    let exe = WutheringWaves.client_exe_for(&db).unwrap();
+    assert_eq!(exe, Path::new("Games/Wuthering Waves Game/Client/Binaries/Win64/Client-Win64-Shipping.exe"));
+    assert_eq!(WutheringWaves.client_exe_for(Path::new("LocalStorage.db")), None);
+}
+
+#[test]
+fn unknown_games_have_nothing_to_detect() {
+    let unknown = games::by_id("unreal").unwrap();
+
+    assert!(!unknown.has_quality_setting());
+    assert!(unknown.registry_locations().is_empty());
+    assert!(unknown.process_names().is_empty());
+    assert_eq!(unknown.client_exe_for(&WutheringWaves.db_path_in(Path::new("Games"))), None);
+}
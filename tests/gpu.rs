@@ -0,0 +1,30 @@
+use wuwa_ploom_core::gpu::{self, Gpu, Tier, VENDOR_NVIDIA};
+
+fn gpu(name: &str, vendor_id: u32, vram_gb: u64, driver: [u16; 4]) -> Gpu {
+    Gpu { name: name.into(), vendor_id, vram: vram_gb << 30, driver: Some(driver) }
+}
+
+#[test]
+fn adapters_are_described_with_memory_and_driver() {
+    let nvidia = gpu("NVIDIA GeForce RTX 3060", VENDOR_NVIDIA, 12, [31, 0, 15, 5186]);
+    assert_eq!(nvidia.describe(), "NVIDIA GeForce RTX 3060 (12 GB), driver 551.86");
+    assert_eq!(gpu("NVIDIA GeForce GTX 1060", VENDOR_NVIDIA, 6, [27, 21, 14, 6089]).driver_version().as_deref(), Some("460.89"));
+
+    let intel = gpu("Intel(R) UHD Graphics 620", 0x8086, 0, [31, 0, 101, 2115]);
+    assert_eq!(intel.describe(), "Intel(R) UHD Graphics 620, driver 31.0.101.2115");
+
+    let gpus = [intel, nvidia.clone()];
+    assert_eq!(gpu::primary(&gpus), Some(&nvidia));
+}
+
+#[test]
+fn models_map_to_tiers_and_laptops_rank_lower() {
+    assert_eq!(gpu::tier_of("NVIDIA GeForce RTX 4090"), Some(Tier::Enthusiast));
+    assert_eq!(gpu::tier_of("NVIDIA GeForce RTX 4070 Laptop GPU"), Some(Tier::Mid));
+    assert_eq!(gpu::tier_of("AMD Radeon RX 6600 XT"), Some(Tier::Mid));
+    assert_eq!(gpu::tier_of("AMD Radeon(TM) Graphics"), Some(Tier::Integrated));
+    assert_eq!(gpu::tier_of("AMD Radeon(TM) Vega 8 Graphics"), Some(Tier::Integrated));
+    assert_eq!(gpu::tier_of("Radeon RX Vega 64"), None);
+    assert_eq!(gpu::tier_of("Radeon Vega Frontier Edition"), None);
+    assert_eq!(gpu::tier_of("Microsoft Basic Render Driver"), None);
+}
@@ -0,0 +1,39 @@
+mod common;
+
+use std::time::{Duration, SystemTime};
+
+use serde_json::json;
+use wuwa_ploom_core::handoff::{self, Intent, QueuedWrite, MAX_AGE};
+
+use common::TempDir;
+
+fn intent() -> Intent {
+    Intent {
+        db_path: "D:\\Games\\LocalStorage.db".into(),
+        draft: json!({"KeyPcVsync": 0}).as_object().unwrap().clone(),
+        queued: Some(QueuedWrite { label: "FPS set to 120".into(), patch: json!({"KeyCustomFrameRate": 120}).as_object().unwrap().clone() }),
+        staged: Some(QueuedWrite { label: "Graphics applied".into(), patch: json!({"KeyQualityLevel": 3}).as_object().unwrap().clone() }),
+    }
+}
+
+#[test]
+fn an_intent_is_resumed_once() {
+    let dir = TempDir::new("handoff");
+    let path = dir.path().join("handoff.json");
+    handoff::save_to(&path, &intent()).unwrap();
+
+    assert_eq!(handoff::take_from(&path, SystemTime::now()), Some(intent()));
+    assert_eq!(handoff::take_from(&path, SystemTime::now()), None);
+}
+
+#[test]
+fn a_stale_intent_is_dropped() {
+    let dir = TempDir::new("handoff_stale");
+    let path = dir.path().join("handoff.json");
+    handoff::save_to(&path, &intent()).unwrap();
+
+    let later = SystemTime::now() + MAX_AGE + Duration::from_secs(60);
+    assert_eq!(handoff::take_from(&path, later), None);
+    assert!(!path.exists());
+    assert!(Intent::default().is_empty());
+}
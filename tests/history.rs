@@ -0,0 +1,57 @@
+mod common;
+
+use serde_json::json;
+use wuwa_ploom_core::db::Patch;
+use wuwa_ploom_core::history::{Author, History};
+use wuwa_ploom_core::status::StatusEvent;
+
+use common::TempDir;
+
+fn patch(value: serde_json::Value) -> Patch {
+    value.as_object().unwrap().clone()
+}
+
+fn written() -> StatusEvent {
+    StatusEvent::Written { label: "FPS set to 120".into(), keys: 1 }
+}
+
+#[test]
+fn changes_are_attributed_by_time() {
+    let mut history = History::default();
+    history.record_write(1000, &written(), &patch(json!({"KeyCustomFrameRate": 120})), &patch(json!({"KeyCustomFrameRate": 60})));
+
+    assert_eq!(history.writes()[0].action, "written");
+    assert_eq!(history.writes()[0].message, "FPS set to 120 (1 key(s) changed).");
+
+    let to = json!(120);
+    assert_eq!(history.attribute("KeyCustomFrameRate", &to, 1003, None), Author::Tool);
+    assert_eq!(history.attribute("KeyCustomFrameRate", &json!(60), 1003, None), Author::External);
+    assert_eq!(history.attribute("KeyCustomFrameRate", &to, 2000, Some(1995)), Author::Game);
+    assert_eq!(history.attribute("KeyCustomFrameRate", &to, 2000, Some(1500)), Author::External);
+}
+
+#[test]
+fn only_other_writers_changes_are_logged_again() {
+    let mut history = History::default();
+    history.record_write(1000, &written(), &patch(json!({"KeyCustomFrameRate": 120})), &patch(json!({"KeyCustomFrameRate": 60})));
+    let before = json!({"KeyCustomFrameRate": 60, "KeyPcVsync": 0});
+    let after = json!({"KeyCustomFrameRate": 120, "KeyPcVsync": 1});
+
+    let added = history.record_changes(&before, &after, 1002, Some(1001));
+    assert_eq!(added.len(), 1);
+    assert_eq!((added[0].key.as_str(), added[0].author), ("KeyPcVsync", Author::Game));
+    let logged: Vec<Author> = history.changes().iter().map(|change| change.author).collect();
+    assert_eq!(logged, [Author::Tool, Author::Game]);
+}
+
+#[test]
+fn history_survives_a_round_trip() {
+    let dir = TempDir::new("history_round_trip");
+    let path = dir.path().join("history.json");
+    let mut history = History::default();
+    history.record_changes(&json!({"KeyPcVsync": 0}), &json!({"KeyPcVsync": 1}), 5000, None);
+
+    history.save_to(&path).unwrap();
+    assert_eq!(History::load_from(&path), history);
+    assert_eq!(History::load_from(&dir.path().join("missing.json")), History::default());
+}
@@ -0,0 +1,40 @@
+use std::path::{Path, PathBuf};
+
+use wuwa_ploom_core::games::{self, GameAdapter, WutheringWaves};
+use wuwa_ploom_core::hooks::Hook;
+
+fn hook() -> Hook {
+    Hook {
+        cli: PathBuf::from("C:\\Tools\\wuwa-ploom-cli.exe"),
+        game_id: "wuwa".into(),
+        db_path: "C:\\Games\\O'Brien\\LocalStorage.db".into(),
+        fps: 120,
+    }
+}
+
+#[test]
+fn hooks_run_the_cli_with_quoted_arguments() {
+    let hook = hook();
+
+    assert_eq!(hook.args(), ["--game", "wuwa", "--db", "C:\\Games\\O'Brien\\LocalStorage.db", "--no-backup", "set-fps", "120"]);
+    assert!(hook.powershell().starts_with("& 'C:\\Tools\\wuwa-ploom-cli.exe' '--game' 'wuwa'"));
+    assert!(hook.powershell().contains("'C:\\Games\\O''Brien\\LocalStorage.db'"));
+    assert!(hook.shell_script().contains("'C:\\Games\\O'\\''Brien\\LocalStorage.db'"));
+
+    let folder = Path::new("Games").join("Wuthering Waves Game");
+    let launch = folder.join("Wuthering Waves.exe");
+    let script = hook.cmd_script(Some(&launch));
+    assert!(script.starts_with("@echo off\r\n"));
+    assert!(script.ends_with(&format!("start \"\" /d \"{}\" \"{}\"\r\n", folder.display(), launch.display())));
+    assert!(!hook.cmd_script(None).contains("start "));
+}
+
+#[test]
+fn the_game_folder_is_found_from_the_database_path() {
+    let folder = Path::new("Games").join("Wuthering Waves Game");
+    let db = WutheringWaves.db_path_in(Path::new("Games"));
+
+    assert_eq!(games::game_folder_of(&db), Some(folder.as_path()));
+    assert_eq!(games::game_folder_of(Path::new("Downloads/LocalStorage.db")), None);
+    assert!(WutheringWaves.launch_exe().is_some());
+}
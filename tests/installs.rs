@@ -0,0 +1,72 @@
+mod common;
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde_json::json;
+use wuwa_ploom_core::platform::StubPlatform;
+use wuwa_ploom_core::{db, installs};
+
+use common::{quality_db, TempDir, V1_0_SETTING};
+
+#[test]
+fn diverging_lists_keys_that_differ_anywhere() {
+    let settings = [
+        json!({"KeyCustomFrameRate": 120, "KeyPcVsync": 0}),
+        json!({"KeyCustomFrameRate": 120, "KeyPcVsync": 0}),
+        json!({"KeyCustomFrameRate": 60, "KeyPcVsync": 0, "KeyFsrEnable": 1}),
+    ];
+    assert_eq!(installs::diverging(&settings), ["KeyCustomFrameRate", "KeyFsrEnable"]);
+    assert!(installs::diverging(&settings[..2]).is_empty());
+}
+
+#[test]
+fn sync_patch_holds_only_missing_values() {
+    let from = json!({"KeyCustomFrameRate": 120, "KeyPcVsync": 0});
+    let to = json!({"KeyCustomFrameRate": 60, "KeyPcVsync": 0});
+    let patch = installs::sync_patch(&from, &to);
+    assert_eq!(patch, json!({"KeyCustomFrameRate": 120}).as_object().unwrap().clone());
+}
+
+#[test]
+fn sync_patch_leaves_the_display_alone() {
+    let from = json!({"KeyCustomFrameRate": 120, "KeyPcResolutionWidth": 2560, "KeyHdrEnable": 1});
+    let to = json!({"KeyCustomFrameRate": 60, "KeyPcResolutionWidth": 1920, "KeyHdrEnable": 0});
+    assert_eq!(installs::sync_patch(&from, &to), json!({"KeyCustomFrameRate": 120}).as_object().unwrap().clone());
+}
+
+#[test]
+fn known_installs_are_those_of_the_active_game() {
+    let dir = TempDir::new("installs_known");
+    let place = |game: &str| {
+        let folder = dir.path().join(game).join("Client").join("Saved").join("LocalStorage");
+        fs::create_dir_all(&folder).unwrap();
+        quality_db(&folder, V1_0_SETTING)
+    };
+    let ours = place("Wuthering Waves Game");
+    let other = place("Punishing Gray Raven");
+
+    assert_eq!(installs::known(&StubPlatform::new(), &[other, ours.clone()]), [PathBuf::from(ours)]);
+}
+
+#[test]
+fn sync_copies_settings_and_backs_up_changed_installs() {
+    let (first, second, third, backups) = (TempDir::new("installs_a"), TempDir::new("installs_b"), TempDir::new("installs_c"), TempDir::new("installs_backups"));
+    let source = quality_db(first.path(), r#"{"KeyCustomFrameRate":120,"KeyPcVsync":0,"KeyQualityLevel":2,"KeyNewShadowQuality":2}"#);
+    let stale = quality_db(second.path(), V1_0_SETTING);
+    let same = quality_db(third.path(), r#"{"KeyCustomFrameRate":120,"KeyPcVsync":0,"KeyQualityLevel":2,"KeyNewShadowQuality":2}"#);
+
+    let missing = first.path().join("missing.db");
+
+    let synced = installs::sync(source.as_ref(), &[source.clone().into(), missing.clone(), stale.clone().into(), same.into()], Some(backups.path()), &StubPlatform::new()).unwrap();
+
+    // The missing install fails on its own; the stale one is still written.
+    assert_eq!(synced.len(), 2);
+    assert!(synced[0].target == missing && synced[0].result.is_err());
+    assert_eq!(synced[1].target, PathBuf::from(&stale));
+    assert!(matches!(synced[1].result, Ok(Some(_))));
+    let setting = db::read_quality_setting_file(&stale).unwrap();
+    assert_eq!(setting["KeyCustomFrameRate"], 120);
+    assert_eq!(setting["KeyPcVsync"], 0);
+    assert_eq!(fs::read_dir(backups.path()).unwrap().count(), 1);
+}
@@ -0,0 +1,38 @@
+use serde_json::{json, Value};
+use wuwa_ploom_core::json_diff::{self, Counts, Diff};
+
+#[test]
+fn nested_values_diff_down_to_the_changed_leaf() {
+    let before = json!({"KeyCustomFrameRate": 60, "KeyBindings": {"Jump": "Space", "Dash": "Shift"}, "Removed": 1, "List": [1, 2]});
+    let after = json!({"KeyCustomFrameRate": 120, "KeyBindings": {"Jump": "Space", "Dash": "Ctrl"}, "List": [1, 2, 3], "New": true});
+
+    let Diff::Nested(members) = json_diff::diff(&before, &after) else {
+        panic!("objects diff member by member");
+    };
+    let keys: Vec<&str> = members.iter().map(|(key, _)| key.as_str()).collect();
+    // Keys of the old value first, then the ones only the new value has.
+    assert_eq!(keys, ["KeyBindings", "KeyCustomFrameRate", "List", "Removed", "New"]);
+    assert_eq!(
+        members[0].1,
+        Diff::Nested(vec![("Dash".into(), Diff::Changed { before: json!("Shift"), after: json!("Ctrl") }), ("Jump".into(), Diff::Same(json!("Space")))])
+    );
+    assert_eq!(members[1].1, Diff::Changed { before: json!(60), after: json!(120) });
+    assert_eq!(members[2].1, Diff::Nested(vec![("0".into(), Diff::Same(json!(1))), ("1".into(), Diff::Same(json!(2))), ("2".into(), Diff::Added(json!(3)))]));
+    assert_eq!(members[3].1, Diff::Removed(json!(1)));
+    assert_eq!(json_diff::count(&members), Counts { added: 2, removed: 1, changed: 2 });
+    assert_eq!(json_diff::count(&members).summary(), "2 changed, 2 added, 1 removed");
+}
+
+#[test]
+fn patch_diff_compares_each_patched_field() {
+    let setting = json!({"KeyCustomFrameRate": 60, "KeyPcVsync": 0});
+    let patch = json!({"KeyCustomFrameRate": 120, "KeyPcVsync": 0, "KeyFsrEnable": 1}).as_object().unwrap().clone();
+
+    let diffs = json_diff::patch_diff(&setting, &patch);
+
+    assert_eq!(diffs[0], ("KeyCustomFrameRate".into(), Diff::Changed { before: json!(60), after: json!(120) }));
+    assert_eq!(diffs[1], ("KeyFsrEnable".into(), Diff::Added(json!(1))));
+    assert_eq!(diffs[2], ("KeyPcVsync".into(), Diff::Same(json!(0))));
+    assert!(json_diff::patch_diff(&Value::Null, &patch).iter().all(|(_, diff)| matches!(diff, Diff::Added(_))));
+    assert!(json_diff::diff(&setting, &setting).counts().is_empty());
+}
@@ -0,0 +1,45 @@
+use serde_json::json;
+use wuwa_ploom_core::db;
+use wuwa_ploom_core::keymap::{self, KeyMap, KeyMapEntry, KeyNames};
+
+fn names(setting_row: &str, frame_rate: &str) -> KeyNames {
+    KeyNames { setting_row: setting_row.into(), frame_rate: frame_rate.into(), ..KeyNames::default() }
+}
+
+#[test]
+fn names_follow_the_client_version() {
+    let mut map = KeyMap::builtin();
+    map.merge(vec![KeyMapEntry { since: "2.4".into(), names: names("GameQualitySettingV2", "KeyFrameRateLimit") }]);
+
+    let original = names("GameQualitySetting", "KeyCustomFrameRate");
+    assert_eq!(map.names_for(Some("1.2.0")), original);
+    assert_eq!(map.names_for(Some("0.9")), original);
+    assert_eq!(map.names_for(Some("2.4.1")).frame_rate, "KeyFrameRateLimit");
+    assert_eq!(map.names_for(None).setting_row, "GameQualitySettingV2");
+}
+
+#[test]
+fn overrides_replace_entries_of_the_same_version() {
+    let mut map = KeyMap::from_json(r#"[{"since": "1.0", "setting_row": "A", "frame_rate": "B"}]"#).unwrap();
+    map.merge(vec![KeyMapEntry { since: "1.0".into(), names: names("C", "D") }]);
+
+    assert_eq!(map.names_for(Some("1.0")), names("C", "D"));
+    assert!(KeyMap::from_json(r#"[{"since": "1.0"}]"#).is_err());
+
+    // Files from before the VSync and preset names were mapped keep the usual ones.
+    let map = KeyMap::from_json(r#"[{"since": "1.0", "setting_row": "A", "frame_rate": "B", "vsync": "KeyVsyncV2"}]"#).unwrap();
+    assert_eq!(map.names_for(None).vsync, "KeyVsyncV2");
+    assert_eq!(map.names_for(None).quality_level, "KeyQualityLevel");
+}
+
+#[test]
+fn fps_helpers_use_the_active_names() {
+    keymap::set_active(names("GameQualitySetting", "KeyFrameRateLimit"));
+    let setting = json!({ "KeyFrameRateLimit": 90, "KeyCustomFrameRate": 60 });
+    let fps = db::fps_from_setting(&setting).unwrap();
+    let patch = db::fps_patch(120);
+    keymap::set_active(names("GameQualitySetting", "KeyCustomFrameRate"));
+
+    assert_eq!(fps, 90);
+    assert_eq!(patch.keys().collect::<Vec<_>>(), vec!["KeyFrameRateLimit"]);
+}
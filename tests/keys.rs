@@ -0,0 +1,36 @@
+mod common;
+
+use wuwa_ploom_core::db;
+
+use common::{fixture_db, TempDir};
+
+#[test]
+fn list_keys_filters_literally_and_counts_past_the_limit() {
+    let dir = TempDir::new("list");
+    let rows = [("Input_A", "1"), ("Input_B", "22"), ("Input%C", "3"), ("PlayerName", "\"Rover\"")];
+    let db_path = fixture_db(dir.path(), &rows, true);
+
+    let listing = db::list_keys(&db_path, "input", 1).unwrap();
+    assert_eq!(listing.total, 3);
+    assert_eq!(listing.entries.len(), 1);
+    assert_eq!(listing.entries[0].key, "Input%C");
+
+    let literal = db::list_keys(&db_path, "%", 10).unwrap();
+    assert_eq!(literal.total, 1);
+    let sizes: Vec<usize> = db::list_keys(&db_path, "_B", 10).unwrap().entries.iter().map(|entry| entry.size).collect();
+    assert_eq!(sizes, [2]);
+}
+
+#[test]
+fn value_prefix_stops_at_the_limit_and_reports_the_full_size() {
+    let dir = TempDir::new("prefix");
+    let value = format!("[{}]", "0,".repeat(5000));
+    let db_path = fixture_db(dir.path(), &[("Cache", &value)], true);
+
+    let (prefix, size) = db::read_value_prefix(&db_path, "Cache", 100).unwrap();
+    assert_eq!(prefix, &value[..100]);
+    assert_eq!(size, value.len());
+
+    let (whole, _) = db::read_value_prefix(&db_path, "Cache", size).unwrap();
+    assert_eq!(whole, value);
+}
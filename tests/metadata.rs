@@ -0,0 +1,22 @@
+use serde_json::{json, Map};
+use wuwa_ploom_core::metadata::{Language, Metadata, Problem};
+
+#[test]
+fn share_code_values_are_checked_against_the_key_table() {
+    let metadata = Metadata::load(Language::English);
+    let mut patch = Map::new();
+    patch.insert("KeyCustomFrameRate".into(), json!(500));
+    patch.insert("KeyQualityLevel".into(), json!(2));
+    patch.insert("KeyHdrEnable".into(), json!(1));
+    patch.insert("KeyNotDescribed".into(), json!("anything"));
+
+    let problems = metadata.check_patch(&patch, Some("1.1.0"));
+    let invalid: Vec<&String> = problems.iter().filter_map(|problem| match problem {
+        Problem::Invalid(text) => Some(text),
+        Problem::Unavailable(_) => None,
+    }).collect();
+    assert_eq!(invalid.len(), 1);
+    assert!(invalid[0].contains("KeyCustomFrameRate"));
+    assert!(problems.iter().any(|problem| matches!(problem, Problem::Unavailable(text) if text.contains("KeyHdrEnable"))));
+    assert!(metadata.get("KeyQualityLevel").unwrap().check(&json!(2), Some("1.1.0")).is_none());
+}
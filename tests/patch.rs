@@ -0,0 +1,119 @@
+mod common;
+
+use serde_json::{json, Value};
+use wuwa_ploom_core::db::{self, Patch};
+
+use common::{fixture_db, quality_db, raw_values, TempDir, V1_0_SETTING, V1_2_SETTING};
+
+fn patch(value: Value) -> Patch {
+    value.as_object().unwrap().clone()
+}
+
+#[test]
+fn fps_patch_changes_only_the_frame_rate() {
+    let dir = TempDir::new("fps");
+    let db_path = quality_db(dir.path(), V1_2_SETTING);
+
+    let inverse = db::apply_patch(&db_path, &db::fps_patch(120)).unwrap();
+
+    assert_eq!(inverse, Some(patch(json!({"KeyCustomFrameRate": 60}))));
+    let setting = db::read_quality_setting_file(&db_path).unwrap();
+    assert_eq!(db::fps_from_setting(&setting).unwrap(), 120);
+    let mut expected: Value = serde_json::from_str(V1_2_SETTING).unwrap();
+    expected["KeyCustomFrameRate"] = json!(120);
+    assert_eq!(setting, expected);
+    assert_eq!(raw_values(&db_path, "PlayerName"), vec!["\"Rover\""]);
+}
+
+#[test]
+fn unchanged_patch_is_a_no_op() {
+    let dir = TempDir::new("noop");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let before = raw_values(&db_path, "GameQualitySetting");
+
+    assert_eq!(db::apply_patch(&db_path, &db::fps_patch(60)).unwrap(), None);
+    assert_eq!(raw_values(&db_path, "GameQualitySetting"), before);
+}
+
+#[test]
+fn inverse_patch_restores_the_original() {
+    let dir = TempDir::new("inverse");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let original = db::read_quality_setting_file(&db_path).unwrap();
+
+    let edit = patch(json!({"KeyQualityLevel": 4, "KeyFsrEnable": 1, "KeyPcVsync": null}));
+    let inverse = db::apply_patch(&db_path, &edit).unwrap().unwrap();
+    let edited = db::read_quality_setting_file(&db_path).unwrap();
+    assert_eq!(edited["KeyFsrEnable"], json!(1));
+    assert!(edited.get("KeyPcVsync").is_none());
+
+    // A key the patch added comes back as a removal.
+    assert_eq!(inverse["KeyFsrEnable"], Value::Null);
+    db::apply_patch(&db_path, &inverse).unwrap();
+    assert_eq!(db::read_quality_setting_file(&db_path).unwrap(), original);
+}
+
+#[test]
+fn missing_setting_row_is_an_error() {
+    let dir = TempDir::new("missing");
+    let db_path = fixture_db(dir.path(), &[("PlayerName", "\"Rover\"")], true);
+
+    assert!(db::read_quality_setting_file(&db_path).is_err());
+    assert!(db::apply_patch(&db_path, &db::fps_patch(120)).is_err());
+    assert!(raw_values(&db_path, "GameQualitySetting").is_empty());
+}
+
+#[test]
+fn missing_frame_rate_key_is_reported_but_patchable() {
+    let dir = TempDir::new("nokey");
+    let db_path = quality_db(dir.path(), r#"{"KeyQualityLevel":1}"#);
+
+    let setting = db::read_quality_setting_file(&db_path).unwrap();
+    assert!(db::fps_from_setting(&setting).is_err());
+    let inverse = db::apply_patch(&db_path, &db::fps_patch(120)).unwrap().unwrap();
+    assert_eq!(inverse["KeyCustomFrameRate"], Value::Null);
+}
+
+#[test]
+fn malformed_json_is_left_untouched() {
+    let dir = TempDir::new("malformed");
+    let db_path = quality_db(dir.path(), r#"{"KeyCustomFrameRate":60,"#);
+
+    assert!(db::read_quality_setting_file(&db_path).is_err());
+    assert!(db::apply_patch(&db_path, &db::fps_patch(120)).is_err());
+    assert_eq!(raw_values(&db_path, "GameQualitySetting"), vec![r#"{"KeyCustomFrameRate":60,"#]);
+}
+
+#[test]
+fn non_object_setting_is_rejected() {
+    let dir = TempDir::new("array");
+    let db_path = quality_db(dir.path(), "[60]");
+
+    assert!(db::apply_patch(&db_path, &db::fps_patch(120)).is_err());
+    assert_eq!(raw_values(&db_path, "GameQualitySetting"), vec!["[60]"]);
+}
+
+#[test]
+fn duplicate_rows_are_all_rewritten_from_the_first() {
+    let dir = TempDir::new("duplicates");
+    let rows = [("GameQualitySetting", V1_0_SETTING), ("GameQualitySetting", V1_2_SETTING)];
+    let db_path = fixture_db(dir.path(), &rows, false);
+
+    db::apply_patch(&db_path, &db::fps_patch(120)).unwrap();
+
+    let values = raw_values(&db_path, "GameQualitySetting");
+    assert_eq!(values.len(), 2);
+    assert_eq!(values[0], values[1]);
+    let stored: Value = serde_json::from_str(&values[0]).unwrap();
+    assert_eq!(stored["KeyCustomFrameRate"], json!(120));
+    assert_eq!(stored["KeyQualityLevel"], json!(2));
+}
+
+#[test]
+fn missing_file_is_reported_without_creating_it() {
+    let dir = TempDir::new("nofile");
+    let db_path = dir.path().join("LocalStorage.db").display().to_string();
+
+    assert!(db::apply_patch(&db_path, &db::fps_patch(120)).is_err());
+    assert!(!dir.path().join("LocalStorage.db").exists());
+}
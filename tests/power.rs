@@ -0,0 +1,38 @@
+use wuwa_ploom_core::power::{self, HIGH_PERFORMANCE, ULTIMATE_PERFORMANCE};
+
+const ENGLISH: &str = "\
+Existing Power Schemes (* Active)
+-----------------------------------
+Power Scheme GUID: 381b4222-f694-41f0-9685-ff5bb260df2e  (Balanced) *
+Power Scheme GUID: 8c5e7fda-e8bf-4a96-9a85-a6e23a8c635c  (High performance)
+Power Scheme GUID: a1841308-3541-4fab-bc81-f71556f20b4a  (Power saver)
+";
+
+#[test]
+fn plans_are_read_whatever_the_language() {
+    let schemes = power::parse_list(ENGLISH);
+    assert_eq!(schemes.len(), 3);
+    assert_eq!(schemes[0].name, "Balanced");
+    assert!(schemes[0].active && !schemes[0].is_fast());
+    assert!(!schemes[1].active && schemes[1].is_fast());
+
+    let german = "GUID des Energieschemas: 8C5E7FDA-E8BF-4A96-9A85-A6E23A8C635C  (Höchstleistung) *\n";
+    let schemes = power::parse_list(german);
+    assert_eq!(schemes[0].guid, HIGH_PERFORMANCE);
+    assert_eq!(schemes[0].name, "Höchstleistung");
+    assert!(schemes[0].active && schemes[0].is_fast());
+}
+
+#[test]
+fn ultimate_performance_is_preferred() {
+    let mut text = ENGLISH.to_string();
+    assert_eq!(power::fastest(&power::parse_list(&text)).map(|scheme| scheme.guid.as_str()), Some(HIGH_PERFORMANCE));
+
+    // Plans named like a fast one are not trusted: names are localized and editable.
+    text.push_str("Power Scheme GUID: 0b5c4a9e-3f4e-4c4d-8a5e-0123456789ab  (Ultimate Performance)\n");
+    assert_eq!(power::fastest(&power::parse_list(&text)).map(|scheme| scheme.guid.as_str()), Some(HIGH_PERFORMANCE));
+
+    text.push_str("Power Scheme GUID: e9a42b02-d5df-448d-aa00-03f14749eb61  (Höchstleistung+)\n");
+    assert_eq!(power::fastest(&power::parse_list(&text)).map(|scheme| scheme.guid.as_str()), Some(ULTIMATE_PERFORMANCE));
+    assert_eq!(power::fastest(&power::parse_list("Power Scheme GUID: 381b4222-f694-41f0-9685-ff5bb260df2e  (Balanced) *")), None);
+}
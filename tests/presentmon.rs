@@ -0,0 +1,49 @@
+mod common;
+
+use std::fs;
+
+use wuwa_ploom_core::presentmon::{self, Verdict};
+
+use common::TempDir;
+
+#[test]
+fn frame_times_are_read_from_the_csv() {
+    let mut csv = String::from("Application,ProcessID,MsBetweenPresents,MsInPresentAPI\n");
+    for _ in 0..99 {
+        csv.push_str("Client-Win64-Shipping.exe,4242,8.333,0.1\n");
+    }
+    csv.push_str("Client-Win64-Shipping.exe,4242,25.0,0.1\n");
+
+    let sample = presentmon::parse_csv(&csv).unwrap();
+    assert_eq!(sample.frames, 100);
+    assert!((sample.average_fps - 117.65).abs() < 0.01, "{}", sample.average_fps);
+    assert!((sample.low_fps - 40.0).abs() < 0.01, "{}", sample.low_fps);
+
+    assert!(presentmon::parse_csv("Application,ProcessID\nClient.exe,1\n").is_none());
+    assert!(presentmon::parse_csv("Application,FrameTime\n").is_none());
+}
+
+#[test]
+fn verdicts_tell_a_missed_unlock_from_a_slow_pc() {
+    let sample = |average_fps| presentmon::FrameSample { frames: 600, average_fps, low_fps: average_fps };
+
+    assert_eq!(sample(119.0).verdict(120), Verdict::AtLimit);
+    assert_eq!(sample(95.0).verdict(120), Verdict::Unlocked);
+    assert_eq!(sample(60.0).verdict(120), Verdict::Capped);
+    assert_eq!(sample(144.0).verdict(120), Verdict::AboveLimit);
+    assert_eq!(sample(45.0).verdict(60), Verdict::Unlocked);
+}
+
+#[test]
+fn the_newest_presentmon_in_a_folder_is_found() {
+    let dir = TempDir::new("presentmon");
+    assert_eq!(presentmon::find_in(dir.path()), None);
+
+    fs::write(dir.path().join("PresentMon-2.2.0-x64.exe"), "").unwrap();
+    fs::write(dir.path().join("PresentMon-2.3.0-x64.exe"), "").unwrap();
+    fs::write(dir.path().join("readme.txt"), "").unwrap();
+    assert_eq!(presentmon::find_in(dir.path()), Some(dir.path().join("PresentMon-2.3.0-x64.exe")));
+
+    fs::write(dir.path().join("PresentMon-2.10.0-x64.exe"), "").unwrap();
+    assert_eq!(presentmon::find_in(dir.path()), Some(dir.path().join("PresentMon-2.10.0-x64.exe")));
+}
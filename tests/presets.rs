@@ -0,0 +1,46 @@
+mod common;
+
+use serde_json::json;
+use wuwa_ploom_core::presets;
+
+use common::TempDir;
+
+#[test]
+fn resolution_and_display_keys_are_machine_specific() {
+    for key in ["KeyPcResolutionWidth", "KeyPcResolutionHeight", "KeyPcDisplayIndex", "KeyHdrMaxBrightness"] {
+        assert!(presets::is_machine_specific(key), "{}", key);
+    }
+    for key in ["KeyCustomFrameRate", "KeyNewShadowQuality", "KeyRenderScale", "KeyBrightness", "KeyUiDisplayMode"] {
+        assert!(!presets::is_machine_specific(key), "{}", key);
+    }
+}
+
+#[test]
+fn transfer_files_carry_every_setting() {
+    let dir = TempDir::new("preset-transfer");
+    let path = dir.path().join("settings.json");
+    let setting = json!({ "KeyCustomFrameRate": 120, "KeyPcResolutionWidth": 2560, "KeyNewShadowQuality": 3 });
+
+    presets::export_transfer(&path, &setting).unwrap();
+
+    let preset = presets::read_preset(&path).unwrap();
+    assert_eq!(preset.version, presets::PRESET_VERSION);
+    assert_eq!(serde_json::Value::Object(preset.settings), setting);
+}
+
+#[test]
+fn share_codes_survive_a_chat_message() {
+    let setting = json!({ "KeyCustomFrameRate": 120, "KeyNewShadowQuality": 3, "KeyRenderScale": 0.9 });
+    let preset = presets::from_setting("Shared settings", &setting).unwrap();
+    let code = presets::to_share_code(&preset).unwrap();
+    assert!(code.starts_with("WWP1."));
+
+    let message = format!("my settings for 120 fps: {}\nenjoy", code);
+    let pasted = presets::from_share_code(&message).unwrap();
+    assert_eq!(pasted.name, "Shared settings");
+    assert_eq!(serde_json::Value::Object(pasted.settings), setting);
+
+    let json = format!("```json\n{}\n```", serde_json::to_string(&preset).unwrap());
+    assert_eq!(presets::from_share_code(&json).unwrap().settings.len(), 3);
+    assert!(presets::from_share_code("WWP1.not-a-code").is_err());
+}
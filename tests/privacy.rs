@@ -0,0 +1,20 @@
+use wuwa_ploom_core::privacy::{self, Identifier};
+
+#[test]
+fn identifier_rows_are_recognised_by_name() {
+    assert_eq!(privacy::classify("AccountId"), Some(Identifier::Account));
+    assert_eq!(privacy::classify("Kuro.Sdk.LastLoginUID"), Some(Identifier::Account));
+    assert_eq!(privacy::classify("player_id"), Some(Identifier::Account));
+    assert_eq!(privacy::classify("DeviceId"), Some(Identifier::Device));
+    assert_eq!(privacy::classify("LocalMachineGuid"), Some(Identifier::Device));
+    // Words that merely contain "uid" or "id" are not identifiers.
+    assert_eq!(privacy::classify("BuildVersion"), None);
+    assert_eq!(privacy::classify("GameQualitySetting"), None);
+    assert_eq!(privacy::classify("GuideShown"), None);
+}
+
+#[test]
+fn masking_keeps_only_the_ends() {
+    assert_eq!(privacy::mask("1234567890"), "12••••••90");
+    assert_eq!(privacy::mask("short"), "•••••");
+}
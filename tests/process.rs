@@ -0,0 +1,88 @@
+mod common;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use wuwa_ploom_core::platform::{Priority, ProcessInfo, StubPlatform};
+use wuwa_ploom_core::process::{self, GameEvent, GameWatcher};
+
+use common::{quality_db, TempDir, V1_0_SETTING};
+
+fn running(names: &[&str]) -> StubPlatform {
+    let processes = names.iter().enumerate().map(|(pid, name)| ProcessInfo::new(pid as u32 + 100, name)).collect();
+    StubPlatform { processes, ..Default::default() }
+}
+
+#[test]
+fn game_running_matches_process_names_case_insensitively() {
+    assert!(!process::is_game_running(&running(&["explorer.exe"])));
+    assert!(process::is_game_running(&running(&["explorer.exe", "client-win64-shipping.exe"])));
+    // Linux cuts Wine process names to 15 bytes.
+    assert!(process::is_game_running(&running(&["Client-Win64-Sh"])));
+    assert!(!process::is_game_running(&running(&["Client-Win64"])));
+}
+
+#[test]
+fn watcher_reports_starts_and_stops_once() {
+    let mut watcher = GameWatcher::new();
+    let idle = running(&["explorer.exe"]);
+    let mut playing = running(&["explorer.exe", "Client-Win64-Shipping.exe"]);
+    playing.processes[1].exe = Some("D:\\Games\\Client-Win64-Shipping.exe".into());
+
+    assert_eq!(watcher.poll(&idle), None);
+    assert_eq!(watcher.poll(&playing), Some(GameEvent::Started(playing.processes[1].clone())));
+    assert_eq!(watcher.poll(&playing), None);
+    assert_eq!(watcher.running().and_then(|process| process.exe.as_deref()), playing.processes[1].exe.as_deref());
+    assert_eq!(watcher.poll(&idle), Some(GameEvent::Stopped));
+    assert!(watcher.running().is_none());
+}
+
+/// A LocalStorage.db in a game folder under `install`; returns it and the game folder.
+fn install(root: &Path, install: &str) -> (String, PathBuf) {
+    let game = root.join(install).join("Wuthering Waves Game");
+    let folder = game.join("Client").join("Saved").join("LocalStorage");
+    fs::create_dir_all(&folder).unwrap();
+    (quality_db(&folder, V1_0_SETTING), game)
+}
+
+#[test]
+fn terminate_only_touches_the_loaded_install() {
+    let dir = TempDir::new("terminate");
+    let (db_path, game) = install(dir.path(), "A");
+    let (_, other) = install(dir.path(), "B");
+    let mut platform = running(&["explorer.exe", "Wuthering Waves.exe", "Client-Win64-Shipping.exe", "Client-Win64-Shipping.exe", "Client-Win64-Shipping.exe"]);
+    platform.processes[0].exe = Some(game.join("explorer.exe"));
+    platform.processes[1].exe = Some(game.join("Wuthering Waves.exe"));
+    platform.processes[2].exe = Some(game.join("Client").join("Binaries").join("Win64").join("Client-Win64-Shipping.exe"));
+    platform.processes[3].exe = Some(other.join("Client").join("Binaries").join("Win64").join("Client-Win64-Shipping.exe"));
+
+    assert_eq!(process::terminate_game(&platform, Path::new(&db_path)), 2);
+    assert_eq!(*platform.terminated.lock().unwrap(), vec![101, 102]);
+}
+
+#[test]
+fn core_lists_become_affinity_masks() {
+    assert_eq!(process::core_mask("0-3"), Some(0b1111));
+    assert_eq!(process::core_mask(" 0-1, 4 ,6-6"), Some(0b101_0011));
+    assert_eq!(process::core_mask(""), None);
+    assert_eq!(process::core_mask("3-1"), None);
+    assert_eq!(process::core_mask("0-64"), None);
+    assert_eq!(process::core_mask("a,b"), None);
+}
+
+#[test]
+fn each_game_process_is_tuned_once_as_it_appears() {
+    let mut watcher = GameWatcher::new();
+    let launcher = running(&["Wuthering Waves.exe"]);
+    let client = running(&["Wuthering Waves.exe", "Client-Win64-Shipping.exe"]);
+    let mut tuned = Vec::new();
+
+    watcher.poll(&launcher);
+    assert!(process::tune_game(&launcher, watcher.pids(), &mut tuned, Priority::High, Some(0xff)).is_empty());
+    watcher.poll(&client);
+    process::tune_game(&client, watcher.pids(), &mut tuned, Priority::High, Some(0xff));
+
+    assert_eq!(tuned, vec![100, 101]);
+    assert_eq!(*client.tuned.lock().unwrap(), vec![(101, Priority::High, Some(0xff))]);
+    assert_eq!(process::tune_game(&running(&[]), &[7], &mut tuned, Priority::High, None), vec![7]);
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use serde_json::json;
+use wuwa_ploom_core::profiles;
+
+use common::TempDir;
+
+fn write_profile(dir: &Path, file: &str, name: &str, fps: i64) {
+    let preset = json!({ "version": 1, "name": name, "author": "", "description": "", "settings": { "KeyCustomFrameRate": fps } });
+    fs::write(dir.join(file), preset.to_string()).unwrap();
+}
+
+#[test]
+fn copies_left_by_sync_tools_are_found_by_profile_name() {
+    let dir = TempDir::new("profile-conflicts");
+    write_profile(dir.path(), "High.json", "High", 120);
+    write_profile(dir.path(), "High (LAPTOP's conflicted copy 2024-05-01).json", "High", 90);
+    write_profile(dir.path(), "High-DESKTOP.json", "High", 60);
+    write_profile(dir.path(), "Low.json", "Low", 30);
+    write_profile(dir.path(), "Low-Power.json", "Low-Power", 30);
+
+    let conflicts = profiles::conflicts_in(dir.path());
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].name, "High");
+    assert_eq!(conflicts[0].versions.len(), 3);
+    assert!(conflicts[0].versions.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+}
+
+#[test]
+fn a_half_synced_profile_is_skipped_and_left_in_place() {
+    let dir = TempDir::new("profile-half-synced");
+    write_profile(dir.path(), "High.json", "High", 120);
+    fs::write(dir.path().join("High-DESKTOP.json"), "{\"version\": 1, \"na").unwrap();
+
+    assert!(profiles::conflicts_in(dir.path()).is_empty());
+    assert!(dir.path().join("High-DESKTOP.json").exists());
+}
+
+#[test]
+fn resolving_keeps_the_chosen_version_under_the_profile_name() {
+    let dir = TempDir::new("profile-resolve");
+    write_profile(dir.path(), "High.json", "High", 120);
+    write_profile(dir.path(), "High.sync-conflict-20240501-101500-ABCDEFG.json", "High", 90);
+    let conflict = profiles::conflicts_in(dir.path()).remove(0);
+
+    profiles::resolve(&conflict, &dir.path().join("High.sync-conflict-20240501-101500-ABCDEFG.json")).unwrap();
+
+    let files: Vec<_> = fs::read_dir(dir.path()).unwrap().flatten().map(|entry| entry.file_name()).collect();
+    assert_eq!(files, ["High.json"]);
+    assert!(fs::read_to_string(dir.path().join("High.json")).unwrap().contains("90"));
+    assert!(profiles::conflicts_in(dir.path()).is_empty());
+}
+
+#[test]
+fn moving_to_a_sync_folder_copies_only_missing_profiles() {
+    let local = TempDir::new("profiles-local");
+    let synced = TempDir::new("profiles-synced");
+    write_profile(local.path(), "High.json", "High", 120);
+    write_profile(local.path(), "Low.json", "Low", 30);
+    write_profile(synced.path(), "High.json", "High", 144);
+
+    assert_eq!(profiles::copy_missing(local.path(), synced.path()).unwrap(), 1);
+    assert!(fs::read_to_string(synced.path().join("High.json")).unwrap().contains("144"));
+    assert!(synced.path().join("Low.json").is_file());
+}
@@ -0,0 +1,33 @@
+mod common;
+
+use std::path::PathBuf;
+
+use wuwa_ploom_core::relocate;
+
+use common::{quality_db, TempDir, V1_0_SETTING};
+
+#[test]
+fn replacement_prefers_the_detected_install() {
+    let (moved, other) = (TempDir::new("relocate_moved"), TempDir::new("relocate_other"));
+    let detected = quality_db(moved.path(), V1_0_SETTING);
+    let known = quality_db(other.path(), V1_0_SETTING);
+    let gone = moved.path().join("old").join("LocalStorage.db").display().to_string();
+
+    let found = relocate::replacement(&gone, Some(&detected), &[PathBuf::from(&known)]);
+    assert_eq!(found, Some(PathBuf::from(&detected)));
+
+    // Detection can point at the file that is gone, e.g. before the launcher learns of the move.
+    let found = relocate::replacement(&gone, Some(&gone), &[PathBuf::from(&known)]);
+    assert_eq!(found, Some(PathBuf::from(&known)));
+    assert_eq!(relocate::replacement(&gone, Some(&gone), &[]), None);
+}
+
+#[test]
+fn migrate_replaces_the_gone_path_in_place() {
+    let mut remembered = vec!["C:\\a.db".to_string(), "D:\\gone.db".to_string(), "E:\\new.db".to_string(), "F:\\b.db".to_string()];
+    relocate::migrate(&mut remembered, "D:\\gone.db", "E:\\new.db");
+    assert_eq!(remembered, ["C:\\a.db", "E:\\new.db", "F:\\b.db"]);
+
+    relocate::migrate(&mut remembered, "G:\\forgotten.db", "H:\\found.db");
+    assert_eq!(remembered.last().map(String::as_str), Some("H:\\found.db"));
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use std::fs;
+
+use wuwa_ploom_core::platform::StubPlatform;
+use wuwa_ploom_core::registry::{Hive, Location, View};
+use wuwa_ploom_core::rtss;
+
+use common::TempDir;
+
+#[test]
+fn limit_is_replaced_in_place_and_other_lines_kept() {
+    let profile = "[OSD]\r\nEnableOSD=1\r\n[Framerate]\r\nLimitDenominator=1\r\nLimit=60\r\n[Hooking]\r\nEnableHooking=1\r\n";
+    let updated = rtss::set_limit(profile, 120);
+    assert_eq!(updated, profile.replace("Limit=60", "Limit=120"));
+    assert_eq!(rtss::read_limit(&updated), Some(120));
+}
+
+#[test]
+fn missing_section_is_added() {
+    let updated = rtss::set_limit("[OSD]\nEnableOSD=1", 90);
+    assert_eq!(updated, "[OSD]\nEnableOSD=1\n\n[Framerate]\nLimit=90\n");
+    assert_eq!(rtss::read_limit("[Framerate]\nLimit=0\n"), None);
+    // `Limit` of another section is not the frame-rate limit.
+    assert_eq!(rtss::read_limit("[Other]\nLimit=30\n"), None);
+}
+
+#[test]
+fn install_comes_from_the_registry_and_profiles_are_written() {
+    let dir = TempDir::new("rtss");
+    fs::create_dir_all(dir.path().join("Profiles")).unwrap();
+    let platform = StubPlatform {
+        registry: vec![(
+            Location::new(Hive::LocalMachine, View::Registry32, "SOFTWARE\\Unwinder\\RTSS"),
+            "InstallDir".into(),
+            dir.path().display().to_string(),
+        )],
+        ..StubPlatform::new()
+    };
+    let install = rtss::find_install(&platform).unwrap();
+    assert_eq!(install, dir.path());
+
+    let exe = "Client-Win64-Shipping.exe";
+    assert_eq!(rtss::current_limit(&install, exe), None);
+    let path = rtss::write_limit(&install, exe, 144).unwrap();
+    assert_eq!(path, dir.path().join("Profiles").join("Client-Win64-Shipping.exe.cfg"));
+    assert_eq!(rtss::current_limit(&install, exe), Some(144));
+
+    // A profile that can't be read is left alone instead of being replaced with a blank one.
+    fs::write(&path, [0xff, 0xfe, 0x00]).unwrap();
+    assert!(rtss::write_limit(&install, exe, 60).is_err());
+    assert_eq!(fs::read(&path).unwrap(), [0xff, 0xfe, 0x00]);
+}
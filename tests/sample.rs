@@ -0,0 +1,35 @@
+mod common;
+
+use wuwa_ploom_core::db;
+use wuwa_ploom_core::{privacy, sample};
+
+use common::TempDir;
+
+#[test]
+fn the_sample_holds_every_kind_of_row() {
+    let dir = TempDir::new("sample");
+    let db_path = dir.path().join("LocalStorage.db").display().to_string();
+    sample::create_at(&db_path).unwrap();
+
+    let setting = db::read_quality_setting_file(&db_path).unwrap();
+    assert_eq!(db::fps_from_setting(&setting).unwrap(), 60);
+    let keys: Vec<String> = db::list_keys(&db_path, "", usize::MAX).unwrap().entries.into_iter().map(|entry| entry.key).collect();
+    assert_eq!(keys.len(), sample::ROWS.len());
+    assert!(keys.iter().any(|key| privacy::classify(key).is_some()));
+    assert!(!sample::is_sample(&db_path));
+}
+
+#[test]
+fn creating_again_starts_over() {
+    let dir = TempDir::new("sample_reset");
+    let db_path = dir.path().join("LocalStorage.db").display().to_string();
+    sample::create_at(&db_path).unwrap();
+    db::apply_patch(&db_path, &db::fps_patch(120)).unwrap();
+    db::delete_rows(&db_path, &["PlayerName".to_string()]).unwrap();
+
+    sample::create_at(&db_path).unwrap();
+
+    let setting = db::read_quality_setting_file(&db_path).unwrap();
+    assert_eq!(db::fps_from_setting(&setting).unwrap(), 60);
+    assert_eq!(db::read_value(&db_path, "PlayerName").unwrap(), "\"Rover\"");
+}
@@ -0,0 +1,40 @@
+use serde_json::{json, Map, Value};
+
+use wuwa_ploom_core::schema::{self, Migration};
+
+fn rename_theme(fields: &mut Map<String, Value>) {
+    if let Some(theme) = fields.remove("dark") {
+        fields.insert("theme".into(), Value::from(if theme == true { "Dark" } else { "Light" }));
+    }
+}
+
+fn add_language(fields: &mut Map<String, Value>) {
+    fields.entry("language").or_insert_with(|| Value::from("English"));
+}
+
+const MIGRATIONS: &[Migration] = &[schema::unversioned_to_v1, rename_theme, add_language];
+
+#[test]
+fn unversioned_files_run_every_step() {
+    let mut value = json!({ "dark": true });
+
+    assert_eq!(schema::migrate(&mut value, MIGRATIONS).unwrap(), 0);
+    assert_eq!(value, json!({ "version": 3, "theme": "Dark", "language": "English" }));
+}
+
+#[test]
+fn only_later_steps_run_for_newer_files() {
+    let mut value = json!({ "version": 2, "dark": true, "language": "English" });
+
+    assert_eq!(schema::migrate(&mut value, MIGRATIONS).unwrap(), 2);
+    assert_eq!(value, json!({ "version": 3, "dark": true, "language": "English" }));
+}
+
+#[test]
+fn files_from_a_newer_release_are_left_alone() {
+    let original = json!({ "version": 9, "theme": "Dark" });
+    let mut value = original.clone();
+
+    assert!(schema::migrate(&mut value, MIGRATIONS).is_err());
+    assert_eq!(value, original);
+}
@@ -0,0 +1,34 @@
+use serde_json::json;
+
+use wuwa_ploom_core::db;
+use wuwa_ploom_core::settings::GameQualitySetting;
+
+#[test]
+fn unknown_keys_survive_a_round_trip() {
+    let blob = json!({
+        "KeyCustomFrameRate": 60,
+        "KeyBrightness": 1.25,
+        "KeyFutureUpscaler": { "Mode": 2 },
+        "KeySomethingNew": "on",
+    });
+
+    let settings = GameQualitySetting::from_value(&blob).unwrap();
+    assert_eq!(settings.custom_frame_rate, Some(60));
+    assert_eq!(settings.brightness, Some(1.25));
+    assert_eq!(settings.other.len(), 2);
+    assert_eq!(settings.to_value(), blob);
+}
+
+#[test]
+fn absent_fields_stay_absent() {
+    let settings = GameQualitySetting { custom_frame_rate: Some(120), ..Default::default() };
+
+    assert_eq!(settings.to_value(), json!({ "KeyCustomFrameRate": 120 }));
+    assert_eq!(settings.to_patch(), db::fps_patch(120));
+}
+
+#[test]
+fn wrongly_typed_known_field_is_an_error() {
+    assert!(GameQualitySetting::from_value(&json!({ "KeyCustomFrameRate": "fast" })).is_err());
+    assert!(db::fps_from_setting(&json!({ "KeyPcVsync": 0 })).is_err());
+}
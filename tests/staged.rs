@@ -0,0 +1,81 @@
+mod common;
+
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use serde_json::json;
+use wuwa_ploom_core::db;
+use wuwa_ploom_core::platform::StubPlatform;
+use wuwa_ploom_core::staged::{self, Finish};
+
+use common::{quality_db, TempDir, V1_0_SETTING};
+
+#[test]
+fn a_staged_write_waits_in_a_clone_and_is_swapped_in() {
+    let dir = TempDir::new("staged");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+
+    let staged = staged::stage(&db_path, &db::fps_patch(120), false, None).unwrap().unwrap();
+
+    assert!(staged.clone.is_file());
+    assert_eq!(staged.inverse, json!({"KeyCustomFrameRate": 60}).as_object().unwrap().clone());
+    assert_eq!(db::read_quality_setting_file(&db_path).unwrap()["KeyCustomFrameRate"], 60);
+    let clone = staged.clone.clone();
+    assert_eq!(staged::finish(staged, &StubPlatform::new()).unwrap(), Finish::Swapped);
+    assert_eq!(db::read_quality_setting_file(&db_path).unwrap()["KeyCustomFrameRate"], 120);
+    assert_eq!(db::read_value(&db_path, "PlayerName").unwrap(), "\"Rover\"");
+    assert!(!clone.exists());
+}
+
+#[test]
+fn changes_made_after_the_clone_are_kept() {
+    let dir = TempDir::new("staged_stale");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let staged = staged::stage(&db_path, &db::fps_patch(120), false, None).unwrap().unwrap();
+
+    // The game saving its settings on exit.
+    thread::sleep(Duration::from_millis(20));
+    db::apply_patch(&db_path, json!({"KeyPcVsync": 0}).as_object().unwrap()).unwrap();
+
+    assert!(matches!(staged::finish(staged, &StubPlatform::new()).unwrap(), Finish::Reapplied(Some(_))));
+    let setting = db::read_quality_setting_file(&db_path).unwrap();
+    assert_eq!(setting["KeyCustomFrameRate"], 120);
+    assert_eq!(setting["KeyPcVsync"], 0);
+}
+
+#[test]
+fn nothing_is_staged_when_nothing_changes() {
+    let dir = TempDir::new("staged_noop");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+
+    assert!(staged::stage(&db_path, &db::fps_patch(60), false, None).unwrap().is_none());
+    assert!(!staged::clone_path(&db_path, false).exists());
+}
+
+#[test]
+fn a_replacing_write_leaves_the_queued_clone_until_it_is_staged() {
+    let dir = TempDir::new("staged_replace");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let first = staged::stage(&db_path, &db::fps_patch(90), false, None).unwrap().unwrap();
+
+    let second = staged::stage(&db_path, &db::fps_patch(120), false, Some(&first)).unwrap().unwrap();
+
+    assert_ne!(second.clone, first.clone);
+    assert!(first.clone.is_file() && second.clone.is_file());
+    staged::discard(first).unwrap();
+    assert_eq!(staged::finish(second, &StubPlatform::new()).unwrap(), Finish::Swapped);
+    assert_eq!(db::read_quality_setting_file(&db_path).unwrap()["KeyCustomFrameRate"], 120);
+}
+
+#[test]
+fn a_failed_finish_still_removes_the_clone() {
+    let dir = TempDir::new("staged_failed");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let staged = staged::stage(&db_path, &db::fps_patch(120), false, None).unwrap().unwrap();
+    let clone = staged.clone.clone();
+    fs::remove_file(&db_path).unwrap();
+
+    assert!(staged::finish(staged, &StubPlatform::new()).is_err());
+    assert!(!clone.exists());
+}
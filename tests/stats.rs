@@ -0,0 +1,41 @@
+mod common;
+
+use wuwa_ploom_core::stats::{Counts, Event, Stats};
+
+use common::TempDir;
+
+#[test]
+fn recent_counts_cover_only_the_window() {
+    let mut stats = Stats::default();
+    stats.record(Event::Revert, 100);
+    stats.record(Event::Revert, 106);
+    stats.record(Event::Patch, 106);
+    stats.record(Event::Backup, 99);
+
+    assert_eq!(stats.recent(7, 106), Counts { patches: 1, reverts: 2, backups: 0 });
+    assert_eq!(stats.recent(1, 106), Counts { patches: 1, reverts: 1, backups: 0 });
+    assert_eq!(stats.total, Counts { patches: 1, reverts: 2, backups: 1 });
+}
+
+#[test]
+fn old_days_are_dropped_but_totals_kept() {
+    let mut stats = Stats::default();
+    stats.record(Event::Patch, 10);
+    stats.record(Event::Patch, 500);
+
+    assert_eq!(stats.recent(1000, 500).patches, 1);
+    assert_eq!(stats.total.patches, 2);
+}
+
+#[test]
+fn stats_round_trip_and_damaged_files_start_over() {
+    let dir = TempDir::new("stats");
+    let path = dir.path().join("stats.json");
+    let mut stats = Stats::default();
+    stats.record(Event::Backup, 3);
+    stats.save_to(&path).unwrap();
+    assert_eq!(Stats::load_from(&path), stats);
+
+    std::fs::write(&path, "{").unwrap();
+    assert_eq!(Stats::load_from(&path), Stats::default());
+}
@@ -0,0 +1,25 @@
+use std::path::PathBuf;
+
+use wuwa_ploom_core::status::{Severity, StatusEvent};
+
+#[test]
+fn every_frontend_gets_the_same_wording() {
+    let written = StatusEvent::Written { label: "FPS set to 120".into(), keys: 1 };
+    assert_eq!(written.message(), "FPS set to 120 (1 key(s) changed).");
+    assert_eq!(written.severity(), Severity::Success);
+    assert_eq!(written.action(), "written");
+
+    let backed_up = StatusEvent::BackedUp { path: PathBuf::from("LocalStorage-1.db") };
+    assert_eq!(backed_up.message(), "Backup saved to LocalStorage-1.db");
+    assert_eq!(StatusEvent::AlreadySet { label: "FPS set to 120".into() }.message(), "FPS set to 120: already set. No need to patch.");
+}
+
+#[test]
+fn severity_follows_the_outcome() {
+    assert_eq!(StatusEvent::Queued { label: "FPS set to 120".into() }.severity(), Severity::Info);
+    assert_eq!(StatusEvent::Refused { reason: "Close the game first.".into() }.severity(), Severity::Warning);
+    let failed = StatusEvent::Failed { error: "database is locked".into() };
+    assert_eq!(failed.severity(), Severity::Error);
+    assert_eq!(failed.message(), "Error: database is locked");
+    assert!(Severity::Error > Severity::Warning);
+}
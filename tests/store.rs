@@ -0,0 +1,44 @@
+mod common;
+
+use std::fs;
+
+use serde_json::json;
+use wuwa_ploom_core::store;
+
+use common::TempDir;
+
+#[test]
+fn atomic_write_replaces_the_file_and_leaves_no_temp() {
+    let dir = TempDir::new("store-write");
+    let path = dir.path().join("nested").join("config.json");
+    store::write_json(&path, &json!({"theme": "Dark"})).unwrap();
+    store::write_json(&path, &json!({"theme": "Light"})).unwrap();
+
+    assert_eq!(store::read_json(&path).unwrap(), Some(json!({"theme": "Light"})));
+    let names: Vec<_> = fs::read_dir(path.parent().unwrap()).unwrap().flatten().map(|entry| entry.file_name()).collect();
+    assert_eq!(names, ["config.json"]);
+}
+
+#[test]
+fn damaged_file_is_kept_aside_and_reads_as_missing() {
+    let dir = TempDir::new("store-damaged");
+    let path = dir.path().join("config.json");
+    // What a save cut short by a crash leaves behind.
+    fs::write(&path, r#"{"theme": "Li"#).unwrap();
+
+    assert_eq!(store::read_json(&path).unwrap(), None);
+    assert!(!path.exists());
+    let kept: Vec<_> = fs::read_dir(dir.path()).unwrap().flatten().map(|entry| entry.file_name().into_string().unwrap()).collect();
+    assert_eq!(kept.len(), 1);
+    assert!(kept[0].starts_with("config.json.broken-"));
+    assert_eq!(fs::read_to_string(dir.path().join(&kept[0])).unwrap(), r#"{"theme": "Li"#);
+
+    store::write_json(&path, &json!({})).unwrap();
+    assert_eq!(store::read_json(&path).unwrap(), Some(json!({})));
+}
+
+#[test]
+fn missing_file_reads_as_none() {
+    let dir = TempDir::new("store-missing");
+    assert_eq!(store::read_json(&dir.path().join("config.json")).unwrap(), None);
+}
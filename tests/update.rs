@@ -0,0 +1,83 @@
+mod common;
+
+use std::fs;
+
+use wuwa_ploom_core::update::{self, Release};
+use wuwa_ploom_core::MyError;
+
+use common::TempDir;
+
+#[test]
+fn newer_versions_compare_numerically() {
+    assert!(update::is_newer("v1.10.0", "1.9.3"));
+    assert!(update::is_newer("1.0.1", "1.0.0"));
+    assert!(!update::is_newer("v1.0.0", "1.0.0"));
+    assert!(!update::is_newer("0.9", "1.0.0"));
+}
+
+#[test]
+fn release_json_parses_with_missing_optional_fields() {
+    let release: Release = serde_json::from_str(
+        r#"{ "tag_name": "v1.2.0", "html_url": "https://github.com/jakubs7/wuwa-ploom/releases/tag/v1.2.0", "assets": [] }"#,
+    )
+    .unwrap();
+    assert_eq!(release.version(), "1.2.0");
+    assert!(release.installer().is_none());
+}
+
+#[test]
+fn checksums_are_looked_up_by_file_name() {
+    let sums = "\
+3f2a0c6e3b1d9a7f5e4c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e  wuwa-ploom.exe
+A1B2C3D4E5F60718293A4B5C6D7E8F90A1B2C3D4E5F60718293A4B5C6D7E8F90 *wuwa-ploom
+";
+    assert_eq!(
+        update::listed_checksum(sums, "wuwa-ploom.exe").as_deref(),
+        Some("3f2a0c6e3b1d9a7f5e4c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5c4b3a2f1e")
+    );
+    assert_eq!(
+        update::listed_checksum(sums, "wuwa-ploom").as_deref(),
+        Some("a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90")
+    );
+    assert_eq!(update::listed_checksum(sums, "wuwa-ploom.msi"), None);
+}
+
+#[test]
+fn a_failed_replacement_is_reported_once() {
+    let dir = TempDir::new("update-failure");
+    assert_eq!(update::take_failure_from(dir.path()), None);
+
+    update::note_failure_in(dir.path(), &MyError::Update("the copy does not match the download".into()));
+    let text = update::take_failure_from(dir.path()).unwrap();
+    assert!(text.contains("the copy does not match the download"), "{}", text);
+    assert_eq!(update::take_failure_from(dir.path()), None);
+}
+
+#[test]
+fn leftover_downloads_are_removed() {
+    let dir = TempDir::new("update-downloads");
+    let downloads = dir.path().join("updates");
+    assert_eq!(update::remove_downloads(&downloads), 0);
+
+    fs::create_dir_all(&downloads).unwrap();
+    fs::write(downloads.join("1.1.0-wuwa-ploom.exe"), b"old").unwrap();
+    fs::write(downloads.join("1.2.0-wuwa-ploom.exe"), b"partial").unwrap();
+    assert_eq!(update::remove_downloads(&downloads), 2);
+    assert_eq!(fs::read_dir(&downloads).unwrap().count(), 0);
+}
+
+#[test]
+fn an_update_replaces_the_program_in_one_step() {
+    let dir = TempDir::new("update-install");
+    let (download, target) = (dir.path().join("1.2.0-wuwa-ploom.exe"), dir.path().join("wuwa-ploom.exe"));
+    fs::write(&download, b"new").unwrap();
+    fs::write(&target, b"old").unwrap();
+
+    update::install_copy(&download, &target).unwrap();
+    assert_eq!(fs::read(&target).unwrap(), b"new");
+    assert!(!dir.path().join("wuwa-ploom.exe.new").exists());
+
+    // Without a download, the program stays as it was.
+    assert!(update::install_copy(&dir.path().join("missing.exe"), &target).is_err());
+    assert_eq!(fs::read(&target).unwrap(), b"new");
+}
@@ -0,0 +1,33 @@
+use std::time::{Duration, Instant};
+
+use wuwa_ploom_core::watchdog::{self, Budget, Tuning, WINDOW};
+
+#[test]
+fn re_applies_stop_at_the_hourly_limit() {
+    let start = Instant::now();
+    let mut budget = Budget::default();
+    for minute in 0..3 {
+        assert!(budget.take(start + Duration::from_secs(minute * 60), 3));
+    }
+    assert!(!budget.take(start + Duration::from_secs(10 * 60), 3));
+    assert_eq!(budget.frees_at(), Some(start + WINDOW));
+    assert_eq!(watchdog::minutes_until(start + WINDOW, start + Duration::from_secs(10 * 60 + 1)), 50);
+
+    // The first re-apply has left the window.
+    assert!(budget.take(start + WINDOW + Duration::from_secs(1), 3));
+    assert!(!budget.take(start + WINDOW + Duration::from_secs(2), 3));
+}
+
+#[test]
+fn zero_means_no_limit() {
+    let start = Instant::now();
+    let mut budget = Budget::default();
+    assert!((0..100).all(|second| budget.take(start + Duration::from_secs(second), 0)));
+}
+
+#[test]
+fn missing_tuning_fields_take_their_defaults() {
+    let tuning: Tuning = serde_json::from_str(r#"{"max_per_hour": 2}"#).unwrap();
+    assert_eq!(tuning, Tuning { max_per_hour: 2, ..Tuning::default() });
+    assert_eq!(Tuning { poll_secs: 0, ..tuning }.poll_interval(), Duration::from_secs(1));
+}
@@ -0,0 +1,81 @@
+// Prefix layouts use `c:` style folder names, which only exist off Windows.
+#![cfg(unix)]
+
+mod common;
+
+use std::fs;
+use std::os::unix::fs::symlink;
+use std::path::{Path, PathBuf};
+
+use wuwa_ploom_core::registry::{Hive, Location, View};
+use wuwa_ploom_core::{detect, wine};
+
+use common::TempDir;
+
+const SYSTEM_REG: &str = r#"WINE REGISTRY Version 2
+;; All keys relative to \\Machine
+
+[Software\\Wow6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\KRInstall Wuthering Waves Overseas] 1719000000
+#time=1dac4a3b2f1e7c0
+"DisplayVersion"="1.2.0"
+"InstallPath"="C:\\Wuthering Waves"
+"#;
+
+/// A Proton prefix for `app_id` in the Steam library under `home`, with `drive_c` linked as `c:`.
+fn proton_prefix(home: &Path, app_id: &str) -> PathBuf {
+    let prefix = home.join(".local/share/Steam/steamapps/compatdata").join(app_id).join("pfx");
+    fs::create_dir_all(prefix.join("drive_c")).unwrap();
+    fs::create_dir_all(prefix.join("dosdevices")).unwrap();
+    symlink("../drive_c", prefix.join("dosdevices").join("c:")).unwrap();
+    prefix
+}
+
+#[test]
+fn proton_prefixes_are_found_in_steam_libraries() {
+    let dir = TempDir::new("wine-prefixes");
+    let prefix = proton_prefix(dir.path(), "3513350");
+    fs::create_dir_all(dir.path().join(".local/share/Steam/steamapps/compatdata/0")).unwrap();
+
+    let prefixes = wine::prefixes(dir.path());
+    assert_eq!(prefixes, vec![prefix.canonicalize().unwrap()]);
+}
+
+#[test]
+fn extra_libraries_come_from_libraryfolders_vdf() {
+    let dir = TempDir::new("wine-libraries");
+    let main = dir.path().join(".local/share/Steam");
+    let sd_card = dir.path().join("sdcard/SteamLibrary");
+    fs::create_dir_all(main.join("steamapps")).unwrap();
+    fs::create_dir_all(sd_card.join("steamapps")).unwrap();
+    let vdf = format!("\"libraryfolders\"\n{{\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n", sd_card.display());
+    fs::write(main.join("steamapps/libraryfolders.vdf"), vdf).unwrap();
+
+    let libraries = wine::steam_libraries(dir.path());
+    assert_eq!(libraries, vec![main.canonicalize().unwrap(), sd_card.canonicalize().unwrap()]);
+}
+
+#[test]
+fn install_path_comes_from_the_prefix_registry() {
+    let dir = TempDir::new("wine-registry");
+    let prefix = proton_prefix(dir.path(), "3513350");
+    fs::write(prefix.join("system.reg"), SYSTEM_REG).unwrap();
+    let db = detect::db_path_in(&prefix.join("drive_c/Wuthering Waves"));
+    fs::create_dir_all(db.parent().unwrap()).unwrap();
+    fs::write(&db, b"").unwrap();
+
+    let key = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Uninstall\\KRInstall Wuthering Waves Overseas";
+    let location = Location::new(Hive::LocalMachine, View::Registry32, key);
+    assert_eq!(wine::registry_string(&prefix, &location, "DisplayVersion").as_deref(), Some("1.2.0"));
+    let found = detect::prefix_install_db_path(&prefix).unwrap();
+    assert!(Path::new(&found).is_file());
+    assert_eq!(Path::new(&found).canonicalize().unwrap(), db.canonicalize().unwrap());
+}
+
+#[test]
+fn prefix_without_the_game_is_a_registry_error() {
+    let dir = TempDir::new("wine-empty");
+    let prefix = proton_prefix(dir.path(), "3513350");
+    fs::write(prefix.join("system.reg"), "WINE REGISTRY Version 2\n").unwrap();
+
+    assert!(detect::prefix_install_db_path(&prefix).is_err());
+}
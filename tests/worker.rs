@@ -0,0 +1,63 @@
+mod common;
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use rusqlite::Connection;
+use serde_json::Value;
+use wuwa_ploom_core::db::{self, Db, Patch};
+use wuwa_ploom_core::MyError;
+
+use common::{quality_db, TempDir, V1_0_SETTING};
+
+#[test]
+fn concurrent_patches_are_queued_not_lost() {
+    let dir = TempDir::new("worker");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+
+    let writers: Vec<_> = (0..8)
+        .map(|index| {
+            let db_path = db_path.clone();
+            thread::spawn(move || {
+                let patch = Patch::from_iter([(format!("KeyTest{}", index), Value::from(index))]);
+                db::apply_patch(&db_path, &patch).unwrap()
+            })
+        })
+        .collect();
+    for writer in writers {
+        assert!(writer.join().unwrap().is_some());
+    }
+
+    let setting = db::wait(Db::new(&db_path).read()).unwrap();
+    assert!((0..8).all(|index| setting[format!("KeyTest{}", index)] == index));
+    assert_eq!(setting["KeyCustomFrameRate"], 60);
+}
+
+#[test]
+fn commands_run_in_the_order_they_were_queued() {
+    let dir = TempDir::new("worker-order");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let handle = Db::new(&db_path);
+
+    let first = handle.patch(db::fps_patch(90));
+    let second = handle.patch(db::fps_patch(120));
+    let read = handle.read();
+
+    assert!(db::wait(first).unwrap().is_some());
+    assert_eq!(db::wait(second).unwrap(), Some(db::fps_patch(90)));
+    assert_eq!(db::wait(read).unwrap()["KeyCustomFrameRate"], 120);
+}
+
+#[test]
+fn reading_now_does_not_wait_out_a_lock() {
+    let dir = TempDir::new("worker-read-now");
+    let db_path = quality_db(dir.path(), V1_0_SETTING);
+    let holder = Connection::open(&db_path).unwrap();
+    holder.execute_batch("BEGIN EXCLUSIVE;").unwrap();
+
+    let started = Instant::now();
+    assert!(matches!(db::read_quality_setting_now(&db_path), Err(MyError::Locked(_))));
+    assert!(started.elapsed() < Duration::from_millis(400));
+    holder.execute_batch("COMMIT;").unwrap();
+    assert!(db::read_quality_setting_now(&db_path).is_ok());
+}